@@ -149,8 +149,10 @@ fn update_plot(widget: &mut PlotWidget, message: PlotUiMessage) {
     widget.update(message);
 
     match hover_pick_event {
-        Some(HoverPickEvent::Hover(point_id)) => {
-            if let Some([x, _]) = widget.point_position(point_id) {
+        Some(HoverPickEvent::Hover(hit)) => {
+            if let Some(point_id) = hit.point_id()
+                && let Some([x, _]) = widget.point_position(point_id)
+            {
                 for series_id in widget.series_ids() {
                     if series_id != point_id.series_id
                         && let Some(p) = widget.nearest_point_horizontal(series_id, x)
@@ -160,8 +162,10 @@ fn update_plot(widget: &mut PlotWidget, message: PlotUiMessage) {
                 }
             }
         }
-        Some(HoverPickEvent::Pick(point_id)) => {
-            if let Some([x, _]) = widget.point_position(point_id) {
+        Some(HoverPickEvent::Pick(hit)) => {
+            if let Some(point_id) = hit.point_id()
+                && let Some([x, _]) = widget.point_position(point_id)
+            {
                 for series_id in widget.series_ids() {
                     if series_id != point_id.series_id
                         && let Some(p) = widget.nearest_point_horizontal(series_id, x)
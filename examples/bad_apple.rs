@@ -1,6 +1,6 @@
 //! A totally necessary example.
 use iced_plot::{
-    Color, MarkerStyle, MarkerType, PlotUiMessage, PlotWidget, PlotWidgetBuilder, Series, ShapeId,
+    PlotUiMessage, PlotWidget, PlotWidgetBuilder, Raster, RasterData, RasterOrigin, ShapeId,
 };
 
 use iced::Element;
@@ -27,7 +27,7 @@ enum Message {
 }
 
 struct App {
-    series_id: ShapeId,
+    raster_id: ShapeId,
     widget: PlotWidget,
     frames: &'static [u8],
     frame_idx: usize,
@@ -42,38 +42,31 @@ impl App {
         let frames = include_bytes!("assets/bad_apple_gray.bin");
         let pixels_per_frame = FRAME_WIDTH * FRAME_HEIGHT;
         let frame_count = frames.len() / pixels_per_frame;
-        let positions = build_positions(FRAME_WIDTH, FRAME_HEIGHT);
-        let initial_colors = if frame_count > 0 {
-            frame_colors(0, frames, FRAME_WIDTH, FRAME_HEIGHT)
+        let initial_pixels = if frame_count > 0 {
+            frame_pixels(0, frames, FRAME_WIDTH, FRAME_HEIGHT)
         } else {
-            vec![Color::from_rgb(0.0, 0.0, 0.0); positions.len()]
+            vec![255; FRAME_WIDTH * FRAME_HEIGHT * 4]
         };
-        let series = Series::markers_only(
-            positions.clone(),
-            MarkerStyle::new_world(1.0, MarkerType::Square),
-        )
-        .with_point_colors(initial_colors);
-        let series_id = series.id;
+        // Frames are stored top row first.
+        let raster = Raster::rgba(FRAME_WIDTH, FRAME_HEIGHT, initial_pixels)
+            .with_origin(RasterOrigin::Upper);
+        let raster_id = raster.id;
         let widget = PlotWidgetBuilder::new()
             .with_data_aspect(1.0)
             .with_x_tick_labels(false)
             .with_y_tick_labels(false)
-            .with_hover_highlight_provider(|context, point| {
-                Some(format!(
-                    "frame: {}\nx: {}, y: {}",
-                    context.point_index,
-                    point.x.round(),
-                    point.y.round()
-                ))
+            .with_hover_highlight_provider(|context, _point| {
+                let cell = context.cell?;
+                Some(format!("row: {}, column: {}", cell.row, cell.column))
             })
             .with_cursor_overlay(false)
-            .add_series(series)
+            .add_raster(raster)
             .disable_controls_help()
             .build()
             .unwrap();
 
         Self {
-            series_id,
+            raster_id,
             widget,
             frames,
             frame_idx: 0,
@@ -118,8 +111,10 @@ impl App {
             return;
         }
 
-        let colors = frame_colors(self.frame_idx, self.frames, FRAME_WIDTH, FRAME_HEIGHT);
-        self.widget.set_series_point_colors(&self.series_id, colors);
+        let pixels = frame_pixels(self.frame_idx, self.frames, FRAME_WIDTH, FRAME_HEIGHT);
+        self.widget
+            .set_raster_data(&self.raster_id, RasterData::Rgba(pixels))
+            .unwrap();
     }
 
     fn view(&self) -> Element<'_, Message> {
@@ -131,26 +126,12 @@ impl App {
     }
 }
 
-fn build_positions(width: usize, height: usize) -> Vec<[f64; 2]> {
-    let mut positions = Vec::with_capacity(width * height);
-    for y in 0..height {
-        for x in 0..width {
-            let py = (height - 1 - y) as f64;
-            positions.push([x as f64, py]);
-        }
-    }
-    positions
-}
-
-fn frame_colors(frame_idx: usize, frames: &[u8], width: usize, height: usize) -> Vec<Color> {
+fn frame_pixels(frame_idx: usize, frames: &[u8], width: usize, height: usize) -> Vec<u8> {
     let pixels_per_frame = width * height;
     let start = frame_idx * pixels_per_frame;
     let end = start + pixels_per_frame;
     frames[start..end]
         .iter()
-        .map(|&value| {
-            let g = value as f32 / 255.0;
-            Color::from_rgb(g, g, g)
-        })
+        .flat_map(|&value| [value, value, value, 255])
         .collect()
 }
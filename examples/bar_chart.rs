@@ -0,0 +1,123 @@
+use iced::{
+    Element,
+    widget::{column, row},
+};
+use iced_plot::{BarGroup, BarStyle, Color, PlotUiMessage, PlotWidget, PlotWidgetBuilder, Series};
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Grouped(PlotUiMessage),
+    Stacked(PlotUiMessage),
+    Horizontal(PlotUiMessage),
+}
+
+struct App {
+    grouped: PlotWidget,
+    stacked: PlotWidget,
+    horizontal: PlotWidget,
+}
+
+fn update(app: &mut App, message: Message) {
+    match message {
+        Message::Grouped(msg) => app.grouped.update(msg),
+        Message::Stacked(msg) => app.stacked.update(msg),
+        Message::Horizontal(msg) => app.horizontal.update(msg),
+    }
+}
+
+fn view(app: &App) -> Element<'_, Message> {
+    column![
+        row![
+            app.grouped.view().map(Message::Grouped),
+            app.stacked.view().map(Message::Stacked),
+        ],
+        app.horizontal.view().map(Message::Horizontal),
+    ]
+    .into()
+}
+
+const QUARTERS: [[f64; 3]; 4] = [
+    [4.0, 2.5, 1.0],
+    [5.0, 3.0, 1.5],
+    [3.5, 4.0, 2.0],
+    [6.0, 3.5, 2.5],
+];
+const COLORS: [Color; 3] = [
+    Color::from_rgb(0.2, 0.6, 1.0),
+    Color::from_rgb(1.0, 0.6, 0.2),
+    Color::from_rgb(0.4, 0.8, 0.4),
+];
+const NAMES: [&str; 3] = ["north", "south", "west"];
+
+fn region_series(style: impl Fn() -> BarStyle) -> impl Iterator<Item = Series> {
+    (0..3).map(move |region| {
+        let positions = QUARTERS
+            .iter()
+            .enumerate()
+            .map(|(quarter, sales)| [quarter as f64 + 1.0, sales[region]])
+            .collect();
+        Series::bars(positions, style())
+            .with_label(NAMES[region])
+            .with_color(COLORS[region])
+    })
+}
+
+fn new() -> App {
+    // Series in the same group split the bar width side by side.
+    let group = BarGroup::new();
+    let grouped = region_series(|| BarStyle::new(0.8).grouped(group))
+        .fold(PlotWidgetBuilder::new(), |builder, series| {
+            builder.add_series(series)
+        })
+        .with_x_label("quarter")
+        .with_y_label("sales")
+        .build()
+        .unwrap();
+
+    // Series in the same stack are drawn end to end.
+    let stack = BarGroup::new();
+    let stacked = region_series(|| BarStyle::new(0.6).stacked(stack))
+        .fold(PlotWidgetBuilder::new(), |builder, series| {
+            builder.add_series(series)
+        })
+        .with_x_label("quarter")
+        .with_y_label("total sales")
+        .build()
+        .unwrap();
+
+    // Horizontal bars centered on y, with a non-zero baseline.
+    let deltas = vec![[3.0, 1.0], [-2.0, 2.0], [1.5, 3.0], [-0.5, 4.0]];
+    let colors = deltas
+        .iter()
+        .map(|[x, _]| {
+            if *x >= 0.0 {
+                Color::from_rgb(0.3, 0.7, 0.3)
+            } else {
+                Color::from_rgb(0.8, 0.3, 0.3)
+            }
+        })
+        .collect();
+    let horizontal = PlotWidgetBuilder::new()
+        .with_x_label("change")
+        .with_y_label("region")
+        .add_series(
+            Series::bars(deltas, BarStyle::horizontal(0.5))
+                .with_point_colors(colors)
+                .with_label("change"),
+        )
+        .build()
+        .unwrap();
+
+    App {
+        grouped,
+        stacked,
+        horizontal,
+    }
+}
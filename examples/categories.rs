@@ -0,0 +1,66 @@
+//! Categorical axis example: grouped bars and a dot plot placed on named categories.
+use iced_plot::{
+    BarGroup, BarStyle, Color, MarkerStyle, PlotUiMessage, PlotWidget, PlotWidgetBuilder, Series,
+};
+
+use iced::Element;
+
+const FRUITS: [&str; 5] = ["apples", "bananas", "cherries", "dates", "elderberries"];
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let group = BarGroup::new();
+
+    // Category indices can be used directly as x values...
+    let last_year = Series::bars(
+        [12.0, 30.0, 7.0, 4.0, 9.0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, sales)| [i as f64, sales])
+            .collect(),
+        BarStyle::new(0.8).grouped(group),
+    )
+    .with_color(Color::from_rgb(0.5, 0.5, 0.6))
+    .with_label("last year");
+
+    // ...or points can name their category, in any order.
+    let this_year = Series::bars(
+        vec![[0.0, 26.0], [0.0, 15.0], [0.0, 11.0], [0.0, 10.0]],
+        BarStyle::new(0.8).grouped(group),
+    )
+    .with_x_categories(["bananas", "apples", "elderberries", "cherries"])
+    .with_color(Color::from_rgb(0.2, 0.6, 1.0))
+    .with_label("this year");
+
+    let target = Series::markers_only(
+        vec![[0.0, 20.0], [0.0, 18.0], [0.0, 25.0]],
+        MarkerStyle::star(10.0),
+    )
+    .with_x_categories(["apples", "dates", "bananas"])
+    .with_color(Color::from_rgb(1.0, 0.6, 0.2))
+    .with_label("target");
+
+    PlotWidgetBuilder::new()
+        .with_x_categories(FRUITS)
+        .add_series(last_year)
+        .add_series(this_year)
+        .add_series(target)
+        .with_x_label("fruit")
+        .with_y_label("crates sold")
+        .build()
+        .unwrap()
+}
@@ -0,0 +1,64 @@
+//! Contour example: filled bands and labelled isolines of the potential of two charges.
+use iced_plot::{
+    Color, ColorScale, Colorbar, Colormap, Contour, LineStyle, PlotUiMessage, PlotWidget,
+    PlotWidgetBuilder,
+};
+
+use iced::Element;
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .theme(iced::theme::Theme::TokyoNightStorm)
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let (cols, rows) = (161, 121);
+    let (min, max) = ([-4.0, -3.0], [4.0, 3.0]);
+    let mut values = Vec::with_capacity(cols * rows);
+
+    // Row 0 is at the bottom of the extent.
+    for row in 0..rows {
+        for col in 0..cols {
+            let x = min[0] + (max[0] - min[0]) * col as f64 / (cols - 1) as f64;
+            let y = min[1] + (max[1] - min[1]) * row as f64 / (rows - 1) as f64;
+            values.push(potential(x, y));
+        }
+    }
+
+    let contour = Contour::new(cols, rows, values)
+        .with_extent(min, max)
+        .with_levels(vec![-2.0, -1.0, -0.5, -0.25, 0.0, 0.25, 0.5, 1.0, 2.0])
+        .with_color_scale(ColorScale::new(Colormap::RedBlue).with_range(-2.0, 2.0))
+        .with_filled(true)
+        .with_fill_opacity(0.8)
+        .with_line_style(LineStyle::solid().with_pixel_width(1.0))
+        .with_line_color(Color::from_rgb(0.1, 0.1, 0.1))
+        .with_label("V");
+
+    PlotWidgetBuilder::new()
+        .add_contour(contour)
+        .with_x_label("x")
+        .with_y_label("y")
+        .with_data_aspect(1.0)
+        .with_colorbar(Colorbar::new().with_label("potential"))
+        .with_cursor_overlay(true)
+        .build()
+        .unwrap()
+}
+
+/// Potential of a positive and a negative point charge, clamped near the charges.
+fn potential(x: f64, y: f64) -> f64 {
+    let charge = |cx: f64, q: f64| q / ((x - cx).powi(2) + y * y).sqrt().max(0.1);
+    (charge(-1.5, 1.0) + charge(1.5, -1.0)).clamp(-5.0, 5.0)
+}
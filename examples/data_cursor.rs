@@ -0,0 +1,64 @@
+//! Data cursor example: every channel read at the cursor x in one tooltip.
+//!
+//! Move the cursor over the plot. Lines are read between samples, and the sparse
+//! markers-only channel snaps to its nearest sample.
+use iced_plot::{
+    Color, DataCursorMode, LineStyle, MarkerStyle, PlotUiMessage, PlotWidget, PlotWidgetBuilder,
+    Series,
+};
+
+use iced::Element;
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let channels = [
+        ("ch 1", 1.0, Color::from_rgb(0.2, 0.6, 1.0)),
+        ("ch 2", 1.7, Color::from_rgb(1.0, 0.6, 0.2)),
+        ("ch 3", 2.9, Color::from_rgb(0.3, 0.8, 0.4)),
+    ];
+
+    let mut builder = PlotWidgetBuilder::new()
+        .with_x_label("time (s)")
+        .with_y_label("amplitude")
+        .with_cursor_overlay(false)
+        .with_data_cursor(DataCursorMode::Interpolate);
+    for (row, (label, frequency, color)) in channels.into_iter().enumerate() {
+        let phase = row as f64 * 0.8;
+        let positions = (0..=200)
+            .map(|i| {
+                let t = i as f64 * 0.05;
+                [t, (t * frequency + phase).sin()]
+            })
+            .collect();
+        let series = Series::line_only(positions, LineStyle::solid().with_pixel_width(2.0))
+            .with_label(label)
+            .with_color(color);
+        builder = builder.add_series(series);
+    }
+
+    let samples = (0..=10)
+        .map(|i| {
+            let t = i as f64;
+            [t, 0.5 * (t * 0.7).cos()]
+        })
+        .collect();
+    let samples = Series::markers_only(samples, MarkerStyle::square(6.0))
+        .with_label("samples")
+        .with_color(Color::from_rgb(0.8, 0.3, 0.8));
+
+    builder.add_series(samples).build().unwrap()
+}
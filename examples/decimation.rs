@@ -0,0 +1,56 @@
+use iced::Element;
+use iced_plot::{
+    Color, Decimation, LineStyle, PlotUiMessage, PlotWidget, PlotWidgetBuilder, Series,
+};
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    // Five million samples of a chirp with occasional spikes.
+    const SAMPLES: usize = 5_000_000;
+    let waveform: Vec<[f64; 2]> = (0..SAMPLES)
+        .map(|i| {
+            let t = i as f64 / 1_000.0;
+            let spike = if i % 250_000 == 0 { 3.0 } else { 0.0 };
+            [t, (t * (1.0 + t * 0.01)).sin() + spike]
+        })
+        .collect();
+    let smooth: Vec<[f64; 2]> = waveform
+        .iter()
+        .map(|&[t, _]| [t, 0.5 * (t * 0.05).cos() - 2.0])
+        .collect();
+
+    // Min/max decimation keeps every spike visible at any zoom level, while only
+    // a few points per pixel column are uploaded to the GPU.
+    let raw = Series::line_only(waveform, LineStyle::solid())
+        .with_label("raw (min/max)")
+        .with_color(Color::from_rgb(0.2, 0.6, 1.0))
+        .with_decimation(Decimation::MinMax);
+
+    // LTTB keeps the overall shape of smooth signals with even fewer points.
+    let envelope = Series::line_only(smooth, LineStyle::solid().with_pixel_width(2.0))
+        .with_label("envelope (LTTB)")
+        .with_color(Color::from_rgb(1.0, 0.6, 0.2))
+        .with_decimation(Decimation::Lttb);
+
+    PlotWidgetBuilder::new()
+        .with_x_label("time (s)")
+        .with_y_label("amplitude")
+        .add_series(raw)
+        .add_series(envelope)
+        .build()
+        .unwrap()
+}
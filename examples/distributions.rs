@@ -0,0 +1,53 @@
+//! Distribution example: box plots and violins comparing groups of samples side by side.
+use iced_plot::{Color, DistributionPlot, LineStyle, PlotUiMessage, PlotWidget, PlotWidgetBuilder};
+use rand_distr::{Distribution, Normal};
+
+use iced::Element;
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let groups: Vec<Vec<f64>> = [(0.0, 1.0), (1.0, 0.5), (-0.5, 2.0), (2.0, 1.5)]
+        .into_iter()
+        .map(|(mean, std_dev)| draw_samples(mean, std_dev, 300))
+        .collect();
+
+    let boxes = DistributionPlot::boxes(groups.clone())
+        .with_positions(vec![0.0, 2.0, 4.0, 6.0])
+        .with_width(0.7)
+        .with_color(Color::from_rgb(0.2, 0.6, 1.0))
+        .with_label("boxes");
+    let violins = DistributionPlot::violins(groups)
+        .with_positions(vec![1.0, 3.0, 5.0, 7.0])
+        .with_width(0.8)
+        .with_color(Color::from_rgb(1.0, 0.6, 0.2))
+        .with_line_style(LineStyle::solid().with_pixel_width(1.5))
+        .with_label("violins");
+
+    PlotWidgetBuilder::new()
+        .add_distribution_plot(boxes)
+        .add_distribution_plot(violins)
+        .with_x_label("group")
+        .with_y_label("value")
+        .build()
+        .unwrap()
+}
+
+fn draw_samples(mean: f64, std_dev: f64, count: usize) -> Vec<f64> {
+    let mut rng = rand::rng();
+    let normal = Normal::new(mean, std_dev).unwrap();
+    (0..count).map(|_| normal.sample(&mut rng)).collect()
+}
@@ -0,0 +1,52 @@
+use iced::Element;
+use iced_plot::{
+    Color, ErrorBarStyle, LineStyle, MarkerStyle, PlotUiMessage, PlotWidget, PlotWidgetBuilder,
+    Series,
+};
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    // Measurements with symmetric y uncertainties.
+    let xs: Vec<f64> = (0..12).map(|i| i as f64 * 0.5).collect();
+    let measured = xs
+        .iter()
+        .map(|&x| [x, (x * 0.8).sin() * 2.0 + 0.1 * x])
+        .collect();
+    let y_err = xs.iter().map(|&x| 0.2 + 0.05 * x).collect::<Vec<_>>();
+
+    let measurements = Series::new(measured, MarkerStyle::circle(6.0), LineStyle::dotted(4.0))
+        .with_label("measurement")
+        .with_color(Color::from_rgb(0.2, 0.6, 1.0))
+        .with_y_errors(y_err);
+
+    // Calibration points with asymmetric errors on both axes, without caps.
+    let calibration = Series::squares(vec![[1.0, -2.0], [3.0, -1.5], [5.0, -2.5]], 7.0)
+        .with_label("calibration")
+        .with_color(Color::from_rgb(1.0, 0.6, 0.2))
+        .with_x_errors(vec![0.3, 0.2, 0.4])
+        .with_y_errors((vec![0.2, 0.4, 0.3], vec![0.6, 0.3, 0.8]))
+        .with_error_bar_style(ErrorBarStyle::default().with_line_width(2.0).without_caps());
+
+    // The default hover tooltip prints `value ± err` for points with errors.
+    PlotWidgetBuilder::new()
+        .with_x_label("x")
+        .with_y_label("y")
+        .add_series(measurements)
+        .add_series(calibration)
+        .build()
+        .unwrap()
+}
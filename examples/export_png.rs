@@ -0,0 +1,61 @@
+//! Renders a plot to a PNG file without opening a window.
+//!
+//! Usage: `cargo run --example export_png --features canvas -- [output.png]`
+use iced_plot::{Color, ExportOptions, Fill, HLine, LineStyle, PlotWidgetBuilder, Series};
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "plot.png".to_owned());
+
+    let sine = Series::line_only(
+        (0..200)
+            .map(|i| {
+                let x = i as f64 * 0.05;
+                [x, x.sin()]
+            })
+            .collect(),
+        LineStyle::solid().with_pixel_width(2.0),
+    )
+    .with_color(Color::from_rgb(0.15, 0.55, 0.95))
+    .with_label("sin(x)");
+    let samples = Series::circles(
+        (0..20)
+            .map(|i| {
+                let x = i as f64 * 0.5;
+                [x, 0.8 * (x * 0.7).cos()]
+            })
+            .collect(),
+        6.0,
+    )
+    .with_color(Color::from_rgb(0.95, 0.45, 0.15))
+    .with_label("samples");
+    let baseline = HLine::new(0.0)
+        .with_label("y = 0")
+        .with_style(LineStyle::dashed(6.0))
+        .with_color(Color::from_rgb(0.5, 0.5, 0.5));
+    let fill = Fill::new(sine.id, baseline.id)
+        .with_label("area")
+        .with_color(Color::from_rgba(0.2, 0.6, 1.0, 0.2));
+
+    let plot = PlotWidgetBuilder::new()
+        .with_x_label("x")
+        .with_y_label("y")
+        .add_series(sine)
+        .add_series(samples)
+        .add_hline(baseline)
+        .add_fill(fill)
+        .build()
+        .unwrap();
+
+    let options = ExportOptions::new(800, 500)
+        .with_scale_factor(2.0)
+        .with_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .with_font_data(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"));
+    let image = plot
+        .export_image(&options)
+        .expect("offscreen rendering is available");
+    image.save_png(&path)?;
+    println!("wrote {}x{} image to {path}", image.width, image.height);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+//! Writes a plot to an SVG file without opening a window.
+//!
+//! Usage: `cargo run --example export_svg -- [output.svg]`
+use iced_plot::{
+    BarStyle, Color, ExportOptions, LineStyle, MarkerStyle, PlotWidgetBuilder, Series, VLine,
+};
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "plot.svg".to_owned());
+
+    let bars = Series::bars(
+        (1..=6).map(|i| [i as f64, 1.0 + (i % 4) as f64]).collect(),
+        BarStyle::new(0.6),
+    )
+    .with_color(Color::from_rgba(0.2, 0.6, 1.0, 0.6))
+    .with_y_errors(vec![0.3, 0.5, 0.2, 0.4, 0.3, 0.6])
+    .with_label("measured");
+    let trend = Series::new(
+        (1..=6).map(|i| [i as f64, 1.2 + 0.4 * i as f64]).collect(),
+        MarkerStyle::star(10.0),
+        LineStyle::dashed(8.0).with_pixel_width(2.0),
+    )
+    .with_color(Color::from_rgb(0.9, 0.4, 0.1))
+    .with_label("trend");
+    let marker = VLine::new(3.5)
+        .with_label("release")
+        .with_style(LineStyle::dotted(3.0))
+        .with_color(Color::from_rgb(0.4, 0.4, 0.4));
+
+    let plot = PlotWidgetBuilder::new()
+        .with_x_label("week")
+        .with_y_label("load")
+        .add_series(bars)
+        .add_series(trend)
+        .add_vline(marker)
+        .build()
+        .unwrap();
+
+    let svg = plot
+        .export_svg(&ExportOptions::new(800, 500))
+        .expect("figure size is valid");
+    std::fs::write(&path, svg)?;
+    println!("wrote {path}");
+    Ok(())
+}
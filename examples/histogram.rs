@@ -0,0 +1,114 @@
+//! Histogram example: samples are binned automatically and re-binned as more arrive.
+use iced::{
+    Element,
+    widget::{button, column, row},
+};
+use iced_plot::{
+    Binning, Color, Histogram, HistogramNorm, LineStyle, PlotUiMessage, PlotWidget,
+    PlotWidgetBuilder, ShapeId,
+};
+use rand_distr::{Distribution, Normal};
+
+fn main() -> iced::Result {
+    iced::application(App::new, App::update, App::view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+#[derive(Debug, Clone)]
+enum Message {
+    Plot(PlotUiMessage),
+    AddSamples,
+    SetBinning(Binning),
+}
+
+struct App {
+    widget: PlotWidget,
+    counts: ShapeId,
+    cumulative: ShapeId,
+    samples: Vec<f64>,
+}
+
+impl App {
+    fn new() -> Self {
+        let samples = draw_samples(200);
+        let counts = Histogram::new(samples.clone())
+            .with_color(Color::from_rgba(0.2, 0.6, 1.0, 0.7))
+            .with_label("count");
+        let cumulative = Histogram::new(samples.clone())
+            .with_norm(HistogramNorm::Cumulative)
+            .with_step(LineStyle::solid().with_pixel_width(2.0))
+            .with_color(Color::from_rgb(1.0, 0.6, 0.2))
+            .with_label("cumulative");
+        let (counts_id, cumulative_id) = (counts.id, cumulative.id);
+
+        let widget = PlotWidgetBuilder::new()
+            .add_histogram(counts)
+            .add_histogram(cumulative)
+            .with_x_label("value")
+            .with_y_label("samples")
+            .with_autoscale_on_updates(true)
+            .build()
+            .unwrap();
+
+        Self {
+            widget,
+            counts: counts_id,
+            cumulative: cumulative_id,
+            samples,
+        }
+    }
+
+    fn update(&mut self, message: Message) {
+        match message {
+            Message::Plot(msg) => self.widget.update(msg),
+            Message::AddSamples => {
+                self.samples.extend(draw_samples(500));
+                for id in [self.counts, self.cumulative] {
+                    self.widget
+                        .set_histogram_samples(&id, self.samples.clone())
+                        .unwrap();
+                }
+            }
+            Message::SetBinning(binning) => {
+                for id in [self.counts, self.cumulative] {
+                    self.widget
+                        .update_histogram(&id, |histogram| histogram.binning = binning)
+                        .unwrap();
+                }
+            }
+        }
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        let controls = row![
+            button("Add 500 samples").on_press(Message::AddSamples),
+            button("Sturges").on_press(Message::SetBinning(Binning::Sturges)),
+            button("Freedman-Diaconis").on_press(Message::SetBinning(Binning::FreedmanDiaconis)),
+            button("20 bins").on_press(Message::SetBinning(Binning::Count(20))),
+            button("Width 0.25").on_press(Message::SetBinning(Binning::Width(0.25))),
+        ]
+        .spacing(8.0);
+        column![controls, self.widget.view().map(Message::Plot)]
+            .spacing(8.0)
+            .padding(8.0)
+            .into()
+    }
+}
+
+/// Samples from a mixture of two normal distributions.
+fn draw_samples(count: usize) -> Vec<f64> {
+    let mut rng = rand::rng();
+    let narrow = Normal::new(-1.0, 0.5).unwrap();
+    let wide = Normal::new(1.5, 1.0).unwrap();
+    (0..count)
+        .map(|i| {
+            if i % 3 == 0 {
+                narrow.sample(&mut rng)
+            } else {
+                wide.sample(&mut rng)
+            }
+        })
+        .collect()
+}
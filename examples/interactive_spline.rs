@@ -1,13 +1,14 @@
 //! Interactive spline demo.
 //!
 //! Demonstrates selectively disabling plot controls and handling drag events for custom interactivity.
+//! The curves are drawn by the plot's built-in spline interpolation, so they stay smooth when zoomed.
 use iced::{
     Element, mouse,
     widget::{column, text},
 };
 use iced_plot::{
-    Color, DragEvent, LineStyle, MarkerStyle, PlotControls, PlotUiMessage, PlotWidget,
-    PlotWidgetBuilder, ScrollAction, Series, ShapeId,
+    Color, DragEvent, LineInterpolation, LineStyle, MarkerStyle, PlotControls, PlotUiMessage,
+    PlotWidget, PlotWidgetBuilder, ScrollAction, Series, ShapeId,
 };
 
 fn main() -> iced::Result {
@@ -28,6 +29,7 @@ struct App {
     control_series_id: ShapeId,
     control_poly_id: ShapeId,
     spline_series_id: ShapeId,
+    monotone_series_id: ShapeId,
     control_points: Vec<[f64; 2]>,
     active_control_point: Option<usize>,
 }
@@ -50,9 +52,21 @@ impl App {
             .with_label("control points")
             .with_color(Color::from_rgb(1.0, 0.5, 0.2));
 
-        let spline = Series::line_only(sample_catmull_rom(&control_points, 28), LineStyle::solid())
-            .with_label("catmull-rom spline")
-            .with_color(Color::from_rgb(0.2, 0.8, 1.0));
+        let spline = Series::line_only(
+            control_points.clone(),
+            LineStyle::solid().with_interpolation(LineInterpolation::CatmullRom),
+        )
+        .with_label("catmull-rom spline")
+        .with_color(Color::from_rgb(0.2, 0.8, 1.0));
+
+        // Needs increasing x, so it straightens out while control points are dragged past
+        // each other.
+        let monotone = Series::line_only(
+            control_points.clone(),
+            LineStyle::solid().with_interpolation(LineInterpolation::MonotoneCubic),
+        )
+        .with_label("monotone cubic")
+        .with_color(Color::from_rgb(0.4, 0.9, 0.4));
 
         let mut controls_cfg = PlotControls::default();
         controls_cfg.unbind_drag(mouse::Button::Left);
@@ -61,6 +75,7 @@ impl App {
         let control_series_id = control_series.id;
         let control_poly_id = control_poly.id;
         let spline_series_id = spline.id;
+        let monotone_series_id = monotone.id;
 
         let widget = PlotWidgetBuilder::new()
             .with_controls(controls_cfg)
@@ -69,6 +84,7 @@ impl App {
             .with_y_label("y")
             .add_series(control_poly)
             .add_series(spline)
+            .add_series(monotone)
             .add_series(control_series)
             .build()
             .unwrap();
@@ -78,6 +94,7 @@ impl App {
             control_series_id,
             control_poly_id,
             spline_series_id,
+            monotone_series_id,
             control_points,
             active_control_point: None,
         }
@@ -123,13 +140,14 @@ impl App {
             && index < self.control_points.len()
         {
             self.control_points[index] = world;
-            let spline = sample_catmull_rom(&self.control_points, 28);
-            self.widget
-                .set_series_positions(&self.control_series_id, &self.control_points);
-            self.widget
-                .set_series_positions(&self.control_poly_id, &self.control_points);
-            self.widget
-                .set_series_positions(&self.spline_series_id, &spline);
+            for id in [
+                self.control_series_id,
+                self.control_poly_id,
+                self.spline_series_id,
+                self.monotone_series_id,
+            ] {
+                self.widget.set_series_positions(&id, &self.control_points);
+            }
         }
     }
 
@@ -143,51 +161,6 @@ impl App {
     }
 }
 
-fn sample_catmull_rom(control_points: &[[f64; 2]], samples_per_segment: usize) -> Vec<[f64; 2]> {
-    if control_points.len() < 2 {
-        return control_points.to_vec();
-    }
-
-    let mut out = Vec::new();
-
-    for i in 0..(control_points.len() - 1) {
-        let p0 = control_points[i.saturating_sub(1)];
-        let p1 = control_points[i];
-        let p2 = control_points[i + 1];
-        let p3 = control_points[(i + 2).min(control_points.len() - 1)];
-
-        for s in 0..samples_per_segment {
-            let t = s as f64 / samples_per_segment as f64;
-            out.push(catmull_rom_point(p0, p1, p2, p3, t));
-        }
-    }
-
-    if let Some(last) = control_points.last().copied() {
-        out.push(last);
-    }
-
-    out
-}
-
-fn catmull_rom_point(p0: [f64; 2], p1: [f64; 2], p2: [f64; 2], p3: [f64; 2], t: f64) -> [f64; 2] {
-    let t2 = t * t;
-    let t3 = t2 * t;
-
-    let x = 0.5
-        * ((2.0 * p1[0])
-            + (-p0[0] + p2[0]) * t
-            + (2.0 * p0[0] - 5.0 * p1[0] + 4.0 * p2[0] - p3[0]) * t2
-            + (-p0[0] + 3.0 * p1[0] - 3.0 * p2[0] + p3[0]) * t3);
-
-    let y = 0.5
-        * ((2.0 * p1[1])
-            + (-p0[1] + p2[1]) * t
-            + (2.0 * p0[1] - 5.0 * p1[1] + 4.0 * p2[1] - p3[1]) * t2
-            + (-p0[1] + 3.0 * p1[1] - 3.0 * p2[1] + p3[1]) * t3);
-
-    [x, y]
-}
-
 fn nearest_control_point_index(
     control_points: &[[f64; 2]],
     world: [f64; 2],
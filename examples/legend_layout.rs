@@ -0,0 +1,63 @@
+//! Legend layout example: a wide legend laid out in rows below the plot.
+use iced_plot::{
+    Color, Legend, LegendOrientation, LegendPosition, LineStyle, MarkerStyle, PlotUiMessage,
+    PlotWidget, PlotWidgetBuilder, Series,
+};
+
+use iced::Element;
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let colors = [
+        Color::from_rgb(0.2, 0.6, 1.0),
+        Color::from_rgb(1.0, 0.6, 0.2),
+        Color::from_rgb(0.3, 0.8, 0.4),
+        Color::from_rgb(0.9, 0.3, 0.5),
+        Color::from_rgb(0.6, 0.4, 0.9),
+        Color::from_rgb(0.9, 0.8, 0.2),
+    ];
+
+    // Try LegendPosition::Best to let the legend find the emptiest corner.
+    let legend = Legend::new()
+        .with_position(LegendPosition::OutsideBottom)
+        .with_orientation(LegendOrientation::Horizontal)
+        .with_columns(3);
+
+    let mut builder = PlotWidgetBuilder::new()
+        .with_x_label("t")
+        .with_y_label("amplitude")
+        .with_legend(legend);
+    for (harmonic, color) in colors.into_iter().enumerate() {
+        let n = (harmonic + 1) as f64;
+        let positions = (0..200)
+            .map(|i| {
+                let t = i as f64 * 0.05;
+                [t, (n * t).sin() / n]
+            })
+            .collect();
+        builder = builder.add_series(
+            Series::new(
+                positions,
+                MarkerStyle::circle(3.0),
+                LineStyle::solid().with_pixel_width(1.5),
+            )
+            .with_color(color)
+            .with_label(format!("harmonic {}", harmonic + 1)),
+        );
+    }
+    builder.build().unwrap()
+}
@@ -0,0 +1,57 @@
+//! Line join and cap example: thick zigzags drawn with each join and cap style.
+use iced_plot::{
+    Color, LineCap, LineJoin, LineStyle, PlotUiMessage, PlotWidget, PlotWidgetBuilder, Series,
+};
+
+use iced::Element;
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let styles = [
+        (LineJoin::miter(), LineCap::Butt, "miter join, butt cap"),
+        (LineJoin::Round, LineCap::Round, "round join, round cap"),
+        (LineJoin::Bevel, LineCap::Square, "bevel join, square cap"),
+    ];
+    let colors = [
+        Color::from_rgb(0.2, 0.6, 1.0),
+        Color::from_rgb(1.0, 0.6, 0.2),
+        Color::from_rgb(0.3, 0.8, 0.4),
+    ];
+
+    let mut builder = PlotWidgetBuilder::new().with_x_label("x").with_y_label("y");
+    for (row, ((join, cap, label), color)) in styles.into_iter().zip(colors).enumerate() {
+        // Corners get sharper to the right, so the miter turns into a bevel past its limit.
+        let offset = 3.0 * row as f64;
+        let positions = [0.0, 1.5, 2.5, 3.2, 3.7, 4.0, 4.2]
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| [x, offset + (i % 2) as f64 * 2.0])
+            .collect();
+        builder = builder.add_series(
+            Series::line_only(
+                positions,
+                LineStyle::solid()
+                    .with_pixel_width(14.0)
+                    .with_join(join)
+                    .with_cap(cap),
+            )
+            .with_color(Color { a: 0.7, ..color })
+            .with_label(label),
+        );
+    }
+    builder.build().unwrap()
+}
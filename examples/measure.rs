@@ -0,0 +1,103 @@
+//! Measurement ruler on log-log axes.
+//!
+//! Drag with the left button to measure between two samples; the ends snap to the nearest
+//! sample. On log-log axes the slope is the exponent of the power law. Drag with the right
+//! button to pan, and press Escape to clear the ruler.
+use iced::{
+    Element, keyboard, mouse,
+    widget::{column, text},
+};
+use iced_plot::{
+    AxisScale, Color, DragAction, KeyAction, PlotControls, PlotUiMessage, PlotWidget,
+    PlotWidgetBuilder, Series, log_formatter, log_tick_producer,
+};
+
+fn main() -> iced::Result {
+    iced::application(App::new, App::update, App::view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+struct App {
+    widget: PlotWidget,
+}
+
+impl App {
+    fn new() -> Self {
+        let power_law = |exponent: f64, scale: f64| -> Vec<[f64; 2]> {
+            (0..=40)
+                .map(|i| {
+                    let x = 10f64.powf(i as f64 / 10.0);
+                    [x, scale * x.powf(exponent)]
+                })
+                .collect()
+        };
+        let square = Series::circles(power_law(2.0, 1.0), 4.0)
+            .with_label("y = x²")
+            .with_color(Color::from_rgb(0.2, 0.6, 1.0));
+        let root = Series::circles(power_law(0.5, 10.0), 4.0)
+            .with_label("y = 10√x")
+            .with_color(Color::from_rgb(1.0, 0.5, 0.2));
+
+        let mut controls = PlotControls::default();
+        controls
+            .bind_drag(mouse::Button::Left, DragAction::Measure)
+            .bind_drag(mouse::Button::Right, DragAction::Pan)
+            .bind_key(
+                keyboard::Key::Named(keyboard::key::Named::Escape),
+                KeyAction::ClearMeasurement,
+            );
+
+        let log = AxisScale::Log { base: 10.0 };
+        let widget = PlotWidgetBuilder::new()
+            .with_controls(controls)
+            .with_measure_snap(true)
+            .with_x_label("x")
+            .with_y_label("y")
+            .with_x_scale(log)
+            .with_y_scale(log)
+            .with_x_tick_producer(|min, max| log_tick_producer(10.0, min, max))
+            .with_y_tick_producer(|min, max| log_tick_producer(10.0, min, max))
+            .with_x_tick_formatter(|t| log_formatter(t, 10.0))
+            .with_y_tick_formatter(|t| log_formatter(t, 10.0))
+            .add_series(square)
+            .add_series(root)
+            .build()
+            .unwrap();
+        Self { widget }
+    }
+
+    fn update(&mut self, message: PlotUiMessage) {
+        if let Some(measurement) = message.get_measurement()
+            && measurement.finished
+        {
+            println!(
+                "measured from {:?} to {:?}: slope {}",
+                measurement.start,
+                measurement.end,
+                format_slope(measurement.slope)
+            );
+        }
+        self.widget.update(message);
+    }
+
+    fn view(&self) -> Element<'_, PlotUiMessage> {
+        let status = match self.widget.measurement() {
+            Some(m) => format!(
+                "Δx ×{:.3}, Δy ×{:.3}, slope {}",
+                m.delta_x,
+                m.delta_y,
+                format_slope(m.slope)
+            ),
+            None => "Left drag: measure, right drag: pan, Escape: clear.".to_string(),
+        };
+        column![text(status).size(16), self.widget.view()]
+            .spacing(8)
+            .into()
+    }
+}
+
+fn format_slope(slope: Option<f64>) -> String {
+    slope.map_or_else(|| "vertical".to_string(), |slope| format!("{slope:.3}"))
+}
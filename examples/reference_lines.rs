@@ -2,7 +2,9 @@
 //! functions that have asymptotes aligned to those lines.
 use iced_plot::PlotUiMessage;
 use iced_plot::PlotWidget;
-use iced_plot::{Color, HLine, LineStyle, MarkerStyle, PlotWidgetBuilder, Series, VLine};
+use iced_plot::{
+    Color, DashPattern, HLine, LineStyle, MarkerStyle, PlotWidgetBuilder, Series, VLine,
+};
 
 use iced::Element;
 use std::f64::consts::{PI, TAU};
@@ -74,7 +76,7 @@ fn new() -> PlotWidget {
         .with_label("2π")
         .with_color(Color::from_rgb(0.9, 0.5, 0.3))
         .with_width(2.0)
-        .with_style(LineStyle::dashed(1.0));
+        .with_style(LineStyle::pattern(DashPattern::dash_dot(8.0, 4.0)));
 
     // Add horizontal reference lines at y = ±1 (asymptotes of tanh)
     let hline1 = HLine::new(1.0)
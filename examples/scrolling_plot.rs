@@ -45,16 +45,10 @@ impl App {
                 self.last_tick = now;
 
                 let y = (self.x * 0.5).sin();
-                // Update the series
+                // Only the new point is uploaded; the series drops its oldest point once
+                // it holds more than 300.
                 self.widget
-                    .update_series(&self.series_id, |series| {
-                        // Add new point
-                        series.positions.push([self.x, y]);
-                        // Keep only last 300 points for scrolling effect
-                        if series.positions.len() > 300 {
-                            series.positions.remove(0);
-                        }
-                    })
+                    .append_series_points(&self.series_id, [[self.x, y]])
                     .unwrap();
                 self.x += 0.1f64;
             }
@@ -73,13 +67,15 @@ impl App {
         let x = 0.0f64;
         let series = Series::markers_only(vec![[x, (x * 0.5).sin()]], MarkerStyle::ring(10.0))
             .with_label("scrolling")
-            .with_color(Color::WHITE);
+            .with_color(Color::WHITE)
+            .with_max_len(300);
         let hline = HLine::new(0.0);
         let fill = Fill::new(hline.id, series.id);
         Self {
             series_id: series.id,
             widget: PlotWidgetBuilder::new()
-                .with_autoscale_on_updates(true)
+                // Keep the newest 20 units of x in view.
+                .with_follow_latest(20.0)
                 .add_series(series)
                 .add_hline(hline)
                 .add_fill(fill)
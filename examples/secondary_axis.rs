@@ -0,0 +1,61 @@
+use iced::Element;
+use iced_plot::{
+    Color, LineStyle, MarkerStyle, PlotUiMessage, PlotWidget, PlotWidgetBuilder, Series, YAxis,
+};
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let hours: Vec<f64> = (0..48).map(|i| i as f64 * 0.5).collect();
+
+    // Temperature in degrees on the left axis.
+    let temperature = Series::new(
+        hours
+            .iter()
+            .map(|&h| {
+                [
+                    h,
+                    15.0 + 6.0 * ((h - 9.0) / 24.0 * std::f64::consts::TAU).sin(),
+                ]
+            })
+            .collect(),
+        MarkerStyle::circle(4.0),
+        LineStyle::solid(),
+    )
+    .with_label("temperature (°C)")
+    .with_color(Color::from_rgb(1.0, 0.5, 0.2));
+
+    // Pressure in hectopascals on the right axis, with its own range.
+    let pressure = Series::line_only(
+        hours
+            .iter()
+            .map(|&h| [h, 1012.0 + 4.0 * (h / 8.0).cos()])
+            .collect(),
+        LineStyle::dashed(6.0),
+    )
+    .with_label("pressure (hPa)")
+    .with_color(Color::from_rgb(0.3, 0.6, 1.0))
+    .with_y_axis(YAxis::Secondary);
+
+    PlotWidgetBuilder::new()
+        .with_x_label("hour")
+        .with_y_label("temperature (°C)")
+        .with_y2_label("pressure (hPa)")
+        .add_series(temperature)
+        .add_series(pressure)
+        .build()
+        .unwrap()
+}
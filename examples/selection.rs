@@ -0,0 +1,85 @@
+//! Box and lasso selection.
+//!
+//! Drag with the left button to draw a lasso, or with the right button to draw a box.
+//! Hold Shift to add to the selection, or Ctrl to remove from it. Escape clears it.
+use iced::{
+    Element, mouse,
+    widget::{column, text},
+};
+use iced_plot::{
+    Color, DragAction, MarkerStyle, PlotControls, PlotUiMessage, PlotWidget, PlotWidgetBuilder,
+    Series,
+};
+
+fn main() -> iced::Result {
+    iced::application(App::new, App::update, App::view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+struct App {
+    widget: PlotWidget,
+}
+
+impl App {
+    fn new() -> Self {
+        // Two clouds on golden-angle spirals.
+        let cloud = |center: [f64; 2], len: usize| -> Vec<[f64; 2]> {
+            (0..len)
+                .map(|i| {
+                    let radius = (i as f64 / len as f64).sqrt() * 3.0;
+                    let angle = i as f64 * 2.399_963;
+                    [
+                        center[0] + radius * angle.cos(),
+                        center[1] + radius * angle.sin(),
+                    ]
+                })
+                .collect()
+        };
+        let left = Series::markers_only(cloud([-2.0, 0.0], 600), MarkerStyle::circle(4.0))
+            .with_label("left")
+            .with_color(Color::from_rgb(0.2, 0.6, 1.0));
+        let right = Series::markers_only(cloud([2.5, 1.0], 400), MarkerStyle::circle(4.0))
+            .with_label("right")
+            .with_color(Color::from_rgb(1.0, 0.5, 0.2));
+
+        let mut controls = PlotControls::default();
+        controls
+            .bind_drag(mouse::Button::Left, DragAction::LassoSelect)
+            .bind_drag(mouse::Button::Right, DragAction::BoxSelect)
+            .bind_drag(mouse::Button::Middle, DragAction::Pan);
+
+        let widget = PlotWidgetBuilder::new()
+            .with_controls(controls)
+            .with_x_label("x")
+            .with_y_label("y")
+            .with_pick_highlight_provider(|_, point| {
+                point.resize_marker(1.5);
+                point.color = Color::from_rgb(0.9, 0.1, 0.3);
+                None
+            })
+            .add_series(left)
+            .add_series(right)
+            .build()
+            .unwrap();
+        Self { widget }
+    }
+
+    fn update(&mut self, message: PlotUiMessage) {
+        self.widget.update(message);
+    }
+
+    fn view(&self) -> Element<'_, PlotUiMessage> {
+        let selected = self.widget.selected_points().len();
+        column![
+            text(format!(
+                "{selected} selected. Left drag: lasso, right drag: box, Shift: add, Ctrl: remove."
+            ))
+            .size(16),
+            self.widget.view(),
+        ]
+        .spacing(8)
+        .into()
+    }
+}
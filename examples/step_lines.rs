@@ -0,0 +1,74 @@
+//! Step line example: the same digital signal drawn with each step interpolation mode.
+use iced_plot::{
+    Color, Fill, HLine, LineInterpolation, LineStyle, MarkerStyle, PlotUiMessage, PlotWidget,
+    PlotWidgetBuilder, Series,
+};
+
+use iced::Element;
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    let bits = [0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0];
+    let modes = [
+        (
+            LineInterpolation::StepPre,
+            "step pre",
+            Color::from_rgb(0.2, 0.6, 1.0),
+        ),
+        (
+            LineInterpolation::StepMid,
+            "step mid",
+            Color::from_rgb(1.0, 0.6, 0.2),
+        ),
+        (
+            LineInterpolation::StepPost,
+            "step post",
+            Color::from_rgb(0.3, 0.8, 0.4),
+        ),
+    ];
+
+    let mut builder = PlotWidgetBuilder::new()
+        .with_x_label("sample")
+        .with_y_label("level");
+    for (row, (interpolation, label, color)) in modes.into_iter().enumerate() {
+        // Stack the signals so their steps can be compared.
+        let offset = 1.5 * (modes.len() - 1 - row) as f64;
+        let positions = bits
+            .iter()
+            .enumerate()
+            .map(|(i, bit)| [i as f64, bit + offset])
+            .collect();
+        let series = Series::new(
+            positions,
+            MarkerStyle::circle(5.0),
+            LineStyle::solid()
+                .with_pixel_width(2.0)
+                .with_interpolation(interpolation),
+        )
+        .with_color(color)
+        .with_label(label);
+
+        // Fills follow the steps too.
+        let baseline = HLine::new(offset).with_color(Color::TRANSPARENT);
+        let fill = Fill::new(series.id, baseline.id).with_color(Color { a: 0.2, ..color });
+        builder = builder
+            .add_series(series)
+            .add_hline(baseline)
+            .add_fill(fill);
+    }
+    builder.build().unwrap()
+}
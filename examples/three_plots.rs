@@ -43,8 +43,10 @@ impl App {
         match plot_id {
             1 => {
                 match msg.get_hover_pick_event() {
-                    Some(HoverPickEvent::Hover(p1)) => {
-                        if let Some([x, _]) = self.w1.point_position(p1) {
+                    Some(HoverPickEvent::Hover(hit)) => {
+                        if let Some(p1) = hit.point_id()
+                            && let Some([x, _]) = self.w1.point_position(p1)
+                        {
                             if let Some(p2) = self.w2.nearest_point_horizontal(self.s2_id, x) {
                                 self.w2.add_hover_point(p2);
                             }
@@ -53,8 +55,10 @@ impl App {
                             }
                         }
                     }
-                    Some(HoverPickEvent::Pick(p1)) => {
-                        if let Some([x, _]) = self.w1.point_position(p1) {
+                    Some(HoverPickEvent::Pick(hit)) => {
+                        if let Some(p1) = hit.point_id()
+                            && let Some([x, _]) = self.w1.point_position(p1)
+                        {
                             if let Some(p2) = self.w2.nearest_point_horizontal(self.s2_id, x) {
                                 self.w2.add_pick_point(p2);
                             }
@@ -73,8 +77,10 @@ impl App {
             }
             2 => {
                 match msg.get_hover_pick_event() {
-                    Some(HoverPickEvent::Hover(p2)) => {
-                        if let Some([x, _]) = self.w2.point_position(p2) {
+                    Some(HoverPickEvent::Hover(hit)) => {
+                        if let Some(p2) = hit.point_id()
+                            && let Some([x, _]) = self.w2.point_position(p2)
+                        {
                             if let Some(p1) = self.w1.nearest_point_horizontal(self.s1_id, x) {
                                 self.w1.add_hover_point(p1);
                             }
@@ -83,8 +89,10 @@ impl App {
                             }
                         }
                     }
-                    Some(HoverPickEvent::Pick(p2)) => {
-                        if let Some([x, _]) = self.w2.point_position(p2) {
+                    Some(HoverPickEvent::Pick(hit)) => {
+                        if let Some(p2) = hit.point_id()
+                            && let Some([x, _]) = self.w2.point_position(p2)
+                        {
                             if let Some(p1) = self.w1.nearest_point_horizontal(self.s1_id, x) {
                                 self.w1.add_pick_point(p1);
                             }
@@ -103,8 +111,10 @@ impl App {
             }
             3 => {
                 match msg.get_hover_pick_event() {
-                    Some(HoverPickEvent::Hover(p3)) => {
-                        if let Some([x, _]) = self.w3.point_position(p3) {
+                    Some(HoverPickEvent::Hover(hit)) => {
+                        if let Some(p3) = hit.point_id()
+                            && let Some([x, _]) = self.w3.point_position(p3)
+                        {
                             if let Some(p1) = self.w1.nearest_point_horizontal(self.s1_id, x) {
                                 self.w1.add_hover_point(p1);
                             }
@@ -113,8 +123,10 @@ impl App {
                             }
                         }
                     }
-                    Some(HoverPickEvent::Pick(p3)) => {
-                        if let Some([x, _]) = self.w3.point_position(p3) {
+                    Some(HoverPickEvent::Pick(hit)) => {
+                        if let Some(p3) = hit.point_id()
+                            && let Some([x, _]) = self.w3.point_position(p3)
+                        {
                             if let Some(p1) = self.w1.nearest_point_horizontal(self.s1_id, x) {
                                 self.w1.add_pick_point(p1);
                             }
@@ -0,0 +1,44 @@
+use iced::Element;
+use iced_plot::{AxisScale, Color, PlotUiMessage, PlotWidget, PlotWidgetBuilder, Series};
+
+fn main() -> iced::Result {
+    iced::application(new, update, view)
+        .font(include_bytes!("fonts/FiraCodeNerdFont-Regular.ttf"))
+        .default_font(iced::Font::with_name("FiraCode Nerd Font"))
+        .run()
+}
+
+fn update(widget: &mut PlotWidget, message: PlotUiMessage) {
+    widget.update(message);
+}
+
+fn view(widget: &PlotWidget) -> Element<'_, PlotUiMessage> {
+    widget.view()
+}
+
+fn new() -> PlotWidget {
+    // Hourly samples for two weeks, starting 2024-03-01 00:00:00 UTC.
+    const START: f64 = 1_709_251_200.0;
+    let values: Vec<[f64; 2]> = (0..24 * 14)
+        .map(|hour| {
+            let t = hour as f64;
+            let daily = (t / 24.0 * std::f64::consts::TAU).sin();
+            [START + t * 3600.0, 15.0 + 6.0 * daily + 0.02 * t]
+        })
+        .collect();
+
+    let series = Series::line_only(values, Default::default())
+        .with_label("temperature")
+        .with_color(Color::from_rgb(1.0, 0.6, 0.2));
+
+    // Time axes pick calendar-aligned ticks and date/time labels automatically.
+    // Zoom in to see the labels switch from days to hours, minutes and seconds.
+    PlotWidgetBuilder::new()
+        .with_x_label("time (UTC)")
+        .with_y_label("°C")
+        .with_x_scale(AxisScale::Time)
+        .with_cursor_overlay(true)
+        .add_series(series)
+        .build()
+        .unwrap()
+}
@@ -3,38 +3,43 @@ use iced::widget::text::Wrapping;
 use iced::widget::{column, container, row, text};
 use iced::{Color, Element, Length};
 
-/// Stack the element with the labels on the bottom and left.
+/// Stack the element with the labels on the bottom, left and right.
 pub(crate) fn stack_with_labels<'a, M: 'a>(
     widget: impl Into<Element<'a, M>>,
     x_label: &'a str,
     y_label: &'a str,
+    y2_label: &'a str,
     axis_label_size: f32,
     axis_label_color: Color,
 ) -> Element<'a, M> {
-    if x_label.is_empty() && y_label.is_empty() {
+    let widget = if x_label.is_empty() {
         widget.into()
-    } else if x_label.is_empty() {
-        row![
-            y_axis_label(y_label, axis_label_size, axis_label_color),
-            widget.into()
-        ]
-        .into()
-    } else if y_label.is_empty() {
+    } else {
         column![
             widget.into(),
             x_axis_label(x_label, axis_label_size, axis_label_color)
         ]
         .into()
-    } else {
-        row![
-            y_axis_label(y_label, axis_label_size, axis_label_color),
-            column![
-                widget.into(),
-                x_axis_label(x_label, axis_label_size, axis_label_color)
-            ]
-        ]
-        .into()
+    };
+    if y_label.is_empty() && y2_label.is_empty() {
+        return widget;
     }
+    row![
+        (!y_label.is_empty()).then(|| y_axis_label(
+            y_label,
+            axis_label_size,
+            axis_label_color,
+            Horizontal::Left
+        )),
+        widget,
+        (!y2_label.is_empty()).then(|| y_axis_label(
+            y2_label,
+            axis_label_size,
+            axis_label_color,
+            Horizontal::Right
+        )),
+    ]
+    .into()
 }
 
 fn x_axis_label<'a, M: 'a>(label: &'a str, size: f32, color: Color) -> Element<'a, M> {
@@ -46,9 +51,14 @@ fn x_axis_label<'a, M: 'a>(label: &'a str, size: f32, color: Color) -> Element<'
         .into()
 }
 
-fn y_axis_label<'a, M: 'a>(label: &'a str, size: f32, color: Color) -> Element<'a, M> {
+pub(crate) fn y_axis_label<'a, M: 'a>(
+    label: &'a str,
+    size: f32,
+    color: Color,
+    align_x: Horizontal,
+) -> Element<'a, M> {
     container(text(label).size(size).color(color).wrapping(Wrapping::Word))
-        .align_x(Horizontal::Left)
+        .align_x(align_x)
         .align_y(Vertical::Center)
         .width(Length::Shrink)
         .max_width(100.0)
@@ -12,6 +12,12 @@ pub enum AxisScale {
         /// The base of the logarithm.
         base: f64,
     },
+
+    /// Time axis: values are seconds since the Unix epoch (UTC).
+    ///
+    /// Plot space is linear in time; use [`crate::time_tick_producer`] and
+    /// [`crate::time_formatter`] for calendar-aligned ticks and date/time labels.
+    Time,
 }
 
 impl AxisScale {
@@ -0,0 +1,355 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{Color, series::Series, series::ShapeId};
+
+/// Direction in which bars extend from their baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BarOrientation {
+    /// Bars are centered on each x position and extend from the baseline to y.
+    #[default]
+    Vertical,
+    /// Bars are centered on each y position and extend from the baseline to x.
+    Horizontal,
+}
+
+/// Identifier shared by bar series that are laid out together.
+///
+/// Use the same group in [`BarLayout::Grouped`] or [`BarLayout::Stacked`] for every
+/// series that should share categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BarGroup(u64);
+
+impl BarGroup {
+    /// Create a new unique bar group.
+    pub fn new() -> Self {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Default for BarGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How bars from several series sharing the same categories are arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BarLayout {
+    /// Each series draws its bars centered on its own positions. Later series draw on top.
+    #[default]
+    Overlap,
+    /// Visible series in the group split the bar width and are placed side by side,
+    /// in the order they were added to the plot.
+    Grouped(BarGroup),
+    /// Visible series in the group are stacked end to end, in the order they were added
+    /// to the plot. Positive and negative values stack separately.
+    Stacked(BarGroup),
+}
+
+/// Bar styling options for a [`Series`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BarStyle {
+    /// Width of each bar (or each group of bars) in world units.
+    pub width: f64,
+    /// Value the bars extend from. For stacked layouts, the stack starts at the
+    /// baseline of the first series in the group.
+    pub baseline: f64,
+    /// Direction of the bars.
+    pub orientation: BarOrientation,
+    /// Arrangement relative to other bar series.
+    pub layout: BarLayout,
+}
+
+impl Default for BarStyle {
+    fn default() -> Self {
+        Self {
+            width: 0.8,
+            baseline: 0.0,
+            orientation: BarOrientation::Vertical,
+            layout: BarLayout::Overlap,
+        }
+    }
+}
+
+impl BarStyle {
+    /// Create a vertical bar style with the given width in world units.
+    pub fn new(width: f64) -> Self {
+        Self {
+            width,
+            ..Self::default()
+        }
+    }
+
+    /// Create a horizontal bar style with the given height in world units.
+    pub fn horizontal(width: f64) -> Self {
+        Self {
+            width,
+            orientation: BarOrientation::Horizontal,
+            ..Self::default()
+        }
+    }
+
+    /// Set the value the bars extend from.
+    ///
+    /// On a log value axis, a baseline that isn't positive starts the bars one power of the
+    /// base below the lowest bar end on that axis.
+    pub fn with_baseline(mut self, baseline: f64) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Set the bar orientation.
+    pub fn with_orientation(mut self, orientation: BarOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the layout relative to other bar series.
+    pub fn with_layout(mut self, layout: BarLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Place the bars side by side with other series in `group`.
+    pub fn grouped(self, group: BarGroup) -> Self {
+        self.with_layout(BarLayout::Grouped(group))
+    }
+
+    /// Stack the bars on top of other series in `group`.
+    pub fn stacked(self, group: BarGroup) -> Self {
+        self.with_layout(BarLayout::Stacked(group))
+    }
+}
+
+/// One laid-out bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct BarRect {
+    /// Lower-left corner.
+    pub(crate) min: [f64; 2],
+    /// Upper-right corner.
+    pub(crate) max: [f64; 2],
+    pub(crate) color: Color,
+    /// Index of the source position in the series.
+    pub(crate) point_index: usize,
+}
+
+impl BarRect {
+    pub(crate) fn contains(&self, point: [f64; 2]) -> bool {
+        point[0] >= self.min[0]
+            && point[0] <= self.max[0]
+            && point[1] >= self.min[1]
+            && point[1] <= self.max[1]
+    }
+}
+
+/// Compute bar rectangles in data coordinates for every bar series.
+///
+/// Series must be passed in plot order; hidden series should already be filtered out so
+/// they don't reserve a slot in grouped layouts or lift stacked bars.
+pub(crate) fn layout_bars<'a>(
+    series: impl IntoIterator<Item = &'a Series>,
+) -> Vec<(ShapeId, Vec<BarRect>)> {
+    let series: Vec<(&Series, BarStyle)> = series
+        .into_iter()
+        .filter_map(|s| s.bar_style.map(|style| (s, style)))
+        .collect();
+
+    let mut group_sizes: HashMap<BarGroup, usize> = HashMap::new();
+    for (_, style) in &series {
+        if let BarLayout::Grouped(group) = style.layout {
+            *group_sizes.entry(group).or_default() += 1;
+        }
+    }
+    let mut group_slots: HashMap<BarGroup, usize> = HashMap::new();
+    let mut stack_bases: HashMap<BarGroup, f64> = HashMap::new();
+    // Running (positive, negative) stack ends per group and category.
+    let mut stack_ends: HashMap<(BarGroup, u64), (f64, f64)> = HashMap::new();
+
+    let mut out = Vec::with_capacity(series.len());
+    for (s, style) in series {
+        let (offset, width) = match style.layout {
+            BarLayout::Grouped(group) => {
+                let count = group_sizes[&group] as f64;
+                let slot = group_slots.entry(group).or_default();
+                let sub_width = style.width / count;
+                let offset = -0.5 * style.width + (*slot as f64 + 0.5) * sub_width;
+                *slot += 1;
+                (offset, sub_width)
+            }
+            BarLayout::Overlap | BarLayout::Stacked(_) => (0.0, style.width),
+        };
+
+        let mut rects = Vec::with_capacity(s.positions.len());
+        for (index, &[x, y]) in s.positions.iter().enumerate() {
+            let (category, value) = match style.orientation {
+                BarOrientation::Vertical => (x, y),
+                BarOrientation::Horizontal => (y, x),
+            };
+            if !(category.is_finite() && value.is_finite()) {
+                continue;
+            }
+
+            // -0.0 and 0.0 are the same category.
+            let category = if category == 0.0 { 0.0 } else { category };
+            let (start, end) = match style.layout {
+                BarLayout::Stacked(group) => {
+                    let base = *stack_bases.entry(group).or_insert(style.baseline);
+                    let length = value - style.baseline;
+                    let ends = stack_ends
+                        .entry((group, category.to_bits()))
+                        .or_insert((base, base));
+                    let end = if length >= 0.0 {
+                        &mut ends.0
+                    } else {
+                        &mut ends.1
+                    };
+                    let start = *end;
+                    *end += length;
+                    (start, *end)
+                }
+                BarLayout::Overlap | BarLayout::Grouped(_) => (style.baseline, value),
+            };
+
+            let center = category + offset;
+            let across = [center - 0.5 * width, center + 0.5 * width];
+            let along = [start.min(end), start.max(end)];
+            let (min, max) = match style.orientation {
+                BarOrientation::Vertical => ([across[0], along[0]], [across[1], along[1]]),
+                BarOrientation::Horizontal => ([along[0], across[0]], [along[1], across[1]]),
+            };
+            let color = s
+                .point_colors
+                .as_ref()
+                .and_then(|colors| colors.get(index))
+                .copied()
+                .unwrap_or(s.color);
+            rects.push(BarRect {
+                min,
+                max,
+                color,
+                point_index: index,
+            });
+        }
+        out.push((s.id, rects));
+    }
+    out
+}
+
+/// Lift bar ends a log value axis can't show, like a zero baseline, to one power of the
+/// base below the lowest positive bar end on that axis, so the bars rise from below the data
+/// instead of disappearing.
+///
+/// `log_value_axis` returns, for a series whose value axis is logarithmic, a key identifying
+/// that axis, the coordinate index of its values and the log base.
+pub(crate) fn clamp_log_bar_ends<K: Copy + Eq + Hash>(
+    bars: &mut [(ShapeId, Vec<BarRect>)],
+    log_value_axis: impl Fn(ShapeId) -> Option<(K, usize, f64)>,
+) {
+    let mut floors: HashMap<K, f64> = HashMap::new();
+    for (id, rects) in bars.iter() {
+        let Some((key, i, base)) = log_value_axis(*id) else {
+            continue;
+        };
+        for value in rects.iter().flat_map(|rect| [rect.min[i], rect.max[i]]) {
+            if value > 0.0 {
+                let floor = floors.entry(key).or_insert(f64::INFINITY);
+                *floor = floor.min(value / base);
+            }
+        }
+    }
+    for (id, rects) in bars.iter_mut() {
+        let Some((key, i, _)) = log_value_axis(*id) else {
+            continue;
+        };
+        let Some(&floor) = floors.get(&key) else {
+            continue;
+        };
+        for rect in rects.iter_mut() {
+            for end in [&mut rect.min[i], &mut rect.max[i]] {
+                if *end <= 0.0 {
+                    *end = floor;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grouped_bars_split_width_in_insertion_order() {
+        let group = BarGroup::new();
+        let a = Series::bars(vec![[0.0, 1.0]], BarStyle::new(1.0).grouped(group));
+        let b = Series::bars(vec![[0.0, 2.0]], BarStyle::new(1.0).grouped(group));
+
+        let bars = layout_bars([&a, &b]);
+
+        assert_eq!(bars[0].1[0].min, [-0.5, 0.0]);
+        assert_eq!(bars[0].1[0].max, [0.0, 1.0]);
+        assert_eq!(bars[1].1[0].min, [0.0, 0.0]);
+        assert_eq!(bars[1].1[0].max, [0.5, 2.0]);
+    }
+
+    #[test]
+    fn stacked_bars_accumulate_per_category_and_sign() {
+        let group = BarGroup::new();
+        let a = Series::bars(
+            vec![[0.0, 2.0], [1.0, -1.0]],
+            BarStyle::new(1.0).stacked(group),
+        );
+        let b = Series::bars(
+            vec![[0.0, 3.0], [1.0, -2.0]],
+            BarStyle::new(1.0).stacked(group),
+        );
+
+        let bars = layout_bars([&a, &b]);
+
+        assert_eq!(bars[1].1[0].min[1], 2.0);
+        assert_eq!(bars[1].1[0].max[1], 5.0);
+        assert_eq!(bars[1].1[1].min[1], -3.0);
+        assert_eq!(bars[1].1[1].max[1], -1.0);
+    }
+
+    #[test]
+    fn horizontal_bars_extend_along_x_from_baseline() {
+        let s = Series::bars(
+            vec![[3.0, 2.0]],
+            BarStyle::horizontal(0.5).with_baseline(1.0),
+        );
+
+        let bars = layout_bars([&s]);
+
+        assert_eq!(bars[0].1[0].min, [1.0, 1.75]);
+        assert_eq!(bars[0].1[0].max, [3.0, 2.25]);
+    }
+
+    #[test]
+    fn negative_zero_category_stacks_with_zero() {
+        let group = BarGroup::new();
+        let a = Series::bars(vec![[0.0, 2.0]], BarStyle::new(1.0).stacked(group));
+        let b = Series::bars(vec![[-0.0, 3.0]], BarStyle::new(1.0).stacked(group));
+
+        let bars = layout_bars([&a, &b]);
+
+        assert_eq!(bars[1].1[0].min[1], 2.0);
+        assert_eq!(bars[1].1[0].max[1], 5.0);
+    }
+
+    #[test]
+    fn zero_baselines_rise_from_below_the_data_on_log_axes() {
+        let a = Series::bars(vec![[0.0, 10.0], [1.0, 1000.0]], BarStyle::new(1.0));
+        let b = Series::bars(vec![[0.0, 100.0]], BarStyle::horizontal(1.0));
+        let mut bars = layout_bars([&a, &b]);
+
+        clamp_log_bar_ends(&mut bars, |id| (id == a.id).then_some(((), 1, 10.0)));
+
+        assert_eq!(bars[0].1[0].min[1], 1.0);
+        assert_eq!(bars[0].1[1].min[1], 1.0);
+        // Series on a linear value axis are left alone.
+        assert_eq!(bars[1].1[0].min[0], 0.0);
+    }
+}
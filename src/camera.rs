@@ -1,6 +1,6 @@
 use glam::{DVec2, Mat4, Vec3};
 
-const EPSILON_SMALL: f64 = 1e-6;
+pub(crate) const EPSILON_SMALL: f64 = 1e-6;
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -106,6 +106,14 @@ impl Camera {
         self.position - self.render_offset
     }
 
+    /// Size of one screen pixel in world units when viewed in `bounds`.
+    pub(crate) fn world_per_px(&self, bounds: &iced::Rectangle) -> [f64; 2] {
+        [
+            2.0 * self.half_extents.x / bounds.width.max(1.0) as f64,
+            2.0 * self.half_extents.y / bounds.height.max(1.0) as f64,
+        ]
+    }
+
     pub(crate) fn x_range(&self) -> [f64; 2] {
         [
             self.position.x - self.half_extents.x,
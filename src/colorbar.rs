@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use iced::alignment::{Horizontal, Vertical};
+use iced::widget::{container, row, stack, text};
+use iced::{Element, Length, Radians, gradient, padding};
+
+use crate::{
+    ColorNorm, ColorScale, Contour, Raster, RasterData, Series, ShapeId, Tick, TickWeight,
+    axes_labels,
+    message::PlotUiMessage,
+    plot_widget::{PLOT_CONTENT_PADDING, PlotWidget},
+    ticks::{self, TickFormatter, TickProducer},
+};
+
+/// A bar beside the plot area showing the color scale of a series, raster or contour, with
+/// its own ticks.
+///
+/// By default it shows the first series with [color values](Series::with_color_values),
+/// or else the first [`Raster`] with scalar data, or else the first [`Contour`].
+#[derive(Clone)]
+pub struct Colorbar {
+    pub(crate) series: Option<ShapeId>,
+    pub(crate) label: String,
+    pub(crate) width: f32,
+    pub(crate) tick_producer: Option<TickProducer>,
+    pub(crate) tick_formatter: Option<TickFormatter>,
+}
+
+impl Default for Colorbar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Colorbar {
+    /// Create a colorbar for the first series with color values.
+    pub fn new() -> Self {
+        Self {
+            series: None,
+            label: String::new(),
+            width: 16.0,
+            tick_producer: None,
+            tick_formatter: None,
+        }
+    }
+
+    /// Show the color scale of the series, raster or contour with the given id.
+    pub fn with_series(mut self, id: ShapeId) -> Self {
+        self.series = Some(id);
+        self
+    }
+
+    /// Set the label shown beside the colorbar ticks.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Set the width of the bar in logical pixels (default: 16 px).
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width.max(1.0);
+        self
+    }
+
+    /// Set a custom tick producer for the colorbar. It receives the color range.
+    pub fn with_tick_producer<F>(mut self, producer: F) -> Self
+    where
+        F: Fn(f64, f64) -> Vec<Tick> + Send + Sync + 'static,
+    {
+        self.tick_producer = Some(Arc::new(producer));
+        self
+    }
+
+    /// Set a custom formatter for the colorbar tick labels.
+    pub fn with_tick_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(Tick) -> String + Send + Sync + 'static,
+    {
+        self.tick_formatter = Some(Arc::new(formatter));
+        self
+    }
+
+    /// The color scale shown, and its range over the values shown.
+    fn source<'a>(&self, widget: &'a PlotWidget) -> Option<(&'a ColorScale, Option<(f64, f64)>)> {
+        let series_source = |series: &'a Series| {
+            let scale = &series.color_scale;
+            Some((scale, scale.resolve_range(series.color_values.as_ref()?)))
+        };
+        let raster_source = |raster: &'a Raster| match &raster.data {
+            RasterData::Scalar(values) => Some((
+                &raster.color_scale,
+                raster.color_scale.resolve_range(values),
+            )),
+            RasterData::Rgba(_) => None,
+        };
+        let contour_source = |contour: &'a Contour| {
+            let scale = &contour.color_scale;
+            (scale, scale.resolve_range(&contour.values))
+        };
+        match self.series {
+            Some(id) => widget
+                .series
+                .get(&id)
+                .and_then(series_source)
+                .or_else(|| widget.rasters.get(&id).and_then(raster_source))
+                .or_else(|| widget.contours.get(&id).map(contour_source)),
+            None => widget
+                .series
+                .values()
+                .find_map(series_source)
+                .or_else(|| widget.rasters.values().find_map(raster_source))
+                .or_else(|| widget.contours.values().next().map(contour_source)),
+        }
+    }
+
+    fn ticks(&self, norm: ColorNorm, (min, max): (f64, f64)) -> Vec<Tick> {
+        if let Some(producer) = &self.tick_producer {
+            return producer(min, max);
+        }
+        match norm {
+            ColorNorm::Linear => ticks::default_tick_producer(min, max)
+                .into_iter()
+                .filter(|tick| tick.line_type != TickWeight::SubMinor)
+                .collect(),
+            ColorNorm::Log => ticks::log_tick_producer(10.0, min, max),
+        }
+    }
+
+    fn format(&self, norm: ColorNorm, tick: Tick) -> String {
+        match (&self.tick_formatter, norm) {
+            (Some(formatter), _) => formatter(tick),
+            (None, ColorNorm::Linear) => ticks::default_formatter(tick),
+            (None, ColorNorm::Log) => ticks::log_formatter(tick, 10.0),
+        }
+    }
+}
+
+/// The colorbar element, placed to the right of the plot area.
+pub(crate) fn colorbar(widget: &PlotWidget) -> Option<Element<'_, PlotUiMessage>> {
+    let colorbar = widget.colorbar.as_ref()?;
+    let (scale, range) = colorbar.source(widget)?;
+    let range = range?;
+    let style = widget.cached_style();
+
+    // Gradients hold at most 8 stops; the maps are smooth enough to interpolate between them.
+    const STOPS: usize = 8;
+    let gradient = (0..STOPS).fold(gradient::Linear::new(Radians(0.0)), |gradient, i| {
+        let t = i as f32 / (STOPS - 1) as f32;
+        gradient.add_stop(t, scale.colormap.sample(t as f64))
+    });
+    let bar = container("")
+        .width(Length::Fixed(colorbar.width))
+        .height(Length::Fill)
+        .style(move |_| gradient.into());
+
+    // Tick positions need the plot area height, which is known after the first render.
+    let tick_labels = widget.camera_bounds.map(|(_, bounds)| {
+        let labels = colorbar
+            .ticks(scale.norm, range)
+            .into_iter()
+            .filter(|tick| (range.0..=range.1).contains(&tick.value))
+            .filter_map(|tick| {
+                let t = scale.normalize(tick.value, range)?;
+                let screen_pos = (1.0 - t as f32) * bounds.height;
+                let label = text(colorbar.format(scale.norm, tick))
+                    .size(widget.tick_label_size)
+                    .color(style.tick_label_color);
+                Some(
+                    container(label)
+                        .height(Length::Fill)
+                        .padding(padding::top(
+                            (screen_pos - widget.tick_label_size / 2.0).max(0.0),
+                        ))
+                        .align_y(Vertical::Top)
+                        .into(),
+                )
+            });
+        stack(labels).height(Length::Fill)
+    });
+
+    let label = (!colorbar.label.is_empty()).then(|| {
+        axes_labels::y_axis_label(
+            &colorbar.label,
+            widget.axis_label_size,
+            style.axis_label_color,
+            Horizontal::Left,
+        )
+    });
+
+    Some(
+        container(row![bar, tick_labels, label].spacing(4.0))
+            .padding(padding::vertical(PLOT_CONTENT_PADDING).left(4.0))
+            .height(Length::Fill)
+            .into(),
+    )
+}
@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use crate::Color;
+
+/// Evenly spaced control points, from the low end of the map to the high end.
+const VIRIDIS: [u32; 9] = [
+    0x440154, 0x472c7a, 0x3b518b, 0x2c718e, 0x21908d, 0x27ad81, 0x5cc863, 0xaadc32, 0xfde725,
+];
+const MAGMA: [u32; 9] = [
+    0x000004, 0x1c1044, 0x4f127b, 0x812581, 0xb5367a, 0xe55964, 0xfb8761, 0xfec287, 0xfcfdbf,
+];
+const INFERNO: [u32; 9] = [
+    0x000004, 0x1f0c48, 0x550f6d, 0x88226a, 0xba3655, 0xe35933, 0xf98c0a, 0xf9c932, 0xfcffa4,
+];
+const PLASMA: [u32; 9] = [
+    0x0d0887, 0x4c02a1, 0x7e03a8, 0xa82296, 0xcb4679, 0xe56b5d, 0xf89441, 0xfdc328, 0xf0f921,
+];
+const CIVIDIS: [u32; 9] = [
+    0x00204d, 0x183671, 0x414d6b, 0x5f636e, 0x7c7b78, 0x9b9377, 0xbcaf6f, 0xdfcb5d, 0xffea46,
+];
+const COOLWARM: [u32; 9] = [
+    0x3b4cc0, 0x6282ea, 0x8db0fe, 0xb8d0f9, 0xdddcdc, 0xf5c4ac, 0xf49a7b, 0xde604d, 0xb40426,
+];
+const RED_BLUE: [u32; 9] = [
+    0xb2182b, 0xd6604d, 0xf4a582, 0xfddbc7, 0xf7f7f7, 0xd1e5f0, 0x92c5de, 0x4393c3, 0x2166ac,
+];
+const GRAYS: [u32; 2] = [0x000000, 0xffffff];
+
+/// A map from normalized values in `[0, 1]` to colors.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Colormap {
+    /// Perceptually uniform, from dark purple through teal to yellow.
+    #[default]
+    Viridis,
+    /// Perceptually uniform, from black through purple to pale yellow.
+    Magma,
+    /// Perceptually uniform, from black through red to pale yellow.
+    Inferno,
+    /// Perceptually uniform, from dark blue through magenta to yellow.
+    Plasma,
+    /// Perceptually uniform and readable with color vision deficiencies, from blue to yellow.
+    Cividis,
+    /// Diverging, from blue through light gray to red.
+    Coolwarm,
+    /// Diverging, from red through white to blue.
+    RedBlue,
+    /// Black to white.
+    Grays,
+    /// Colors spaced evenly from the low end of the map to the high end.
+    Custom(Arc<[Color]>),
+}
+
+impl Colormap {
+    /// The color at `t`, which is clamped to `[0, 1]`.
+    pub fn sample(&self, t: f64) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        match self {
+            Self::Viridis => sample_hex(&VIRIDIS, t),
+            Self::Magma => sample_hex(&MAGMA, t),
+            Self::Inferno => sample_hex(&INFERNO, t),
+            Self::Plasma => sample_hex(&PLASMA, t),
+            Self::Cividis => sample_hex(&CIVIDIS, t),
+            Self::Coolwarm => sample_hex(&COOLWARM, t),
+            Self::RedBlue => sample_hex(&RED_BLUE, t),
+            Self::Grays => sample_hex(&GRAYS, t),
+            Self::Custom(colors) => sample_stops(colors.len(), t, |i| colors[i]),
+        }
+    }
+}
+
+fn sample_hex(stops: &[u32], t: f64) -> Color {
+    sample_stops(stops.len(), t, |i| {
+        let [_, r, g, b] = stops[i].to_be_bytes();
+        Color::from_rgb8(r, g, b)
+    })
+}
+
+/// Interpolate linearly between `len` evenly spaced stops.
+fn sample_stops(len: usize, t: f64, stop: impl Fn(usize) -> Color) -> Color {
+    match len {
+        0 => Color::TRANSPARENT,
+        1 => stop(0),
+        _ => {
+            let position = t * (len - 1) as f64;
+            let i = (position.floor() as usize).min(len - 2);
+            let f = (position - i as f64) as f32;
+            let (a, b) = (stop(i), stop(i + 1));
+            Color::from_rgba(
+                a.r + (b.r - a.r) * f,
+                a.g + (b.g - a.g) * f,
+                a.b + (b.b - a.b) * f,
+                a.a + (b.a - a.a) * f,
+            )
+        }
+    }
+}
+
+/// How values are normalized into `[0, 1]` before looking up a color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorNorm {
+    /// Linear between the minimum and the maximum.
+    #[default]
+    Linear,
+    /// Logarithmic between the minimum and the maximum. Non-positive values have no color.
+    Log,
+}
+
+/// Maps scalar values to colors through a [`Colormap`].
+///
+/// Values are normalized between `min` and `max`, then looked up in the colormap. Values
+/// outside the range are clamped to its ends.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColorScale {
+    /// The colormap values are looked up in.
+    pub colormap: Colormap,
+    /// How values are normalized into `[0, 1]`.
+    pub norm: ColorNorm,
+    /// Lower end of the range. If none, the smallest value is used.
+    pub min: Option<f64>,
+    /// Upper end of the range. If none, the largest value is used.
+    pub max: Option<f64>,
+}
+
+impl From<Colormap> for ColorScale {
+    fn from(colormap: Colormap) -> Self {
+        Self::new(colormap)
+    }
+}
+
+impl ColorScale {
+    /// Create a linear color scale fitted to the values.
+    pub fn new(colormap: Colormap) -> Self {
+        Self {
+            colormap,
+            ..Self::default()
+        }
+    }
+
+    /// Set both ends of the range.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = Some(min);
+        self.max = Some(max);
+        self
+    }
+
+    /// Set the lower end of the range.
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Set the upper end of the range.
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Set how values are normalized.
+    pub fn with_norm(mut self, norm: ColorNorm) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// The `(min, max)` range used for `values`, filling unset ends from the values.
+    ///
+    /// Returns `None` if the range is empty or reversed, or has a non-positive end on a
+    /// log scale.
+    pub(crate) fn resolve_range<'a>(
+        &self,
+        values: impl IntoIterator<Item = &'a f64>,
+    ) -> Option<(f64, f64)> {
+        let (mut min, mut max) = (self.min, self.max);
+        if self.fits_values() {
+            let (lo, hi) = values
+                .into_iter()
+                .copied()
+                .filter(|&v| self.normalizable(v))
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                    (lo.min(v), hi.max(v))
+                });
+            min = min.or(lo.is_finite().then_some(lo));
+            max = max.or(hi.is_finite().then_some(hi));
+        }
+        let (min, max) = (min?, max?);
+        (self.normalizable(min) && self.normalizable(max) && min <= max).then_some((min, max))
+    }
+
+    /// Whether an end of the range is taken from the values.
+    pub(crate) fn fits_values(&self) -> bool {
+        self.min.is_none() || self.max.is_none()
+    }
+
+    fn normalizable(&self, value: f64) -> bool {
+        value.is_finite() && (self.norm == ColorNorm::Linear || value > 0.0)
+    }
+
+    /// Normalize `value` into `[0, 1]` over `range`. Constant ranges map to the middle.
+    pub(crate) fn normalize(&self, value: f64, (min, max): (f64, f64)) -> Option<f64> {
+        if !self.normalizable(value) {
+            return None;
+        }
+        let (value, min, max) = match self.norm {
+            ColorNorm::Linear => (value, min, max),
+            ColorNorm::Log => (value.log10(), min.log10(), max.log10()),
+        };
+        if max > min {
+            Some(((value - min) / (max - min)).clamp(0.0, 1.0))
+        } else {
+            Some(0.5)
+        }
+    }
+
+    /// The colors for `values`, falling back to `fallback` for values without a color.
+    pub(crate) fn colors<'a, I>(&self, values: I, fallback: Color) -> Vec<Color>
+    where
+        I: IntoIterator<Item = &'a f64> + Clone,
+    {
+        let range = self.resolve_range(values.clone());
+        values
+            .into_iter()
+            .map(|&value| {
+                range
+                    .and_then(|range| self.normalize(value, range))
+                    .map_or(fallback, |t| self.colormap.sample(t))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colormap_interpolates_between_stops() {
+        assert_eq!(Colormap::Grays.sample(0.0), Color::BLACK);
+        assert_eq!(Colormap::Grays.sample(2.0), Color::WHITE);
+        assert_eq!(Colormap::Grays.sample(0.5), Color::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!(
+            Colormap::Viridis.sample(1.0),
+            Color::from_rgb8(0xfd, 0xe7, 0x25)
+        );
+    }
+
+    #[test]
+    fn color_scale_fits_range_and_skips_unmapped_values() {
+        let scale = ColorScale::new(Colormap::Grays).with_norm(ColorNorm::Log);
+        let values = [1.0, 10.0, 100.0, 0.0, f64::NAN];
+
+        assert_eq!(scale.resolve_range(&values), Some((1.0, 100.0)));
+        let colors = scale.colors(&values, Color::TRANSPARENT);
+        assert_eq!(colors[1], Color::from_rgb(0.5, 0.5, 0.5));
+        assert_eq!(colors[2], Color::WHITE);
+        assert_eq!(colors[3], Color::TRANSPARENT);
+        assert_eq!(colors[4], Color::TRANSPARENT);
+
+        let clamped = ColorScale::new(Colormap::Grays).with_range(0.0, 1.0);
+        assert_eq!(clamped.colors(&[2.0], Color::TRANSPARENT), [Color::WHITE]);
+    }
+}
@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+
+use crate::{
+    Color, ColorScale, LineStyle, TickWeight,
+    series::{SeriesError, ShapeId},
+    ticks::{Tick, default_formatter, nice_step},
+};
+
+/// Which values a [`Contour`] draws isolines at.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContourLevels {
+    /// About this many evenly spaced levels at round values between the smallest and the
+    /// largest value.
+    Count(usize),
+    /// These levels. Non-finite levels are ignored.
+    Values(Vec<f64>),
+}
+
+impl Default for ContourLevels {
+    fn default() -> Self {
+        Self::Count(8)
+    }
+}
+
+impl From<usize> for ContourLevels {
+    fn from(count: usize) -> Self {
+        Self::Count(count)
+    }
+}
+
+impl From<Vec<f64>> for ContourLevels {
+    fn from(levels: Vec<f64>) -> Self {
+        Self::Values(levels)
+    }
+}
+
+/// Contour lines (isolines) of values sampled on a regular grid, optionally with the bands
+/// between levels filled.
+///
+/// Isolines are traced with marching squares, splitting each grid cell into four triangles
+/// around its mean value to resolve saddles. Each level is drawn as a line series, labelled
+/// in the legend as `"<label>: <level>"` when the contour has a label. Bands are filled with
+/// the color of their middle value, including the bands below the lowest level and above
+/// the highest one. Cells touching a non-finite value are left out.
+#[derive(Debug, Clone)]
+pub struct Contour {
+    /// Unique identifier for the contour.
+    pub id: ShapeId,
+    /// Number of grid points per row.
+    pub columns: usize,
+    /// Number of rows of grid points.
+    pub rows: usize,
+    /// Grid values, row by row from the bottom of the extent.
+    pub values: Vec<f64>,
+    /// Position of the first grid point, in data coordinates.
+    pub min: [f64; 2],
+    /// Position of the last grid point, in data coordinates.
+    pub max: [f64; 2],
+    /// Levels to draw isolines at.
+    pub levels: ContourLevels,
+    /// Style of the isolines. If none, only the bands are drawn.
+    pub line_style: Option<LineStyle>,
+    /// Color of every isoline. If none, isolines are colored through the color scale.
+    pub line_color: Option<Color>,
+    /// Maps levels and bands to colors.
+    pub color_scale: ColorScale,
+    /// Whether the bands between levels are filled.
+    pub filled: bool,
+    /// Opacity of the filled bands.
+    pub fill_opacity: f32,
+    /// Optional label, shown in the legend for each level.
+    pub label: Option<String>,
+    /// Ids of the line series drawn for each level, reused when the contour is updated.
+    pub(crate) level_ids: Vec<ShapeId>,
+    /// Color and triangle list vertices of each filled band, in data coordinates.
+    pub(crate) bands: Vec<(Color, Vec<[f64; 2]>)>,
+}
+
+impl Contour {
+    /// Create a contour from one value per grid point, with grid points one unit apart.
+    pub fn new(columns: usize, rows: usize, values: Vec<f64>) -> Self {
+        Self {
+            id: ShapeId::new(),
+            columns,
+            rows,
+            values,
+            min: [0.0, 0.0],
+            max: [
+                columns.saturating_sub(1) as f64,
+                rows.saturating_sub(1) as f64,
+            ],
+            levels: ContourLevels::default(),
+            line_style: Some(LineStyle::default()),
+            line_color: None,
+            color_scale: ColorScale::default(),
+            filled: false,
+            fill_opacity: 1.0,
+            label: None,
+            level_ids: Vec::new(),
+            bands: Vec::new(),
+        }
+    }
+
+    /// Set the positions of the first and last grid points in data coordinates.
+    pub fn with_extent(mut self, min: [f64; 2], max: [f64; 2]) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set the levels, either a count or explicit values.
+    pub fn with_levels(mut self, levels: impl Into<ContourLevels>) -> Self {
+        self.levels = levels.into();
+        self
+    }
+
+    /// Set the style of the isolines.
+    pub fn with_line_style(mut self, style: LineStyle) -> Self {
+        self.line_style = Some(style);
+        self
+    }
+
+    /// Don't draw isolines, e.g. for a filled contour.
+    pub fn without_lines(mut self) -> Self {
+        self.line_style = None;
+        self
+    }
+
+    /// Draw every isoline in one color instead of through the color scale.
+    pub fn with_line_color(mut self, color: impl Into<Color>) -> Self {
+        self.line_color = Some(color.into());
+        self
+    }
+
+    /// Set the color scale used for levels and bands.
+    pub fn with_color_scale(mut self, scale: impl Into<ColorScale>) -> Self {
+        self.color_scale = scale.into();
+        self
+    }
+
+    /// Set whether the bands between levels are filled.
+    pub fn with_filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Set the opacity of the filled bands.
+    pub fn with_fill_opacity(mut self, opacity: f32) -> Self {
+        self.fill_opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set a label, shown in the legend for each level.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        if !label.is_empty() {
+            self.label = Some(label);
+        }
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SeriesError> {
+        if self.columns < 2 || self.rows < 2 || self.values.len() != self.columns * self.rows {
+            return Err(SeriesError::InvalidContourGrid);
+        }
+        let valid_extent = (0..2).all(|axis| {
+            self.min[axis].is_finite()
+                && self.max[axis].is_finite()
+                && self.min[axis] < self.max[axis]
+        });
+        if !valid_extent {
+            return Err(SeriesError::InvalidContourExtent);
+        }
+        Ok(())
+    }
+
+    /// The sorted levels, with their legend text.
+    pub(crate) fn resolve_levels(&self) -> Vec<(f64, String)> {
+        match &self.levels {
+            ContourLevels::Count(count) => {
+                let (lo, hi) = self
+                    .values
+                    .iter()
+                    .copied()
+                    .filter(|v| v.is_finite())
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                        (lo.min(v), hi.max(v))
+                    });
+                if *count == 0 || lo >= hi {
+                    return Vec::new();
+                }
+                let step = nice_step((hi - lo) / *count as f64);
+                let first = (lo / step).floor() as i64 + 1;
+                (first..)
+                    .map(|k| k as f64 * step)
+                    .take_while(|&level| level < hi)
+                    .filter(|&level| level > lo)
+                    .map(|level| {
+                        let text = default_formatter(Tick::new(level, step, TickWeight::Major));
+                        (level, text)
+                    })
+                    .collect()
+            }
+            ContourLevels::Values(levels) => {
+                let mut levels: Vec<f64> =
+                    levels.iter().copied().filter(|l| l.is_finite()).collect();
+                levels.sort_by(f64::total_cmp);
+                levels.dedup();
+                levels
+                    .into_iter()
+                    .map(|level| (level, level.to_string()))
+                    .collect()
+            }
+        }
+    }
+
+    /// Trace the isolines and bands for `levels`, which must be sorted.
+    pub(crate) fn trace(&self, levels: &[f64]) -> ContourGeometry {
+        let grid = TriangleGrid::new(self);
+        let range = self.color_scale.resolve_range(&self.values);
+        let normalized = |value: f64| {
+            range
+                .and_then(|range| self.color_scale.normalize(value, range))
+                .unwrap_or(0.0)
+        };
+
+        let lines = levels
+            .iter()
+            .map(|&level| {
+                let color = self.line_color.unwrap_or_else(|| {
+                    range
+                        .and_then(|range| self.color_scale.normalize(level, range))
+                        .map_or(Color::BLACK, |t| self.color_scale.colormap.sample(t))
+                });
+                (color, grid.isolines(level))
+            })
+            .collect();
+
+        let bands = if self.filled {
+            let (data_min, data_max) = range.unwrap_or((0.0, 0.0));
+            let mut triangles = grid.bands(levels);
+            (0..=levels.len())
+                .map(|band| {
+                    let lower = if band == 0 {
+                        data_min
+                    } else {
+                        levels[band - 1]
+                    };
+                    let upper = levels.get(band).copied().unwrap_or(data_max);
+                    let t = (normalized(lower) + normalized(upper)) / 2.0;
+                    let mut color = self.color_scale.colormap.sample(t);
+                    color.a *= self.fill_opacity;
+                    (color, std::mem::take(&mut triangles[band]))
+                })
+                .filter(|(_, vertices)| !vertices.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        ContourGeometry { lines, bands }
+    }
+}
+
+/// Isolines and filled bands of a [`Contour`], in data coordinates.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ContourGeometry {
+    /// Color and polylines of each level. Polylines are separated by NaN points.
+    pub(crate) lines: Vec<(Color, Vec<[f64; 2]>)>,
+    /// Color and triangle list vertices of each non-empty band.
+    pub(crate) bands: Vec<(Color, Vec<[f64; 2]>)>,
+}
+
+/// A grid vertex: either a grid point or the center of a cell.
+type VertexId = usize;
+
+/// An edge between two vertices, smaller id first.
+type EdgeKey = (VertexId, VertexId);
+
+#[derive(Debug, Clone, Copy)]
+struct Vertex {
+    id: VertexId,
+    position: [f64; 2],
+    value: f64,
+}
+
+/// The grid split into four triangles per cell around the cell's mean value.
+struct TriangleGrid<'a> {
+    contour: &'a Contour,
+    step: [f64; 2],
+}
+
+impl<'a> TriangleGrid<'a> {
+    fn new(contour: &'a Contour) -> Self {
+        let step = [
+            (contour.max[0] - contour.min[0]) / (contour.columns - 1) as f64,
+            (contour.max[1] - contour.min[1]) / (contour.rows - 1) as f64,
+        ];
+        Self { contour, step }
+    }
+
+    fn point(&self, row: usize, column: usize) -> Vertex {
+        Vertex {
+            id: row * self.contour.columns + column,
+            position: [
+                self.contour.min[0] + column as f64 * self.step[0],
+                self.contour.min[1] + row as f64 * self.step[1],
+            ],
+            value: self.contour.values[row * self.contour.columns + column],
+        }
+    }
+
+    /// The triangles of every cell without non-finite values.
+    fn triangles(&self) -> impl Iterator<Item = [Vertex; 3]> + '_ {
+        let (columns, rows) = (self.contour.columns, self.contour.rows);
+        (0..rows - 1)
+            .flat_map(move |row| (0..columns - 1).map(move |column| (row, column)))
+            .flat_map(move |(row, column)| {
+                let corners = [
+                    self.point(row, column),
+                    self.point(row, column + 1),
+                    self.point(row + 1, column + 1),
+                    self.point(row + 1, column),
+                ];
+                let finite = corners.iter().all(|corner| corner.value.is_finite());
+                let center = Vertex {
+                    id: columns * rows + row * (columns - 1) + column,
+                    position: [
+                        corners[0].position[0] + self.step[0] / 2.0,
+                        corners[0].position[1] + self.step[1] / 2.0,
+                    ],
+                    value: corners.iter().map(|corner| corner.value).sum::<f64>() / 4.0,
+                };
+                (0..4)
+                    .filter(move |_| finite)
+                    .map(move |i| [corners[i], corners[(i + 1) % 4], center])
+            })
+    }
+
+    /// Polylines where the values cross `level`, separated by NaN points.
+    fn isolines(&self, level: f64) -> Vec<[f64; 2]> {
+        let mut crossings: HashMap<EdgeKey, ([f64; 2], Vec<usize>)> = HashMap::new();
+        let mut segments: Vec<[EdgeKey; 2]> = Vec::new();
+
+        for triangle in self.triangles() {
+            let mut ends = [(0, 0); 2];
+            let mut count = 0;
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+                if (a.value >= level) == (b.value >= level) {
+                    continue;
+                }
+                let (a, b) = if a.id < b.id { (a, b) } else { (b, a) };
+                let key = (a.id, b.id);
+                let t = (level - a.value) / (b.value - a.value);
+                let entry = crossings.entry(key).or_insert_with(|| {
+                    let position = [
+                        a.position[0] + (b.position[0] - a.position[0]) * t,
+                        a.position[1] + (b.position[1] - a.position[1]) * t,
+                    ];
+                    (position, Vec::new())
+                });
+                entry.1.push(segments.len());
+                ends[count] = key;
+                count += 1;
+            }
+            // A plane crosses a triangle on exactly two edges.
+            if count == 2 {
+                segments.push(ends);
+            }
+        }
+
+        // Stitch segments sharing a crossing into polylines. Every crossing is on an edge
+        // shared by at most two triangles, so it joins at most two segments.
+        let mut visited = vec![false; segments.len()];
+        let mut out = Vec::new();
+        for start in 0..segments.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let walk = |from: EdgeKey, visited: &mut Vec<bool>| {
+                let mut keys = Vec::new();
+                let mut key = from;
+                while let Some(&next) = crossings[&key].1.iter().find(|&&s| !visited[s]) {
+                    visited[next] = true;
+                    let [a, b] = segments[next];
+                    key = if a == key { b } else { a };
+                    keys.push(key);
+                }
+                keys
+            };
+            let forward = walk(segments[start][1], &mut visited);
+            let backward = walk(segments[start][0], &mut visited);
+
+            if !out.is_empty() {
+                out.push([f64::NAN, f64::NAN]);
+            }
+            let keys = backward
+                .iter()
+                .rev()
+                .chain(&segments[start])
+                .chain(&forward);
+            out.extend(keys.map(|key| crossings[key].0));
+        }
+        out
+    }
+
+    /// Triangle list vertices of each band between `levels`, from below the lowest level
+    /// to above the highest one.
+    fn bands(&self, levels: &[f64]) -> Vec<Vec<[f64; 2]>> {
+        let mut bands = vec![Vec::new(); levels.len() + 1];
+        for triangle in self.triangles() {
+            let (lo, hi) = triangle
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                    (lo.min(v.value), hi.max(v.value))
+                });
+            let first = levels.partition_point(|&level| level <= lo);
+            let last = levels.partition_point(|&level| level <= hi);
+            for band in first..=last {
+                let mut polygon: Vec<Vertex> = triangle.to_vec();
+                if band > first {
+                    polygon = clip(&polygon, levels[band - 1], true);
+                }
+                if band < last {
+                    polygon = clip(&polygon, levels[band], false);
+                }
+                // The clipped polygon is convex, so fan it out from its first vertex.
+                for i in 1..polygon.len().saturating_sub(1) {
+                    bands[band].extend(
+                        [polygon[0], polygon[i], polygon[i + 1]].map(|vertex| vertex.position),
+                    );
+                }
+            }
+        }
+        bands
+    }
+}
+
+/// Clip a polygon to where the values are above (or below) `level`, interpolating linearly
+/// along its edges.
+fn clip(polygon: &[Vertex], level: f64, keep_above: bool) -> Vec<Vertex> {
+    let inside = |vertex: &Vertex| (vertex.value >= level) == keep_above;
+    let mut out = Vec::with_capacity(polygon.len() + 1);
+    for (i, &a) in polygon.iter().enumerate() {
+        let b = polygon[(i + 1) % polygon.len()];
+        if inside(&a) {
+            out.push(a);
+        }
+        if inside(&a) != inside(&b) {
+            let t = (level - a.value) / (b.value - a.value);
+            out.push(Vertex {
+                id: a.id,
+                position: [
+                    a.position[0] + (b.position[0] - a.position[0]) * t,
+                    a.position[1] + (b.position[1] - a.position[1]) * t,
+                ],
+                value: level,
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Colormap;
+
+    fn cone() -> Contour {
+        // Distance from the center of a 5x5 grid.
+        let values = (0..25)
+            .map(|i| {
+                let (row, column) = ((i / 5) as f64 - 2.0, (i % 5) as f64 - 2.0);
+                (row * row + column * column).sqrt()
+            })
+            .collect();
+        Contour::new(5, 5, values).with_extent([-2.0, -2.0], [2.0, 2.0])
+    }
+
+    #[test]
+    fn isolines_close_around_a_peak() {
+        let contour = cone();
+        assert!(contour.validate().is_ok());
+
+        let line = TriangleGrid::new(&contour).isolines(1.5);
+        assert!(line.iter().all(|p| p[0].is_finite()), "one polyline");
+        assert_eq!(line.first(), line.last(), "closed");
+        for [x, y] in &line {
+            let r = (x * x + y * y).sqrt();
+            assert!((1.2..=1.6).contains(&r), "{r}");
+        }
+    }
+
+    #[test]
+    fn levels_are_round_and_bands_cover_the_grid() {
+        let contour = cone()
+            .with_levels(4)
+            .with_filled(true)
+            .with_color_scale(Colormap::Grays);
+        let levels = contour.resolve_levels();
+        let texts: Vec<_> = levels.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(texts, ["1", "2"]);
+
+        let levels: Vec<f64> = levels.into_iter().map(|(level, _)| level).collect();
+        let geometry = contour.trace(&levels);
+        assert_eq!(geometry.lines.len(), 2);
+        assert_eq!(geometry.bands.len(), 3);
+
+        let area: f64 = geometry
+            .bands
+            .iter()
+            .flat_map(|(_, vertices)| vertices.chunks_exact(3))
+            .map(|t| {
+                ((t[1][0] - t[0][0]) * (t[2][1] - t[0][1])
+                    - (t[2][0] - t[0][0]) * (t[1][1] - t[0][1]))
+                    .abs()
+                    / 2.0
+            })
+            .sum();
+        assert!((area - 16.0).abs() < 1e-9, "{area}");
+        assert!(matches!(
+            Contour::new(1, 3, vec![0.0; 3]).validate(),
+            Err(SeriesError::InvalidContourGrid)
+        ));
+    }
+}
@@ -41,6 +41,21 @@ pub enum DragAction {
 
     /// Draw a selection rectangle and zoom to it on release.
     BoxZoom,
+
+    /// Draw a selection rectangle and select the points inside it on release.
+    ///
+    /// Hold Shift to add to the selection, or Ctrl to remove from it.
+    BoxSelect,
+
+    /// Draw a freehand outline and select the points inside it on release.
+    ///
+    /// Hold Shift to add to the selection, or Ctrl to remove from it.
+    LassoSelect,
+
+    /// Draw a ruler and show the deltas, distance and slope between its ends. The ruler
+    /// stays until the next measurement or until it is cleared with
+    /// [`KeyAction::ClearMeasurement`] or [`PlotWidget::clear_measurement`](crate::PlotWidget::clear_measurement).
+    Measure,
 }
 
 /// Action that can be performed by scrolling.
@@ -75,6 +90,9 @@ pub enum KeyAction {
     /// Clear picked points.
     ClearPick,
 
+    /// Clear the measurement ruler.
+    ClearMeasurement,
+
     /// Pan by a fraction of the current visible camera span.
     PanBy {
         /// Direction to pan.
@@ -473,6 +491,9 @@ fn drag_action_label(action: DragAction) -> &'static str {
     match action {
         DragAction::Pan => "pan",
         DragAction::BoxZoom => "box zoom",
+        DragAction::BoxSelect => "box select",
+        DragAction::LassoSelect => "lasso select",
+        DragAction::Measure => "measure",
     }
 }
 
@@ -495,6 +516,7 @@ fn key_action_label(action: KeyAction) -> String {
     match action {
         KeyAction::Autoscale => "reset / autoscale".to_owned(),
         KeyAction::ClearPick => "clear picked points".to_owned(),
+        KeyAction::ClearMeasurement => "clear measurement".to_owned(),
         KeyAction::PanBy {
             direction,
             fraction,
@@ -0,0 +1,240 @@
+//! Data cursor: a vertical cursor reading every series at the cursor x.
+use iced::Color;
+
+use crate::{
+    LineInterpolation, Point, picking::marker_center_world, plot_state::SeriesSpan,
+    plot_widget::HighlightPoint,
+};
+
+/// How the data cursor reads each series at the cursor x.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DataCursorMode {
+    /// The sample nearest the cursor x, as drawn: on a log x-axis, the nearest in
+    /// decades.
+    Snap,
+    /// The value the line shows at the cursor x: along the drawn line, curve or step
+    /// between samples. Nothing is read outside the line's x range or across a gap in it.
+    ///
+    /// Series without a line, or whose x is not sorted, snap instead.
+    #[default]
+    Interpolate,
+}
+
+/// Every series read at the cursor x, for the combined tooltip.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DataCursorReadout {
+    /// Cursor position in the plot area, in logical pixels.
+    pub(crate) screen_xy: [f32; 2],
+    /// The cursor x, formatted.
+    pub(crate) title: String,
+    pub(crate) entries: Vec<DataCursorEntry>,
+}
+
+/// One series read by the data cursor.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DataCursorEntry {
+    pub(crate) label: String,
+    pub(crate) color: Color,
+    /// The value, formatted.
+    pub(crate) text: String,
+    /// Marker drawn on the series where it was read.
+    pub(crate) point: HighlightPoint,
+}
+
+/// What the data cursor read from a series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SpanReading {
+    /// The sample at this local index of the span.
+    Sample(usize),
+    /// A position on the line between samples, in plot space.
+    Line([f64; 2]),
+}
+
+/// Read a series' span at plot x `x`.
+///
+/// Lines are read where they are drawn: straight, stepped or along the tessellated curve,
+/// and nothing is read across a line break. Snapping picks the sample nearest in plot x.
+pub(crate) fn read_span(
+    span: &SeriesSpan,
+    points: &[Point],
+    mode: DataCursorMode,
+    x: f64,
+) -> Option<SpanReading> {
+    let span_points = &points[span.start..span.start + span.len];
+    let index = span.point_index(points);
+    let interpolation = span
+        .line_style
+        .map(|style| style.interpolation)
+        .filter(|_| mode == DataCursorMode::Interpolate && index.is_sorted_x());
+    let Some(interpolation) = interpolation else {
+        // Marker centers sit at the same offset from every position of a span.
+        let first = span_points.first()?;
+        let offset = marker_center_world(first).x - first.position[0];
+        return index
+            .nearest_x(x + offset, |i| {
+                marker_center_world(&span_points[i]).to_array()
+            })
+            .map(SpanReading::Sample);
+    };
+
+    let after = span_points.partition_point(|point| point.position[0] < x);
+    let end = span_points.get(after)?.position;
+    if end[0] == x {
+        return Some(SpanReading::Sample(after));
+    }
+    let before = after.checked_sub(1)?;
+    if span.breaks_line_before(after) {
+        return None;
+    }
+    let start = span_points[before].position;
+    let lerp = |[x0, y0]: [f64; 2], [x1, y1]: [f64; 2]| y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+    let y = match interpolation {
+        LineInterpolation::Linear => lerp(start, end),
+        LineInterpolation::StepPre => end[1],
+        LineInterpolation::StepPost => start[1],
+        LineInterpolation::StepMid if x < (start[0] + end[0]) / 2.0 => start[1],
+        LineInterpolation::StepMid => end[1],
+        LineInterpolation::CatmullRom
+        | LineInterpolation::MonotoneCubic
+        | LineInterpolation::NaturalCubic => {
+            let (before, after) = (span.start + before, span.start + after);
+            let paths = span.line_paths(points);
+            let path =
+                paths.get(paths.partition_point(|path| {
+                    path.last().is_some_and(|&(_, owner)| owner < after)
+                }))?;
+            // Path vertices carry non-decreasing point indices, so the pieces of the curve
+            // between the two samples are found by binary search, then scanned.
+            let first = path.partition_point(|&(_, owner)| owner < before);
+            let last = path.partition_point(|&(_, owner)| owner <= after);
+            path[first..last].windows(2).find_map(|piece| {
+                let (a, b) = (piece[0].0, piece[1].0);
+                (a[0].min(b[0]) <= x && x <= a[0].max(b[0]))
+                    .then(|| if a[0] == b[0] { a[1] } else { lerp(a, b) })
+            })?
+        }
+    };
+    Some(SpanReading::Line([x, y]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DataCursorMode, SpanReading, read_span};
+    use crate::{
+        AxisScale, LineInterpolation, LineStyle, MarkerStyle, PlotWidget, Series,
+        plot_state::PlotState,
+    };
+
+    /// Read the only series of `widget` at plot x `x`, returning the plot position read.
+    fn read(widget: &PlotWidget, mode: DataCursorMode, x: f64) -> Option<[f64; 2]> {
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(widget);
+        let span = &state.series[0];
+        Some(match read_span(span, &state.points, mode, x)? {
+            SpanReading::Sample(local) => state.points[span.start + local].position,
+            SpanReading::Line(position) => position,
+        })
+    }
+
+    fn line(positions: Vec<[f64; 2]>, interpolation: LineInterpolation) -> PlotWidget {
+        let mut widget = PlotWidget::new();
+        let style = LineStyle::solid().with_interpolation(interpolation);
+        widget
+            .add_series(Series::line_only(positions, style))
+            .unwrap();
+        widget
+    }
+
+    #[test]
+    fn reads_lines_between_samples_and_snaps_otherwise() {
+        let positions = vec![[0.0, 0.0], [2.0, 4.0], [4.0, 2.0]];
+        let interpolate = DataCursorMode::Interpolate;
+        let read_line =
+            |interpolation, mode, x| read(&line(positions.clone(), interpolation), mode, x);
+
+        assert_eq!(
+            read_line(LineInterpolation::Linear, interpolate, 1.0),
+            Some([1.0, 2.0])
+        );
+        assert_eq!(
+            read_line(LineInterpolation::Linear, interpolate, 2.0),
+            Some([2.0, 4.0])
+        );
+        assert_eq!(
+            read_line(LineInterpolation::StepPost, interpolate, 3.5),
+            Some([3.5, 4.0])
+        );
+        assert_eq!(
+            read_line(LineInterpolation::StepPre, interpolate, 0.5),
+            Some([0.5, 4.0])
+        );
+        assert_eq!(
+            read_line(LineInterpolation::StepMid, interpolate, 1.5),
+            Some([1.5, 4.0])
+        );
+        // No line outside its x range.
+        assert_eq!(read_line(LineInterpolation::Linear, interpolate, 5.0), None);
+        assert_eq!(
+            read_line(LineInterpolation::Linear, interpolate, -0.1),
+            None
+        );
+
+        // Snapping, and series without a line, read the nearest sample.
+        assert_eq!(
+            read_line(LineInterpolation::Linear, DataCursorMode::Snap, 1.2),
+            Some([2.0, 4.0])
+        );
+        let mut markers = PlotWidget::new();
+        markers
+            .add_series(Series::markers_only(
+                positions.clone(),
+                MarkerStyle::circle(4.0),
+            ))
+            .unwrap();
+        assert_eq!(read(&markers, interpolate, 5.0), Some([4.0, 2.0]));
+    }
+
+    #[test]
+    fn curves_are_read_where_they_are_drawn() {
+        let positions = (0..10).map(|i| [i as f64, (i as f64).sin()]).collect();
+        let widget = line(positions, LineInterpolation::NaturalCubic);
+
+        let [x, y] = read(&widget, DataCursorMode::Interpolate, 1.5).unwrap();
+
+        // The spline follows the sine closely, unlike the straight chord at 0.875.
+        assert_eq!(x, 1.5);
+        assert!((y - 1.5f64.sin()).abs() < 0.02, "{y}");
+    }
+
+    #[test]
+    fn gaps_in_a_line_read_nothing() {
+        let positions = vec![[0.0, 0.0], [1.0, 1.0], [2.0, f64::NAN], [3.0, 3.0]];
+        let widget = line(positions, LineInterpolation::Linear);
+
+        assert_eq!(
+            read(&widget, DataCursorMode::Interpolate, 0.5),
+            Some([0.5, 0.5])
+        );
+        assert_eq!(read(&widget, DataCursorMode::Interpolate, 2.5), None);
+    }
+
+    #[test]
+    fn reading_follows_the_plot_space_of_log_axes() {
+        let log = AxisScale::Log { base: 10.0 };
+        let mut widget = line(
+            vec![[1.0, 1.0], [10.0, 5.0], [100.0, 100.0]],
+            LineInterpolation::Linear,
+        );
+        widget.set_x_axis_scale(log);
+        widget.set_y_axis_scale(log);
+
+        // Halfway between decades is halfway along the drawn line.
+        let [_, y] = read(&widget, DataCursorMode::Interpolate, 0.5).unwrap();
+        assert!((y - 0.5 * 5f64.log10()).abs() < 1e-12);
+        // 40 is nearer 100 than 10 on the axis, though not in value.
+        assert_eq!(
+            read(&widget, DataCursorMode::Snap, 40f64.log10()),
+            Some([2.0, 2.0])
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use std::{collections::VecDeque, ops::Range};
+
+/// Level-of-detail reduction applied to a [`Series`](crate::Series) before drawing.
+///
+/// Decimated series are reduced to roughly one or two points per horizontal pixel around the
+/// visible x-range, and are re-decimated once the view pans out of that range or zooms far
+/// enough to change the pixel size. Positions must be sorted by increasing x, which adding
+/// or appending to a decimated series checks. Hover and pick still report indices into the
+/// original positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Decimation {
+    /// Draw every point.
+    #[default]
+    None,
+    /// Keep the minimum and maximum y of each pixel column. Preserves peaks, which
+    /// makes it a good fit for dense waveforms.
+    MinMax,
+    /// Largest-Triangle-Three-Buckets: keep the points that best preserve the visual
+    /// shape of the line.
+    Lttb,
+}
+
+/// Pick the indices of `positions[window]` to draw for the given number of pixel columns.
+/// `column_of` maps a position to its pixel column, or `None` if it can't be placed.
+///
+/// Non-finite points are always kept so that line gaps survive decimation.
+pub(crate) fn decimate(
+    decimation: Decimation,
+    positions: &VecDeque<[f64; 2]>,
+    window: Range<usize>,
+    columns: usize,
+    column_of: impl Fn([f64; 2]) -> Option<i64>,
+) -> Vec<usize> {
+    let columns = columns.max(1);
+    match decimation {
+        Decimation::MinMax if window.len() > 2 * columns => {
+            min_max(positions, window, columns, column_of)
+        }
+        Decimation::Lttb if window.len() > 2 * columns => lttb(positions, window, 2 * columns),
+        _ => window.collect(),
+    }
+}
+
+/// Whether the finite x values are in increasing order. Non-finite x values mark gaps and
+/// are ignored.
+pub(crate) fn sorted_by_x<'a>(positions: impl IntoIterator<Item = &'a [f64; 2]>) -> bool {
+    let mut last = f64::NEG_INFINITY;
+    positions
+        .into_iter()
+        .filter(|p| p[0].is_finite())
+        .all(|p| std::mem::replace(&mut last, p[0]) <= p[0])
+}
+
+fn is_finite([x, y]: [f64; 2]) -> bool {
+    x.is_finite() && y.is_finite()
+}
+
+/// Split the window into `bucket_count` equal index ranges.
+fn bucket(window: &Range<usize>, bucket_count: usize, bucket: usize) -> Range<usize> {
+    let len = window.len();
+    let start = window.start + bucket * len / bucket_count;
+    let end = window.start + (bucket + 1) * len / bucket_count;
+    start..end
+}
+
+/// Keep the lowest and highest point of each pixel column. Positions are sorted by x, so
+/// each column is a run of consecutive indices.
+fn min_max(
+    positions: &VecDeque<[f64; 2]>,
+    window: Range<usize>,
+    columns: usize,
+    column_of: impl Fn([f64; 2]) -> Option<i64>,
+) -> Vec<usize> {
+    let mut out = Vec::with_capacity(2 * columns + 2);
+    let mut column: Option<i64> = None;
+    let mut min: Option<usize> = None;
+    let mut max: Option<usize> = None;
+    for index in window {
+        let p = positions[index];
+        let Some(p_column) = column_of(p).filter(|_| is_finite(p)) else {
+            // Flush the extremes found so far so the gap lands in the right place.
+            out.extend(ordered(min.take(), max.take()));
+            out.push(index);
+            continue;
+        };
+        if column != Some(p_column) {
+            out.extend(ordered(min.take(), max.take()));
+            column = Some(p_column);
+        }
+        if min.is_none_or(|m| p[1] < positions[m][1]) {
+            min = Some(index);
+        }
+        if max.is_none_or(|m| p[1] > positions[m][1]) {
+            max = Some(index);
+        }
+    }
+    out.extend(ordered(min, max));
+    out
+}
+
+fn ordered(a: Option<usize>, b: Option<usize>) -> impl Iterator<Item = usize> {
+    let (first, second) = match (a, b) {
+        (Some(a), Some(b)) if a == b => (Some(a), None),
+        (Some(a), Some(b)) => (Some(a.min(b)), Some(a.max(b))),
+        (a, b) => (a.or(b), None),
+    };
+    first.into_iter().chain(second)
+}
+
+fn lttb(positions: &VecDeque<[f64; 2]>, window: Range<usize>, threshold: usize) -> Vec<usize> {
+    let first = window.start;
+    let last = window.end - 1;
+    let inner = first + 1..last;
+    let bucket_count = threshold.saturating_sub(2).max(1);
+
+    let mut out = Vec::with_capacity(threshold + 2);
+    out.push(first);
+    let mut selected = first;
+    for b in 0..bucket_count {
+        let current = bucket(&inner, bucket_count, b);
+        if current.is_empty() {
+            continue;
+        }
+        // Average of the next bucket (or the last point) is the third triangle vertex.
+        let next = if b + 1 < bucket_count {
+            bucket(&inner, bucket_count, b + 1)
+        } else {
+            last..last + 1
+        };
+        let (mut sum, mut count) = ([0.0, 0.0], 0.0);
+        for index in next.filter(|&i| is_finite(positions[i])) {
+            sum[0] += positions[index][0];
+            sum[1] += positions[index][1];
+            count += 1.0;
+        }
+        let avg = if count > 0.0 {
+            [sum[0] / count, sum[1] / count]
+        } else {
+            positions[last]
+        };
+
+        let a = positions[selected];
+        let mut best: Option<(usize, f64)> = None;
+        for index in current {
+            let p = positions[index];
+            if !is_finite(p) {
+                out.push(index);
+                continue;
+            }
+            let area = ((a[0] - avg[0]) * (p[1] - a[1]) - (a[0] - p[0]) * (avg[1] - a[1])).abs();
+            if best.is_none_or(|(_, best_area)| area > best_area) {
+                best = Some((index, area));
+            }
+        }
+        if let Some((index, _)) = best {
+            // Keep gap markers and the chosen point in index order.
+            let at = out.partition_point(|&i| i < index);
+            out.insert(at, index);
+            selected = index;
+        }
+    }
+    if last != first {
+        out.push(last);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_keeps_peaks_and_gaps_in_index_order() {
+        let mut positions: VecDeque<[f64; 2]> = (0..100).map(|i| [i as f64, 0.0]).collect();
+        positions[10][1] = 5.0;
+        positions[30][1] = -5.0;
+        positions[60][1] = f64::NAN;
+
+        let indices = decimate(Decimation::MinMax, &positions, 0..100, 4, |[x, _]| {
+            Some((x / 25.0) as i64)
+        });
+
+        assert!(indices.contains(&10));
+        assert!(indices.contains(&30));
+        assert!(indices.contains(&60));
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+        assert!(indices.len() <= 2 * 4 + 1);
+    }
+
+    #[test]
+    fn lttb_keeps_endpoints_and_respects_threshold() {
+        let positions: VecDeque<[f64; 2]> = (0..1000)
+            .map(|i| [i as f64, (i as f64 * 0.1).sin()])
+            .collect();
+
+        let indices = decimate(Decimation::Lttb, &positions, 100..900, 50, |_| Some(0));
+
+        assert_eq!(indices.first(), Some(&100));
+        assert_eq!(indices.last(), Some(&899));
+        assert!(indices.len() <= 100);
+        assert!(indices.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn small_windows_are_not_decimated() {
+        let positions: VecDeque<[f64; 2]> = (0..10).map(|i| [i as f64, i as f64]).collect();
+
+        let indices = decimate(Decimation::MinMax, &positions, 2..8, 100, |_| Some(0));
+
+        assert_eq!(indices, vec![2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn min_max_buckets_by_pixel_column_not_by_index() {
+        // Half the samples are crowded into the first of four columns.
+        let positions: VecDeque<[f64; 2]> = (0..100)
+            .map(|i| {
+                let x = if i < 50 {
+                    i as f64 * 0.01
+                } else {
+                    1.0 + (i - 50) as f64 * 0.06
+                };
+                [x, (i * 37 % 11) as f64]
+            })
+            .collect();
+
+        let indices = decimate(Decimation::MinMax, &positions, 0..100, 4, |[x, _]| {
+            Some(x.floor() as i64)
+        });
+
+        assert_eq!(indices.iter().filter(|&&i| i < 50).count(), 2);
+        assert!(indices.len() <= 2 * 4);
+    }
+
+    #[test]
+    fn sorted_by_x_ignores_gaps() {
+        assert!(sorted_by_x(&[
+            [0.0, 1.0],
+            [f64::NAN, 0.0],
+            [1.0, 0.0],
+            [1.0, 2.0]
+        ]));
+        assert!(!sorted_by_x(&[[0.0, 1.0], [2.0, 0.0], [1.0, 0.0]]));
+    }
+}
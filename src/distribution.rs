@@ -0,0 +1,491 @@
+use crate::{
+    Color, LineStyle, MarkerStyle,
+    bar::BarRect,
+    histogram::quantile,
+    series::{Series, SeriesError, ShapeId},
+};
+
+/// Number of points the density outline of a violin is evaluated at.
+const VIOLIN_POINTS: usize = 64;
+
+/// How a [`DistributionPlot`] draws each group of samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistributionStyle {
+    /// A box from the first to the third quartile with a line at the median, whiskers out
+    /// to the furthest samples within the whisker range, and a marker for every outlier.
+    #[default]
+    Box,
+    /// The outline of a Gaussian kernel density estimate, mirrored around the group's
+    /// position, with lines at the quartiles and the median.
+    Violin,
+}
+
+/// Summary statistics of one group of a [`DistributionPlot`], reported to hover and pick
+/// tooltips.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DistributionStats {
+    /// Index of the group.
+    pub index: usize,
+    /// Number of finite samples in the group.
+    pub count: usize,
+    /// Mean of the samples.
+    pub mean: f64,
+    /// Smallest sample within the whisker range.
+    pub lower_whisker: f64,
+    /// First quartile.
+    pub q1: f64,
+    /// Median.
+    pub median: f64,
+    /// Third quartile.
+    pub q3: f64,
+    /// Largest sample within the whisker range.
+    pub upper_whisker: f64,
+    /// Value of the hovered outlier, if an outlier marker was hovered rather than the box.
+    pub outlier: Option<f64>,
+}
+
+/// Box plots or violins of several groups of samples, side by side.
+///
+/// Group `i` is centered on x = `i` unless [positions](Self::with_positions) are set, so
+/// groups line up with categories. The plot shows in the legend as one entry, and hovering
+/// or picking a box (or violin) reports a [`PointId`](crate::PointId) whose `point_index`
+/// is the group index; see [`PlotWidget::distribution_stats`](crate::PlotWidget::distribution_stats).
+/// Non-finite samples are ignored, and empty groups are skipped.
+#[derive(Debug, Clone)]
+pub struct DistributionPlot {
+    /// Unique identifier for the plot.
+    pub id: ShapeId,
+    /// Samples of each group.
+    pub groups: Vec<Vec<f64>>,
+    /// x position of each group. If none, group `i` is at x = `i`.
+    pub positions: Option<Vec<f64>>,
+    /// How each group is drawn.
+    pub style: DistributionStyle,
+    /// Width of each box or violin in world units.
+    pub width: f64,
+    /// Whiskers reach the furthest samples within this many interquartile ranges of the box.
+    pub whisker_range: f64,
+    /// Color of the lines, fills and outliers.
+    pub color: Color,
+    /// Opacity of the box or violin fill.
+    pub fill_opacity: f32,
+    /// Style of the outlines, medians and whiskers.
+    pub line_style: LineStyle,
+    /// Marker drawn for each outlier of a box plot.
+    pub outlier_marker: MarkerStyle,
+    /// Optional label for legends.
+    pub label: Option<String>,
+    /// Statistics of each group, refreshed by the plot widget when the samples change.
+    pub(crate) stats: Vec<Option<DistributionStats>>,
+    /// Group and value of each outlier, in the order of the outlier series' points.
+    pub(crate) outliers: Vec<(usize, f64)>,
+    /// Boxes, or the (undrawn) bounds of violins for picking, in data coordinates.
+    pub(crate) rects: Vec<BarRect>,
+    /// Triangle list vertices of the violin bodies, in data coordinates.
+    pub(crate) bodies: Vec<[f64; 2]>,
+    /// Id of the series drawing outlines, medians and whiskers.
+    pub(crate) lines_id: ShapeId,
+    /// Id of the series drawing outliers.
+    pub(crate) outliers_id: ShapeId,
+}
+
+impl DistributionPlot {
+    /// Create box plots of each group of samples.
+    pub fn boxes(groups: Vec<Vec<f64>>) -> Self {
+        Self::new(groups, DistributionStyle::Box)
+    }
+
+    /// Create violins of each group of samples.
+    pub fn violins(groups: Vec<Vec<f64>>) -> Self {
+        Self::new(groups, DistributionStyle::Violin)
+    }
+
+    fn new(groups: Vec<Vec<f64>>, style: DistributionStyle) -> Self {
+        Self {
+            id: ShapeId::new(),
+            groups,
+            positions: None,
+            style,
+            width: 0.6,
+            whisker_range: 1.5,
+            color: Color::from_rgb(0.3, 0.3, 0.9),
+            fill_opacity: 0.4,
+            line_style: LineStyle::default(),
+            outlier_marker: MarkerStyle::ring(6.0),
+            label: None,
+            stats: Vec::new(),
+            outliers: Vec::new(),
+            rects: Vec::new(),
+            bodies: Vec::new(),
+            lines_id: ShapeId::new(),
+            outliers_id: ShapeId::new(),
+        }
+    }
+
+    /// Set the x position of each group.
+    pub fn with_positions(mut self, positions: Vec<f64>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
+    /// Set the width of each box or violin in world units.
+    pub fn with_width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set how many interquartile ranges the whiskers may reach beyond the box.
+    pub fn with_whisker_range(mut self, range: f64) -> Self {
+        self.whisker_range = range;
+        self
+    }
+
+    /// Set the color of the lines, fills and outliers.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set the opacity of the box or violin fill.
+    pub fn with_fill_opacity(mut self, opacity: f32) -> Self {
+        self.fill_opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Set the style of the outlines, medians and whiskers.
+    pub fn with_line_style(mut self, style: LineStyle) -> Self {
+        self.line_style = style;
+        self
+    }
+
+    /// Set the marker drawn for each outlier.
+    pub fn with_outlier_marker(mut self, marker: MarkerStyle) -> Self {
+        self.outlier_marker = marker;
+        self
+    }
+
+    /// Set a label for this plot (shown in legend when non-empty).
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        if !label.is_empty() {
+            self.label = Some(label);
+        }
+        self
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SeriesError> {
+        if let Some(positions) = &self.positions
+            && (positions.len() != self.groups.len() || !positions.iter().all(|x| x.is_finite()))
+        {
+            return Err(SeriesError::InvalidDistributionPositions);
+        }
+        if !(self.width.is_finite() && self.width > 0.0) {
+            return Err(SeriesError::InvalidBarWidth);
+        }
+        if !(self.whisker_range.is_finite() && self.whisker_range >= 0.0) {
+            return Err(SeriesError::InvalidWhiskerRange);
+        }
+        Ok(())
+    }
+
+    /// Statistics of the group at `index`.
+    pub(crate) fn group_stats(&self, index: usize) -> Option<DistributionStats> {
+        self.stats.get(index).copied().flatten()
+    }
+
+    /// Statistics of the group of the outlier at `index` of the outlier series.
+    pub(crate) fn outlier_stats(&self, index: usize) -> Option<DistributionStats> {
+        let (group, value) = *self.outliers.get(index)?;
+        Some(DistributionStats {
+            outlier: Some(value),
+            ..self.group_stats(group)?
+        })
+    }
+
+    /// The x position of the group at `index`.
+    pub(crate) fn position(&self, index: usize) -> f64 {
+        self.positions
+            .as_ref()
+            .and_then(|positions| positions.get(index).copied())
+            .unwrap_or(index as f64)
+    }
+
+    /// Refresh the statistics and geometry from the samples, returning the series that
+    /// draw the lines and the outliers.
+    pub(crate) fn build(&mut self) -> (Series, Series) {
+        self.stats = self
+            .groups
+            .iter()
+            .enumerate()
+            .map(|(index, samples)| self.compute_stats(index, samples))
+            .collect();
+        self.outliers.clear();
+        self.rects.clear();
+        self.bodies.clear();
+
+        let half = self.width / 2.0;
+        let mut fill = self.color;
+        fill.a *= self.fill_opacity;
+        let mut lines = Vec::new();
+        let mut polyline = |points: &[[f64; 2]]| {
+            if !lines.is_empty() {
+                lines.push([f64::NAN, f64::NAN]);
+            }
+            lines.extend_from_slice(points);
+        };
+
+        for (index, stats) in self.stats.iter().enumerate() {
+            let Some(stats) = stats else {
+                continue;
+            };
+            let x = self.position(index);
+            match self.style {
+                DistributionStyle::Box => {
+                    self.rects.push(BarRect {
+                        min: [x - half, stats.q1],
+                        max: [x + half, stats.q3],
+                        color: fill,
+                        point_index: index,
+                    });
+                    polyline(&[
+                        [x - half, stats.q1],
+                        [x + half, stats.q1],
+                        [x + half, stats.q3],
+                        [x - half, stats.q3],
+                        [x - half, stats.q1],
+                    ]);
+                    polyline(&[[x - half, stats.median], [x + half, stats.median]]);
+                    for (end, whisker) in [
+                        (stats.q1, stats.lower_whisker),
+                        (stats.q3, stats.upper_whisker),
+                    ] {
+                        polyline(&[[x, end], [x, whisker]]);
+                        polyline(&[[x - half / 2.0, whisker], [x + half / 2.0, whisker]]);
+                    }
+                    for &value in &self.groups[index] {
+                        if value.is_finite()
+                            && (value < stats.lower_whisker || value > stats.upper_whisker)
+                        {
+                            self.outliers.push((index, value));
+                        }
+                    }
+                }
+                DistributionStyle::Violin => {
+                    let samples = &self.groups[index];
+                    let Some(density) = Kde::new(samples, stats) else {
+                        // All samples are equal, so there is no spread to draw.
+                        polyline(&[[x - half, stats.median], [x + half, stats.median]]);
+                        continue;
+                    };
+                    let (lo, hi) = density.range;
+                    let outline: Vec<[f64; 2]> = (0..VIOLIN_POINTS)
+                        .map(|i| {
+                            let y = lo + (hi - lo) * i as f64 / (VIOLIN_POINTS - 1) as f64;
+                            [half * density.scaled(y), y]
+                        })
+                        .collect();
+                    for pair in outline.windows(2) {
+                        let ([a, ya], [b, yb]) = (pair[0], pair[1]);
+                        self.bodies.extend([
+                            [x - a, ya],
+                            [x + a, ya],
+                            [x + b, yb],
+                            [x - a, ya],
+                            [x + b, yb],
+                            [x - b, yb],
+                        ]);
+                    }
+                    let mut outline_points: Vec<[f64; 2]> =
+                        outline.iter().map(|&[w, y]| [x - w, y]).collect();
+                    outline_points.extend(outline.iter().rev().map(|&[w, y]| [x + w, y]));
+                    outline_points.push(outline_points[0]);
+                    polyline(&outline_points);
+                    for y in [stats.q1, stats.median, stats.q3] {
+                        let w = half * density.scaled(y);
+                        polyline(&[[x - w, y], [x + w, y]]);
+                    }
+                    // Violins are picked by their bounds, which aren't drawn.
+                    self.rects.push(BarRect {
+                        min: [x - half, lo],
+                        max: [x + half, hi],
+                        color: Color::TRANSPARENT,
+                        point_index: index,
+                    });
+                }
+            }
+        }
+
+        let mut lines = Series::line_only(lines, self.line_style)
+            .with_color(self.color)
+            .with_pickable(false);
+        lines.id = self.lines_id;
+        let positions = self
+            .outliers
+            .iter()
+            .map(|&(index, value)| [self.position(index), value])
+            .collect();
+        let mut outliers =
+            Series::markers_only(positions, self.outlier_marker).with_color(self.color);
+        outliers.id = self.outliers_id;
+        (lines, outliers)
+    }
+
+    fn compute_stats(&self, index: usize, samples: &[f64]) -> Option<DistributionStats> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|v| v.is_finite()).collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+        let (q1, median, q3) = (
+            quantile(&sorted, 0.25),
+            quantile(&sorted, 0.5),
+            quantile(&sorted, 0.75),
+        );
+        let reach = self.whisker_range.max(0.0) * (q3 - q1);
+        let lower_whisker = sorted
+            .iter()
+            .copied()
+            .find(|&v| v >= q1 - reach)
+            .unwrap_or(q1);
+        let upper_whisker = sorted
+            .iter()
+            .rev()
+            .copied()
+            .find(|&v| v <= q3 + reach)
+            .unwrap_or(q3);
+        Some(DistributionStats {
+            index,
+            count: sorted.len(),
+            mean: sorted.iter().sum::<f64>() / sorted.len() as f64,
+            lower_whisker,
+            q1,
+            median,
+            q3,
+            upper_whisker,
+            outlier: None,
+        })
+    }
+}
+
+/// Gaussian kernel density estimate of one group, with Scott's rule for the bandwidth.
+struct Kde<'a> {
+    samples: &'a [f64],
+    bandwidth: f64,
+    /// Range the outline covers: the range of the samples.
+    range: (f64, f64),
+    /// Largest density over the range, so the widest point fills the width.
+    peak: f64,
+}
+
+impl<'a> Kde<'a> {
+    fn new(samples: &'a [f64], stats: &DistributionStats) -> Option<Self> {
+        let n = stats.count as f64;
+        let variance = samples
+            .iter()
+            .filter(|v| v.is_finite())
+            .map(|v| (v - stats.mean).powi(2))
+            .sum::<f64>()
+            / n;
+        let bandwidth = variance.sqrt() * n.powf(-0.2);
+        let (lo, hi) = samples
+            .iter()
+            .copied()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+                (lo.min(v), hi.max(v))
+            });
+        if !(bandwidth > 0.0 && lo < hi) {
+            return None;
+        }
+        let mut kde = Self {
+            samples,
+            bandwidth,
+            range: (lo, hi),
+            peak: 1.0,
+        };
+        kde.peak = (0..VIOLIN_POINTS)
+            .map(|i| kde.density(lo + (hi - lo) * i as f64 / (VIOLIN_POINTS - 1) as f64))
+            .fold(0.0, f64::max);
+        (kde.peak > 0.0).then_some(kde)
+    }
+
+    /// Unnormalized density at `y`; only the shape matters.
+    fn density(&self, y: f64) -> f64 {
+        self.samples
+            .iter()
+            .filter(|v| v.is_finite())
+            .map(|v| (-0.5 * ((y - v) / self.bandwidth).powi(2)).exp())
+            .sum()
+    }
+
+    /// Density at `y` relative to the peak, in `[0, 1]`.
+    fn scaled(&self, y: f64) -> f64 {
+        (self.density(y) / self.peak).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_stats_split_whiskers_and_outliers() {
+        let mut samples: Vec<f64> = (1..=9).map(f64::from).collect();
+        samples.extend([30.0, f64::NAN]);
+        let mut plot = DistributionPlot::boxes(vec![samples, vec![], vec![2.0]])
+            .with_positions(vec![10.0, 20.0, 30.0]);
+        let (_, outliers) = plot.build();
+
+        let stats = plot.group_stats(0).unwrap();
+        assert_eq!(stats.count, 10);
+        assert_eq!((stats.q1, stats.median, stats.q3), (3.25, 5.5, 7.75));
+        assert_eq!((stats.lower_whisker, stats.upper_whisker), (1.0, 9.0));
+        assert_eq!(outliers.positions, [[10.0, 30.0]]);
+        assert_eq!(plot.outlier_stats(0).unwrap().outlier, Some(30.0));
+
+        assert!(plot.group_stats(1).is_none());
+        assert_eq!(plot.group_stats(2).unwrap().median, 2.0);
+        assert_eq!(plot.rects.len(), 2);
+        assert_eq!(plot.rects[1].min, [29.7, 2.0]);
+    }
+
+    #[test]
+    fn violin_outline_stays_within_width_and_range() {
+        let samples = vec![0.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, 4.0];
+        let mut plot = DistributionPlot::violins(vec![samples]).with_width(1.0);
+        let (lines, outliers) = plot.build();
+
+        assert!(outliers.positions.is_empty());
+        assert!(!plot.bodies.is_empty());
+        for &[x, y] in plot.bodies.iter().chain(lines.positions.iter()) {
+            if x.is_finite() {
+                assert!((-0.5..=0.5).contains(&x), "{x}");
+                assert!((0.0..=4.0).contains(&y), "{y}");
+            }
+        }
+        assert_eq!(plot.rects[0].color, Color::TRANSPARENT);
+    }
+
+    #[test]
+    fn invalid_positions_widths_and_whiskers_are_rejected() {
+        let groups = vec![vec![1.0], vec![2.0]];
+        let plot = DistributionPlot::boxes(groups.clone());
+        assert_eq!(plot.validate(), Ok(()));
+        assert_eq!(
+            plot.clone().with_positions(vec![0.0]).validate(),
+            Err(SeriesError::InvalidDistributionPositions)
+        );
+        assert_eq!(
+            plot.clone().with_positions(vec![0.0, f64::NAN]).validate(),
+            Err(SeriesError::InvalidDistributionPositions)
+        );
+        assert_eq!(
+            plot.clone().with_width(0.0).validate(),
+            Err(SeriesError::InvalidBarWidth)
+        );
+        assert_eq!(
+            plot.with_whisker_range(-1.0).validate(),
+            Err(SeriesError::InvalidWhiskerRange)
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use core::fmt;
+use std::collections::VecDeque;
+
+use crate::Color;
+
+/// Per-point error values for one axis of a [`Series`](crate::Series).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorValues {
+    /// The same error below and above each point.
+    Symmetric(VecDeque<f64>),
+    /// Separate errors below (`lower`) and above (`upper`) each point.
+    Asymmetric {
+        lower: VecDeque<f64>,
+        upper: VecDeque<f64>,
+    },
+}
+
+impl ErrorValues {
+    /// Create symmetric errors, one value per point.
+    pub fn symmetric(values: Vec<f64>) -> Self {
+        Self::Symmetric(values.into())
+    }
+
+    /// Create asymmetric errors, one lower and one upper value per point.
+    pub fn asymmetric(lower: Vec<f64>, upper: Vec<f64>) -> Self {
+        Self::Asymmetric {
+            lower: lower.into(),
+            upper: upper.into(),
+        }
+    }
+
+    /// Get the `(lower, upper)` error of the point at `index`.
+    pub fn get(&self, index: usize) -> Option<(f64, f64)> {
+        match self {
+            Self::Symmetric(values) => values.get(index).map(|&v| (v, v)),
+            Self::Asymmetric { lower, upper } => {
+                lower.get(index).copied().zip(upper.get(index).copied())
+            }
+        }
+    }
+
+    /// Append `count` zero errors.
+    pub(crate) fn extend_zero(&mut self, count: usize) {
+        match self {
+            Self::Symmetric(values) => values.resize(values.len() + count, 0.0),
+            Self::Asymmetric { lower, upper } => {
+                lower.resize(lower.len() + count, 0.0);
+                upper.resize(upper.len() + count, 0.0);
+            }
+        }
+    }
+
+    /// Keep the errors of the first `len` points, padding with zero errors.
+    pub(crate) fn resize(&mut self, len: usize) {
+        match self {
+            Self::Symmetric(values) => values.resize(len, 0.0),
+            Self::Asymmetric { lower, upper } => {
+                lower.resize(len, 0.0);
+                upper.resize(len, 0.0);
+            }
+        }
+    }
+
+    /// Remove the errors of the first `count` points.
+    pub(crate) fn remove_front(&mut self, count: usize) {
+        match self {
+            Self::Symmetric(values) => {
+                values.drain(..count.min(values.len()));
+            }
+            Self::Asymmetric { lower, upper } => {
+                lower.drain(..count.min(lower.len()));
+                upper.drain(..count.min(upper.len()));
+            }
+        }
+    }
+
+    /// Whether every error is finite and non-negative.
+    pub(crate) fn all_valid(&self) -> bool {
+        let valid = |values: &VecDeque<f64>| values.iter().all(|v| v.is_finite() && *v >= 0.0);
+        match self {
+            Self::Symmetric(values) => valid(values),
+            Self::Asymmetric { lower, upper } => valid(lower) && valid(upper),
+        }
+    }
+
+    pub(crate) fn len_matches(&self, len: usize) -> bool {
+        match self {
+            Self::Symmetric(values) => values.len() == len,
+            Self::Asymmetric { lower, upper } => lower.len() == len && upper.len() == len,
+        }
+    }
+}
+
+impl From<Vec<f64>> for ErrorValues {
+    fn from(values: Vec<f64>) -> Self {
+        Self::symmetric(values)
+    }
+}
+
+impl From<(Vec<f64>, Vec<f64>)> for ErrorValues {
+    fn from((lower, upper): (Vec<f64>, Vec<f64>)) -> Self {
+        Self::asymmetric(lower, upper)
+    }
+}
+
+/// End cap drawn across each end of an error bar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorBarCap {
+    /// No cap; only the whisker is drawn.
+    None,
+    /// A perpendicular line of the given length in logical pixels.
+    Line { width: f32 },
+}
+
+impl Default for ErrorBarCap {
+    fn default() -> Self {
+        Self::Line { width: 6.0 }
+    }
+}
+
+/// Styling options for error bars.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorBarStyle {
+    /// Width of the whiskers and caps in logical pixels.
+    pub line_width: f32,
+    /// Cap drawn at both ends of each whisker.
+    pub cap: ErrorBarCap,
+    /// Color of the error bars. If none, the point color is used.
+    pub color: Option<Color>,
+}
+
+impl Default for ErrorBarStyle {
+    fn default() -> Self {
+        Self {
+            line_width: 1.0,
+            cap: ErrorBarCap::default(),
+            color: None,
+        }
+    }
+}
+
+impl ErrorBarStyle {
+    /// Set the whisker and cap width in logical pixels.
+    pub fn with_line_width(mut self, width: f32) -> Self {
+        self.line_width = width;
+        self
+    }
+
+    /// Set the cap style.
+    pub fn with_cap(mut self, cap: ErrorBarCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Set the cap length in logical pixels.
+    pub fn with_cap_width(self, width: f32) -> Self {
+        self.with_cap(ErrorBarCap::Line { width })
+    }
+
+    /// Draw whiskers without caps.
+    pub fn without_caps(self) -> Self {
+        self.with_cap(ErrorBarCap::None)
+    }
+
+    /// Set the error bar color instead of using the point color.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+}
+
+/// One whisker in plot coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ErrorBar {
+    pub(crate) start: [f64; 2],
+    pub(crate) end: [f64; 2],
+    /// True for y errors (vertical whisker, horizontal caps).
+    pub(crate) vertical: bool,
+    pub(crate) color: Color,
+}
+
+impl ErrorBar {
+    /// Whisker followed by cap segments, given the plot-space size of one logical pixel.
+    pub(crate) fn segments(
+        &self,
+        cap: ErrorBarCap,
+        world_per_px: [f64; 2],
+    ) -> impl Iterator<Item = [[f64; 2]; 2]> {
+        let caps = match cap {
+            ErrorBarCap::Line { width } if width > 0.0 => {
+                let cap = |[x, y]: [f64; 2]| {
+                    if self.vertical {
+                        let half = 0.5 * width as f64 * world_per_px[0];
+                        [[x - half, y], [x + half, y]]
+                    } else {
+                        let half = 0.5 * width as f64 * world_per_px[1];
+                        [[x, y - half], [x, y + half]]
+                    }
+                };
+                Some([cap(self.start), cap(self.end)])
+            }
+            _ => None,
+        };
+        std::iter::once([self.start, self.end]).chain(caps.into_iter().flatten())
+    }
+}
+
+/// Displays a `(lower, upper)` error as `± e`, or `+upper/-lower` when asymmetric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ErrorDisplay {
+    pub(crate) lower: f64,
+    pub(crate) upper: f64,
+}
+
+impl fmt::Display for ErrorDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.lower == self.upper {
+            write!(f, "± {:.2}", self.upper)
+        } else {
+            write!(f, "+{:.2}/-{:.2}", self.upper, self.lower)
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    borrow::Cow,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use iced::{
+    Color, Font, Pixels, Rectangle,
+    advanced::{
+        graphics::{geometry::Renderer as _, text::font_system},
+        renderer::Headless,
+    },
+    widget::canvas::{self, Frame},
+};
+
+use super::{ExportError, ExportOptions, Figure, Label, RgbaImage, background_color};
+use crate::{
+    PlotWidget,
+    plot_renderer::canvas::{draw_static, draw_swatch},
+};
+
+impl PlotWidget {
+    /// Render the plot into an RGBA image on the CPU.
+    ///
+    /// The current view is exported, including the grid, series, fills, reference lines,
+    /// tick and axis labels, and the legend; hover/pick highlights and other interactive
+    /// overlays are not. The image is `options.width * options.scale_factor` by
+    /// `options.height * options.scale_factor` physical pixels.
+    ///
+    /// Rendering uses `tiny-skia`, so no GPU is required.
+    pub fn export_image(&self, options: &ExportOptions) -> Result<RgbaImage, ExportError> {
+        let figure = Figure::new(self, options)?;
+        if let Some(bytes) = options.font_data {
+            font_system()
+                .write()
+                .map_err(|_| ExportError::FontSystemUnavailable)?
+                .load_font(Cow::Borrowed(bytes));
+        }
+        let mut renderer = headless_renderer(options.font, Pixels(self.tick_label_size))
+            .ok_or(ExportError::RendererUnavailable)?;
+
+        let mut frame = Frame::new(&renderer, figure.size);
+        draw_panel(
+            &mut frame,
+            Rectangle::with_size(figure.size),
+            &figure.style.frame,
+        );
+        draw_panel(&mut frame, figure.plot_area, &figure.style.plot_area);
+        frame.with_clip(figure.plot, |frame| {
+            draw_static(frame, &figure.state, figure.state.bounds);
+        });
+        for label in &figure.labels {
+            draw_label(&mut frame, label, options.font);
+        }
+        if let Some(legend) = &figure.legend {
+            draw_panel(&mut frame, legend.bounds, &figure.style.legend);
+            for item in &legend.items {
+                draw_swatch(&mut frame, item.swatch_position, &item.swatch);
+                draw_label(&mut frame, &item.label, options.font);
+            }
+        }
+        renderer.draw_geometry(frame.into_geometry());
+
+        let width = (options.width as f32 * options.scale_factor)
+            .round()
+            .max(1.0) as u32;
+        let height = (options.height as f32 * options.scale_factor)
+            .round()
+            .max(1.0) as u32;
+        let mut pixels = renderer.screenshot(
+            iced::Size::new(width, height),
+            options.scale_factor,
+            Color::TRANSPARENT,
+        );
+        unpremultiply(&mut pixels);
+
+        Ok(RgbaImage {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+/// Create a CPU renderer. `tiny-skia` is ready immediately, so the future is polled once
+/// instead of pulling in an executor.
+fn headless_renderer(font: Font, text_size: Pixels) -> Option<iced::Renderer> {
+    let future = pin!(<iced::Renderer as Headless>::new(
+        font,
+        text_size,
+        Some("tiny-skia"),
+    ));
+    match future.poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(renderer) => renderer,
+        Poll::Pending => None,
+    }
+}
+
+/// Fill and outline a rectangle with a container style.
+fn draw_panel(frame: &mut Frame, bounds: Rectangle, style: &iced::widget::container::Style) {
+    let path =
+        canvas::Path::rounded_rectangle(bounds.position(), bounds.size(), style.border.radius);
+    if let Some(color) = background_color(style) {
+        frame.fill(&path, color);
+    }
+    if style.border.width > 0.0 {
+        frame.stroke(
+            &path,
+            canvas::Stroke::default()
+                .with_width(style.border.width)
+                .with_color(style.border.color),
+        );
+    }
+}
+
+fn draw_label(frame: &mut Frame, label: &Label, font: Font) {
+    frame.fill_text(canvas::Text {
+        content: label.content.clone(),
+        position: label.position,
+        color: label.color,
+        size: Pixels(label.size),
+        font,
+        align_x: if label.centered {
+            iced::widget::text::Alignment::Center
+        } else {
+            iced::widget::text::Alignment::Left
+        },
+        align_y: iced::alignment::Vertical::Top,
+        ..canvas::Text::default()
+    });
+}
+
+/// Convert premultiplied RGBA, as produced by the renderer, to straight alpha.
+fn unpremultiply(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        if alpha == 0 || alpha == 255 {
+            continue;
+        }
+        for channel in &mut pixel[..3] {
+            *channel = ((*channel as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PlotWidgetBuilder, Series};
+
+    #[test]
+    fn export_draws_series_inside_plot_area() {
+        let color = Color::from_rgb(1.0, 0.0, 0.0);
+        let widget = PlotWidgetBuilder::new()
+            .add_series(Series::squares(vec![[0.0, 0.0]], 40.0).with_color(color))
+            .build()
+            .unwrap();
+
+        let image = widget
+            .export_image(&ExportOptions::new(100, 80).with_scale_factor(2.0))
+            .unwrap();
+
+        assert_eq!((image.width, image.height), (200, 160));
+        let center = ((80 * image.width + 100) * 4) as usize;
+        assert_eq!(image.pixels[center..center + 4], [255, 0, 0, 255]);
+        let corner = ((2 * image.width + 2) * 4) as usize;
+        assert_ne!(image.pixels[corner..corner + 3], [255, 0, 0]);
+    }
+}
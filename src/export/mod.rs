@@ -0,0 +1,485 @@
+//! Rendering a [`PlotWidget`] outside of an iced window.
+//!
+//! Exporters lay the plot out the same way [`PlotWidget::view`] does: axis labels around a
+//! plot area holding the data, with tick labels along its left, bottom and, for a secondary
+//! y-axis, right edges and the legend in its top-left corner.
+//!
+//! SVG export and PNG encoding need no renderer, so this module is always compiled; SVG
+//! embeds rasters as PNG. Only [`PlotWidget::export_image`] draws through iced's canvas and
+//! needs the `canvas` feature.
+#[cfg(feature = "canvas")]
+mod image;
+mod png;
+mod svg;
+
+pub use png::RgbaImage;
+
+use iced::{Color, Font, Point, Rectangle, Size, Theme, widget::container};
+
+use crate::{
+    LegendPosition, PlotWidget,
+    legend::{
+        LEGEND_SPACING, LegendEntry, SWATCH_PADDING, SWATCH_SIZE, SwatchPart, corner_position,
+        plot_box,
+    },
+    plot_state::PlotState,
+    plot_widget::{FRAME_PADDING, PLOT_CONTENT_PADDING, apply_data_aspect},
+    style::PlotStyle,
+    text::{LINE_HEIGHT, text_width},
+};
+
+/// Widest a y-axis label may get, matching the on-screen layout.
+const Y_LABEL_MAX_WIDTH: f32 = 100.0;
+
+/// Options for exporting a [`PlotWidget`].
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    /// Width of the exported figure in logical pixels.
+    pub width: u32,
+    /// Height of the exported figure in logical pixels.
+    pub height: u32,
+    /// Number of physical pixels per logical pixel in raster output.
+    pub scale_factor: f32,
+    /// Theme used to resolve the plot's [`PlotStyle`].
+    pub theme: Theme,
+    /// Font used for tick labels, axis labels, and the legend.
+    pub font: Font,
+    /// Font data to load before rendering. Fonts loaded by the running iced
+    /// application are available without this.
+    pub font_data: Option<&'static [u8]>,
+}
+
+impl ExportOptions {
+    /// Export a figure of the given size in logical pixels.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            scale_factor: 1.0,
+            theme: Theme::Light,
+            font: Font::default(),
+            font_data: None,
+        }
+    }
+
+    /// Set the number of physical pixels per logical pixel.
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+
+    /// Set the theme used to resolve the plot style.
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Set the font used for text.
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Load the given font data before rendering, e.g. when exporting without a window.
+    pub fn with_font_data(mut self, bytes: &'static [u8]) -> Self {
+        self.font_data = Some(bytes);
+        self
+    }
+
+    fn validate(&self) -> Result<(), ExportError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(ExportError::InvalidSize);
+        }
+        if !(self.scale_factor.is_finite() && self.scale_factor > 0.0) {
+            return Err(ExportError::InvalidScaleFactor);
+        }
+        Ok(())
+    }
+}
+
+/// Errors that can occur when exporting a plot.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportError {
+    /// Width or height is zero.
+    InvalidSize,
+    /// Scale factor must be finite and positive.
+    InvalidScaleFactor,
+    /// The offscreen renderer could not be created.
+    RendererUnavailable,
+    /// The shared font system is unusable because a thread panicked while holding it.
+    FontSystemUnavailable,
+}
+
+/// A line of text positioned by its top-left corner, or its top-center when `centered`.
+pub(crate) struct Label {
+    pub(crate) content: String,
+    pub(crate) position: Point,
+    pub(crate) size: f32,
+    pub(crate) color: Color,
+    pub(crate) centered: bool,
+}
+
+pub(crate) struct LegendItem {
+    /// Top-left corner of the swatch, which is [`SWATCH_SIZE`] large.
+    pub(crate) swatch_position: Point,
+    pub(crate) swatch: Vec<SwatchPart>,
+    pub(crate) label: Label,
+}
+
+pub(crate) struct LegendLayout {
+    pub(crate) bounds: Rectangle,
+    pub(crate) items: Vec<LegendItem>,
+}
+
+/// Everything an exporter needs to draw, in logical pixels.
+pub(crate) struct Figure {
+    pub(crate) size: Size,
+    pub(crate) style: PlotStyle,
+    /// Plot area container, including its padding around the data.
+    pub(crate) plot_area: Rectangle,
+    /// Region the plot data is drawn into; `state.bounds` has the same size.
+    pub(crate) plot: Rectangle,
+    pub(crate) state: PlotState,
+    pub(crate) labels: Vec<Label>,
+    pub(crate) legend: Option<LegendLayout>,
+}
+
+impl Figure {
+    pub(crate) fn new(widget: &PlotWidget, options: &ExportOptions) -> Result<Self, ExportError> {
+        options.validate()?;
+        let size = Size::new(options.width as f32, options.height as f32);
+        let style = (widget.style)(&options.theme);
+
+        let mut content = inset(Rectangle::with_size(size), FRAME_PADDING);
+
+        // Legends outside the plot take their space from the side they are on.
+        let legend_entries = if widget.legend_enabled && !widget.legend_collapsed {
+            widget.legend_entries()
+        } else {
+            Vec::new()
+        };
+        let legend_outside = content;
+        if !legend_entries.is_empty() {
+            let (_, legend_size) = widget.legend.layout(&legend_entries);
+            match widget.legend.position {
+                LegendPosition::OutsideRight => {
+                    content.width = (content.width - legend_size.width - LEGEND_SPACING).max(0.0);
+                }
+                LegendPosition::OutsideBottom => {
+                    content.height =
+                        (content.height - legend_size.height - LEGEND_SPACING).max(0.0);
+                }
+                _ => {}
+            }
+        }
+        let x_label_height = if widget.x_axis_label.is_empty() {
+            0.0
+        } else {
+            widget.axis_label_size * LINE_HEIGHT
+        };
+        let y_label_width = if widget.y_axis_label.is_empty() {
+            0.0
+        } else {
+            text_width(&widget.y_axis_label, widget.axis_label_size).min(Y_LABEL_MAX_WIDTH)
+        };
+        let y2_label_width = if widget.y2_axis_label.is_empty() {
+            0.0
+        } else {
+            text_width(&widget.y2_axis_label, widget.axis_label_size).min(Y_LABEL_MAX_WIDTH)
+        };
+        let plot_area = Rectangle {
+            x: content.x + y_label_width,
+            y: content.y,
+            width: (content.width - y_label_width - y2_label_width).max(0.0),
+            height: (content.height - x_label_height).max(0.0),
+        };
+        let plot = inset(plot_area, PLOT_CONTENT_PADDING);
+        let state = export_state(widget, &style, plot);
+
+        let mut labels = Vec::new();
+        if !widget.x_axis_label.is_empty() {
+            labels.push(Label {
+                content: widget.x_axis_label.clone(),
+                position: Point::new(plot_area.center_x(), plot_area.y + plot_area.height),
+                size: widget.axis_label_size,
+                color: style.axis_label_color,
+                centered: true,
+            });
+        }
+        if !widget.y_axis_label.is_empty() {
+            labels.push(Label {
+                content: widget.y_axis_label.clone(),
+                position: Point::new(
+                    content.x,
+                    content.center_y() - 0.5 * widget.axis_label_size * LINE_HEIGHT,
+                ),
+                size: widget.axis_label_size,
+                color: style.axis_label_color,
+                centered: false,
+            });
+        }
+        if !widget.y2_axis_label.is_empty() {
+            labels.push(Label {
+                content: widget.y2_axis_label.clone(),
+                position: Point::new(
+                    content.x + content.width - y2_label_width,
+                    content.center_y() - 0.5 * widget.axis_label_size * LINE_HEIGHT,
+                ),
+                size: widget.axis_label_size,
+                color: style.axis_label_color,
+                centered: false,
+            });
+        }
+        labels.extend(tick_labels(widget, &state, &style, plot_area));
+
+        let legend = legend_layout(widget, legend_entries, plot_area, legend_outside, &state);
+
+        Ok(Self {
+            size,
+            style,
+            plot_area,
+            plot,
+            state,
+            labels,
+            legend,
+        })
+    }
+}
+
+/// Build plot state for the widget's current view, drawn into `plot`.
+fn export_state(widget: &PlotWidget, style: &PlotStyle, plot: Rectangle) -> PlotState {
+    let mut state = PlotState {
+        bounds: plot,
+        grid_style: style.grid,
+        ..PlotState::default()
+    };
+    if let Some((camera, _)) = widget.camera_bounds {
+        state.camera = camera;
+        state.y2_map = widget.y2_map;
+    }
+    state.rebuild_from_widget(widget);
+    if widget.camera_bounds.is_none() {
+        state.autoscale(false);
+    }
+    if let Some(aspect) = widget.data_aspect {
+        apply_data_aspect(&mut state.camera, &state.bounds, aspect);
+    }
+    if state.y2_map != state.built_y2_map || widget.has_visible_camera_dependent_geometry() {
+        state.rebuild_from_widget(widget);
+    }
+    state.update_ticks(
+        widget.x_tick_producer.as_ref(),
+        widget.y_tick_producer.as_ref(),
+        widget.y2_tick_producer.as_ref(),
+    );
+    state
+}
+
+/// Tick labels placed like the on-screen ones: x labels along the bottom of the plot area,
+/// y labels along its left edge and secondary y labels along its right edge.
+fn tick_labels(
+    widget: &PlotWidget,
+    state: &PlotState,
+    style: &PlotStyle,
+    plot_area: Rectangle,
+) -> Vec<Label> {
+    let size = widget.tick_label_size;
+    let mut labels =
+        Vec::with_capacity(state.x_ticks.len() + state.y_ticks.len() + state.y2_ticks.len());
+    if let Some(formatter) = &widget.x_axis_formatter {
+        for tick in &state.x_ticks {
+            let content = formatter(tick.tick);
+            let centering_offset = 2.0 * (content.len() as f32);
+            labels.push(Label {
+                position: Point::new(
+                    plot_area.x + tick.screen_pos - centering_offset,
+                    plot_area.y + plot_area.height - size * LINE_HEIGHT,
+                ),
+                content,
+                size,
+                color: style.tick_label_color,
+                centered: false,
+            });
+        }
+    }
+    if let Some(formatter) = &widget.y_axis_formatter {
+        for tick in &state.y_ticks {
+            labels.push(Label {
+                content: formatter(tick.tick),
+                position: Point::new(plot_area.x, plot_area.y + tick.screen_pos - 5.0),
+                size,
+                color: style.tick_label_color,
+                centered: false,
+            });
+        }
+    }
+    if let Some(formatter) = &widget.y2_axis_formatter {
+        for tick in &state.y2_ticks {
+            let content = formatter(tick.tick);
+            labels.push(Label {
+                position: Point::new(
+                    plot_area.x + plot_area.width - text_width(&content, size),
+                    plot_area.y + tick.screen_pos - 5.0,
+                ),
+                content,
+                size,
+                color: style.tick_label_color,
+                centered: false,
+            });
+        }
+    }
+    labels
+}
+
+/// The legend laid out in its [position](crate::LegendPosition), inside the plot area or
+/// in the `outside` space left for it. The collapse toggle is interactive, so it is left
+/// out; a collapsed legend is not exported.
+fn legend_layout(
+    widget: &PlotWidget,
+    entries: Vec<LegendEntry>,
+    plot_area: Rectangle,
+    outside: Rectangle,
+    state: &PlotState,
+) -> Option<LegendLayout> {
+    if entries.is_empty() {
+        return None;
+    }
+    let options = widget.legend;
+    let (cells, size) = options.layout(&entries);
+    let origin = match options.position {
+        LegendPosition::OutsideRight => Point::new(
+            outside.x + outside.width - size.width,
+            outside.center_y() - 0.5 * size.height,
+        ),
+        LegendPosition::OutsideBottom => Point::new(
+            outside.center_x() - 0.5 * size.width,
+            outside.y + outside.height - size.height,
+        ),
+        _ => {
+            let covered = |rect| {
+                let (min, max) = plot_box(&state.camera, &state.bounds, rect);
+                let mut count = 0;
+                for span in state.series.iter() {
+                    span.for_each_point_within(&state.points, min, max, |_| count += 1);
+                }
+                count
+            };
+            let position = options.resolve_position(plot_area.size(), size, covered);
+            let corner = corner_position(position, plot_area.size(), size);
+            Point::new(plot_area.x + corner.x, plot_area.y + corner.y)
+        }
+    };
+
+    let row_height = options.row_height();
+    let world_per_px = state.camera.world_per_px(&state.bounds)[0];
+    let items = entries
+        .into_iter()
+        .zip(cells)
+        .map(|(entry, cell)| {
+            let cell = Point::new(origin.x + cell.x, origin.y + cell.y);
+            let mut color = entry.color;
+            color.a = 1.0;
+            LegendItem {
+                swatch_position: Point::new(
+                    cell.x + SWATCH_PADDING,
+                    cell.y + 0.5 * (row_height - SWATCH_SIZE.height),
+                ),
+                swatch: entry.swatch(world_per_px),
+                label: Label {
+                    position: Point::new(
+                        cell.x + SWATCH_SIZE.width + 2.0 * SWATCH_PADDING + LEGEND_SPACING,
+                        cell.y + 0.5 * (row_height - options.text_size * LINE_HEIGHT),
+                    ),
+                    content: entry.label,
+                    size: options.text_size,
+                    color,
+                    centered: false,
+                },
+            }
+        })
+        .collect();
+
+    Some(LegendLayout {
+        bounds: Rectangle::new(origin, size),
+        items,
+    })
+}
+
+/// Background color of a container style, if it is a solid color.
+pub(crate) fn background_color(style: &container::Style) -> Option<Color> {
+    match style.background? {
+        iced::Background::Color(color) => Some(color),
+        iced::Background::Gradient(_) => None,
+    }
+}
+
+fn inset(rect: Rectangle, padding: f32) -> Rectangle {
+    Rectangle {
+        x: rect.x + padding,
+        y: rect.y + padding,
+        width: (rect.width - 2.0 * padding).max(0.0),
+        height: (rect.height - 2.0 * padding).max(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Legend, PlotWidgetBuilder, Series};
+
+    #[test]
+    fn figure_places_plot_between_axis_labels() {
+        let widget = PlotWidgetBuilder::new()
+            .with_x_label("time")
+            .with_y_label("value")
+            .add_series(
+                Series::line_only(vec![[0.0, 0.0], [1.0, 2.0]], Default::default()).with_label("a"),
+            )
+            .build()
+            .unwrap();
+
+        let figure = Figure::new(&widget, &ExportOptions::new(400, 300)).unwrap();
+
+        assert!(figure.plot_area.x > FRAME_PADDING);
+        assert!(figure.plot_area.y + figure.plot_area.height < 300.0 - FRAME_PADDING);
+        assert_eq!(figure.state.bounds.size(), figure.plot.size());
+        assert!(!figure.state.x_ticks.is_empty());
+        assert_eq!(
+            figure.legend.as_ref().map(|legend| legend.items.len()),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn outside_legends_take_space_from_the_plot() {
+        let builder = || {
+            PlotWidgetBuilder::new().add_series(
+                Series::line_only(vec![[0.0, 0.0], [1.0, 2.0]], Default::default()).with_label("a"),
+            )
+        };
+        let options = ExportOptions::new(400, 300);
+        let inside = Figure::new(&builder().build().unwrap(), &options).unwrap();
+        let outside = Figure::new(
+            &builder()
+                .with_legend(Legend::new().with_position(LegendPosition::OutsideRight))
+                .build()
+                .unwrap(),
+            &options,
+        )
+        .unwrap();
+
+        let legend = outside.legend.unwrap().bounds;
+        assert!(outside.plot_area.width < inside.plot_area.width);
+        assert!(legend.x >= outside.plot_area.x + outside.plot_area.width);
+        assert!(legend.x + legend.width <= 400.0 - FRAME_PADDING + 0.01);
+    }
+
+    #[test]
+    fn zero_size_is_rejected() {
+        let widget = PlotWidget::new();
+
+        let result = Figure::new(&widget, &ExportOptions::new(0, 300));
+
+        assert!(matches!(result, Err(ExportError::InvalidSize)));
+    }
+}
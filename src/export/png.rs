@@ -0,0 +1,69 @@
+use std::{io, path::Path};
+
+/// An 8-bit RGBA image with straight (non-premultiplied) alpha, rows top to bottom.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RgbaImage {
+    /// Width in physical pixels.
+    pub width: u32,
+    /// Height in physical pixels.
+    pub height: u32,
+    /// `width * height * 4` bytes of RGBA data.
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaImage {
+    /// Encode the image as PNG. Fails when the size is zero or doesn't match `pixels`.
+    pub fn to_png(&self) -> io::Result<Vec<u8>> {
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&self.pixels)?;
+        writer.finish()?;
+        Ok(out)
+    }
+
+    /// Encode the image as PNG and write it to `path`.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_png()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RgbaImage;
+
+    #[test]
+    fn png_decodes_to_the_same_pixels() {
+        let (width, height) = (37, 5);
+        let pixels: Vec<u8> = (0..width * height * 4)
+            .map(|i| if i < 200 { 0x7f } else { (i * 31 % 251) as u8 })
+            .collect();
+        let image = RgbaImage {
+            width,
+            height,
+            pixels: pixels.clone(),
+        };
+        let bytes = image.to_png().unwrap();
+
+        let mut reader = png::Decoder::new(std::io::Cursor::new(bytes))
+            .read_info()
+            .unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+        assert_eq!((info.width, info.height), (width, height));
+        assert_eq!(info.color_type, png::ColorType::Rgba);
+        assert_eq!(&decoded[..info.buffer_size()], &pixels[..]);
+    }
+
+    #[test]
+    fn mismatched_pixel_data_is_an_error() {
+        let image = RgbaImage {
+            width: 2,
+            height: 2,
+            pixels: vec![0; 12],
+        };
+        assert!(image.to_png().is_err());
+    }
+}
@@ -0,0 +1,928 @@
+use std::fmt::{self, Write};
+
+use base64::{Engine, prelude::BASE64_STANDARD};
+use iced::{Color, Font, Point, Rectangle, font::Family, widget::container};
+
+use super::{ExportError, ExportOptions, Figure, Label, RgbaImage, background_color};
+use crate::{
+    LineCap, LineJoin, LineType, PlotWidget, Size,
+    grid::TickWeight,
+    legend::{SwatchPart, SwatchShape},
+    plot_renderer::{
+        SegmentDashes, blend_colors, cap_outline, join_outline, marker_type_from_u32, stroke_is_on,
+        world_to_canvas_point,
+    },
+    plot_state::PlotState,
+    plot_widget::{world_to_screen_position_x, world_to_screen_position_y},
+    point::{MARKER_SIZE_WORLD, MarkerType},
+    raster::{RasterOrigin, RasterSampling},
+    text::LINE_HEIGHT,
+    transform::data_value_to_plot_with_axis_range,
+};
+
+const GRID_STROKE_WIDTH: f32 = 0.5;
+/// Height of the ascender above the baseline, relative to the font size.
+const ASCENT: f32 = 0.8;
+
+impl PlotWidget {
+    /// Render the plot as an SVG document.
+    ///
+    /// The current view is exported as vector graphics, including the grid, series,
+    /// fills, reference lines, tick and axis labels, and the legend; hover/pick highlights
+    /// and other interactive overlays are not. Rasters are embedded as PNG images.
+    /// `options.scale_factor` is ignored.
+    pub fn export_svg(&self, options: &ExportOptions) -> Result<String, ExportError> {
+        let figure = Figure::new(self, options)?;
+        let mut svg = String::new();
+        write_svg(&mut svg, &figure, options.font).expect("writing to a String cannot fail");
+        Ok(svg)
+    }
+}
+
+fn write_svg(out: &mut String, figure: &Figure, font: Font) -> fmt::Result {
+    let Figure {
+        size, plot, state, ..
+    } = figure;
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = size.width,
+        h = size.height,
+    )?;
+    writeln!(
+        out,
+        r#"<defs><clipPath id="plot"><rect width="{}" height="{}"/></clipPath></defs>"#,
+        num(plot.width),
+        num(plot.height),
+    )?;
+    write_panel(out, Rectangle::with_size(*size), &figure.style.frame)?;
+    write_panel(out, figure.plot_area, &figure.style.plot_area)?;
+
+    writeln!(
+        out,
+        r#"<g clip-path="url(#plot)" transform="translate({} {})">"#,
+        num(plot.x),
+        num(plot.y),
+    )?;
+    let bounds = state.bounds;
+    write_grid(out, state, bounds)?;
+    write_rasters(out, state, bounds)?;
+    write_fills(out, state, bounds)?;
+    write_bars(out, state, bounds)?;
+    write_lines(out, state, bounds)?;
+    write_error_bars(out, state, bounds)?;
+    write_reference_lines(out, state, bounds)?;
+    write_markers(out, state, bounds)?;
+    writeln!(out, "</g>")?;
+
+    let family = font_family(font);
+    for label in &figure.labels {
+        write_text(out, label, &family)?;
+    }
+    if let Some(legend) = &figure.legend {
+        write_panel(out, legend.bounds, &figure.style.legend)?;
+        for item in &legend.items {
+            write_swatch(out, item.swatch_position, &item.swatch)?;
+            write_text(out, &item.label, &family)?;
+        }
+    }
+    writeln!(out, "</svg>")
+}
+
+fn write_panel(out: &mut String, bounds: Rectangle, style: &container::Style) -> fmt::Result {
+    let fill = background_color(style);
+    let border = style.border.width > 0.0;
+    if fill.is_none() && !border {
+        return Ok(());
+    }
+    write!(
+        out,
+        r#"<rect x="{}" y="{}" width="{}" height="{}""#,
+        num(bounds.x),
+        num(bounds.y),
+        num(bounds.width),
+        num(bounds.height),
+    )?;
+    let radius = style.border.radius.top_left;
+    if radius > 0.0 {
+        write!(out, r#" rx="{}""#, num(radius))?;
+    }
+    match fill {
+        Some(color) => write!(out, "{}", Paint::Fill(color))?,
+        None => write!(out, r#" fill="none""#)?,
+    }
+    if border {
+        write!(
+            out,
+            r#"{} stroke-width="{}""#,
+            Paint::Stroke(style.border.color),
+            num(style.border.width),
+        )?;
+    }
+    writeln!(out, "/>")
+}
+
+fn write_grid(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    let color = |weight| match weight {
+        TickWeight::Major => state.grid_style.major,
+        TickWeight::Minor => state.grid_style.minor,
+        TickWeight::SubMinor => state.grid_style.sub_minor,
+    };
+    for tick in &state.x_ticks {
+        let x = tick.screen_pos;
+        write_line(
+            out,
+            Point::new(x, 0.0),
+            Point::new(x, bounds.height),
+            &Stroke::solid(GRID_STROKE_WIDTH, color(tick.tick.line_type)),
+        )?;
+    }
+    for tick in &state.y_ticks {
+        let y = tick.screen_pos;
+        write_line(
+            out,
+            Point::new(0.0, y),
+            Point::new(bounds.width, y),
+            &Stroke::solid(GRID_STROKE_WIDTH, color(tick.tick.line_type)),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_rasters(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    for span in state.rasters.iter() {
+        let grid = &span.grid;
+        // PNG rows go top to bottom.
+        let stride = grid.columns * 4;
+        let pixels = match grid.origin {
+            RasterOrigin::Upper => span.pixels.to_vec(),
+            RasterOrigin::Lower => span
+                .pixels
+                .chunks_exact(stride)
+                .rev()
+                .flatten()
+                .copied()
+                .collect(),
+        };
+        let image = RgbaImage {
+            width: grid.columns as u32,
+            height: grid.rows as u32,
+            pixels,
+        };
+        // Raster grids always match their pixel data, so encoding only fails for empty grids.
+        let Ok(png) = image.to_png() else {
+            continue;
+        };
+        let a = world_to_canvas_point(grid.min, &state.camera, &bounds);
+        let b = world_to_canvas_point(grid.max, &state.camera, &bounds);
+        let rendering = match span.sampling {
+            RasterSampling::Nearest => r#" style="image-rendering:pixelated""#,
+            RasterSampling::Linear => "",
+        };
+        writeln!(
+            out,
+            r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none"{rendering} href="data:image/png;base64,{}"/>"#,
+            num(a.x.min(b.x)),
+            num(a.y.min(b.y)),
+            num((a.x - b.x).abs()),
+            num((a.y - b.y).abs()),
+            BASE64_STANDARD.encode(png),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_fills(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    for fill in state.fills.iter() {
+        if fill.vertices.len() < 3 {
+            continue;
+        }
+        write!(out, r#"<path d=""#)?;
+        for triangle in fill.vertices.chunks_exact(3) {
+            let [a, b, c] =
+                [0, 1, 2].map(|i| world_to_canvas_point(triangle[i], &state.camera, &bounds));
+            if !drawable(&[a, b, c]) {
+                continue;
+            }
+            write!(
+                out,
+                "M{} {}L{} {}L{} {}Z",
+                num(a.x),
+                num(a.y),
+                num(b.x),
+                num(b.y),
+                num(c.x),
+                num(c.y),
+            )?;
+        }
+        writeln!(out, r#""{}/>"#, Paint::Fill(fill.color))?;
+    }
+    Ok(())
+}
+
+fn write_bars(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    for rect in state.bars.iter().flat_map(|span| span.rects.iter()) {
+        let a = world_to_canvas_point(rect.min, &state.camera, &bounds);
+        let b = world_to_canvas_point(rect.max, &state.camera, &bounds);
+        if !drawable(&[a, b]) {
+            continue;
+        }
+        write_rect(
+            out,
+            Point::new(a.x.min(b.x), a.y.min(b.y)),
+            iced::Size::new((a.x - b.x).abs(), (a.y - b.y).abs()),
+            rect.color,
+        )?;
+    }
+    Ok(())
+}
+
+fn write_lines(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    for series in state.series.iter() {
+        let Some(line_style) = series.line_style else {
+            continue;
+        };
+        if series.len < 2 {
+            continue;
+        }
+
+        let width = line_style.width.to_px(&state.camera, &bounds).max(0.5);
+        let range = series.start..series.start + series.len;
+        let colors = state.point_colors.get(range).unwrap_or(&[]);
+        let uniform = colors.iter().all(|&color| color == series.color);
+        let color_of = |index: usize| {
+            state
+                .point_colors
+                .get(index)
+                .copied()
+                .unwrap_or(series.color)
+        };
+
+        let half_width = width * 0.5;
+        // One polyline per unbroken run when a single stroke can draw it: patterns in plot
+        // units change length with each segment's direction, like per-point colors do.
+        let polyline = uniform && !line_style.line_type.has_world_dashes();
+        let line_type = line_style.line_type.in_pixels(1.0);
+        for path in series.line_paths(&state.points) {
+            let points: Vec<Point> = path
+                .iter()
+                .map(|&(position, _)| world_to_canvas_point(position, &state.camera, &bounds))
+                .collect();
+            if !drawable(&points) {
+                continue;
+            }
+
+            // Segments with their direction, color, line type and the distance along the
+            // line at their end, skipping zero-length ones like the other renderers.
+            let mut segments = Vec::with_capacity(points.len());
+            let mut dashes = SegmentDashes::new(line_style.line_type);
+            for index in 1..points.len() {
+                let (p0, p1) = (points[index - 1], points[index]);
+                let length = p0.distance(p1);
+                if length <= f32::EPSILON {
+                    continue;
+                }
+                let color = blend_colors(color_of(path[index - 1].1), color_of(path[index].1));
+                let (line_type, distance) =
+                    dashes.segment(path[index - 1].0, path[index].0, length);
+                if !polyline {
+                    // One segment each, offsetting the dash pattern by the distance along
+                    // the line like the canvas renderer does.
+                    let stroke = Stroke {
+                        offset: distance,
+                        ..Stroke::new(width, color, line_type)
+                    };
+                    write_line(out, p0, p1, &stroke)?;
+                }
+                segments.push((
+                    p0,
+                    p1,
+                    (p1 - p0) * (1.0 / length),
+                    color,
+                    line_type,
+                    distance + length,
+                ));
+            }
+
+            if polyline {
+                // Dash patterns and joins flow across vertices. Dashes keep butt ends; only
+                // the line's own ends get caps.
+                let stroke = Stroke {
+                    join: Some(line_style.join),
+                    cap: (line_type == LineType::Solid).then_some(line_style.cap),
+                    ..Stroke::new(width, series.color, line_type)
+                };
+                write_polyline(out, &points, &stroke)?;
+            } else {
+                for pair in segments.windows(2) {
+                    let ((_, joint, d0, color, line_type, distance), (_, _, d1, _, _, _)) =
+                        (pair[0], pair[1]);
+                    if stroke_is_on(line_type, distance) {
+                        let outline = join_outline(joint, d0, d1, half_width, line_style.join);
+                        write_outline(out, &outline, color)?;
+                    }
+                }
+            }
+            if polyline && line_type == LineType::Solid {
+                continue;
+            }
+            if let Some(&(start, _, d, color, line_type, _)) = segments.first()
+                && stroke_is_on(line_type, 0.0)
+            {
+                let outline = cap_outline(start, d * -1.0, half_width, line_style.cap);
+                write_outline(out, &outline, color)?;
+            }
+            if let Some(&(_, end, d, color, line_type, distance)) = segments.last()
+                && stroke_is_on(line_type, distance)
+            {
+                write_outline(out, &cap_outline(end, d, half_width, line_style.cap), color)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_error_bars(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    let world_per_px = state.camera.world_per_px(&bounds);
+    for span in state.error_bars.iter() {
+        let width = span.style.line_width.max(0.5);
+        for bar in span.bars.iter() {
+            for [start, end] in bar.segments(span.style.cap, world_per_px) {
+                let start = world_to_canvas_point(start, &state.camera, &bounds);
+                let end = world_to_canvas_point(end, &state.camera, &bounds);
+                if drawable(&[start, end]) {
+                    write_line(out, start, end, &Stroke::solid(width, bar.color))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_reference_lines(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    let world_per_px = state.camera.world_per_px(&bounds);
+    for vline in state.vlines.iter() {
+        let Some(x) = data_value_to_plot_with_axis_range(
+            vline.x,
+            state.x_axis_scale,
+            vline.transform.as_ref(),
+            Some(state.camera.x_range()),
+        )
+        .and_then(|x| world_to_screen_position_x(x, &state.camera, &bounds)) else {
+            continue;
+        };
+        let width = vline.line_style.width.to_px(&state.camera, &bounds);
+        write_line(
+            out,
+            Point::new(x, 0.0),
+            Point::new(x, bounds.height),
+            &Stroke::new(
+                width.max(0.5),
+                vline.color,
+                vline.line_style.line_type.in_pixels(world_per_px[1]),
+            ),
+        )?;
+    }
+
+    for hline in state.hlines.iter() {
+        let Some(y) = data_value_to_plot_with_axis_range(
+            hline.y,
+            state.y_axis_scale,
+            hline.transform.as_ref(),
+            Some(state.camera.y_range()),
+        )
+        .and_then(|y| world_to_screen_position_y(y, &state.camera, &bounds)) else {
+            continue;
+        };
+        let width = hline.line_style.width.to_px(&state.camera, &bounds);
+        write_line(
+            out,
+            Point::new(0.0, y),
+            Point::new(bounds.width, y),
+            &Stroke::new(
+                width.max(0.5),
+                hline.color,
+                hline.line_style.line_type.in_pixels(world_per_px[0]),
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+fn write_markers(out: &mut String, state: &PlotState, bounds: Rectangle) -> fmt::Result {
+    let camera = &state.camera;
+    for series in state.series.iter() {
+        if series.marker == u32::MAX {
+            continue;
+        }
+        let marker_type = marker_type_from_u32(series.marker);
+
+        let end = series.start + series.len;
+        for (index, point) in state.points[series.start..end].iter().enumerate() {
+            let color = *state
+                .point_colors
+                .get(series.start + index)
+                .unwrap_or(&series.color);
+            let world = point.position;
+            let size = point.size;
+
+            // World-sized squares extend up and right from their position.
+            if marker_type == MarkerType::Square && point.size_mode == MARKER_SIZE_WORLD {
+                let size = f64::from(size);
+                let a = world_to_canvas_point([world[0], world[1] + size], camera, &bounds);
+                let b = world_to_canvas_point([world[0] + size, world[1]], camera, &bounds);
+                if !drawable(&[a, b]) {
+                    continue;
+                }
+                write_rect(
+                    out,
+                    Point::new(a.x.min(b.x), a.y.min(b.y)),
+                    iced::Size::new((b.x - a.x).abs(), (b.y - a.y).abs()),
+                    color,
+                )?;
+                continue;
+            }
+
+            let mut center_world = world;
+            if point.size_mode == MARKER_SIZE_WORLD {
+                let half = f64::from(size) * 0.5;
+                center_world[0] += half;
+                center_world[1] += half;
+            }
+            let center = world_to_canvas_point(center_world, camera, &bounds);
+            let radius = Size::size_px(size, point.size_mode, camera, &bounds) * 0.5;
+            if !drawable(&[center]) || radius.is_nan() {
+                continue;
+            }
+            write_marker(out, center, radius, marker_type, color)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_marker(
+    out: &mut String,
+    center: Point,
+    radius: f32,
+    marker_type: MarkerType,
+    color: Color,
+) -> fmt::Result {
+    match marker_type {
+        MarkerType::FilledCircle => writeln!(
+            out,
+            r#"<circle cx="{}" cy="{}" r="{}"{}/>"#,
+            num(center.x),
+            num(center.y),
+            num(radius),
+            Paint::Fill(color),
+        ),
+        MarkerType::Square => write_rect(
+            out,
+            Point::new(center.x - radius, center.y - radius),
+            iced::Size::new(2.0 * radius, 2.0 * radius),
+            color,
+        ),
+        MarkerType::EmptyCircle => writeln!(
+            out,
+            r#"<circle cx="{}" cy="{}" r="{}" fill="none"{} stroke-width="{}"/>"#,
+            num(center.x),
+            num(center.y),
+            num(radius * 0.85),
+            Paint::Stroke(color),
+            num((radius * 0.3).max(1.0)),
+        ),
+        MarkerType::Triangle => {
+            let h = radius * 0.866;
+            write_polygon(
+                out,
+                &[
+                    Point::new(center.x, center.y - h),
+                    Point::new(center.x + radius, center.y + h),
+                    Point::new(center.x - radius, center.y + h),
+                ],
+                color,
+            )
+        }
+        MarkerType::Star => {
+            let mut points = Vec::with_capacity(10);
+            for index in 0..5 {
+                let outer_angle =
+                    -std::f32::consts::FRAC_PI_2 + index as f32 * std::f32::consts::TAU / 5.0;
+                let inner_angle = outer_angle + std::f32::consts::TAU / 10.0;
+                points.push(Point::new(
+                    center.x + outer_angle.cos() * radius,
+                    center.y + outer_angle.sin() * radius,
+                ));
+                points.push(Point::new(
+                    center.x + inner_angle.cos() * radius * 0.45,
+                    center.y + inner_angle.sin() * radius * 0.45,
+                ));
+            }
+            write_polygon(out, &points, color)
+        }
+    }
+}
+
+fn write_swatch(out: &mut String, origin: Point, parts: &[SwatchPart]) -> fmt::Result {
+    let offset = |point: Point| Point::new(origin.x + point.x, origin.y + point.y);
+    for part in parts {
+        match &part.shape {
+            SwatchShape::Polygon(points) => {
+                let points: Vec<_> = points.iter().map(|&point| offset(point)).collect();
+                write_polygon(out, &points, part.color)?;
+            }
+            &SwatchShape::Ring {
+                center,
+                radius,
+                width,
+            } => {
+                let center = offset(center);
+                writeln!(
+                    out,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="none"{} stroke-width="{}"/>"#,
+                    num(center.x),
+                    num(center.y),
+                    num(radius),
+                    Paint::Stroke(part.color),
+                    num(width),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_text(out: &mut String, label: &Label, family: &str) -> fmt::Result {
+    // Labels are positioned by the top of their line box; SVG positions the baseline.
+    let baseline = label.position.y + label.size * (0.5 * (LINE_HEIGHT - 1.0) + ASCENT);
+    write!(
+        out,
+        r#"<text x="{}" y="{}" font-family="{}" font-size="{}"{}"#,
+        num(label.position.x),
+        num(baseline),
+        Escaped(family),
+        num(label.size),
+        Paint::Fill(label.color),
+    )?;
+    if label.centered {
+        write!(out, r#" text-anchor="middle""#)?;
+    }
+    writeln!(out, ">{}</text>", Escaped(&label.content))
+}
+
+fn write_rect(out: &mut String, top_left: Point, size: iced::Size, color: Color) -> fmt::Result {
+    writeln!(
+        out,
+        r#"<rect x="{}" y="{}" width="{}" height="{}"{}/>"#,
+        num(top_left.x),
+        num(top_left.y),
+        num(size.width),
+        num(size.height),
+        Paint::Fill(color),
+    )
+}
+
+fn write_polygon(out: &mut String, points: &[Point], color: Color) -> fmt::Result {
+    write!(out, r#"<polygon points=""#)?;
+    write_points(out, points)?;
+    writeln!(out, r#""{}/>"#, Paint::Fill(color))
+}
+
+/// Fill a join or cap outline, if it has an area.
+fn write_outline(out: &mut String, outline: &[Point], color: Color) -> fmt::Result {
+    if outline.len() < 3 {
+        return Ok(());
+    }
+    write_polygon(out, outline, color)
+}
+
+fn write_polyline(out: &mut String, points: &[Point], stroke: &Stroke) -> fmt::Result {
+    write!(out, r#"<polyline points=""#)?;
+    write_points(out, points)?;
+    writeln!(out, r#"" fill="none"{stroke}/>"#)
+}
+
+fn write_line(out: &mut String, p0: Point, p1: Point, stroke: &Stroke) -> fmt::Result {
+    writeln!(
+        out,
+        r#"<line x1="{}" y1="{}" x2="{}" y2="{}"{stroke}/>"#,
+        num(p0.x),
+        num(p0.y),
+        num(p1.x),
+        num(p1.y),
+    )
+}
+
+fn write_points(out: &mut String, points: &[Point]) -> fmt::Result {
+    for (index, point) in points.iter().enumerate() {
+        if index > 0 {
+            out.push(' ');
+        }
+        write!(out, "{},{}", num(point.x), num(point.y))?;
+    }
+    Ok(())
+}
+
+/// Stroke attributes for a line drawn with a [`LineType`] [in pixels](LineType::in_pixels).
+struct Stroke {
+    width: f32,
+    color: Color,
+    line_type: LineType,
+    /// Distance into the dash pattern at which the line starts.
+    offset: f32,
+    /// Corner and end shapes, left to SVG's defaults when unset.
+    join: Option<LineJoin>,
+    cap: Option<LineCap>,
+}
+
+impl Stroke {
+    fn new(width: f32, color: Color, line_type: LineType) -> Self {
+        Self {
+            width,
+            color,
+            line_type,
+            offset: 0.0,
+            join: None,
+            cap: None,
+        }
+    }
+
+    fn solid(width: f32, color: Color) -> Self {
+        Self::new(width, color, LineType::Solid)
+    }
+}
+
+impl fmt::Display for Stroke {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Paint::Stroke(self.color))?;
+        let mut offset = self.offset;
+        match self.line_type {
+            LineType::Solid => write!(f, r#" stroke-width="{}""#, num(self.width))?,
+            LineType::Dashed { .. } | LineType::Pattern(_) => {
+                // Same pattern as the GPU and canvas renderers.
+                write!(f, r#" stroke-width="{}""#, num(self.width))?;
+                if let Some(pattern) = self.line_type.dash_pattern_px(1.0) {
+                    write!(f, r#" stroke-dasharray=""#)?;
+                    for (index, &length) in pattern.lengths().iter().enumerate() {
+                        let separator = if index > 0 { " " } else { "" };
+                        write!(f, "{separator}{}", num(length))?;
+                    }
+                    f.write_char('"')?;
+                    offset += pattern.offset;
+                }
+            }
+            LineType::Dotted { spacing } => {
+                // Round caps on zero-length dashes draw the dots.
+                write!(
+                    f,
+                    r#" stroke-width="{}" stroke-linecap="round" stroke-dasharray="0 {}""#,
+                    num(self.width.max(1.5)),
+                    num(2.0 * spacing.max(1.0)),
+                )?;
+            }
+        }
+        if offset != 0.0 && self.line_type != LineType::Solid {
+            write!(f, r#" stroke-dashoffset="{}""#, num(offset))?;
+        }
+        match self.join {
+            Some(LineJoin::Miter { limit }) => write!(
+                f,
+                r#" stroke-linejoin="miter" stroke-miterlimit="{}""#,
+                num(limit.max(1.0))
+            )?,
+            Some(LineJoin::Round) => write!(f, r#" stroke-linejoin="round""#)?,
+            Some(LineJoin::Bevel) => write!(f, r#" stroke-linejoin="bevel""#)?,
+            None => {}
+        }
+        match self.cap {
+            Some(LineCap::Round) => write!(f, r#" stroke-linecap="round""#)?,
+            Some(LineCap::Square) => write!(f, r#" stroke-linecap="square""#)?,
+            Some(LineCap::Butt) | None => {}
+        }
+        Ok(())
+    }
+}
+
+/// A `fill` or `stroke` color attribute, with its opacity when translucent.
+enum Paint {
+    Fill(Color),
+    Stroke(Color),
+}
+
+impl fmt::Display for Paint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (attribute, color) = match *self {
+            Self::Fill(color) => ("fill", color),
+            Self::Stroke(color) => ("stroke", color),
+        };
+        let [r, g, b, _] = color.into_rgba8();
+        write!(f, r##" {attribute}="#{r:02x}{g:02x}{b:02x}""##)?;
+        if color.a < 1.0 {
+            write!(f, r#" {attribute}-opacity="{:.3}""#, color.a.max(0.0))?;
+        }
+        Ok(())
+    }
+}
+
+/// Text escaped for use in XML content and attribute values.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&apos;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn font_family(font: Font) -> String {
+    match font.family {
+        Family::Name(name) => format!("{name}, sans-serif"),
+        Family::Serif => "serif".to_owned(),
+        Family::SansSerif => "sans-serif".to_owned(),
+        Family::Cursive => "cursive".to_owned(),
+        Family::Fantasy => "fantasy".to_owned(),
+        Family::Monospace => "monospace".to_owned(),
+    }
+}
+
+/// Whether all points have coordinates. Shapes with a NaN coordinate are skipped; infinite
+/// ones are clamped by [`num`].
+fn drawable(points: &[Point]) -> bool {
+    points
+        .iter()
+        .all(|point| !point.x.is_nan() && !point.y.is_nan())
+}
+
+/// Farthest a coordinate is written from the origin. Far enough outside any figure that
+/// clamped line ends keep their direction across the visible area.
+const MAX_COORDINATE: f32 = 1.0e7;
+
+/// Coordinates are written with two decimals, which is well below a pixel at any zoom
+/// a figure is likely to be viewed at.
+fn num(value: f32) -> impl fmt::Display {
+    struct Num(f32);
+    impl fmt::Display for Num {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            // SVG has no NaN or infinity. Writers skip NaN coordinates; this is a fallback.
+            let value = if self.0.is_nan() {
+                0.0
+            } else {
+                self.0.clamp(-MAX_COORDINATE, MAX_COORDINATE)
+            };
+            let value = (value * 100.0).round() / 100.0;
+            // Avoid "-0".
+            write!(f, "{}", if value == 0.0 { 0.0 } else { value })
+        }
+    }
+    Num(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DashPattern, HLine, LineStyle, MarkerStyle, PlotWidgetBuilder, Raster, Series};
+
+    #[test]
+    fn svg_contains_styled_series_reference_lines_and_labels() {
+        let widget = PlotWidgetBuilder::new()
+            .with_x_label("time <s>")
+            .add_series(
+                Series::new(
+                    vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.5]],
+                    MarkerStyle::star(6.0),
+                    LineStyle::dashed(8.0),
+                )
+                .with_color(Color::from_rgb(1.0, 0.0, 0.0))
+                .with_label("series"),
+            )
+            .add_hline(HLine::new(0.5).with_style(LineStyle::dotted(3.0)))
+            .build()
+            .unwrap();
+
+        let svg = widget.export_svg(&ExportOptions::new(300, 200)).unwrap();
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert!(svg.contains(r##"<polyline points=""##));
+        assert!(svg.contains(r##"stroke="#ff0000" stroke-width="1" stroke-dasharray="8 4""##));
+        assert!(svg.contains(r#"stroke-linecap="round" stroke-dasharray="0 6""#));
+        // Three star markers, the round caps at both ends of the dashed line, and two
+        // dashes and a star in the legend swatch.
+        assert_eq!(svg.matches("<polygon").count(), 8);
+        assert!(svg.contains(">time &lt;s&gt;</text>"));
+        assert!(svg.contains(">series</text>"));
+    }
+
+    #[test]
+    fn rasters_are_embedded_as_png() {
+        let widget = PlotWidgetBuilder::new()
+            .add_raster(Raster::rgba(2, 1, vec![255, 0, 0, 255, 0, 0, 255, 255]))
+            .build()
+            .unwrap();
+        let svg = widget.export_svg(&ExportOptions::new(300, 200)).unwrap();
+
+        assert!(svg.contains(
+            r#"preserveAspectRatio="none" style="image-rendering:pixelated" href="data:image/png;base64,iVBORw0KGgo"#
+        ));
+    }
+
+    #[test]
+    fn line_runs_split_at_gaps() {
+        let widget = PlotWidgetBuilder::new()
+            .add_series(Series::line_only(
+                vec![
+                    [0.0, 0.0],
+                    [1.0, 1.0],
+                    [2.0, f64::NAN],
+                    [3.0, 1.0],
+                    [4.0, 0.0],
+                ],
+                LineStyle::solid(),
+            ))
+            .build()
+            .unwrap();
+
+        let svg = widget.export_svg(&ExportOptions::new(300, 200)).unwrap();
+
+        assert_eq!(svg.matches("<polyline").count(), 2);
+    }
+
+    #[test]
+    fn far_away_points_are_written_as_finite_coordinates() {
+        let widget = PlotWidgetBuilder::new()
+            .with_y_lim(-1.0, 1.0)
+            .add_series(Series::new(
+                vec![[0.0, 0.0], [1.0, 1e300], [2.0, -1e300]],
+                MarkerStyle::circle(4.0),
+                LineStyle::solid(),
+            ))
+            .build()
+            .unwrap();
+
+        let svg = widget.export_svg(&ExportOptions::new(300, 200)).unwrap();
+
+        assert!(!svg.contains("NaN") && !svg.contains("inf"));
+        assert!(svg.contains("10000000"));
+    }
+
+    #[test]
+    fn line_joins_and_caps_are_exported() {
+        let positions = vec![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]];
+        let export = |series: Series| {
+            PlotWidgetBuilder::new()
+                .add_series(series)
+                .build()
+                .unwrap()
+                .export_svg(&ExportOptions::new(300, 200))
+                .unwrap()
+        };
+
+        let svg = export(Series::line_only(
+            positions.clone(),
+            LineStyle::solid()
+                .with_join(LineJoin::miter())
+                .with_cap(LineCap::Square),
+        ));
+        assert!(
+            svg.contains(
+                r#"stroke-linejoin="miter" stroke-miterlimit="4" stroke-linecap="square""#
+            )
+        );
+
+        // Per-point colors are drawn segment by segment, with the join and caps filled in.
+        let svg = export(
+            Series::line_only(
+                positions,
+                LineStyle::solid()
+                    .with_join(LineJoin::Bevel)
+                    .with_cap(LineCap::Round),
+            )
+            .with_point_colors(vec![Color::BLACK, Color::WHITE, Color::BLACK]),
+        );
+        assert_eq!(svg.matches(r##"stroke="#808080""##).count(), 2);
+        assert_eq!(svg.matches(r##"fill="#808080""##).count(), 3);
+    }
+
+    #[test]
+    fn dash_patterns_are_exported_with_their_offset() {
+        let svg = PlotWidgetBuilder::new()
+            .add_series(Series::line_only(
+                vec![[0.0, 0.0], [1.0, 1.0]],
+                LineStyle::pattern(DashPattern::dash_dot(6.0, 2.0).with_offset(3.0)),
+            ))
+            .build()
+            .unwrap()
+            .export_svg(&ExportOptions::new(300, 200))
+            .unwrap();
+        assert!(svg.contains(r#"stroke-dasharray="6 2 1 2" stroke-dashoffset="3""#));
+    }
+}
@@ -16,6 +16,8 @@ pub struct Fill {
     pub label: Option<String>,
     /// Fill color (and opacity).
     pub color: Color,
+    /// Whether the fill can be hovered and picked.
+    pub pickable: bool,
 }
 
 impl Fill {
@@ -27,6 +29,7 @@ impl Fill {
             end,
             label: None,
             color: Color::from_rgba(0.2, 0.6, 1.0, 0.25),
+            pickable: true,
         }
     }
 
@@ -44,4 +47,10 @@ impl Fill {
         self.color = color;
         self
     }
+
+    /// Enable or disable hover/pick behavior for this fill.
+    pub fn with_pickable(mut self, pickable: bool) -> Self {
+        self.pickable = pickable;
+        self
+    }
 }
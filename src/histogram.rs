@@ -0,0 +1,410 @@
+use crate::{
+    BarStyle, Color, LineStyle,
+    series::{Series, SeriesError, ShapeId},
+};
+
+/// Upper limit on the number of bins, so a tiny bin width or a far outlier can't allocate
+/// an unbounded number of them.
+const MAX_BINS: usize = 10_000;
+
+/// How a [`Histogram`] chooses its bins.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Binning {
+    /// This many bins of equal width spanning the range, at most 10 000.
+    Count(usize),
+    /// Bins of this width, with edges at multiples of it.
+    Width(f64),
+    /// `ceil(log2(n)) + 1` bins, where `n` is the number of samples. Works well for roughly
+    /// normal data.
+    #[default]
+    Sturges,
+    /// Bins of width `2 * IQR / cbrt(n)`, which is robust to outliers. Falls back to
+    /// [`Sturges`](Self::Sturges) when the interquartile range is zero.
+    FreedmanDiaconis,
+}
+
+/// What a [`Histogram`] bin's height shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistogramNorm {
+    /// The number of samples in the bin.
+    #[default]
+    Count,
+    /// The fraction of samples in the bin divided by its width, so the total area is one.
+    Density,
+    /// The number of samples in the bin and every bin before it.
+    Cumulative,
+}
+
+/// How a [`Histogram`] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HistogramStyle {
+    /// One bar per bin.
+    #[default]
+    Bars,
+    /// The outline of the bins, as a line.
+    Step(LineStyle),
+}
+
+/// One bin of a [`Histogram`], reported to hover and pick tooltips.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramBin {
+    /// Lower edge of the bin.
+    pub start: f64,
+    /// Upper edge of the bin.
+    pub end: f64,
+    /// Number of samples in the bin.
+    pub count: usize,
+    /// Height of the bin, after normalization.
+    pub value: f64,
+}
+
+/// The distribution of a set of samples, binned automatically.
+///
+/// The histogram is drawn as a series with the same id, so it shows in the legend and can
+/// be hovered like any other series. Bins include their lower edge, and the last bin also
+/// includes its upper edge. Non-finite samples and samples outside the
+/// [range](Self::with_range) are ignored.
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    /// Unique identifier for the histogram and its series.
+    pub id: ShapeId,
+    /// The raw samples.
+    pub samples: Vec<f64>,
+    /// How bins are chosen.
+    pub binning: Binning,
+    /// Range covered by the bins. If none, the range of the samples is used.
+    pub range: Option<(f64, f64)>,
+    /// What bin heights show.
+    pub norm: HistogramNorm,
+    /// How the bins are drawn.
+    pub style: HistogramStyle,
+    /// Color of the bars or line.
+    pub color: Color,
+    /// Optional label for legends.
+    pub label: Option<String>,
+    /// The bins, refreshed by the plot widget when the samples change.
+    pub(crate) bins: Vec<HistogramBin>,
+}
+
+impl Histogram {
+    /// Create a histogram of `samples`.
+    pub fn new(samples: Vec<f64>) -> Self {
+        Self {
+            id: ShapeId::new(),
+            samples,
+            binning: Binning::default(),
+            range: None,
+            norm: HistogramNorm::default(),
+            style: HistogramStyle::default(),
+            color: Color::from_rgb(0.3, 0.3, 0.9),
+            label: None,
+            bins: Vec::new(),
+        }
+    }
+
+    /// Set how bins are chosen.
+    pub fn with_binning(mut self, binning: Binning) -> Self {
+        self.binning = binning;
+        self
+    }
+
+    /// Set the range covered by the bins.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Set what bin heights show.
+    pub fn with_norm(mut self, norm: HistogramNorm) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    /// Draw the outline of the bins as a line instead of bars.
+    pub fn with_step(mut self, line_style: LineStyle) -> Self {
+        self.style = HistogramStyle::Step(line_style);
+        self
+    }
+
+    /// Set the color of the bars or line.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Set a label for this histogram (shown in legend when non-empty).
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        if !label.is_empty() {
+            self.label = Some(label);
+        }
+        self
+    }
+
+    /// The bin drawn at `point_index` of the histogram's series.
+    pub(crate) fn bin(&self, point_index: usize) -> Option<HistogramBin> {
+        let index = match self.style {
+            HistogramStyle::Bars => point_index,
+            // Step outlines go up at the first edge, then along the top of every bin (two
+            // points each), then down at the last edge.
+            HistogramStyle::Step(_) => point_index.saturating_sub(1) / 2,
+        };
+        self.bins
+            .get(index.min(self.bins.len().saturating_sub(1)))
+            .copied()
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SeriesError> {
+        let valid_binning = match self.binning {
+            Binning::Count(count) => (1..=MAX_BINS).contains(&count),
+            Binning::Width(width) => width.is_finite() && width > 0.0,
+            Binning::Sturges | Binning::FreedmanDiaconis => true,
+        };
+        if !valid_binning {
+            return Err(SeriesError::InvalidBinning);
+        }
+        if let Some((min, max)) = self.range
+            && !(min.is_finite() && max.is_finite() && min <= max)
+        {
+            return Err(SeriesError::InvalidHistogramRange);
+        }
+        Ok(())
+    }
+
+    /// Refresh the bins from the samples.
+    pub(crate) fn rebin(&mut self) {
+        let mut samples: Vec<f64> = self
+            .samples
+            .iter()
+            .copied()
+            .filter(|sample| {
+                sample.is_finite()
+                    && self
+                        .range
+                        .is_none_or(|(min, max)| (min..=max).contains(sample))
+            })
+            .collect();
+        samples.sort_by(f64::total_cmp);
+        self.bins = match (samples.first(), samples.last()) {
+            (Some(&lo), Some(&hi)) => {
+                let (min, max) = self.range.unwrap_or((lo, hi));
+                let (start, width, count) = self.bin_layout(&samples, min, max);
+                bin_samples(&samples, start, width, count, self.norm)
+            }
+            _ => Vec::new(),
+        };
+    }
+
+    /// First edge, width and number of the bins covering `min..=max`.
+    fn bin_layout(&self, sorted: &[f64], min: f64, max: f64) -> (f64, f64, usize) {
+        // A single value still gets a bin of unit width around it.
+        let (min, max) = if max > min {
+            (min, max)
+        } else {
+            (min - 0.5, max + 0.5)
+        };
+        let span = max - min;
+        let sturges = (sorted.len() as f64).log2().ceil() as usize + 1;
+        let width = match self.binning {
+            Binning::Count(count) => {
+                let count = count.clamp(1, MAX_BINS);
+                return (min, span / count as f64, count);
+            }
+            Binning::Width(width) if width.is_finite() && width > 0.0 => {
+                let start = (min / width).floor() * width;
+                let count = ((max - start) / width).ceil().max(1.0) as usize;
+                return (start, width, count.min(MAX_BINS));
+            }
+            Binning::Width(_) | Binning::Sturges => span / sturges as f64,
+            Binning::FreedmanDiaconis => {
+                let iqr = quantile(sorted, 0.75) - quantile(sorted, 0.25);
+                if iqr > 0.0 {
+                    2.0 * iqr / (sorted.len() as f64).cbrt()
+                } else {
+                    span / sturges as f64
+                }
+            }
+        };
+        let count = ((span / width).ceil() as usize).clamp(1, MAX_BINS);
+        (min, span / count as f64, count)
+    }
+
+    /// The series drawing the bins.
+    pub(crate) fn series(&self) -> Series {
+        let mut series = match self.style {
+            HistogramStyle::Bars => {
+                let positions = self
+                    .bins
+                    .iter()
+                    .map(|bin| [(bin.start + bin.end) / 2.0, bin.value])
+                    .collect();
+                let width = self.bins.first().map_or(1.0, |bin| bin.end - bin.start);
+                Series::bars(positions, BarStyle::new(width))
+            }
+            HistogramStyle::Step(line_style) => {
+                let mut positions = Vec::with_capacity(self.bins.len() * 2 + 2);
+                if let (Some(first), Some(last)) = (self.bins.first(), self.bins.last()) {
+                    positions.push([first.start, 0.0]);
+                    for bin in &self.bins {
+                        positions.push([bin.start, bin.value]);
+                        positions.push([bin.end, bin.value]);
+                    }
+                    positions.push([last.end, 0.0]);
+                }
+                Series::line_only(positions, line_style)
+            }
+        }
+        .with_color(self.color);
+        series.id = self.id;
+        series.label = self.label.clone();
+        series
+    }
+}
+
+/// Count sorted samples into `count` bins of `width` from `start`.
+fn bin_samples(
+    sorted: &[f64],
+    start: f64,
+    width: f64,
+    count: usize,
+    norm: HistogramNorm,
+) -> Vec<HistogramBin> {
+    let mut counts = vec![0usize; count];
+    for &sample in sorted {
+        let index = ((sample - start) / width).floor();
+        // The last bin includes its upper edge.
+        if index >= 0.0 {
+            counts[(index as usize).min(count - 1)] += 1;
+        }
+    }
+    let total = sorted.len() as f64;
+    let mut cumulative = 0;
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            cumulative += count;
+            let value = match norm {
+                HistogramNorm::Count => count as f64,
+                HistogramNorm::Density => count as f64 / (total * width),
+                HistogramNorm::Cumulative => cumulative as f64,
+            };
+            HistogramBin {
+                start: start + i as f64 * width,
+                end: start + (i + 1) as f64 * width,
+                count,
+                value,
+            }
+        })
+        .collect()
+}
+
+/// Linearly interpolated quantile of sorted, non-empty samples.
+pub(crate) fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let i = position.floor() as usize;
+    let next = sorted[(i + 1).min(sorted.len() - 1)];
+    sorted[i] + (next - sorted[i]) * (position - i as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binned(histogram: Histogram) -> Histogram {
+        let mut histogram = histogram;
+        histogram.rebin();
+        histogram
+    }
+
+    #[test]
+    fn bins_cover_samples_and_normalize() {
+        let samples = vec![0.0, 0.5, 1.0, 1.5, 2.0, 4.0, f64::NAN];
+        let counts = binned(Histogram::new(samples.clone()).with_binning(Binning::Count(4)));
+        let heights: Vec<_> = counts.bins.iter().map(|bin| bin.value).collect();
+        assert_eq!(heights, [2.0, 2.0, 1.0, 1.0]);
+        assert_eq!((counts.bins[0].start, counts.bins[3].end), (0.0, 4.0));
+
+        let density = binned(
+            Histogram::new(samples.clone())
+                .with_binning(Binning::Count(4))
+                .with_norm(HistogramNorm::Density),
+        );
+        let area: f64 = density.bins.iter().map(|bin| bin.value).sum();
+        assert!((area - 1.0).abs() < 1e-12);
+
+        let cumulative = binned(
+            Histogram::new(samples)
+                .with_binning(Binning::Width(1.5))
+                .with_norm(HistogramNorm::Cumulative),
+        );
+        let heights: Vec<_> = cumulative.bins.iter().map(|bin| bin.value).collect();
+        assert_eq!(heights, [3.0, 5.0, 6.0]);
+        assert_eq!(cumulative.bins[2].count, 1);
+    }
+
+    #[test]
+    fn bin_counts_are_used_as_given() {
+        // Deriving the count back from the width rounds these up by one.
+        for (count, max) in [(49, 1.0), (15, 2.2)] {
+            let histogram =
+                binned(Histogram::new(vec![0.0, max]).with_binning(Binning::Count(count)));
+            assert_eq!(histogram.bins.len(), count);
+            assert_eq!(histogram.bins.last().unwrap().count, 1);
+        }
+    }
+
+    #[test]
+    fn invalid_binning_and_ranges_are_rejected() {
+        for binning in [
+            Binning::Count(0),
+            Binning::Count(MAX_BINS + 1),
+            Binning::Width(0.0),
+        ] {
+            let histogram = Histogram::new(vec![1.0]).with_binning(binning);
+            assert_eq!(histogram.validate(), Err(SeriesError::InvalidBinning));
+        }
+        let histogram = Histogram::new(vec![1.0]).with_range(2.0, 1.0);
+        assert_eq!(
+            histogram.validate(),
+            Err(SeriesError::InvalidHistogramRange)
+        );
+        assert_eq!(Histogram::new(vec![]).validate(), Ok(()));
+    }
+
+    #[test]
+    fn automatic_rules_pick_bin_counts() {
+        let samples: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let sturges = binned(Histogram::new(samples.clone()));
+        assert_eq!(sturges.bins.len(), 8);
+
+        // IQR = 49.5, so bins are 2 * 49.5 / cbrt(100) ≈ 21.3 wide.
+        let fd = binned(Histogram::new(samples).with_binning(Binning::FreedmanDiaconis));
+        assert_eq!(fd.bins.len(), 5);
+        assert_eq!(fd.bins.iter().map(|bin| bin.count).sum::<usize>(), 100);
+    }
+
+    #[test]
+    fn step_outline_maps_points_to_bins() {
+        let histogram = binned(
+            Histogram::new(vec![0.0, 1.0, 1.0, 2.0])
+                .with_binning(Binning::Count(2))
+                .with_step(LineStyle::solid()),
+        );
+        let series = histogram.series();
+        assert_eq!(
+            series.positions,
+            [
+                [0.0, 0.0],
+                [0.0, 1.0],
+                [1.0, 1.0],
+                [1.0, 3.0],
+                [2.0, 3.0],
+                [2.0, 0.0]
+            ]
+        );
+        assert_eq!(histogram.bin(2).map(|bin| bin.count), Some(1));
+        assert_eq!(histogram.bin(3).map(|bin| bin.count), Some(3));
+        assert_eq!(histogram.bin(5).map(|bin| bin.count), Some(3));
+    }
+}
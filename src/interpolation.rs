@@ -0,0 +1,465 @@
+use iced::Rectangle;
+
+use crate::camera::Camera;
+
+/// How a series line connects consecutive points.
+///
+/// Smooth curves are tessellated in plot space against the current pixel scale, so they stay
+/// smooth at any zoom level. They are tessellated again once the view is zoomed or panned
+/// well away from the one they were last tessellated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineInterpolation {
+    /// Straight segments between points.
+    #[default]
+    Linear,
+    /// Steps that change value at each point, so the line reaches a point's value at its
+    /// x position and holds it back to the previous point.
+    StepPre,
+    /// Steps that change value halfway between points.
+    StepMid,
+    /// Steps that hold each point's value until the next point.
+    StepPost,
+    /// A uniform Catmull-Rom curve through every point. Works for any point order, so it
+    /// also suits parametric curves.
+    CatmullRom,
+    /// A monotone cubic (Fritsch–Carlson) that never overshoots the data: it stays within
+    /// the range of each pair of neighbouring points.
+    ///
+    /// Needs strictly increasing or decreasing x; otherwise the line is drawn straight.
+    MonotoneCubic,
+    /// A natural cubic spline with zero curvature at both ends.
+    ///
+    /// Needs strictly increasing or decreasing x; otherwise the line is drawn straight.
+    NaturalCubic,
+}
+
+/// Largest distance, in pixels, between a tessellated curve and the true curve.
+const CURVE_TOLERANCE_PX: f64 = 0.25;
+/// Pixels across each axis of the view curves are tessellated for before the plot has a
+/// view, with the whole line filling it.
+const CURVE_REFERENCE_PX: f64 = 4096.0;
+/// Cap on the pieces one curve segment is split into, however far the view is zoomed.
+const MAX_CURVE_SUBDIVISIONS: usize = 1024;
+/// Cap on the pieces of one tessellated curve. Longer curves are split more coarsely.
+const MAX_CURVE_PIECES: usize = 1 << 20;
+
+impl LineInterpolation {
+    /// Whether the line is a smooth curve.
+    pub(crate) fn is_curve(self) -> bool {
+        matches!(
+            self,
+            Self::CatmullRom | Self::MonotoneCubic | Self::NaturalCubic
+        )
+    }
+
+    /// Whether the path through points sorted by x is sorted by x too. Catmull-Rom curves
+    /// can swing back between points.
+    pub(crate) fn keeps_x_order(self) -> bool {
+        self != Self::CatmullRom
+    }
+
+    /// Number of corners a step line turns at between two points: the most pieces it
+    /// draws from one point to the next, less one.
+    pub(crate) fn corner_count(self) -> usize {
+        match self {
+            Self::StepPre | Self::StepPost => 1,
+            Self::StepMid => 2,
+            _ => 0,
+        }
+    }
+
+    /// The corners a step line turns at between consecutive points `start` and `end`, each
+    /// paired with whether it carries the value of `end`. Other lines have none.
+    pub(crate) fn corners(
+        self,
+        [x0, y0]: [f64; 2],
+        [x1, y1]: [f64; 2],
+    ) -> impl Iterator<Item = ([f64; 2], bool)> {
+        let mid = (x0 + x1) / 2.0;
+        let corners = match self {
+            Self::StepPre => [Some(([x0, y1], true)), None],
+            Self::StepMid => [Some(([mid, y0], false)), Some(([mid, y1], true))],
+            Self::StepPost => [Some(([x1, y0], false)), None],
+            _ => [None, None],
+        };
+        corners.into_iter().flatten()
+    }
+
+    /// The vertices drawn through `points`, in plot space. Each vertex is paired with the
+    /// index of the point whose value (and color) it carries; the segment ending at a
+    /// vertex also belongs to that point.
+    ///
+    /// Curves are tessellated for `view`, or for their whole extent if the plot has no view
+    /// yet.
+    pub(crate) fn path(
+        self,
+        points: &[[f64; 2]],
+        view: Option<&CurveView>,
+    ) -> Vec<([f64; 2], usize)> {
+        if self.is_curve() {
+            return match self.curve(points) {
+                Some(curve) => tessellate(points, &curve, view),
+                None => Self::Linear.path(points, view),
+            };
+        }
+        let Some(&first) = points.first() else {
+            return Vec::new();
+        };
+        let mut path = Vec::with_capacity(points.len() * (self.corner_count() + 1));
+        path.push((first, 0));
+        for (index, pair) in points.windows(2).enumerate() {
+            for (corner, at_end) in self.corners(pair[0], pair[1]) {
+                path.push((corner, index + usize::from(at_end)));
+            }
+            path.push((pair[1], index + 1));
+        }
+        path
+    }
+
+    /// The inner Bézier control points of each curve segment between consecutive points,
+    /// or `None` if the curve can't be fitted to these points.
+    fn curve(self, points: &[[f64; 2]]) -> Option<Vec<[[f64; 2]; 2]>> {
+        if points.len() < 2 {
+            return None;
+        }
+        if self == Self::CatmullRom {
+            let last = points.len() - 1;
+            return Some(
+                (0..last)
+                    .map(|i| {
+                        let [x0, y0] = points[i.saturating_sub(1)];
+                        let [x1, y1] = points[i];
+                        let [x2, y2] = points[i + 1];
+                        let [x3, y3] = points[(i + 2).min(last)];
+                        [
+                            [x1 + (x2 - x0) / 6.0, y1 + (y2 - y0) / 6.0],
+                            [x2 - (x3 - x1) / 6.0, y2 - (y3 - y1) / 6.0],
+                        ]
+                    })
+                    .collect(),
+            );
+        }
+
+        // The cubic splines are functions of x, with a slope at each point.
+        let steps: Vec<f64> = points.windows(2).map(|p| p[1][0] - p[0][0]).collect();
+        let increasing = steps[0] > 0.0;
+        if steps
+            .iter()
+            .any(|&h| !h.is_finite() || h == 0.0 || (h > 0.0) != increasing)
+        {
+            return None;
+        }
+        let secants: Vec<f64> = points
+            .windows(2)
+            .zip(&steps)
+            .map(|(p, h)| (p[1][1] - p[0][1]) / h)
+            .collect();
+        let slopes = if self == Self::MonotoneCubic {
+            monotone_slopes(&secants)
+        } else {
+            natural_slopes(&steps, &secants)
+        };
+        Some(
+            points
+                .windows(2)
+                .enumerate()
+                .map(|(i, p)| {
+                    let third = steps[i] / 3.0;
+                    [
+                        [p[0][0] + third, p[0][1] + slopes[i] * third],
+                        [p[1][0] - third, p[1][1] - slopes[i + 1] * third],
+                    ]
+                })
+                .collect(),
+        )
+    }
+}
+
+/// The view curves are tessellated for: the size of a pixel in plot units, and the visible
+/// plot-space ranges padded by their size on each side. Segments outside the ranges are
+/// drawn straight, so panning doesn't need to tessellate again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CurveView {
+    world_per_px: [f64; 2],
+    x_range: [f64; 2],
+    y_range: [f64; 2],
+}
+
+impl CurveView {
+    /// The view for `camera` over `bounds`, or `None` before the plot has a view.
+    pub(crate) fn around(camera: &Camera, bounds: &Rectangle) -> Option<Self> {
+        let world_per_px = camera.world_per_px(bounds);
+        if bounds.width < 1.0
+            || bounds.height < 1.0
+            || !world_per_px.iter().all(|&w| w.is_finite() && w > 0.0)
+        {
+            return None;
+        }
+        let pad = |[min, max]: [f64; 2]| [min - (max - min), max + (max - min)];
+        Some(Self {
+            world_per_px,
+            x_range: pad(camera.x_range()),
+            y_range: pad(camera.y_range()),
+        })
+    }
+
+    /// Whether curves tessellated for this view still look smooth in the view of `camera`
+    /// over `bounds`: it stays within the padded ranges, and isn't zoomed or resized much.
+    pub(crate) fn covers(&self, camera: &Camera, bounds: &Rectangle) -> bool {
+        let Some(current) = Self::around(camera, bounds) else {
+            return true;
+        };
+        let ([left, right], [bottom, top]) = camera.axis_ranges();
+        left >= self.x_range[0]
+            && right <= self.x_range[1]
+            && bottom >= self.y_range[0]
+            && top <= self.y_range[1]
+            && (0..2).all(|axis| {
+                (0.8..=1.25).contains(&(current.world_per_px[axis] / self.world_per_px[axis]))
+            })
+    }
+
+    /// Whether the bounding box of `control` overlaps the padded ranges.
+    fn overlaps(&self, control: &[[f64; 2]; 4]) -> bool {
+        let overlaps_axis = |axis: usize, [min, max]: [f64; 2]| {
+            control.iter().any(|p| p[axis] >= min) && control.iter().any(|p| p[axis] <= max)
+        };
+        overlaps_axis(0, self.x_range) && overlaps_axis(1, self.y_range)
+    }
+}
+
+/// Fritsch–Carlson slopes, limited so each segment stays monotone.
+fn monotone_slopes(secants: &[f64]) -> Vec<f64> {
+    let n = secants.len() + 1;
+    let mut slopes = vec![0.0; n];
+    slopes[0] = secants[0];
+    slopes[n - 1] = secants[n - 2];
+    for i in 1..n - 1 {
+        let (before, after) = (secants[i - 1], secants[i]);
+        if before * after > 0.0 {
+            slopes[i] = (before + after) / 2.0;
+        }
+    }
+    for (i, &secant) in secants.iter().enumerate() {
+        if secant == 0.0 {
+            slopes[i] = 0.0;
+            slopes[i + 1] = 0.0;
+            continue;
+        }
+        let (a, b) = (slopes[i] / secant, slopes[i + 1] / secant);
+        let magnitude = a.hypot(b);
+        if magnitude > 3.0 {
+            let tau = 3.0 / magnitude;
+            slopes[i] = tau * a * secant;
+            slopes[i + 1] = tau * b * secant;
+        }
+    }
+    slopes
+}
+
+/// Slopes of the natural cubic spline, whose second derivative is zero at both ends.
+fn natural_slopes(steps: &[f64], secants: &[f64]) -> Vec<f64> {
+    let n = secants.len() + 1;
+    // Solve the tridiagonal system for the second derivatives at the interior points
+    // (Thomas algorithm); the end ones are zero.
+    let mut curvature = vec![0.0; n];
+    let mut diagonal = vec![0.0; n];
+    let mut rhs = vec![0.0; n];
+    for i in 1..n - 1 {
+        diagonal[i] = 2.0 * (steps[i - 1] + steps[i]);
+        rhs[i] = 6.0 * (secants[i] - secants[i - 1]);
+        if i > 1 {
+            let factor = steps[i - 1] / diagonal[i - 1];
+            diagonal[i] -= factor * steps[i - 1];
+            rhs[i] -= factor * rhs[i - 1];
+        }
+    }
+    for i in (1..n - 1).rev() {
+        curvature[i] = (rhs[i] - steps[i] * curvature[i + 1]) / diagonal[i];
+    }
+
+    let mut slopes: Vec<f64> = (0..n - 1)
+        .map(|i| secants[i] - steps[i] * (2.0 * curvature[i] + curvature[i + 1]) / 6.0)
+        .collect();
+    slopes.push(secants[n - 2] + steps[n - 2] * (curvature[n - 2] + 2.0 * curvature[n - 1]) / 6.0);
+    slopes
+}
+
+/// Flatten cubic Bézier segments into a path, splitting each segment in `view` into enough
+/// pieces to stay within [`CURVE_TOLERANCE_PX`] of the curve at its pixel scale. Without a
+/// view, every segment is split for the points spanning [`CURVE_REFERENCE_PX`] on each
+/// axis. Vertices in the first half of a segment belong to its start point, the rest to
+/// its end point.
+fn tessellate(
+    points: &[[f64; 2]],
+    curve: &[[[f64; 2]; 2]],
+    view: Option<&CurveView>,
+) -> Vec<([f64; 2], usize)> {
+    let world_per_px = view.map_or_else(
+        || {
+            [0, 1].map(|axis| {
+                let (min, max) = points
+                    .iter()
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| {
+                        (min.min(p[axis]), max.max(p[axis]))
+                    });
+                ((max - min) / CURVE_REFERENCE_PX).max(f64::MIN_POSITIVE)
+            })
+        },
+        |view| view.world_per_px,
+    );
+    let to_px = |[x, y]: [f64; 2]| [x / world_per_px[0], y / world_per_px[1]];
+    let mut subdivisions: Vec<usize> = curve
+        .iter()
+        .enumerate()
+        .map(|(index, &[b1, b2])| {
+            let control = [points[index], b1, b2, points[index + 1]];
+            if view.is_some_and(|view| !view.overlaps(&control)) {
+                return 1;
+            }
+            subdivisions(control.map(to_px))
+        })
+        .collect();
+    let total: usize = subdivisions.iter().sum();
+    if total > MAX_CURVE_PIECES {
+        let scale = MAX_CURVE_PIECES as f64 / total as f64;
+        for pieces in &mut subdivisions {
+            *pieces = ((*pieces as f64 * scale) as usize).max(1);
+        }
+    }
+
+    let mut path = Vec::with_capacity(subdivisions.iter().sum::<usize>() + 1);
+    path.push((points[0], 0));
+    for (index, (&[b1, b2], &pieces)) in curve.iter().zip(&subdivisions).enumerate() {
+        let (b0, b3) = (points[index], points[index + 1]);
+        for piece in 1..pieces {
+            let t = piece as f64 / pieces as f64;
+            let owner = if 2 * piece < pieces { index } else { index + 1 };
+            path.push((bezier_point([b0, b1, b2, b3], t), owner));
+        }
+        path.push((b3, index + 1));
+    }
+    path
+}
+
+/// Pieces needed to flatten a cubic Bézier (in pixels) to within the tolerance, from the
+/// bound on its second derivative.
+fn subdivisions(control: [[f64; 2]; 4]) -> usize {
+    let second_difference = |a: [f64; 2], b: [f64; 2], c: [f64; 2]| {
+        (a[0] - 2.0 * b[0] + c[0]).hypot(a[1] - 2.0 * b[1] + c[1])
+    };
+    let bend = second_difference(control[0], control[1], control[2])
+        .max(second_difference(control[1], control[2], control[3]));
+    let pieces = (0.75 * bend / CURVE_TOLERANCE_PX).sqrt().ceil();
+    if pieces.is_finite() {
+        (pieces as usize).clamp(1, MAX_CURVE_SUBDIVISIONS)
+    } else {
+        1
+    }
+}
+
+fn bezier_point([b0, b1, b2, b3]: [[f64; 2]; 4], t: f64) -> [f64; 2] {
+    let s = 1.0 - t;
+    let weights = [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t];
+    [
+        weights[0] * b0[0] + weights[1] * b1[0] + weights[2] * b2[0] + weights[3] * b3[0],
+        weights[0] * b0[1] + weights[1] * b1[1] + weights[2] * b2[1] + weights[3] * b3[1],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_insert_corners_owned_by_the_held_value() {
+        let points = [[0.0, 0.0], [2.0, 1.0]];
+        let path = |mode: LineInterpolation| mode.path(&points, None);
+
+        assert_eq!(
+            path(LineInterpolation::Linear),
+            [([0.0, 0.0], 0), ([2.0, 1.0], 1)]
+        );
+        assert_eq!(
+            path(LineInterpolation::StepPre),
+            [([0.0, 0.0], 0), ([0.0, 1.0], 1), ([2.0, 1.0], 1)]
+        );
+        assert_eq!(
+            path(LineInterpolation::StepMid),
+            [
+                ([0.0, 0.0], 0),
+                ([1.0, 0.0], 0),
+                ([1.0, 1.0], 1),
+                ([2.0, 1.0], 1)
+            ]
+        );
+        assert_eq!(
+            path(LineInterpolation::StepPost),
+            [([0.0, 0.0], 0), ([2.0, 0.0], 0), ([2.0, 1.0], 1)]
+        );
+    }
+
+    #[test]
+    fn curves_pass_through_points_independent_of_scale() {
+        let points = [[0.0, 0.0], [1.0, 2.0], [2.0, 1.0], [3.0, 3.0]];
+        let scaled = points.map(|[x, y]| [x * 1e6, y * 1e-6]);
+        for mode in [
+            LineInterpolation::CatmullRom,
+            LineInterpolation::MonotoneCubic,
+            LineInterpolation::NaturalCubic,
+        ] {
+            let path = mode.path(&points, None);
+            assert!(path.len() > 4 * points.len(), "{mode:?}");
+            assert!(path.len() <= 3 * MAX_CURVE_SUBDIVISIONS + 1, "{mode:?}");
+            // Tessellation follows the shape, not the units.
+            assert_eq!(mode.path(&scaled, None).len(), path.len(), "{mode:?}");
+            for (index, &point) in points.iter().enumerate() {
+                assert!(path.contains(&(point, index)), "{mode:?}");
+            }
+            // Owners never go backwards along the path.
+            assert!(path.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        }
+    }
+
+    #[test]
+    fn long_curves_are_capped() {
+        let points: Vec<[f64; 2]> = (0..200_000)
+            .map(|i| [i as f64, if i % 2 == 0 { 0.0 } else { 1.0 }])
+            .collect();
+        let path = LineInterpolation::CatmullRom.path(&points, None);
+        assert!(path.len() <= MAX_CURVE_PIECES + points.len());
+        assert_eq!(
+            path.last(),
+            Some(&(points[points.len() - 1], points.len() - 1))
+        );
+    }
+
+    #[test]
+    fn monotone_cubic_never_overshoots() {
+        let points = [[0.0, 0.0], [1.0, 0.0], [2.0, 10.0], [3.0, 10.0], [4.0, 9.0]];
+        let path = LineInterpolation::MonotoneCubic.path(&points, None);
+        for &([x, y], _) in &path {
+            let segment = (x.floor() as usize).min(points.len() - 2);
+            let (low, high) = {
+                let (a, b) = (points[segment][1], points[segment + 1][1]);
+                (a.min(b), a.max(b))
+            };
+            assert!((low - 1e-9..=high + 1e-9).contains(&y), "{x} {y}");
+        }
+        // The natural spline overshoots the same data.
+        let natural = LineInterpolation::NaturalCubic.path(&points, None);
+        assert!(
+            natural
+                .iter()
+                .any(|&([_, y], _)| !(0.0..=10.0).contains(&y))
+        );
+    }
+
+    #[test]
+    fn cubic_splines_need_monotone_x() {
+        let points = [[0.0, 0.0], [2.0, 1.0], [1.0, 2.0]];
+        let linear = LineInterpolation::Linear.path(&points, None);
+        assert_eq!(LineInterpolation::NaturalCubic.path(&points, None), linear);
+        assert!(LineInterpolation::CatmullRom.path(&points, None).len() > linear.len());
+    }
+}
@@ -1,10 +1,235 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use glam::DVec2;
+
+use iced::advanced::{Layout, Widget, layout, renderer, widget::Tree};
 use iced::alignment::{Horizontal, Vertical};
 use iced::widget::{Container, button, column, container, row, text};
-use iced::{Color, Element, Length, color};
+use iced::{Color, Element, Length, Point, Rectangle, Size, Theme, color, mouse};
 
-use crate::LineStyle;
 use crate::series::ShapeId;
-use crate::{message::PlotUiMessage, plot_widget::PlotWidget};
+use crate::{LineStyle, LineType};
+use crate::{
+    camera::Camera,
+    message::PlotUiMessage,
+    plot_renderer::{arc, marker_type_from_u32},
+    plot_widget::{PLOT_CONTENT_PADDING, PlotWidget},
+    point::MarkerType,
+    text::{LINE_HEIGHT, text_width},
+};
+
+/// Size of a legend swatch in logical pixels, wide enough to show a few dashes.
+pub(crate) const SWATCH_SIZE: Size = Size::new(24.0, 12.0);
+/// Padding around a swatch, inside its visibility toggle.
+pub(crate) const SWATCH_PADDING: f32 = 2.0;
+/// Padding inside the legend panel, and the spacing between its entries.
+pub(crate) const LEGEND_PADDING: f32 = 4.0;
+pub(crate) const LEGEND_SPACING: f32 = 4.0;
+/// Radius of the marker drawn in a swatch, whatever the series' marker size.
+const SWATCH_MARKER_RADIUS: f32 = 4.0;
+const HIDDEN_COLOR: Color = color!(120, 120, 120);
+
+/// Where the legend is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LegendPosition {
+    /// Inside the plot area, in its top-left corner.
+    #[default]
+    TopLeft,
+    /// Inside the plot area, in its top-right corner.
+    TopRight,
+    /// Inside the plot area, in its bottom-left corner.
+    BottomLeft,
+    /// Inside the plot area, in its bottom-right corner.
+    BottomRight,
+    /// Outside the plot, to the right of the axes.
+    OutsideRight,
+    /// Outside the plot, below the x-axis label.
+    OutsideBottom,
+    /// The corner inside the plot area that covers the fewest visible data points.
+    Best,
+}
+
+/// Direction in which legend entries flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LegendOrientation {
+    /// Entries fill each column top to bottom.
+    #[default]
+    Vertical,
+    /// Entries fill each row left to right.
+    Horizontal,
+}
+
+/// Placement and layout of the plot legend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Legend {
+    pub(crate) position: LegendPosition,
+    pub(crate) orientation: LegendOrientation,
+    pub(crate) columns: Option<usize>,
+    pub(crate) text_size: f32,
+}
+
+impl Default for Legend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Legend {
+    /// A single column of entries in the top-left corner of the plot.
+    pub fn new() -> Self {
+        Self {
+            position: LegendPosition::TopLeft,
+            orientation: LegendOrientation::Vertical,
+            columns: None,
+            text_size: 12.0,
+        }
+    }
+
+    /// Set where the legend is placed.
+    pub fn with_position(mut self, position: LegendPosition) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the direction in which entries flow.
+    pub fn with_orientation(mut self, orientation: LegendOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Set the number of columns. By default, vertical legends have one column and
+    /// horizontal legends put all entries in one row.
+    pub fn with_columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns.max(1));
+        self
+    }
+
+    /// Set the size of the entry labels in logical pixels (default: 12 px).
+    pub fn with_text_size(mut self, size: f32) -> Self {
+        self.text_size = size.max(1.0);
+        self
+    }
+
+    /// The number of columns and rows holding `count` entries.
+    pub(crate) fn grid_size(&self, count: usize) -> (usize, usize) {
+        let columns = match (self.columns, self.orientation) {
+            (Some(columns), _) => columns,
+            (None, LegendOrientation::Vertical) => 1,
+            (None, LegendOrientation::Horizontal) => count,
+        }
+        .clamp(1, count.max(1));
+        (columns, count.div_ceil(columns))
+    }
+
+    /// The column and row of entry `index` in a grid of `rows` rows and `columns` columns.
+    pub(crate) fn cell(&self, index: usize, (columns, rows): (usize, usize)) -> (usize, usize) {
+        match self.orientation {
+            LegendOrientation::Vertical => (index / rows, index % rows),
+            LegendOrientation::Horizontal => (index % columns, index / columns),
+        }
+    }
+
+    /// Height of a legend row.
+    pub(crate) fn row_height(&self) -> f32 {
+        (SWATCH_SIZE.height + 2.0 * SWATCH_PADDING).max(self.text_size * LINE_HEIGHT)
+    }
+
+    /// Lay out the entries, estimating label widths from their length: the top-left corner
+    /// of each entry relative to the panel, and the size of the panel.
+    pub(crate) fn layout(&self, entries: &[LegendEntry]) -> (Vec<Point>, Size) {
+        let grid = self.grid_size(entries.len());
+        let mut column_widths = vec![0.0f32; grid.0];
+        for (index, entry) in entries.iter().enumerate() {
+            let (column, _) = self.cell(index, grid);
+            let width = SWATCH_SIZE.width
+                + 2.0 * SWATCH_PADDING
+                + LEGEND_SPACING
+                + text_width(&entry.label, self.text_size);
+            column_widths[column] = column_widths[column].max(width);
+        }
+
+        let row_height = self.row_height();
+        let mut column_x = Vec::with_capacity(grid.0);
+        let mut x = LEGEND_PADDING;
+        for width in &column_widths {
+            column_x.push(x);
+            x += width + LEGEND_SPACING;
+        }
+        let cells = (0..entries.len())
+            .map(|index| {
+                let (column, row) = self.cell(index, grid);
+                Point::new(
+                    column_x[column],
+                    LEGEND_PADDING + row as f32 * (row_height + LEGEND_SPACING),
+                )
+            })
+            .collect();
+        let size = Size::new(
+            x - LEGEND_SPACING + LEGEND_PADDING,
+            2.0 * LEGEND_PADDING + grid.1 as f32 * (row_height + LEGEND_SPACING) - LEGEND_SPACING,
+        );
+        (cells, size)
+    }
+
+    /// The position to place a legend of `size` at, inside an area of `area`.
+    /// [`LegendPosition::Best`] picks the corner covering the fewest data points, as
+    /// `covered` counts them for a rectangle in the area, preferring the top-left one.
+    pub(crate) fn resolve_position(
+        &self,
+        area: Size,
+        size: Size,
+        covered: impl Fn(Rectangle) -> usize,
+    ) -> LegendPosition {
+        if self.position != LegendPosition::Best {
+            return self.position;
+        }
+        [
+            LegendPosition::TopLeft,
+            LegendPosition::TopRight,
+            LegendPosition::BottomLeft,
+            LegendPosition::BottomRight,
+        ]
+        .into_iter()
+        .min_by_key(|&corner| covered(Rectangle::new(corner_position(corner, area, size), size)))
+        .unwrap_or_default()
+    }
+}
+
+/// The plot-space box under `rect`, given relative to the plot area that the view of
+/// `camera` in `bounds` is inset in.
+pub(crate) fn plot_box(
+    camera: &Camera,
+    bounds: &Rectangle,
+    rect: Rectangle,
+) -> ([f64; 2], [f64; 2]) {
+    let size = DVec2::new(bounds.width as f64, bounds.height as f64);
+    let corner = |x: f32, y: f32| {
+        let screen = DVec2::new(
+            (x - PLOT_CONTENT_PADDING) as f64,
+            (y - PLOT_CONTENT_PADDING) as f64,
+        );
+        camera.screen_to_world(screen, size)
+    };
+    let a = corner(rect.x, rect.y);
+    let b = corner(rect.x + rect.width, rect.y + rect.height);
+    (a.min(b).to_array(), a.max(b).to_array())
+}
+
+/// Top-left corner of a legend of `size` placed in a corner of an area of `area`.
+/// Positions outside the plot are placed in the top-left corner.
+pub(crate) fn corner_position(position: LegendPosition, area: Size, size: Size) -> Point {
+    let right = (area.width - size.width).max(0.0);
+    let bottom = (area.height - size.height).max(0.0);
+    match position {
+        LegendPosition::TopRight => Point::new(right, 0.0),
+        LegendPosition::BottomLeft => Point::new(0.0, bottom),
+        LegendPosition::BottomRight => Point::new(right, bottom),
+        LegendPosition::TopLeft
+        | LegendPosition::OutsideRight
+        | LegendPosition::OutsideBottom
+        | LegendPosition::Best => Point::ORIGIN,
+    }
+}
 
 #[derive(Debug, Clone)]
 /// An entry in the plot legend.
@@ -12,11 +237,194 @@ pub(crate) struct LegendEntry {
     pub(crate) id: ShapeId,
     pub(crate) label: String,
     pub(crate) color: Color,
-    pub(crate) _marker: u32,
-    pub(crate) _line_style: Option<LineStyle>,
+    pub(crate) marker: u32,
+    pub(crate) line_style: Option<LineStyle>,
+    /// Color of the area the entry covers, for bars, fills and distributions.
+    pub(crate) area: Option<Color>,
     pub(crate) hidden: bool,
 }
 
+impl LegendEntry {
+    /// A miniature of the entry, drawn back to front in a box of [`SWATCH_SIZE`]: the
+    /// area it covers, its line with its width and dash pattern, and its marker. Hidden
+    /// entries are drawn in gray.
+    pub(crate) fn swatch(&self, world_per_px: f64) -> Vec<SwatchPart> {
+        let recolor = |color: Color| {
+            if self.hidden {
+                Color {
+                    a: color.a,
+                    ..HIDDEN_COLOR
+                }
+            } else {
+                color
+            }
+        };
+        let color = recolor(self.color);
+        let center = Point::new(SWATCH_SIZE.width * 0.5, SWATCH_SIZE.height * 0.5);
+        let mut parts = Vec::new();
+
+        if let Some(area) = self.area {
+            parts.push(SwatchPart {
+                shape: SwatchShape::Polygon(rectangle(Point::ORIGIN, SWATCH_SIZE)),
+                color: recolor(area),
+            });
+        }
+
+        if let Some(line_style) = self.line_style {
+            let width = match line_style.width {
+                crate::Size::Pixels(width) => width,
+                crate::Size::World(_) => 2.0,
+            }
+            .clamp(1.0, SWATCH_SIZE.height * 0.5);
+            let line = |start: f32, end: f32| SwatchPart {
+                shape: SwatchShape::Polygon(rectangle(
+                    Point::new(start, center.y - width * 0.5),
+                    Size::new(end - start, width),
+                )),
+                color,
+            };
+            match line_style.line_type.in_pixels(world_per_px) {
+                LineType::Dotted { spacing } => {
+                    // Spaced like the CPU renderers' dots, starting a dot in.
+                    let step = spacing.max(1.0) * 2.0;
+                    let radius = width.max(1.5) * 0.5;
+                    let mut x = radius;
+                    while x <= SWATCH_SIZE.width - radius {
+                        parts.push(SwatchPart {
+                            shape: SwatchShape::Polygon(arc(
+                                Point::new(x, center.y),
+                                radius,
+                                0.0,
+                                TAU,
+                            )),
+                            color,
+                        });
+                        x += step;
+                    }
+                }
+                line_type => match line_type.dash_pattern_px(1.0) {
+                    Some(pattern) => parts.extend(
+                        pattern
+                            .dashes(0.0, SWATCH_SIZE.width)
+                            .into_iter()
+                            .map(|dash| line(dash.start, dash.end)),
+                    ),
+                    None => parts.push(line(0.0, SWATCH_SIZE.width)),
+                },
+            }
+        }
+
+        if self.marker != u32::MAX {
+            parts.push(SwatchPart {
+                shape: marker_shape(marker_type_from_u32(self.marker), center),
+                color,
+            });
+        }
+        parts
+    }
+}
+
+/// A filled shape in a legend swatch, in pixels from the swatch's top-left corner.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SwatchShape {
+    /// A polygon that is convex or star-shaped around the mean of its points.
+    Polygon(Vec<Point>),
+    /// A circle outline with a stroke `width` wide.
+    Ring {
+        center: Point,
+        radius: f32,
+        width: f32,
+    },
+}
+
+impl SwatchShape {
+    /// Triangles covering the shape, for drawing it as a mesh.
+    #[cfg(not(feature = "canvas"))]
+    fn triangles(&self) -> Vec<[Point; 3]> {
+        match self {
+            Self::Polygon(points) => {
+                if points.len() < 3 {
+                    return Vec::new();
+                }
+                let scale = 1.0 / points.len() as f32;
+                let center = points.iter().fold(Point::ORIGIN, |sum, point| {
+                    Point::new(sum.x + point.x * scale, sum.y + point.y * scale)
+                });
+                (0..points.len())
+                    .map(|index| [center, points[index], points[(index + 1) % points.len()]])
+                    .collect()
+            }
+            Self::Ring {
+                center,
+                radius,
+                width,
+            } => {
+                let outer = arc(*center, radius + width * 0.5, 0.0, TAU);
+                let inner = arc(*center, (radius - width * 0.5).max(0.0), 0.0, TAU);
+                outer
+                    .windows(2)
+                    .zip(inner.windows(2))
+                    .flat_map(|(o, i)| [[o[0], o[1], i[1]], [o[0], i[1], i[0]]])
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A shape in a legend swatch and its color.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SwatchPart {
+    pub(crate) shape: SwatchShape,
+    pub(crate) color: Color,
+}
+
+fn rectangle(top_left: Point, size: Size) -> Vec<Point> {
+    vec![
+        top_left,
+        Point::new(top_left.x + size.width, top_left.y),
+        Point::new(top_left.x + size.width, top_left.y + size.height),
+        Point::new(top_left.x, top_left.y + size.height),
+    ]
+}
+
+/// The marker's shape, in the proportions the renderers draw it.
+fn marker_shape(marker_type: MarkerType, center: Point) -> SwatchShape {
+    let radius = SWATCH_MARKER_RADIUS;
+    match marker_type {
+        MarkerType::FilledCircle => SwatchShape::Polygon(arc(center, radius, 0.0, TAU)),
+        MarkerType::EmptyCircle => SwatchShape::Ring {
+            center,
+            radius: radius * 0.85,
+            width: (radius * 0.3).max(1.0),
+        },
+        MarkerType::Square => SwatchShape::Polygon(rectangle(
+            Point::new(center.x - radius, center.y - radius),
+            Size::new(2.0 * radius, 2.0 * radius),
+        )),
+        MarkerType::Triangle => {
+            let h = radius * 0.866;
+            SwatchShape::Polygon(vec![
+                Point::new(center.x, center.y - h),
+                Point::new(center.x + radius, center.y + h),
+                Point::new(center.x - radius, center.y + h),
+            ])
+        }
+        MarkerType::Star => SwatchShape::Polygon(
+            (0..10)
+                .map(|index| {
+                    let angle = -FRAC_PI_2 + index as f32 * TAU / 10.0;
+                    let r = if index % 2 == 0 {
+                        radius
+                    } else {
+                        radius * 0.45
+                    };
+                    Point::new(center.x + angle.cos() * r, center.y + angle.sin() * r)
+                })
+                .collect(),
+        ),
+    }
+}
+
 pub(crate) fn legend(widget: &PlotWidget, collapsed: bool) -> Option<Element<'_, PlotUiMessage>> {
     let entries: Vec<LegendEntry> = widget.legend_entries();
 
@@ -26,37 +434,50 @@ pub(crate) fn legend(widget: &PlotWidget, collapsed: bool) -> Option<Element<'_,
         return Some(legend_container(label_button("▶ Legend")).into());
     }
 
-    let mut col = column![label_button("▼ Legend")]
-        .spacing(4.0)
-        .width(Length::Shrink)
-        .height(Length::Shrink);
+    let options = widget.legend;
+    let grid = options.grid_size(entries.len());
+    let mut columns: Vec<Vec<Element<'_, PlotUiMessage>>> =
+        (0..grid.0).map(|_| Vec::new()).collect();
 
-    for e in entries {
+    let world_per_px = widget
+        .camera_bounds
+        .map_or(1.0, |(camera, bounds)| camera.world_per_px(&bounds)[0]);
+    for (index, e) in entries.into_iter().enumerate() {
         let mut series_color = e.color;
         series_color.a = 1.0;
-        let swatch_color = if e.hidden {
-            color!(120, 120, 120)
-        } else {
-            series_color
-        };
-
-        let swatch = container("")
-            .width(Length::Fixed(12.0))
-            .height(Length::Fixed(12.0))
-            .style(move |_| swatch_color.into());
 
+        let swatch = Swatch {
+            parts: e.swatch(world_per_px),
+        };
         let swatch_btn: Element<'_, PlotUiMessage> = button(swatch)
-            .padding(2.0)
+            .padding(SWATCH_PADDING)
+            .style(button::text)
             .on_press(PlotUiMessage::ToggleSeriesVisibility(e.id))
             .into();
 
-        let row = row![swatch_btn, text(e.label).size(12.0).color(series_color)]
-            .spacing(4.0)
-            .width(Length::Shrink);
+        // Rows have a fixed height, so they line up across columns.
+        let row = row![
+            swatch_btn,
+            text(e.label).size(options.text_size).color(series_color)
+        ]
+        .spacing(LEGEND_SPACING)
+        .width(Length::Shrink)
+        .height(Length::Fixed(options.row_height()))
+        .align_y(Vertical::Center);
 
-        col = col.push(row);
+        let (column, _) = options.cell(index, grid);
+        columns[column].push(row.into());
     }
 
+    let grid = row(columns
+        .into_iter()
+        .map(|entries| column(entries).spacing(LEGEND_SPACING).into()))
+    .spacing(LEGEND_SPACING);
+    let col = column![label_button("▼ Legend"), grid]
+        .spacing(LEGEND_SPACING)
+        .width(Length::Shrink)
+        .height(Length::Shrink);
+
     Some(
         legend_container(col)
             .style(|theme| widget.update_style(theme).legend)
@@ -73,7 +494,188 @@ fn legend_container<'a>(
     content: impl Into<Element<'a, PlotUiMessage>>,
 ) -> Container<'a, PlotUiMessage> {
     container(content)
-        .padding(4.0)
+        .padding(LEGEND_PADDING)
         .align_x(Horizontal::Left)
         .align_y(Vertical::Top)
 }
+
+/// Draws the parts of a legend swatch.
+struct Swatch {
+    parts: Vec<SwatchPart>,
+}
+
+impl<Message> Widget<Message, Theme, iced::Renderer> for Swatch {
+    fn size(&self) -> Size<Length> {
+        Size::new(
+            Length::Fixed(SWATCH_SIZE.width),
+            Length::Fixed(SWATCH_SIZE.height),
+        )
+    }
+
+    fn layout(
+        &mut self,
+        _tree: &mut Tree,
+        _renderer: &iced::Renderer,
+        _limits: &layout::Limits,
+    ) -> layout::Node {
+        layout::Node::new(SWATCH_SIZE)
+    }
+
+    fn draw(
+        &self,
+        _tree: &Tree,
+        renderer: &mut iced::Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        use iced::advanced::Renderer as _;
+
+        let bounds = layout.bounds();
+        // CPU renderers can't draw meshes, but draw canvas geometry on any backend.
+        #[cfg(feature = "canvas")]
+        {
+            use iced::advanced::graphics::geometry::Renderer as _;
+
+            let mut frame = iced::widget::canvas::Frame::new(renderer, bounds.size());
+            crate::plot_renderer::canvas::draw_swatch(&mut frame, Point::ORIGIN, &self.parts);
+            renderer.with_translation(iced::Vector::new(bounds.x, bounds.y), |renderer| {
+                renderer.draw_geometry(frame.into_geometry());
+            });
+        }
+        #[cfg(not(feature = "canvas"))]
+        {
+            use iced::advanced::graphics::{
+                color,
+                mesh::{Indexed, Mesh, Renderer as _, SolidVertex2D},
+            };
+
+            let mut vertices = Vec::new();
+            for part in &self.parts {
+                let color = color::pack(part.color);
+                vertices.extend(part.shape.triangles().into_iter().flatten().map(|point| {
+                    SolidVertex2D {
+                        position: [point.x, point.y],
+                        color,
+                    }
+                }));
+            }
+            if vertices.is_empty() {
+                return;
+            }
+            let mesh = Mesh::Solid {
+                buffers: Indexed {
+                    indices: (0..vertices.len() as u32).collect(),
+                    vertices,
+                },
+                transformation: iced::Transformation::IDENTITY,
+                clip_bounds: Rectangle::with_size(bounds.size()),
+            };
+            renderer.with_translation(iced::Vector::new(bounds.x, bounds.y), |renderer| {
+                renderer.draw_mesh(mesh);
+            });
+        }
+    }
+}
+
+impl<'a, Message: 'a> From<Swatch> for Element<'a, Message> {
+    fn from(swatch: Swatch) -> Self {
+        Element::new(swatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DashPattern;
+
+    fn entry(marker: u32, line_style: Option<LineStyle>, area: Option<Color>) -> LegendEntry {
+        LegendEntry {
+            id: ShapeId::new(),
+            label: "entry".into(),
+            color: Color::from_rgb(1.0, 0.0, 0.0),
+            marker,
+            line_style,
+            area,
+            hidden: false,
+        }
+    }
+
+    #[test]
+    fn swatches_show_dashes_and_the_marker_on_top() {
+        let style = LineStyle::pattern(DashPattern::new([6.0, 4.0])).with_pixel_width(2.0);
+        let parts = entry(MarkerType::Square as u32, Some(style), None).swatch(1.0);
+
+        // Dashes at 0..6, 10..16 and 20..24, then the square.
+        assert_eq!(parts.len(), 4);
+        assert_eq!(
+            parts[1].shape,
+            SwatchShape::Polygon(rectangle(Point::new(10.0, 5.0), Size::new(6.0, 2.0)))
+        );
+        assert_eq!(
+            parts[3].shape,
+            marker_shape(MarkerType::Square, Point::new(12.0, 6.0))
+        );
+    }
+
+    #[test]
+    fn entries_flow_down_columns_or_across_rows() {
+        let vertical = Legend::new().with_columns(2);
+        assert_eq!(vertical.grid_size(5), (2, 3));
+        assert_eq!(vertical.cell(3, (2, 3)), (1, 0));
+
+        let horizontal = Legend::new().with_orientation(LegendOrientation::Horizontal);
+        assert_eq!(horizontal.grid_size(5), (5, 1));
+        let horizontal = horizontal.with_columns(2);
+        assert_eq!(horizontal.cell(3, (2, 3)), (1, 1));
+    }
+
+    #[test]
+    fn best_position_covers_the_fewest_points() {
+        let legend = Legend::new().with_position(LegendPosition::Best);
+        let area = Size::new(400.0, 300.0);
+        let size = Size::new(100.0, 50.0);
+        fn covered(points: &[Point]) -> impl Fn(Rectangle) -> usize + '_ {
+            move |rect| points.iter().filter(|&&p| rect.contains(p)).count()
+        }
+        // Points along the top push the legend down, preferring the left.
+        let top = [Point::new(50.0, 20.0), Point::new(350.0, 20.0)];
+        assert_eq!(
+            legend.resolve_position(area, size, covered(&top)),
+            LegendPosition::BottomLeft
+        );
+        // A wide data extent with few points in one corner still leaves the others free.
+        let spread = [
+            Point::new(10.0, 10.0),
+            Point::new(20.0, 280.0),
+            Point::new(390.0, 290.0),
+            Point::new(200.0, 150.0),
+        ];
+        assert_eq!(
+            legend.resolve_position(area, size, covered(&spread)),
+            LegendPosition::TopRight
+        );
+        assert_eq!(
+            legend.resolve_position(area, size, covered(&[])),
+            LegendPosition::TopLeft
+        );
+    }
+
+    #[test]
+    fn fill_swatches_keep_their_alpha_and_hidden_entries_are_gray() {
+        let area = Color::from_rgba(0.0, 0.0, 1.0, 0.3);
+        let mut fill = entry(u32::MAX, None, Some(area));
+        assert_eq!(fill.swatch(1.0)[0].color, area);
+
+        fill.hidden = true;
+        assert_eq!(
+            fill.swatch(1.0)[0].color,
+            Color {
+                a: 0.3,
+                ..HIDDEN_COLOR
+            }
+        );
+    }
+}
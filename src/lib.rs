@@ -3,6 +3,19 @@
 //! - Works with large datasets (up to millions of points)
 //! - Retains GPU buffers between frames for fast redraws and picking
 //! - Axes/labels, legends, reference lines, hover/pick highlighting, crosshairs, axis linking, etc.
+//! - Colormaps for per-point values, with a colorbar beside the plot
+//! - Raster images and heatmaps drawn as textures, with per-cell hover and pick
+//! - Contour lines and filled contour bands from gridded data
+//! - Histograms binned automatically from raw samples
+//! - Box plots and violins comparing distributions side by side
+//! - Categorical axes with named categories
+//! - Step lines (pre, mid, post) and smooth splines (Catmull-Rom, monotone, natural cubic), with fills that follow them
+//! - Custom dash patterns with an offset, in pixels or plot units
+//! - Legends inside a corner, outside the plot or placed automatically, in rows or columns
+//! - Hover and pick on markers, line segments, fills and reference lines
+//! - Box and lasso selection of points, adding to or removing from the selection with Shift or Ctrl
+//! - A data cursor reading every series at the cursor x in one tooltip
+//! - A measurement ruler showing Δx, Δy, distance and slope, optionally snapped to data points
 //!
 //! Quick start:
 //!
@@ -25,11 +38,23 @@
 pub(crate) mod axes_labels;
 pub(crate) mod axis_link;
 pub(crate) mod axis_scale;
+pub(crate) mod bar;
 pub(crate) mod camera;
+pub(crate) mod colorbar;
+pub(crate) mod colormap;
+pub(crate) mod contour;
 pub(crate) mod controls;
+pub(crate) mod data_cursor;
+pub(crate) mod decimation;
+pub(crate) mod distribution;
+pub(crate) mod error_bars;
+pub(crate) mod export;
 pub(crate) mod fill;
 pub(crate) mod grid;
+pub(crate) mod histogram;
+pub(crate) mod interpolation;
 pub(crate) mod legend;
+pub(crate) mod measure;
 pub(crate) mod message;
 pub(crate) mod picking;
 pub(crate) mod plot_overlay;
@@ -38,9 +63,13 @@ pub(crate) mod plot_state;
 pub(crate) mod plot_widget;
 pub(crate) mod plot_widget_builder;
 pub(crate) mod point;
+pub(crate) mod raster;
 pub(crate) mod reference_lines;
+pub(crate) mod secondary_axis;
 pub(crate) mod series;
+pub(crate) mod spatial_index;
 pub(crate) mod style;
+pub(crate) mod text;
 pub(crate) mod ticks;
 pub(crate) mod transform;
 
@@ -50,20 +79,42 @@ pub use iced::Color;
 // Re-exports of public types.
 pub use axis_link::AxisLink;
 pub use axis_scale::AxisScale;
+pub use bar::{BarGroup, BarLayout, BarOrientation, BarStyle};
+pub use colorbar::Colorbar;
+pub use colormap::{ColorNorm, ColorScale, Colormap};
+pub use contour::{Contour, ContourLevels};
 pub use controls::{ClickAction, DragAction, KeyAction, PanDirection, PlotControls, ScrollAction};
+pub use data_cursor::DataCursorMode;
+pub use decimation::Decimation;
+pub use distribution::{DistributionPlot, DistributionStats, DistributionStyle};
+pub use error_bars::{ErrorBarCap, ErrorBarStyle, ErrorDisplay, ErrorValues};
+pub use export::{ExportError, ExportOptions, RgbaImage};
 pub use fill::Fill;
 pub use grid::TickWeight;
-pub use message::{DragEvent, HoverPickEvent, PlotUiMessage, PointId, TooltipContext};
+pub use histogram::{Binning, Histogram, HistogramBin, HistogramNorm, HistogramStyle};
+pub use interpolation::LineInterpolation;
+pub use legend::{Legend, LegendOrientation, LegendPosition};
+pub use measure::Measurement;
+pub use message::{
+    DragEvent, HoverPickEvent, PickHit, PlotUiMessage, PointId, SelectionEvent, SelectionMode,
+    TooltipContext,
+};
 pub use plot_overlay::PlotOverlay;
 pub use plot_renderer::PlotRenderStrategy;
 pub use plot_widget::{HighlightPoint, PlotWidget};
 pub use plot_widget_builder::PlotWidgetBuilder;
 pub use point::{MarkerType, Point};
+pub use raster::{Raster, RasterCell, RasterData, RasterOrigin, RasterSampling};
 pub use reference_lines::{HLine, VLine};
-pub use series::{LineStyle, LineType, MarkerStyle, Series, ShapeId, Size};
+pub use secondary_axis::YAxis;
+pub use series::{
+    DashPattern, DashUnits, LineCap, LineJoin, LineStyle, LineType, MarkerStyle, Series, ShapeId,
+    Size,
+};
 pub use style::{GridStyle, PlotStyle, default_style};
 pub use ticks::{
-    Tick, TickFormatter, TickProducer, default_formatter, default_tick_producer, log_formatter,
-    log_tick_producer,
+    Tick, TickFormatter, TickProducer, category_formatter, category_tick_producer,
+    default_formatter, default_tick_producer, log_formatter, log_tick_producer, time_formatter,
+    time_tick_producer,
 };
 pub use transform::{CoordinateSystem, PositionTransform, Transform};
@@ -0,0 +1,105 @@
+//! Measurement ruler: deltas, distance and slope between two points.
+use crate::{AxisScale, PointId};
+
+/// A ruler drawn with [`DragAction::Measure`](crate::DragAction::Measure), in data
+/// coordinates of the x-axis and the primary y-axis.
+///
+/// Deltas respect the axis scale: they are differences on linear and time axes, and ratios
+/// on log axes. Distance and slope are measured in plot space, where log axes count powers
+/// of their base.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    /// Where the ruler starts.
+    pub start: [f64; 2],
+    /// Where the ruler ends.
+    pub end: [f64; 2],
+    /// The data point the start snapped to, if any.
+    pub start_point: Option<PointId>,
+    /// The data point the end snapped to, if any.
+    pub end_point: Option<PointId>,
+    /// `end - start` on a linear or time x-axis, `end / start` on a log x-axis.
+    pub delta_x: f64,
+    /// `end - start` on a linear or time y-axis, `end / start` on a log y-axis.
+    pub delta_y: f64,
+    /// Length of the ruler in plot space.
+    pub distance: f64,
+    /// Rise over run in plot space, or `None` for a vertical or zero-length ruler. On
+    /// log-log axes, this is the exponent of the power law through both ends.
+    pub slope: Option<f64>,
+    /// Whether the drag has ended. Measurements are published while dragging too.
+    pub finished: bool,
+}
+
+impl Measurement {
+    /// Measure from `start` to `end` on the given axis scales. Returns `None` when either
+    /// end can't be shown on the axes.
+    pub(crate) fn between(
+        start: [f64; 2],
+        end: [f64; 2],
+        x_scale: AxisScale,
+        y_scale: AxisScale,
+    ) -> Option<Self> {
+        let dx = x_scale.data_to_plot(end[0])? - x_scale.data_to_plot(start[0])?;
+        let dy = y_scale.data_to_plot(end[1])? - y_scale.data_to_plot(start[1])?;
+        Some(Self {
+            start,
+            end,
+            start_point: None,
+            end_point: None,
+            delta_x: delta(start[0], end[0], x_scale),
+            delta_y: delta(start[1], end[1], y_scale),
+            distance: dx.hypot(dy),
+            slope: (dx != 0.0).then(|| dy / dx),
+            finished: false,
+        })
+    }
+}
+
+fn delta(start: f64, end: f64, scale: AxisScale) -> f64 {
+    match scale {
+        AxisScale::Log { .. } => end / start,
+        AxisScale::Linear | AxisScale::Time => end - start,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Measurement;
+    use crate::AxisScale;
+
+    #[test]
+    fn measures_differences_on_linear_axes() {
+        let m = Measurement::between([1.0, 1.0], [4.0, 5.0], AxisScale::Linear, AxisScale::Linear)
+            .unwrap();
+
+        assert_eq!((m.delta_x, m.delta_y), (3.0, 4.0));
+        assert_eq!(m.distance, 5.0);
+        assert_eq!(m.slope, Some(4.0 / 3.0));
+
+        // Vertical and zero-length rulers have no slope.
+        let vertical =
+            Measurement::between([1.0, 1.0], [1.0, 5.0], AxisScale::Linear, AxisScale::Linear);
+        assert_eq!(vertical.unwrap().slope, None);
+        let point =
+            Measurement::between([1.0, 1.0], [1.0, 1.0], AxisScale::Linear, AxisScale::Linear);
+        assert_eq!(point.unwrap().slope, None);
+    }
+
+    #[test]
+    fn log_axes_report_ratios_and_power_law_slopes() {
+        let log = AxisScale::Log { base: 10.0 };
+        let m = Measurement::between([10.0, 2.0], [1000.0, 0.02], log, log).unwrap();
+
+        assert!((m.delta_x - 100.0).abs() < 1e-9);
+        assert!((m.delta_y - 0.01).abs() < 1e-12);
+        // y ~ x^-1: two decades across, two down.
+        assert!((m.slope.unwrap() + 1.0).abs() < 1e-9);
+        assert!((m.distance - 8f64.sqrt()).abs() < 1e-9);
+
+        // Nothing to measure where a log axis can't go.
+        assert_eq!(
+            Measurement::between([0.0, 1.0], [1.0, 1.0], log, AxisScale::Linear),
+            None
+        );
+    }
+}
@@ -1,6 +1,14 @@
+use glam::DVec2;
 use iced::Rectangle;
 
-use crate::{camera::Camera, series::ShapeId, ticks::PositionedTick};
+use crate::{
+    camera::Camera, data_cursor::DataCursorReadout, distribution::DistributionStats,
+    error_bars::ErrorDisplay, histogram::HistogramBin, measure::Measurement, raster::RasterCell,
+    secondary_axis::SecondaryAxisMap, series::ShapeId, ticks::PositionedTick,
+};
+
+/// Extent of the data in plot coordinates, as `(min, max)`.
+pub(crate) type DataBounds = Option<(DVec2, DVec2)>;
 
 /// Messages sent by the plot widget to the application.
 ///
@@ -37,12 +45,31 @@ impl PlotUiMessage {
             None
         }
     }
+
+    /// Get the box or lasso selection event from the render update.
+    pub fn get_selection_event(&self) -> Option<&SelectionEvent> {
+        if let PlotUiMessage::RenderUpdate(update) = self {
+            update.details.selection.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Get the measurement from the render update.
+    pub fn get_measurement(&self) -> Option<&Measurement> {
+        if let PlotUiMessage::RenderUpdate(update) = self {
+            update.details.measurement.as_ref()
+        } else {
+            None
+        }
+    }
 }
 
 /// Context passed to hover/pick highlight callbacks.
 ///
 /// Contains information identifying the point being highlighted.
 #[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
 pub struct TooltipContext<'a> {
     /// ID of the series
     pub series_id: ShapeId,
@@ -50,6 +77,31 @@ pub struct TooltipContext<'a> {
     pub series_label: &'a str,
     /// Index within the series [0..len)
     pub point_index: usize,
+    /// `(lower, upper)` x error of the point, if the series has x errors
+    pub x_error: Option<(f64, f64)>,
+    /// `(lower, upper)` y error of the point, if the series has y errors
+    pub y_error: Option<(f64, f64)>,
+    /// The cell, if the point is a cell of a [`Raster`](crate::Raster)
+    pub cell: Option<RasterCell>,
+    /// The bin, if the point is part of a [`Histogram`](crate::Histogram)
+    pub bin: Option<HistogramBin>,
+    /// The statistics of the group, if the point is a box, violin or outlier of a
+    /// [`DistributionPlot`](crate::DistributionPlot)
+    pub distribution: Option<DistributionStats>,
+}
+
+impl TooltipContext<'_> {
+    /// Format the x error of the point as `± e` (or `+upper/-lower` when asymmetric).
+    pub fn display_x_error(&self) -> Option<ErrorDisplay> {
+        self.x_error
+            .map(|(lower, upper)| ErrorDisplay { lower, upper })
+    }
+
+    /// Format the y error of the point as `± e` (or `+upper/-lower` when asymmetric).
+    pub fn display_y_error(&self) -> Option<ErrorDisplay> {
+        self.y_error
+            .map(|(lower, upper)| ErrorDisplay { lower, upper })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -66,6 +118,8 @@ pub struct CursorPositionUiPayload {
     /// World/data-space coordinates for the cursor
     pub x: f64,
     pub y: f64,
+    /// Cursor value on the secondary y-axis, if the plot has one.
+    pub y2: Option<f64>,
     /// Formatted text to render
     pub text: String,
 }
@@ -79,8 +133,23 @@ pub struct PlotRenderUpdate {
     pub cursor_position_ui: Option<CursorPositionUiPayload>,
     pub x_ticks: Option<Vec<PositionedTick>>,
     pub y_ticks: Option<Vec<PositionedTick>>,
-    /// Internal: Camera and bounds for coordinate conversion (only used internally, not part of public API)
-    pub(crate) camera_bounds: Option<Box<(Camera, Rectangle)>>,
+    /// Internal: the rest of the update, boxed so messages stay small (not part of public
+    /// API)
+    pub(crate) details: Box<RenderUpdateDetails>,
+}
+
+/// The parts of a [`PlotRenderUpdate`] that are only read by the widget and its getters.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RenderUpdateDetails {
+    pub(crate) selection: Option<SelectionEvent>,
+    pub(crate) measurement: Option<Measurement>,
+    pub(crate) clear_measurement: bool,
+    pub(crate) clear_data_cursor: bool,
+    pub(crate) data_cursor: Option<DataCursorReadout>,
+    pub(crate) y2_ticks: Option<Vec<PositionedTick>>,
+    /// Camera, bounds and secondary y-axis map for coordinate conversion, and the data
+    /// extent for legend placement.
+    pub(crate) camera_bounds: Option<(Camera, Rectangle, SecondaryAxisMap, DataBounds)>,
 }
 
 /// Drag interaction event in data/world coordinates.
@@ -109,24 +178,115 @@ pub enum DragEvent {
     },
 }
 
+/// How a box or lasso selection combines with the current selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionMode {
+    /// Replace the selection.
+    #[default]
+    Replace,
+    /// Add the points to the selection (Shift held).
+    Add,
+    /// Remove the points from the selection (Ctrl held).
+    Subtract,
+}
+
+/// Points selected by a [`BoxSelect`](crate::DragAction::BoxSelect) or
+/// [`LassoSelect`](crate::DragAction::LassoSelect) drag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionEvent {
+    /// How the points combine with the current selection.
+    pub mode: SelectionMode,
+    /// The points of pickable series inside the rectangle or outline.
+    pub points: Vec<PointId>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-/// Identifier for a point in a series.
+/// Identifier for a point in a series, or a cell in a raster.
 pub struct PointId {
-    /// ID of the series
+    /// ID of the series or raster
     pub series_id: ShapeId,
-    /// Index within the series [0..len)
+    /// Index within the series [0..len), or `row * columns + column` for a raster cell
     pub point_index: usize,
 }
 
+/// The shape under the cursor in a hover or pick event.
+///
+/// On plots with many points, markers are found by a GPU pick pass. Only markers are:
+/// line segments, bars, fills, reference lines and raster cells are always picked on the
+/// CPU, from their geometry, once no marker is under the cursor. Those hits follow the
+/// shapes' outlines rather than the drawn pixels, so antialiased edges and dash gaps count
+/// as part of the shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickHit {
+    /// A marker, bar, raster cell or part of a distribution plot.
+    Point(PointId),
+    /// A line segment of a series.
+    Segment {
+        /// The point whose value the line shows under the cursor: the nearer end of the
+        /// segment, or the point holding the value of a step.
+        point: PointId,
+        /// Index of the point the segment starts at. It ends at the next drawn point.
+        segment: usize,
+        /// Position on the line under the cursor, in data coordinates.
+        position: [f64; 2],
+    },
+    /// The inside of a [`Fill`](crate::Fill).
+    Fill {
+        /// ID of the fill
+        fill_id: ShapeId,
+        /// Cursor position, in data coordinates. The y value is on the axis of the first
+        /// series bounding the fill, or the primary y-axis for fills between lines.
+        position: [f64; 2],
+    },
+    /// A [`VLine`](crate::VLine) or [`HLine`](crate::HLine).
+    ReferenceLine {
+        /// ID of the line
+        line_id: ShapeId,
+        /// Position on the line under the cursor, in data coordinates of the x-axis and
+        /// the primary y-axis.
+        position: [f64; 2],
+    },
+}
+
+impl PickHit {
+    /// The ID of the series, raster, fill or line that was hit.
+    pub fn shape_id(&self) -> ShapeId {
+        match *self {
+            PickHit::Point(point) | PickHit::Segment { point, .. } => point.series_id,
+            PickHit::Fill { fill_id, .. } => fill_id,
+            PickHit::ReferenceLine { line_id, .. } => line_id,
+        }
+    }
+
+    /// The point that was hit, or the point whose value a hit segment shows.
+    pub fn point_id(&self) -> Option<PointId> {
+        match *self {
+            PickHit::Point(point) | PickHit::Segment { point, .. } => Some(point),
+            PickHit::Fill { .. } | PickHit::ReferenceLine { .. } => None,
+        }
+    }
+
+    /// The position under the cursor on a segment, fill or reference line, in data
+    /// coordinates.
+    pub fn position(&self) -> Option<[f64; 2]> {
+        match *self {
+            PickHit::Point(_) => None,
+            PickHit::Segment { position, .. }
+            | PickHit::Fill { position, .. }
+            | PickHit::ReferenceLine { position, .. } => Some(position),
+        }
+    }
+}
+
 /// The hover or pick event.
 #[derive(Debug, Clone, Copy)]
 pub enum HoverPickEvent {
-    /// Hover a point.
-    Hover(PointId),
+    /// Hover a shape.
+    Hover(PickHit),
     /// Clear all hovered points.
     ClearHover,
-    /// Pick a point.
-    Pick(PointId),
+    /// Pick a shape.
+    Pick(PickHit),
     /// Clear all picked points.
     ClearPick,
 }
@@ -8,7 +8,14 @@ use glam::{DVec2, Vec2};
 use iced::Rectangle;
 use iced::wgpu::*;
 
-use crate::{Point, PointId, Size, camera::Camera, plot_state::SeriesSpan};
+use crate::{
+    AxisScale, HLine, LineInterpolation, PickHit, Point, PointId, ShapeId, Size, VLine, YAxis,
+    axis_scale::plot_point_to_data,
+    camera::Camera,
+    plot_state::{BarSpan, FillSpan, RasterSpan, SeriesSpan},
+    secondary_axis::SecondaryAxisMap,
+    transform::data_value_to_plot_with_axis_range,
+};
 
 /// Threshold for number of points above which GPU picking is used instead of CPU picking.
 pub(crate) const CPU_PICK_THRESHOLD: usize = 5000;
@@ -19,7 +26,7 @@ pub(crate) const CPU_PICK_THRESHOLD: usize = 5000;
 #[derive(Debug, Clone, Default)]
 pub(crate) struct PickingState {
     /// Last hover hit, if any.
-    pub(crate) last_hover_cache: Option<PointId>,
+    pub(crate) last_hover_cache: Option<PickHit>,
 
     /// When set, the matching GPU result is interpreted as a *pick* (click)
     /// instead of a hover.
@@ -35,7 +42,7 @@ pub(crate) struct PickingState {
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum HoverRequest {
     /// Immediate CPU result.
-    CpuHit(PointId),
+    CpuHit(PickHit),
     /// Immediate CPU miss.
     CpuMiss,
     /// GPU request was submitted; result will arrive in a later frame.
@@ -44,9 +51,61 @@ pub(crate) enum HoverRequest {
 
 #[derive(Debug, Clone, Copy)]
 pub(crate) enum GpuResultEvent {
-    Hover(PointId),
+    Hover(PickHit),
     HoverMiss,
-    Pick(PointId),
+    Pick(PickHit),
+}
+
+/// The shapes hit-tested on the CPU, and the axes that map plot positions back to data.
+pub(crate) struct PickTargets<'a> {
+    pub(crate) points: &'a [Point],
+    pub(crate) series: &'a [SeriesSpan],
+    pub(crate) bars: &'a [BarSpan],
+    pub(crate) fills: &'a [FillSpan],
+    pub(crate) rasters: &'a [RasterSpan],
+    pub(crate) vlines: &'a [VLine],
+    pub(crate) hlines: &'a [HLine],
+    pub(crate) camera: &'a Camera,
+    pub(crate) bounds: &'a Rectangle,
+    pub(crate) x_axis_scale: AxisScale,
+    pub(crate) y_axis_scale: AxisScale,
+    pub(crate) y2_axis_scale: AxisScale,
+    pub(crate) y2_map: SecondaryAxisMap,
+}
+
+impl PickTargets<'_> {
+    fn viewport(&self) -> DVec2 {
+        DVec2::new(
+            self.bounds.width.max(1.0) as f64,
+            self.bounds.height.max(1.0) as f64,
+        )
+    }
+
+    fn to_screen(&self, [x, y]: [f64; 2]) -> DVec2 {
+        let viewport = self.viewport();
+        let ndc_x = (x - self.camera.position.x) / self.camera.half_extents.x;
+        let ndc_y = (y - self.camera.position.y) / self.camera.half_extents.y;
+        DVec2::new(
+            (ndc_x + 1.0) * 0.5 * viewport.x,
+            (1.0 - ndc_y) * 0.5 * viewport.y,
+        )
+    }
+
+    fn to_world(&self, cursor: Vec2) -> DVec2 {
+        self.camera.screen_to_world(
+            DVec2::new(cursor.x as f64, cursor.y as f64),
+            self.viewport(),
+        )
+    }
+
+    /// Convert a plot-space position to data coordinates on the given y-axis.
+    fn to_data(&self, [x, y]: [f64; 2], y_axis: YAxis) -> [f64; 2] {
+        let (y, y_scale) = match y_axis {
+            YAxis::Primary => (y, self.y_axis_scale),
+            YAxis::Secondary => (self.y2_map.to_secondary(y), self.y2_axis_scale),
+        };
+        plot_point_to_data([x, y], self.x_axis_scale, y_scale).unwrap_or([x, y])
+    }
 }
 
 impl PickingState {
@@ -66,30 +125,25 @@ impl PickingState {
     /// Request hover picking for the current cursor position.
     ///
     /// CPU vs GPU is decided internally based on point count.
-    #[allow(clippy::too_many_arguments)]
     pub(crate) fn request_hover(
         &mut self,
         instance_id: u64,
+        targets: &PickTargets,
         cursor: Vec2,
         hover_radius_px: f32,
         force_cpu: bool,
-        points: &[Point],
-        series: &[SeriesSpan],
-        camera: &Camera,
-        bounds: &Rectangle,
-        valid_point_id: impl Fn(&PointId) -> bool,
+        valid_hit: impl Fn(&PickHit) -> bool,
     ) -> HoverRequest {
-        if force_cpu || points.len() < CPU_PICK_THRESHOLD {
-            if let Some(point) =
-                cpu_pick_hit(points, series, camera, bounds, cursor, hover_radius_px)
-                && valid_point_id(&point)
-            {
-                self.last_hover_cache = Some(point);
-                HoverRequest::CpuHit(point)
+        if force_cpu || targets.points.len() < CPU_PICK_THRESHOLD {
+            if let Some(hit) = cpu_pick(targets, cursor, hover_radius_px, valid_hit) {
+                self.last_hover_cache = Some(hit);
+                HoverRequest::CpuHit(hit)
             } else {
                 HoverRequest::CpuMiss
             }
         } else {
+            // Shapes below the markers are picked on the CPU once the GPU reports no
+            // marker under the cursor.
             self.submit_gpu_request(instance_id, cursor, hover_radius_px);
             HoverRequest::RequestedGpu
         }
@@ -98,45 +152,38 @@ impl PickingState {
     /// Request a click-to-pick hit at the current cursor position.
     ///
     /// Returns an immediate hit when using cache/CPU. For GPU, submits a request and returns None.
-    #[allow(clippy::too_many_arguments)]
     pub(crate) fn request_pick_hit(
         &mut self,
         instance_id: u64,
+        targets: &PickTargets,
         cursor: Vec2,
         hover_radius_px: f32,
         force_cpu: bool,
-        points: &[Point],
-        series: &[SeriesSpan],
-        camera: &Camera,
-        bounds: &Rectangle,
-        valid_point_id: impl Fn(&PointId) -> bool,
-    ) -> Option<PointId> {
-        if let Some(point) = self.last_hover_cache
-            && valid_point_id(&point)
+        valid_hit: impl Fn(&PickHit) -> bool,
+    ) -> Option<PickHit> {
+        if let Some(hit) = self.last_hover_cache
+            && valid_hit(&hit)
         {
-            return Some(point);
+            return Some(hit);
         }
 
-        if force_cpu || points.len() < CPU_PICK_THRESHOLD {
-            if let Some(point) =
-                cpu_pick_hit(points, series, camera, bounds, cursor, hover_radius_px)
-                && valid_point_id(&point)
-            {
-                return Some(point);
-            }
-        } else {
-            self.submit_gpu_request(instance_id, cursor, hover_radius_px);
-            // Mark this seq as a pick request.
-            self.pending_gpu_pick_seq = Some(self.pick_seq);
+        if force_cpu || targets.points.len() < CPU_PICK_THRESHOLD {
+            return cpu_pick(targets, cursor, hover_radius_px, valid_hit);
         }
+        self.submit_gpu_request(instance_id, cursor, hover_radius_px);
+        // Mark this seq as a pick request.
+        self.pending_gpu_pick_seq = Some(self.pick_seq);
         None
     }
 
     /// Consume and interpret a GPU pick result (if available).
+    ///
+    /// `fallback` is picked when no marker was hit, for shapes drawn below the markers.
     pub(crate) fn consume_gpu_result(
         &mut self,
         instance_id: u64,
-        valid_point_id: impl Fn(&PointId) -> bool,
+        valid_hit: impl Fn(&PickHit) -> bool,
+        fallback: impl FnOnce() -> Option<PickHit>,
     ) -> Option<GpuResultEvent> {
         let res = take_result(instance_id)?;
         if res.seq <= self.pick_result_seq {
@@ -144,19 +191,20 @@ impl PickingState {
         }
 
         let mut out = None;
+        let hit = res
+            .hit
+            .map(PickHit::Point)
+            .filter(&valid_hit)
+            .or_else(|| fallback().filter(&valid_hit));
 
         if self.pending_gpu_pick_seq == Some(res.seq) {
             self.pending_gpu_pick_seq = None;
-            if let Some(point) = res.hit
-                && valid_point_id(&point)
-            {
-                out = Some(GpuResultEvent::Pick(point));
+            if let Some(hit) = hit {
+                out = Some(GpuResultEvent::Pick(hit));
             }
-        } else if let Some(point) = res.hit
-            && valid_point_id(&point)
-        {
-            self.last_hover_cache = Some(point);
-            out = Some(GpuResultEvent::Hover(point));
+        } else if let Some(hit) = hit {
+            self.last_hover_cache = Some(hit);
+            out = Some(GpuResultEvent::Hover(hit));
         } else {
             out = Some(GpuResultEvent::HoverMiss);
         }
@@ -170,7 +218,44 @@ impl PickingState {
     }
 }
 
-fn marker_center_world(pt: &Point) -> DVec2 {
+/// Pick the topmost shape under the cursor on the CPU.
+fn cpu_pick(
+    targets: &PickTargets,
+    cursor: Vec2,
+    hover_radius_px: f32,
+    valid_hit: impl Fn(&PickHit) -> bool,
+) -> Option<PickHit> {
+    cpu_pick_hit(targets, cursor, hover_radius_px)
+        .map(PickHit::Point)
+        .filter(&valid_hit)
+        .or_else(|| cpu_pick_below_markers(targets, cursor, hover_radius_px, valid_hit))
+}
+
+/// Pick the topmost shape drawn below the markers: reference lines, then line segments,
+/// bars, fills and raster cells.
+pub(crate) fn cpu_pick_below_markers(
+    targets: &PickTargets,
+    cursor: Vec2,
+    hover_radius_px: f32,
+    valid_hit: impl Fn(&PickHit) -> bool,
+) -> Option<PickHit> {
+    cpu_pick_reference_line(targets, cursor, hover_radius_px)
+        .filter(&valid_hit)
+        .or_else(|| cpu_pick_segment(targets, cursor, hover_radius_px).filter(&valid_hit))
+        .or_else(|| {
+            cpu_pick_bar(targets, cursor)
+                .map(PickHit::Point)
+                .filter(&valid_hit)
+        })
+        .or_else(|| cpu_pick_fill(targets, cursor).filter(&valid_hit))
+        .or_else(|| {
+            cpu_pick_raster(targets, cursor)
+                .map(PickHit::Point)
+                .filter(&valid_hit)
+        })
+}
+
+pub(crate) fn marker_center_world(pt: &Point) -> DVec2 {
     let mut world = DVec2::new(pt.position[0], pt.position[1]);
     if pt.size_mode == crate::point::MARKER_SIZE_WORLD {
         let half = pt.size as f64 * 0.5;
@@ -180,77 +265,379 @@ fn marker_center_world(pt: &Point) -> DVec2 {
     world
 }
 
-fn cpu_pick_hit(
-    points: &[Point],
-    series: &[SeriesSpan],
-    camera: &Camera,
-    bounds: &Rectangle,
-    cursor: Vec2,
-    hover_radius_px: f32,
-) -> Option<PointId> {
-    if points.is_empty() || series.is_empty() {
-        return None;
-    }
-
-    let width = bounds.width.max(1.0) as f64;
-    let height = bounds.height.max(1.0) as f64;
-    let cursor_x = cursor.x as f64;
-    let cursor_y = cursor.y as f64;
+fn cpu_pick_hit(targets: &PickTargets, cursor: Vec2, hover_radius_px: f32) -> Option<PointId> {
+    nearest_point(targets, cursor, hover_radius_px, |span| {
+        span.marker != u32::MAX
+    })
+    .map(|(point, _)| point)
+}
 
-    let mut span_idx = 0usize;
-    let mut span_start = 0usize;
-    let mut best: Option<(usize, f64)> = None;
+/// Find the data point of a pickable series nearest the cursor, with or without a marker,
+/// returning it with its plot-space position. Only series on the primary y-axis are
+/// snapped to, as measurements are taken on that axis.
+pub(crate) fn cpu_snap(
+    targets: &PickTargets,
+    cursor: Vec2,
+    radius_px: f32,
+) -> Option<(PointId, [f64; 2])> {
+    nearest_point(targets, cursor, radius_px, |span| {
+        span.y_axis == YAxis::Primary
+    })
+}
 
-    for (idx, pt) in points.iter().enumerate() {
-        while span_idx < series.len() && idx >= span_start + series[span_idx].len {
-            span_start += series[span_idx].len;
-            span_idx += 1;
+/// Find the point nearest the cursor within `radius_px` plus half its marker size, among
+/// the pickable series that `include` accepts. Returns it with its plot-space position.
+fn nearest_point(
+    targets: &PickTargets,
+    cursor: Vec2,
+    radius_px: f32,
+    include: impl Fn(&SeriesSpan) -> bool,
+) -> Option<(PointId, [f64; 2])> {
+    let PickTargets {
+        points,
+        camera,
+        bounds,
+        ..
+    } = *targets;
+    let viewport = targets.viewport();
+    let target = targets.to_world(cursor);
+    // Pixels per world unit, so distances are measured on screen.
+    let scale = [
+        0.5 * viewport.x / camera.half_extents.x,
+        0.5 * viewport.y / camera.half_extents.y,
+    ];
+
+    let mut best: Option<(PointId, [f64; 2], f64)> = None;
+    for span in targets
+        .series
+        .iter()
+        .filter(|span| span.pickable && span.len > 0 && include(span))
+    {
+        let span_points = &points[span.start..span.start + span.len];
+        // All points of a series share its marker size.
+        let marker_px = Size::size_px(
+            span_points[0].size,
+            span_points[0].size_mode,
+            camera,
+            bounds,
+        ) as f64;
+        let radius = radius_px as f64 + marker_px * 0.5;
+        let Some((local_idx, d2)) =
+            span.point_index(points)
+                .nearest(target.to_array(), scale, radius * radius, |i| {
+                    marker_center_world(&span_points[i]).to_array()
+                })
+        else {
+            continue;
+        };
+        // Earlier series win ties, as they come first in the point buffer.
+        if best.is_some_and(|(_, _, best_d2)| best_d2 <= d2) {
+            continue;
         }
-        if span_idx >= series.len() {
-            break;
+        if let Some(&point_index) = span.point_indices.get(local_idx) {
+            let point = PointId {
+                series_id: span.id,
+                point_index,
+            };
+            best = Some((point, span_points[local_idx].position, d2));
         }
-        if !series[span_idx].pickable {
+    }
+    best.map(|(point, position, _)| (point, position))
+}
+
+/// Find the line segment nearest the cursor, within the hover radius plus half the line
+/// width.
+///
+/// The hit reports the point whose value the line shows there: the nearer end of a straight
+/// or curved segment, or the point holding the value of a step.
+pub(crate) fn cpu_pick_segment(
+    targets: &PickTargets,
+    cursor: Vec2,
+    hover_radius_px: f32,
+) -> Option<PickHit> {
+    let PickTargets {
+        points,
+        camera,
+        bounds,
+        ..
+    } = *targets;
+    let world_per_px = camera.world_per_px(bounds);
+    let cursor_world = targets.to_world(cursor);
+    let cursor = DVec2::new(cursor.x as f64, cursor.y as f64);
+
+    let mut best: Option<(PickHit, f64)> = None;
+    for span in targets
+        .series
+        .iter()
+        .filter(|span| span.pickable && span.len >= 2)
+    {
+        let Some(line_style) = span.line_style else {
             continue;
-        }
+        };
+        let radius = hover_radius_px as f64 + line_style.width.to_px(camera, bounds) as f64 * 0.5;
+        let reach = [radius * world_per_px[0], radius * world_per_px[1]];
+        let span_points = &points[span.start..span.start + span.len];
+
+        // Each piece comes with its owners: the local indices of the points whose values
+        // its ends carry, and of the point starting its data segment.
+        let mut consider = |[start, end]: [[f64; 2]; 2], (start_owner, end_owner, segment)| {
+            let (a, b) = (targets.to_screen(start), targets.to_screen(end));
+            let t = segment_parameter(cursor, a, b);
+            let d2 = cursor.distance_squared(a.lerp(b, t));
+            if d2 > radius * radius || best.is_some_and(|(_, best_d2)| best_d2 <= d2) {
+                return;
+            }
+            let owner = if start_owner == end_owner || t < 0.5 {
+                start_owner
+            } else {
+                end_owner
+            };
+            let (Some(&point_index), Some(&segment)) = (
+                span.point_indices.get(owner),
+                span.point_indices.get(segment),
+            ) else {
+                return;
+            };
+            let position = DVec2::from(start).lerp(DVec2::from(end), t).to_array();
+            let hit = PickHit::Segment {
+                point: PointId {
+                    series_id: span.id,
+                    point_index,
+                },
+                segment,
+                position: targets.to_data(position, span.y_axis),
+            };
+            best = Some((hit, d2));
+        };
 
-        let world = marker_center_world(pt);
-        let ndc_x = (world.x - camera.position.x) / camera.half_extents.x;
-        let ndc_y = (world.y - camera.position.y) / camera.half_extents.y;
-        let screen_x = (ndc_x + 1.0) * 0.5 * width;
-        let screen_y = (1.0 - ndc_y) * 0.5 * height;
-
-        let dx = screen_x - cursor_x;
-        let dy = screen_y - cursor_y;
-        let d2 = dx * dx + dy * dy;
-        let marker_px = Size::size_px(pt.size, pt.size_mode, camera, bounds) as f64;
-        let radius = hover_radius_px as f64 + marker_px * 0.5;
-        if d2 <= radius * radius {
-            if let Some((_, best_d2)) = best {
-                if d2 < best_d2 {
-                    best = Some((idx, d2));
+        // Only pieces reaching the cursor's column can be hit when the line keeps x sorted;
+        // anything else goes through the spatial index over the drawn pieces.
+        let sorted =
+            span.point_index(points).is_sorted_x() && line_style.interpolation.keeps_x_order();
+        let column = |x: f64| {
+            (
+                x >= cursor_world.x - reach[0],
+                x <= cursor_world.x + reach[0],
+            )
+        };
+        if sorted && line_style.interpolation == LineInterpolation::Linear {
+            let first = span_points
+                .partition_point(|p| !column(p.position[0]).0)
+                .saturating_sub(1);
+            let end = span_points.partition_point(|p| column(p.position[0]).1);
+            for i in first..end.min(span_points.len() - 1) {
+                if !span.breaks_line_before(i + 1) {
+                    let ends = [span_points[i].position, span_points[i + 1].position];
+                    consider(ends, (i, i + 1, i));
                 }
-            } else {
-                best = Some((idx, d2));
             }
+        } else if sorted {
+            for path in span.line_paths(points) {
+                let first = path
+                    .partition_point(|&(p, _)| !column(p[0]).0)
+                    .saturating_sub(1);
+                let end = path.partition_point(|&(p, _)| column(p[0]).1);
+                for (ends, owners) in span.path_pieces(points, path, first..end.min(path.len() - 1))
+                {
+                    consider(ends, owners);
+                }
+            }
+        } else {
+            span.segment_index(points)
+                .for_each_near(cursor_world.to_array(), reach, consider);
         }
     }
+    best.map(|(hit, _)| hit)
+}
+
+/// The parameter along `a`–`b` of the point nearest `p`.
+fn segment_parameter(p: DVec2, a: DVec2, b: DVec2) -> f64 {
+    let ab = b - a;
+    if ab.length_squared() > 0.0 {
+        ((p - a).dot(ab) / ab.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+/// Find the reference line nearest the cursor, within the hover radius plus half the line
+/// width. Horizontal lines are drawn over vertical ones, so they win ties.
+fn cpu_pick_reference_line(
+    targets: &PickTargets,
+    cursor: Vec2,
+    hover_radius_px: f32,
+) -> Option<PickHit> {
+    let PickTargets { camera, bounds, .. } = *targets;
+    let cursor_world = targets.to_world(cursor);
+    let cursor = DVec2::new(cursor.x as f64, cursor.y as f64);
+    let within = |distance: f64, width: Size| {
+        let radius = hover_radius_px as f64 + width.to_px(camera, bounds) as f64 * 0.5;
+        (distance <= radius).then_some(distance)
+    };
 
-    let (best_idx, _) = best?;
-    let mut span_idx = 0usize;
-    let mut span_start = 0usize;
-    while span_idx < series.len() && best_idx >= span_start + series[span_idx].len {
-        span_start += series[span_idx].len;
-        span_idx += 1;
+    let vlines = targets
+        .vlines
+        .iter()
+        .filter(|line| line.pickable)
+        .filter_map(|line| {
+            let x = data_value_to_plot_with_axis_range(
+                line.x,
+                targets.x_axis_scale,
+                line.transform.as_ref(),
+                Some(camera.x_range()),
+            )?;
+            let distance = within(
+                (targets.to_screen([x, 0.0]).x - cursor.x).abs(),
+                line.line_style.width,
+            )?;
+            Some((line.id, [x, cursor_world.y], distance))
+        });
+    let hlines = targets
+        .hlines
+        .iter()
+        .filter(|line| line.pickable)
+        .filter_map(|line| {
+            let y = data_value_to_plot_with_axis_range(
+                line.y,
+                targets.y_axis_scale,
+                line.transform.as_ref(),
+                Some(camera.y_range()),
+            )?;
+            let distance = within(
+                (targets.to_screen([0.0, y]).y - cursor.y).abs(),
+                line.line_style.width,
+            )?;
+            Some((line.id, [cursor_world.x, y], distance))
+        });
+
+    let mut best: Option<(ShapeId, [f64; 2], f64)> = None;
+    for (line_id, position, distance) in vlines.chain(hlines) {
+        if best.is_none_or(|(_, _, best_distance)| distance <= best_distance) {
+            best = Some((line_id, position, distance));
+        }
     }
-    let span = series.get(span_idx)?;
-    let local_idx = best_idx - span_start;
-    let point_index = *span.point_indices.get(local_idx)?;
-    Some(PointId {
-        series_id: span.id,
-        point_index,
+    // Reference lines are placed on the primary y-axis.
+    best.map(|(line_id, position, _)| PickHit::ReferenceLine {
+        line_id,
+        position: targets.to_data(position, YAxis::Primary),
+    })
+}
+
+/// Find the topmost pickable bar under the cursor.
+fn cpu_pick_bar(targets: &PickTargets, cursor: Vec2) -> Option<PointId> {
+    let world = targets.to_world(cursor);
+
+    // Later bars are drawn on top, so search back to front.
+    targets
+        .bars
+        .iter()
+        .rev()
+        .filter(|span| span.pickable)
+        .find_map(|span| {
+            span.rects
+                .iter()
+                .rev()
+                .find(|rect| rect.contains([world.x, world.y]))
+                .map(|rect| PointId {
+                    series_id: span.id,
+                    point_index: rect.point_index,
+                })
+        })
+}
+
+/// Find the topmost pickable fill under the cursor.
+fn cpu_pick_fill(targets: &PickTargets, cursor: Vec2) -> Option<PickHit> {
+    let world = targets.to_world(cursor);
+    let (fill_id, y_axis) = targets.fills.iter().rev().find_map(|span| {
+        let fill_id = span.fill_id?;
+        span.vertices
+            .chunks_exact(3)
+            .any(|triangle| triangle_contains(triangle, world))
+            .then_some((fill_id, span.y_axis))
+    })?;
+    Some(PickHit::Fill {
+        fill_id,
+        position: targets.to_data(world.to_array(), y_axis),
     })
 }
 
+fn triangle_contains(triangle: &[[f64; 2]], p: DVec2) -> bool {
+    let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(DVec2::from);
+    let side = |u: DVec2, v: DVec2| (v - u).perp_dot(p - u);
+    let (ab, bc, ca) = (side(a, b), side(b, c), side(c, a));
+    (ab >= 0.0 && bc >= 0.0 && ca >= 0.0) || (ab <= 0.0 && bc <= 0.0 && ca <= 0.0)
+}
+
+/// Pick the raster cell under the cursor. Later rasters are drawn on top.
+fn cpu_pick_raster(targets: &PickTargets, cursor: Vec2) -> Option<PointId> {
+    let world = targets.to_world(cursor);
+
+    targets
+        .rasters
+        .iter()
+        .rev()
+        .filter(|span| span.pickable)
+        .find_map(|span| {
+            span.cell_index_at([world.x, world.y])
+                .map(|point_index| PointId {
+                    series_id: span.id,
+                    point_index,
+                })
+        })
+}
+
+/// Find the points of pickable series inside a screen-space polygon, like a selection
+/// rectangle or lasso outline.
+pub(crate) fn cpu_select(targets: &PickTargets, polygon: &[Vec2]) -> Vec<PointId> {
+    let polygon: Vec<DVec2> = polygon.iter().map(|&p| targets.to_world(p)).collect();
+    let Some(&first) = polygon.first() else {
+        return Vec::new();
+    };
+    let (min, max) = polygon
+        .iter()
+        .fold((first, first), |(min, max), &p| (min.min(p), max.max(p)));
+
+    let mut selected = Vec::new();
+    for span in targets.series.iter().filter(|span| span.pickable) {
+        let span_points = &targets.points[span.start..span.start + span.len];
+        let first = selected.len();
+        // Only the points in the outline's bounding box are tested against it.
+        span.for_each_point_within(
+            targets.points,
+            min.to_array(),
+            max.to_array(),
+            |local_idx| {
+                if polygon_contains(&polygon, marker_center_world(&span_points[local_idx]))
+                    && let Some(&point_index) = span.point_indices.get(local_idx)
+                {
+                    selected.push(PointId {
+                        series_id: span.id,
+                        point_index,
+                    });
+                }
+            },
+        );
+        // The index visits scattered points out of order.
+        selected[first..].sort_unstable_by_key(|point| point.point_index);
+    }
+    selected
+}
+
+/// Even-odd test of whether `p` is inside the closed polygon.
+fn polygon_contains(polygon: &[DVec2], p: DVec2) -> bool {
+    let mut inside = false;
+    let mut prev = polygon[polygon.len() - 1];
+    for &curr in polygon {
+        if (curr.y > p.y) != (prev.y > p.y)
+            && p.x < prev.x + (p.y - prev.y) * (curr.x - prev.x) / (curr.y - prev.y)
+        {
+            inside = !inside;
+        }
+        prev = curr;
+    }
+    inside
+}
+
 // ---- GPU picking ----
 
 #[derive(Debug, Clone, Copy)]
@@ -528,6 +915,13 @@ impl PickingPass {
         self.id_map = map;
     }
 
+    /// Update the id map entry of a single marker instance.
+    pub(crate) fn set_id(&mut self, instance: usize, entry: (u32, u32)) {
+        if let Some(slot) = self.id_map.get_mut(instance) {
+            *slot = entry;
+        }
+    }
+
     fn poll_pending(&mut self, device: &Device, points: &[Point], series: &[SeriesSpan]) {
         let Some(pending) = self.pending.as_ref() else {
             return;
@@ -757,15 +1151,17 @@ impl PickingPass {
             return None;
         }
 
-        let (span_idx_u32, local_idx_u32) = self.id_map[idx];
+        // Entries hold the stream position of the point, which stays valid as appended
+        // points shift the span.
+        let (span_idx_u32, position_u32) = self.id_map[idx];
         let span_idx = span_idx_u32 as usize;
-        let local_idx = local_idx_u32 as usize;
 
         if span_idx >= series.len() {
             return None;
         }
 
         let span: &SeriesSpan = &series[span_idx];
+        let local_idx = position_u32.wrapping_sub(span.ring_head as u32) as usize;
         if !span.pickable {
             return None;
         }
@@ -789,56 +1185,78 @@ mod tests {
     use glam::{DVec2, Vec2};
     use iced::Rectangle;
 
-    use super::cpu_pick_hit;
+    use super::{PickTargets, cpu_pick_below_markers, cpu_pick_hit, cpu_pick_segment, cpu_select};
     use crate::{
-        Color, LineStyle, Point, PointId, ShapeId, camera::Camera, plot_state::SeriesSpan,
+        AxisScale, Color, LineInterpolation, LineStyle, PickHit, Point, PointId, ShapeId, VLine,
+        YAxis,
+        camera::Camera,
+        plot_state::{DataBounds, FillSpan, SeriesSpan},
+        secondary_axis::SecondaryAxisMap,
     };
 
+    const CAMERA: Camera = Camera {
+        position: DVec2::ZERO,
+        half_extents: DVec2::ONE,
+        render_offset: DVec2::ZERO,
+    };
+
+    const BOUNDS: Rectangle = Rectangle {
+        x: 0.0,
+        y: 0.0,
+        width: 100.0,
+        height: 100.0,
+    };
+
+    /// Targets over a 100 × 100 px plot showing -1..1 on both axes.
+    fn targets<'a>(points: &'a [Point], series: &'a [SeriesSpan]) -> PickTargets<'a> {
+        PickTargets {
+            points,
+            series,
+            bars: &[],
+            fills: &[],
+            rasters: &[],
+            vlines: &[],
+            hlines: &[],
+            camera: &CAMERA,
+            bounds: &BOUNDS,
+            x_axis_scale: AxisScale::Linear,
+            y_axis_scale: AxisScale::Linear,
+            y2_axis_scale: AxisScale::Linear,
+            y2_map: SecondaryAxisMap::IDENTITY,
+        }
+    }
+
+    fn span(id: u64, start: usize, len: usize, marker: u32, pickable: bool) -> SeriesSpan {
+        SeriesSpan {
+            id: ShapeId(id),
+            start,
+            len,
+            point_indices: (0..len).collect(),
+            line_breaks: Arc::from([]),
+            line_style: Some(LineStyle::solid()),
+            color: Color::BLACK,
+            marker,
+            pickable,
+            y_axis: YAxis::Primary,
+            bounds: DataBounds::default(),
+            max_len: None,
+            ring_head: 0,
+            block_bounds: Default::default(),
+            source_dropped: 0,
+            source_len: len,
+            index: Arc::default(),
+            curve_view: None,
+            paths: Arc::default(),
+            segments: Arc::default(),
+        }
+    }
+
     #[test]
     fn cpu_pick_skips_unpickable_series() {
         let points = [Point::new(0.0, 0.0, 6.0), Point::new(0.1, 0.0, 6.0)];
-        let series = [
-            SeriesSpan {
-                id: ShapeId(1),
-                start: 0,
-                len: 1,
-                point_indices: Arc::from([0usize]),
-                line_style: Some(LineStyle::solid()),
-                color: Color::BLACK,
-                marker: 0,
-                pickable: false,
-            },
-            SeriesSpan {
-                id: ShapeId(2),
-                start: 1,
-                len: 1,
-                point_indices: Arc::from([0usize]),
-                line_style: Some(LineStyle::solid()),
-                color: Color::BLACK,
-                marker: 0,
-                pickable: true,
-            },
-        ];
-        let camera = Camera {
-            position: DVec2::ZERO,
-            half_extents: DVec2::ONE,
-            render_offset: DVec2::ZERO,
-        };
-        let bounds = Rectangle {
-            x: 0.0,
-            y: 0.0,
-            width: 100.0,
-            height: 100.0,
-        };
+        let series = [span(1, 0, 1, 0, false), span(2, 1, 1, 0, true)];
 
-        let hit = cpu_pick_hit(
-            &points,
-            &series,
-            &camera,
-            &bounds,
-            Vec2::new(50.0, 50.0),
-            8.0,
-        );
+        let hit = cpu_pick_hit(&targets(&points, &series), Vec2::new(50.0, 50.0), 8.0);
 
         assert_eq!(
             hit,
@@ -848,4 +1266,233 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn line_pick_reports_the_segment_and_the_position_on_it() {
+        // A line without markers from (-0.5, -0.5) to (0.5, 0.5).
+        let points = [Point::new(-0.5, -0.5, 1.0), Point::new(0.5, 0.5, 1.0)];
+        let series = [span(1, 0, 2, u32::MAX, true)];
+        let targets = targets(&points, &series);
+
+        // Nothing to hit on markers, so the line itself is picked, nearer its first point.
+        assert_eq!(cpu_pick_hit(&targets, Vec2::new(40.0, 60.0), 4.0), None);
+        let Some(PickHit::Segment {
+            point,
+            segment,
+            position,
+        }) = cpu_pick_segment(&targets, Vec2::new(41.0, 59.0), 4.0)
+        else {
+            panic!("expected a segment hit");
+        };
+        assert_eq!(point.point_index, 0);
+        assert_eq!(segment, 0);
+        assert!((position[0] - -0.18).abs() < 1e-9 && (position[1] - -0.18).abs() < 1e-9);
+
+        assert_eq!(
+            cpu_pick_segment(&targets, Vec2::new(62.0, 38.0), 4.0).and_then(|h| h.point_id()),
+            Some(PointId {
+                series_id: ShapeId(1),
+                point_index: 1,
+            })
+        );
+        assert_eq!(cpu_pick_segment(&targets, Vec2::new(60.0, 60.0), 4.0), None);
+    }
+
+    #[test]
+    fn step_pick_reports_the_point_holding_the_drawn_value() {
+        // A step-post line from (-0.8, -0.5) holds -0.5 until x = 0.8, then rises to 0.5.
+        let points = [Point::new(-0.8, -0.5, 1.0), Point::new(0.8, 0.5, 1.0)];
+        let mut series = [span(1, 0, 2, u32::MAX, true)];
+        series[0].line_style =
+            Some(LineStyle::solid().with_interpolation(LineInterpolation::StepPost));
+        let targets = targets(&points, &series);
+        let pick =
+            |x, y| cpu_pick_segment(&targets, Vec2::new(x, y), 4.0).and_then(|hit| hit.point_id());
+
+        // Near the end of the low step, closer to the second point than the first.
+        assert_eq!(
+            pick(85.0, 76.0),
+            Some(PointId {
+                series_id: ShapeId(1),
+                point_index: 0,
+            })
+        );
+        // On the riser at x = 0.8.
+        assert_eq!(
+            pick(91.0, 50.0),
+            Some(PointId {
+                series_id: ShapeId(1),
+                point_index: 1,
+            })
+        );
+        assert_eq!(pick(50.0, 50.0), None);
+    }
+
+    #[test]
+    fn step_picks_find_pieces_of_long_and_unsorted_lines() {
+        let step_post = |points: &[Point]| {
+            let mut series = [span(1, 0, points.len(), u32::MAX, true)];
+            series[0].line_style =
+                Some(LineStyle::solid().with_interpolation(LineInterpolation::StepPost));
+            series
+        };
+        let pick = |points: &[Point], series: &[SeriesSpan], x, y| match cpu_pick_segment(
+            &targets(points, series),
+            Vec2::new(x, y),
+            4.0,
+        ) {
+            Some(PickHit::Segment { point, segment, .. }) => Some((point.point_index, segment)),
+            _ => None,
+        };
+
+        // Sorted x: a step from 0 up to 0.5 at x = 0, found by searching the drawn path.
+        let points: Vec<Point> = (0..=1000)
+            .map(|i| Point::new(i as f64 * 0.002 - 1.0, if i < 500 { 0.0 } else { 0.5 }, 1.0))
+            .collect();
+        let series = step_post(&points);
+        assert_eq!(pick(&points, &series, 50.0, 30.0), Some((500, 499)));
+        assert_eq!(pick(&points, &series, 50.0, 40.0), Some((499, 499)));
+        assert_eq!(pick(&points, &series, 70.0, 40.0), None);
+
+        // Unsorted x: the line doubles back along y = 0.5, then drops at x = -0.8.
+        let points = [
+            Point::new(-0.8, -0.5, 1.0),
+            Point::new(0.8, 0.5, 1.0),
+            Point::new(-0.8, 0.0, 1.0),
+        ];
+        let series = step_post(&points);
+        assert_eq!(pick(&points, &series, 50.0, 26.0), Some((1, 1)));
+        assert_eq!(pick(&points, &series, 11.0, 40.0), Some((2, 1)));
+        assert_eq!(pick(&points, &series, 50.0, 40.0), None);
+    }
+
+    #[test]
+    fn select_finds_pickable_points_inside_a_concave_outline() {
+        let points = [
+            Point::new(-0.4, 0.4, 4.0),
+            Point::new(0.0, 0.4, 4.0),
+            Point::new(0.0, -0.6, 4.0),
+            Point::new(0.4, 0.4, 4.0),
+        ];
+        let series = [span(1, 0, 3, 0, true), span(2, 3, 1, 0, false)];
+        // A U shape, open between x = 40 and 60 px down to y = 70 px.
+        let outline = [
+            [20.0, 10.0],
+            [40.0, 10.0],
+            [40.0, 70.0],
+            [60.0, 70.0],
+            [60.0, 10.0],
+            [80.0, 10.0],
+            [80.0, 90.0],
+            [20.0, 90.0],
+        ]
+        .map(Vec2::from);
+
+        let selected = cpu_select(&targets(&points, &series), &outline);
+
+        // The point in the notch is outside, and the one in the right arm is not pickable.
+        let point = |point_index| PointId {
+            series_id: ShapeId(1),
+            point_index,
+        };
+        assert_eq!(selected, vec![point(0), point(2)]);
+        assert!(cpu_select(&targets(&points, &series), &[]).is_empty());
+    }
+
+    #[test]
+    fn select_queries_the_index_of_unsorted_series_in_point_order() {
+        let points: Vec<Point> = (0..500u32)
+            .map(|i| {
+                let x = (i.wrapping_mul(7919) % 1000) as f64 / 500.0 - 1.0;
+                let y = (i.wrapping_mul(104_729) % 1000) as f64 / 500.0 - 1.0;
+                Point::new(x, y, 4.0)
+            })
+            .collect();
+        let series = [span(1, 0, points.len(), 0, true)];
+        // The plot area from x = -0.5 to 0.5 and y = -0.5 to 0.5.
+        let outline = [[25.0, 25.0], [75.0, 25.0], [75.0, 75.0], [25.0, 75.0]].map(Vec2::from);
+
+        let selected: Vec<usize> = cpu_select(&targets(&points, &series), &outline)
+            .iter()
+            .map(|point| point.point_index)
+            .collect();
+
+        let inside: Vec<usize> = (0..points.len())
+            .filter(|&i| {
+                let [x, y] = points[i].position;
+                x.abs() < 0.5 && y.abs() < 0.5
+            })
+            .collect();
+        assert!(inside.len() > 50);
+        assert_eq!(selected, inside);
+    }
+
+    #[test]
+    fn reference_lines_are_picked_over_fills() {
+        let fills = [FillSpan {
+            fill_id: Some(ShapeId(3)),
+            source: Some(ShapeId(3)),
+            color: Color::BLACK,
+            vertices: Arc::from([[-1.0, -1.0], [1.0, -1.0], [-1.0, 1.0]]),
+            y_axis: YAxis::Primary,
+        }];
+        let vline = VLine::new(-0.5);
+        let vlines = [vline.clone()];
+        let with_lines = PickTargets {
+            fills: &fills,
+            vlines: &vlines,
+            ..targets(&[], &[])
+        };
+        let pick = |x, y| cpu_pick_below_markers(&with_lines, Vec2::new(x, y), 4.0, |_| true);
+
+        assert_eq!(
+            pick(26.0, 75.0),
+            Some(PickHit::ReferenceLine {
+                line_id: vline.id,
+                position: [-0.5, -0.5],
+            })
+        );
+        assert_eq!(
+            pick(12.5, 75.0),
+            Some(PickHit::Fill {
+                fill_id: ShapeId(3),
+                position: [-0.75, -0.5],
+            })
+        );
+        // Outside the fill's triangle.
+        assert_eq!(pick(80.0, 20.0), None);
+        // Fills bounded by a secondary-axis series report secondary y values.
+        let fills = [FillSpan {
+            y_axis: YAxis::Secondary,
+            ..fills[0].clone()
+        }];
+        let secondary = PickTargets {
+            fills: &fills,
+            y2_map: SecondaryAxisMap {
+                scale: 10.0,
+                offset: 5.0,
+            },
+            ..targets(&[], &[])
+        };
+        assert_eq!(
+            cpu_pick_below_markers(&secondary, Vec2::new(12.5, 75.0), 4.0, |_| true),
+            Some(PickHit::Fill {
+                fill_id: ShapeId(3),
+                position: [-0.75, 0.0],
+            })
+        );
+        // Fills without an id, like contour bands, are never picked.
+        let fills = [FillSpan {
+            fill_id: None,
+            ..fills[0].clone()
+        }];
+        let unpickable = PickTargets {
+            fills: &fills,
+            ..targets(&[], &[])
+        };
+        assert_eq!(
+            cpu_pick_below_markers(&unpickable, Vec2::new(12.5, 75.0), 4.0, |_| true),
+            None
+        );
+    }
 }
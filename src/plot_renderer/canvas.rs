@@ -1,15 +1,19 @@
 use super::{
-    CROSSHAIR_RGBA, SELECTION_FILL_RGBA, highlight_marker_plot_position, highlight_mask_color,
-    highlight_mask_plot_position,
+    CROSSHAIR_RGBA, LASSO_WIDTH_PX, SELECTION_FILL_RGBA, SELECTION_OUTLINE_RGBA, SegmentDashes,
+    blend_colors, cap_outline, highlight_marker_plot_position, highlight_mask_color,
+    highlight_mask_plot_position, join_outline, marker_type_from_u32, ruler_segments, stroke_is_on,
+    world_to_canvas_point,
 };
 use crate::{
     LineType, Size,
     camera::Camera,
     grid::TickWeight,
-    plot_state::PlotState,
+    legend::{SwatchPart, SwatchShape},
+    plot_state::{PlotState, RasterSpan, SelectionKind},
     plot_widget::{world_to_screen_position_x, world_to_screen_position_y},
     point::{MARKER_SIZE_WORLD, MarkerType},
-    transform::{data_point_to_plot_with_transform, data_value_to_plot_with_axis_range},
+    raster::RasterOrigin,
+    transform::data_value_to_plot_with_axis_range,
 };
 use iced::{
     Color, Rectangle,
@@ -26,19 +30,12 @@ fn selection_fill_color() -> Color {
     rgba_to_color(SELECTION_FILL_RGBA)
 }
 
-fn crosshair_color() -> Color {
-    rgba_to_color(CROSSHAIR_RGBA)
+fn selection_outline_color() -> Color {
+    rgba_to_color(SELECTION_OUTLINE_RGBA)
 }
 
-fn marker_type_from_u32(marker: u32) -> MarkerType {
-    match marker {
-        0 => MarkerType::FilledCircle,
-        1 => MarkerType::EmptyCircle,
-        2 => MarkerType::Square,
-        3 => MarkerType::Star,
-        4 => MarkerType::Triangle,
-        _ => MarkerType::FilledCircle,
-    }
+fn crosshair_color() -> Color {
+    rgba_to_color(CROSSHAIR_RGBA)
 }
 
 #[derive(Default)]
@@ -58,11 +55,7 @@ pub(crate) fn draw(
     let static_layer = caches
         .static_layer
         .draw_with_bounds(renderer, frame_bounds, |frame| {
-            draw_grid(frame, state, bounds);
-            draw_fills(frame, state, bounds);
-            draw_lines(frame, state, bounds);
-            draw_reference_lines(frame, state, bounds);
-            draw_markers(frame, state, bounds);
+            draw_static(frame, state, bounds);
         });
 
     let overlay_layer = caches
@@ -71,11 +64,26 @@ pub(crate) fn draw(
             draw_highlights(frame, state, bounds);
             draw_selection(frame, state);
             draw_crosshairs(frame, state);
+            draw_ruler(frame, state);
         });
 
     vec![static_layer, overlay_layer]
 }
 
+/// Draw the grid and all plot data, without hover/pick highlights or other overlays.
+///
+/// Also used by image export, which renders the same layer into an offscreen frame.
+pub(crate) fn draw_static(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
+    draw_grid(frame, state, bounds);
+    draw_rasters(frame, state, bounds);
+    draw_fills(frame, state, bounds);
+    draw_bars(frame, state, bounds);
+    draw_lines(frame, state, bounds);
+    draw_error_bars(frame, state, bounds);
+    draw_reference_lines(frame, state, bounds);
+    draw_markers(frame, state, bounds);
+}
+
 fn draw_grid(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
     for tick in &state.x_ticks {
         let color = match tick.tick.line_type {
@@ -114,6 +122,81 @@ fn draw_grid(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
     }
 }
 
+fn draw_rasters(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
+    for span in state.rasters.iter() {
+        draw_raster(frame, span, &state.camera, bounds);
+    }
+}
+
+/// Draw the visible cells of a raster as flat rectangles.
+///
+/// When cells are smaller than a pixel, only every n-th cell is drawn, sized to cover the
+/// skipped ones. Runs of equally colored cells in a row are merged into one rectangle.
+fn draw_raster(frame: &mut Frame, span: &RasterSpan, camera: &Camera, bounds: Rectangle) {
+    let grid = &span.grid;
+    let cell_size = [
+        (grid.max[0] - grid.min[0]) / grid.columns as f64,
+        (grid.max[1] - grid.min[1]) / grid.rows as f64,
+    ];
+    let (x_range, y_range) = camera.axis_ranges();
+    let visible = |range: [f64; 2], axis: usize, len: usize| {
+        let start = ((range[0] - grid.min[axis]) / cell_size[axis]).floor();
+        let end = ((range[1] - grid.min[axis]) / cell_size[axis]).ceil();
+        (start.clamp(0.0, len as f64) as usize)..(end.clamp(0.0, len as f64) as usize)
+    };
+    let columns = visible(x_range, 0, grid.columns);
+    let rows_from_bottom = visible(y_range, 1, grid.rows);
+    if columns.is_empty() || rows_from_bottom.is_empty() {
+        return;
+    }
+    let step = |cell_size: f64, extent: f64, pixels: f32| {
+        let cell_px = cell_size / extent * pixels as f64;
+        (1.0 / cell_px).ceil().max(1.0) as usize
+    };
+    let step_x = step(cell_size[0], x_range[1] - x_range[0], bounds.width);
+    let step_y = step(cell_size[1], y_range[1] - y_range[0], bounds.height);
+
+    // Cell edges are snapped to whole pixels so neighboring rectangles don't leave seams.
+    let to_canvas = |x: f64, y: f64| {
+        let point = world_to_canvas_point([x, y], camera, &bounds);
+        iced::Point::new(point.x.round(), point.y.round())
+    };
+    let color_at = |from_bottom: usize, column: usize| {
+        let row = match grid.origin {
+            RasterOrigin::Lower => from_bottom,
+            RasterOrigin::Upper => grid.rows - 1 - from_bottom,
+        };
+        let index = (row * grid.columns + column) * 4;
+        let pixel = &span.pixels[index..index + 4];
+        Color::from_rgba8(pixel[0], pixel[1], pixel[2], pixel[3] as f32 / 255.0)
+    };
+
+    for from_bottom in rows_from_bottom.clone().step_by(step_y) {
+        let y0 = grid.min[1] + from_bottom as f64 * cell_size[1];
+        let y1 =
+            grid.min[1] + (from_bottom + step_y).min(rows_from_bottom.end) as f64 * cell_size[1];
+        let mut run: Option<(usize, Color)> = None;
+        for column in columns.clone().step_by(step_x).chain([columns.end]) {
+            let color = (column < columns.end).then(|| color_at(from_bottom, column));
+            if let Some((start, run_color)) = run {
+                if color == Some(run_color) {
+                    continue;
+                }
+                if run_color.a > 0.0 {
+                    let x0 = grid.min[0] + start as f64 * cell_size[0];
+                    let x1 = grid.min[0] + column as f64 * cell_size[0];
+                    let a = to_canvas(x0, y0);
+                    let b = to_canvas(x1, y1);
+                    let top_left = iced::Point::new(a.x.min(b.x), a.y.min(b.y));
+                    let size = iced::Size::new((a.x - b.x).abs(), (a.y - b.y).abs());
+                    frame.fill_rectangle(top_left, size, run_color);
+                }
+            }
+            run = color.map(|color| (column, color));
+        }
+    }
+}
+
 fn draw_fills(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
     for fill in state.fills.iter() {
         for triangle in fill.vertices.chunks_exact(3) {
@@ -133,6 +216,16 @@ fn draw_fills(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
     }
 }
 
+fn draw_bars(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
+    for rect in state.bars.iter().flat_map(|span| span.rects.iter()) {
+        let a = world_to_canvas_point(rect.min, &state.camera, &bounds);
+        let b = world_to_canvas_point(rect.max, &state.camera, &bounds);
+        let top_left = iced::Point::new(a.x.min(b.x), a.y.min(b.y));
+        let size = iced::Size::new((a.x - b.x).abs(), (a.y - b.y).abs());
+        frame.fill_rectangle(top_left, size, rect.color);
+    }
+}
+
 fn draw_lines(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
     for series in state.series.iter() {
         let Some(line_style) = series.line_style else {
@@ -143,52 +236,94 @@ fn draw_lines(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
         }
 
         let width = line_style.width.to_px(&state.camera, &bounds).max(0.5);
-        let points = &state.points[series.start..series.start + series.len];
-        let mut distance_along_strip = 0.0;
-
-        for index in 1..points.len() {
-            let break_segment = series
-                .point_indices
-                .get(index)
-                .zip(series.point_indices.get(index - 1))
-                .is_some_and(|(curr, prev)| *curr != *prev + 1);
-
-            if break_segment {
-                distance_along_strip = 0.0;
-                continue;
+
+        for path in series.line_paths(&state.points) {
+            // Segments are stroked with butt ends, then the joins and caps are filled in
+            // the way the shader draws them: each join in the color of the segment ending
+            // there, where the dash pattern is on.
+            let mut segments = Vec::with_capacity(path.len());
+            let mut dashes = SegmentDashes::new(line_style.line_type);
+            for segment in path.windows(2) {
+                let ((position0, index0), (position1, index1)) = (segment[0], segment[1]);
+                let p0 = world_to_canvas_point(position0, &state.camera, &bounds);
+                let p1 = world_to_canvas_point(position1, &state.camera, &bounds);
+                let delta = iced::Vector::new(p1.x - p0.x, p1.y - p0.y);
+                let segment_length = (delta.x * delta.x + delta.y * delta.y).sqrt();
+                if segment_length <= f32::EPSILON {
+                    continue;
+                }
+
+                let c0 = *state.point_colors.get(index0).unwrap_or(&series.color);
+                let c1 = *state.point_colors.get(index1).unwrap_or(&series.color);
+                let color = blend_colors(c0, c1);
+                let (line_type, distance) = dashes.segment(position0, position1, segment_length);
+                draw_styled_line_segment(frame, p0, p1, line_type, width, color, distance);
+                segments.push((
+                    p0,
+                    p1,
+                    delta * (1.0 / segment_length),
+                    color,
+                    line_type,
+                    distance + segment_length,
+                ));
             }
 
-            let p0 = world_to_canvas_point(points[index - 1].position, &state.camera, &bounds);
-            let p1 = world_to_canvas_point(points[index].position, &state.camera, &bounds);
-            let delta = iced::Vector::new(p1.x - p0.x, p1.y - p0.y);
-            let segment_length = (delta.x * delta.x + delta.y * delta.y).sqrt();
-            if segment_length <= f32::EPSILON {
-                continue;
+            let half_width = width * 0.5;
+            for pair in segments.windows(2) {
+                let ((_, joint, d0, color, line_type, distance), (_, _, d1, _, _, _)) =
+                    (pair[0], pair[1]);
+                if stroke_is_on(line_type, distance) {
+                    fill_outline(
+                        frame,
+                        &join_outline(joint, d0, d1, half_width, line_style.join),
+                        color,
+                    );
+                }
+            }
+            if let Some(&(start, _, d, color, line_type, _)) = segments.first()
+                && stroke_is_on(line_type, 0.0)
+            {
+                fill_outline(
+                    frame,
+                    &cap_outline(start, d * -1.0, half_width, line_style.cap),
+                    color,
+                );
+            }
+            if let Some(&(_, end, d, color, line_type, distance)) = segments.last()
+                && stroke_is_on(line_type, distance)
+            {
+                fill_outline(
+                    frame,
+                    &cap_outline(end, d, half_width, line_style.cap),
+                    color,
+                );
             }
+        }
+    }
+}
 
-            let c0 = *state
-                .point_colors
-                .get(series.start + index - 1)
-                .unwrap_or(&series.color);
-            let c1 = *state
-                .point_colors
-                .get(series.start + index)
-                .unwrap_or(&series.color);
-            draw_styled_line_segment(
-                frame,
-                p0,
-                p1,
-                line_style.line_type,
-                width,
-                blend_colors(c0, c1),
-                distance_along_strip,
-            );
-            distance_along_strip += segment_length;
+fn draw_error_bars(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
+    let world_per_px = state.camera.world_per_px(&bounds);
+    for span in state.error_bars.iter() {
+        let width = span.style.line_width.max(0.5);
+        for bar in span.bars.iter() {
+            for [start, end] in bar.segments(span.style.cap, world_per_px) {
+                draw_styled_line_segment(
+                    frame,
+                    world_to_canvas_point(start, &state.camera, &bounds),
+                    world_to_canvas_point(end, &state.camera, &bounds),
+                    LineType::Solid,
+                    width,
+                    bar.color,
+                    0.0,
+                );
+            }
         }
     }
 }
 
 fn draw_reference_lines(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
+    let world_per_px = state.camera.world_per_px(&bounds);
     for vline in state.vlines.iter() {
         let Some(vx_plot) = data_value_to_plot_with_axis_range(
             vline.x,
@@ -205,7 +340,7 @@ fn draw_reference_lines(frame: &mut Frame, state: &PlotState, bounds: Rectangle)
             frame,
             iced::Point::new(x, 0.0),
             iced::Point::new(x, bounds.height),
-            vline.line_style.line_type,
+            vline.line_style.line_type.in_pixels(world_per_px[1]),
             vline
                 .line_style
                 .width
@@ -232,7 +367,7 @@ fn draw_reference_lines(frame: &mut Frame, state: &PlotState, bounds: Rectangle)
             frame,
             iced::Point::new(0.0, y),
             iced::Point::new(bounds.width, y),
-            hline.line_style.line_type,
+            hline.line_style.line_type.in_pixels(world_per_px[0]),
             hline
                 .line_style
                 .width
@@ -301,21 +436,17 @@ fn draw_highlights(frame: &mut Frame, state: &PlotState, bounds: Rectangle) {
         if marker_style.marker_type == MarkerType::Square
             && let Size::World(size) = marker_style.size
         {
-            let Some(top_left_plot) = data_point_to_plot_with_transform(
+            let Some(top_left_plot) = state.data_point_to_plot(
                 [highlight.x, highlight.y + size],
-                state.x_axis_scale,
-                state.y_axis_scale,
+                highlight.y_axis,
                 &highlight.transform,
-                Some(state.camera.axis_ranges()),
             ) else {
                 continue;
             };
-            let Some(bottom_right_plot) = data_point_to_plot_with_transform(
+            let Some(bottom_right_plot) = state.data_point_to_plot(
                 [highlight.x + size, highlight.y],
-                state.x_axis_scale,
-                state.y_axis_scale,
+                highlight.y_axis,
                 &highlight.transform,
-                Some(state.camera.axis_ranges()),
             ) else {
                 continue;
             };
@@ -350,6 +481,28 @@ fn draw_selection(frame: &mut Frame, state: &PlotState) {
         return;
     }
 
+    if state.selection.kind == SelectionKind::Lasso {
+        if state.selection.path.len() < 2 {
+            return;
+        }
+        let outline = canvas::Path::new(|builder| {
+            for (i, p) in state.selection.path.iter().enumerate() {
+                let p = iced::Point::new(p.x, p.y);
+                if i == 0 {
+                    builder.move_to(p);
+                } else {
+                    builder.line_to(p);
+                }
+            }
+            builder.close();
+        });
+        let stroke = canvas::Stroke::default()
+            .with_color(selection_outline_color())
+            .with_width(LASSO_WIDTH_PX);
+        frame.stroke(&outline, stroke);
+        return;
+    }
+
     let start = state.selection.start;
     let end = state.selection.end;
     let top_left = iced::Point::new(start.x.min(end.x), start.y.min(end.y));
@@ -359,24 +512,36 @@ fn draw_selection(frame: &mut Frame, state: &PlotState) {
 }
 
 fn draw_crosshairs(frame: &mut Frame, state: &PlotState) {
-    if !state.crosshairs_enabled {
+    if !state.crosshairs_enabled && !state.data_cursor_enabled {
         return;
     }
 
     let pos = state.crosshairs_position;
-    let horizontal = canvas::Path::line(
-        iced::Point::new(0.0, pos.y),
-        iced::Point::new(state.bounds.width, pos.y),
-    );
+    let stroke = canvas::Stroke::default().with_color(crosshair_color());
+    if state.crosshairs_enabled {
+        let horizontal = canvas::Path::line(
+            iced::Point::new(0.0, pos.y),
+            iced::Point::new(state.bounds.width, pos.y),
+        );
+        frame.stroke(&horizontal, stroke);
+    }
     let vertical = canvas::Path::line(
         iced::Point::new(pos.x, 0.0),
         iced::Point::new(pos.x, state.bounds.height),
     );
-    let stroke = canvas::Stroke::default().with_color(crosshair_color());
-    frame.stroke(&horizontal, stroke);
     frame.stroke(&vertical, stroke);
 }
 
+fn draw_ruler(frame: &mut Frame, state: &PlotState) {
+    let Some(segments) = ruler_segments(state) else {
+        return;
+    };
+    let stroke = canvas::Stroke::default().with_color(selection_outline_color());
+    for [start, end] in segments {
+        frame.stroke(&canvas::Path::line(start, end), stroke);
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn draw_marker(
     frame: &mut Frame,
@@ -460,6 +625,46 @@ fn stroke_segment(frame: &mut Frame, p0: iced::Point, p1: iced::Point, width: f3
     );
 }
 
+/// Draw the parts of a legend swatch with its top-left corner at `origin`.
+pub(crate) fn draw_swatch(frame: &mut Frame, origin: iced::Point, parts: &[SwatchPart]) {
+    let offset = |point: iced::Point| iced::Point::new(origin.x + point.x, origin.y + point.y);
+    for part in parts {
+        match &part.shape {
+            SwatchShape::Polygon(points) => {
+                let outline: Vec<_> = points.iter().map(|&point| offset(point)).collect();
+                fill_outline(frame, &outline, part.color);
+            }
+            &SwatchShape::Ring {
+                center,
+                radius,
+                width,
+            } => frame.stroke(
+                &canvas::Path::circle(offset(center), radius),
+                canvas::Stroke::default()
+                    .with_width(width)
+                    .with_color(part.color),
+            ),
+        }
+    }
+}
+
+fn fill_outline(frame: &mut Frame, outline: &[iced::Point], color: Color) {
+    let [first, rest @ ..] = outline else {
+        return;
+    };
+    if rest.len() < 2 {
+        return;
+    }
+    let path = canvas::Path::new(|builder| {
+        builder.move_to(*first);
+        for &point in rest {
+            builder.line_to(point);
+        }
+        builder.close();
+    });
+    frame.fill(&path, color);
+}
+
 fn draw_styled_line_segment(
     frame: &mut Frame,
     p0: iced::Point,
@@ -478,29 +683,22 @@ fn draw_styled_line_segment(
     let direction = iced::Vector::new(delta.x / length, delta.y / length);
 
     match line_type {
-        LineType::Solid => stroke_segment(frame, p0, p1, width, color),
-        LineType::Dashed { length: dash } => {
-            let dash = dash.max(1.0);
-            let gap = (dash * 0.5).max(1.0);
-            let pattern = dash + gap;
-            let mut pattern_start = -(phase_start.rem_euclid(pattern));
-            while pattern_start < length {
-                let seg_start = pattern_start.max(0.0);
-                let seg_end = (pattern_start + dash).min(length);
-                if seg_end > seg_start {
-                    let start = iced::Point::new(
-                        p0.x + direction.x * seg_start,
-                        p0.y + direction.y * seg_start,
-                    );
-                    let end = iced::Point::new(
-                        p0.x + direction.x * seg_end,
-                        p0.y + direction.y * seg_end,
-                    );
-                    stroke_segment(frame, start, end, width, color);
-                }
-                pattern_start += pattern;
+        LineType::Dashed { .. } | LineType::Pattern(_) => {
+            let Some(pattern) = line_type.dash_pattern_px(1.0) else {
+                stroke_segment(frame, p0, p1, width, color);
+                return;
+            };
+            for dash in pattern.dashes(phase_start, length) {
+                let start = iced::Point::new(
+                    p0.x + direction.x * dash.start,
+                    p0.y + direction.y * dash.start,
+                );
+                let end =
+                    iced::Point::new(p0.x + direction.x * dash.end, p0.y + direction.y * dash.end);
+                stroke_segment(frame, start, end, width, color);
             }
         }
+        LineType::Solid => stroke_segment(frame, p0, p1, width, color),
         LineType::Dotted { spacing } => {
             let spacing = spacing.max(1.0);
             let step = spacing * 2.0;
@@ -522,15 +720,6 @@ fn draw_styled_line_segment(
     }
 }
 
-fn blend_colors(a: Color, b: Color) -> Color {
-    Color::from_rgba(
-        (a.r + b.r) * 0.5,
-        (a.g + b.g) * 0.5,
-        (a.b + b.b) * 0.5,
-        (a.a + b.a) * 0.5,
-    )
-}
-
 fn marker_path(center: iced::Point, radius: f32, marker_type: MarkerType) -> canvas::Path {
     match marker_type {
         MarkerType::FilledCircle | MarkerType::EmptyCircle => canvas::Path::circle(center, radius),
@@ -574,12 +763,3 @@ fn marker_path(center: iced::Point, radius: f32, marker_type: MarkerType) -> can
         }),
     }
 }
-
-fn world_to_canvas_point(world: [f64; 2], camera: &Camera, bounds: &Rectangle) -> iced::Point {
-    let ndc_x = (world[0] - camera.position.x) / camera.half_extents.x;
-    let ndc_y = (world[1] - camera.position.y) / camera.half_extents.y;
-    iced::Point::new(
-        (ndc_x as f32 + 1.0) * 0.5 * bounds.width,
-        (1.0 - ndc_y as f32) * 0.5 * bounds.height,
-    )
-}
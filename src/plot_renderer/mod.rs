@@ -4,10 +4,10 @@ mod shader;
 pub(crate) use shader::{PlotRenderer, RenderParams};
 
 use crate::{
-    plot_state::PlotState, plot_widget::HighlightPoint, series::Size,
-    transform::data_point_to_plot_with_transform,
+    LineCap, LineJoin, LineType, camera::Camera, plot_state::PlotState,
+    plot_widget::HighlightPoint, point::MarkerType, series::Size,
 };
-use iced::Color;
+use iced::{Color, Rectangle};
 
 /// Ways in which the widget can be rendered.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -50,7 +50,12 @@ impl PlotRenderStrategy {
 }
 
 const SELECTION_FILL_RGBA: [f32; 4] = [0.2, 0.6, 1.0, 0.2];
+const SELECTION_OUTLINE_RGBA: [f32; 4] = [0.2, 0.6, 1.0, 0.8];
+/// Width of the lasso outline, in logical pixels.
+const LASSO_WIDTH_PX: f32 = 1.5;
 const CROSSHAIR_RGBA: [f32; 4] = [0.5, 0.5, 0.5, 0.5];
+/// Length of the ticks across the ends of the measurement ruler, in logical pixels.
+const RULER_TICK_PX: f32 = 8.0;
 
 fn color_to_rgba(color: Color) -> [f32; 4] {
     [color.r, color.g, color.b, color.a]
@@ -72,12 +77,10 @@ fn highlight_marker_plot_position(
     highlight: &HighlightPoint,
     state: &PlotState,
 ) -> Option<[f64; 2]> {
-    data_point_to_plot_with_transform(
+    state.data_point_to_plot(
         [highlight.x, highlight.y],
-        state.x_axis_scale,
-        state.y_axis_scale,
+        highlight.y_axis,
         &highlight.transform,
-        Some(state.camera.axis_ranges()),
     )
 }
 
@@ -91,11 +94,265 @@ fn highlight_mask_plot_position(highlight: &HighlightPoint, state: &PlotState) -
         world[1] += half;
     }
 
-    data_point_to_plot_with_transform(
-        world,
-        state.x_axis_scale,
-        state.y_axis_scale,
-        &highlight.transform,
-        Some(state.camera.axis_ranges()),
+    state.data_point_to_plot(world, highlight.y_axis, &highlight.transform)
+}
+
+pub(crate) fn marker_type_from_u32(marker: u32) -> MarkerType {
+    match marker {
+        0 => MarkerType::FilledCircle,
+        1 => MarkerType::EmptyCircle,
+        2 => MarkerType::Square,
+        3 => MarkerType::Star,
+        4 => MarkerType::Triangle,
+        _ => MarkerType::FilledCircle,
+    }
+}
+
+pub(crate) fn blend_colors(a: Color, b: Color) -> Color {
+    Color::from_rgba(
+        (a.r + b.r) * 0.5,
+        (a.g + b.g) * 0.5,
+        (a.b + b.b) * 0.5,
+        (a.a + b.a) * 0.5,
     )
 }
+
+/// Segments of the measurement ruler in logical pixels within the plot area: the ruler,
+/// then a tick across each end.
+fn ruler_segments(state: &PlotState) -> Option<[[iced::Point; 2]; 3]> {
+    let measurement = state.ruler.measurement.as_ref()?;
+    let to_screen = |[x, y]: [f64; 2]| {
+        let plot = [
+            state.x_axis_scale.data_to_plot(x)?,
+            state.y_axis_scale.data_to_plot(y)?,
+        ];
+        Some(world_to_canvas_point(plot, &state.camera, &state.bounds))
+    };
+    let (start, end) = (to_screen(measurement.start)?, to_screen(measurement.end)?);
+    let direction = glam::Vec2::new(end.x - start.x, end.y - start.y).normalize_or_zero();
+    let tick = direction.perp() * (RULER_TICK_PX * 0.5);
+    let across = |p: iced::Point| {
+        [
+            iced::Point::new(p.x - tick.x, p.y - tick.y),
+            iced::Point::new(p.x + tick.x, p.y + tick.y),
+        ]
+    };
+    Some([[start, end], across(start), across(end)])
+}
+
+/// Convert a plot-space position to a position inside `bounds`, relative to its top-left corner.
+pub(crate) fn world_to_canvas_point(
+    world: [f64; 2],
+    camera: &Camera,
+    bounds: &Rectangle,
+) -> iced::Point {
+    let ndc_x = (world[0] - camera.position.x) / camera.half_extents.x;
+    let ndc_y = (world[1] - camera.position.y) / camera.half_extents.y;
+    iced::Point::new(
+        (ndc_x as f32 + 1.0) * 0.5 * bounds.width,
+        (1.0 - ndc_y as f32) * 0.5 * bounds.height,
+    )
+}
+
+/// Resolves a line's dash pattern to pixels one segment at a time, as the CPU renderers
+/// draw it. Patterns in plot units are scaled along each segment's own direction, so a dash
+/// covers the same plot length whichever way the line runs.
+pub(crate) struct SegmentDashes {
+    line_type: LineType,
+    /// Distance along the line so far: in plot units for patterns in plot units, in pixels
+    /// otherwise.
+    distance: f64,
+}
+
+impl SegmentDashes {
+    pub(crate) fn new(line_type: LineType) -> Self {
+        Self {
+            line_type,
+            distance: 0.0,
+        }
+    }
+
+    /// The line type [in pixels](LineType::in_pixels) for the next segment, from `start` to
+    /// `end` in plot space and `length_px` pixels long, and the distance along the line at
+    /// its start in those pixels.
+    pub(crate) fn segment(
+        &mut self,
+        start: [f64; 2],
+        end: [f64; 2],
+        length_px: f32,
+    ) -> (LineType, f32) {
+        let length = (end[0] - start[0]).hypot(end[1] - start[1]);
+        let world_per_px = length / length_px as f64;
+        let (start_px, length) = if self.line_type.has_world_dashes() {
+            (self.distance / world_per_px.max(1e-12), length)
+        } else {
+            (self.distance, length_px as f64)
+        };
+        self.distance += length;
+        (self.line_type.in_pixels(world_per_px), start_px as f32)
+    }
+}
+
+/// Whether a line of `line_type` is drawn (not in a gap) at `distance` pixels along it, in
+/// the CPU renderers' dash pattern. The line type must be [in pixels](LineType::in_pixels).
+/// Dotted lines are drawn as dots, so they have no joins or caps.
+pub(crate) fn stroke_is_on(line_type: LineType, distance: f32) -> bool {
+    match line_type {
+        LineType::Dotted { .. } => false,
+        _ => line_type
+            .dash_pattern_px(1.0)
+            .is_none_or(|pattern| pattern.is_on(distance)),
+    }
+}
+
+/// Outline of the join where a stroke with `half_width` turns from direction `d0` into
+/// `d1` at `at`: the wedge on the outer side of the turn, as `line.wgsl` fills it. Empty
+/// when the stroke goes straight on. The directions must be unit length.
+pub(crate) fn join_outline(
+    at: iced::Point,
+    d0: iced::Vector,
+    d1: iced::Vector,
+    half_width: f32,
+    join: LineJoin,
+) -> Vec<iced::Point> {
+    let cross = d0.x * d1.y - d0.y * d1.x;
+    let dot = d0.x * d1.x + d0.y * d1.y;
+    if cross.abs() <= 1e-6 && dot > 0.0 {
+        return Vec::new();
+    }
+    // The outer side is right of a left turn and left of a right one; a full reversal
+    // is rounded through the forward direction.
+    let turn = cross.atan2(dot);
+    let (outer, sweep) = if cross > 0.0 {
+        (-1.0, turn)
+    } else {
+        (1.0, -turn.abs())
+    };
+    let normal0 = iced::Vector::new(-d0.y * outer, d0.x * outer);
+    let normal1 = iced::Vector::new(-d1.y * outer, d1.x * outer);
+    let offset =
+        |v: iced::Vector, length: f32| iced::Point::new(at.x + v.x * length, at.y + v.y * length);
+    let corner0 = offset(normal0, half_width);
+    let corner1 = offset(normal1, half_width);
+
+    match join {
+        LineJoin::Round => {
+            let mut outline = vec![at];
+            outline.extend(arc(at, half_width, normal0.y.atan2(normal0.x), sweep));
+            outline
+        }
+        LineJoin::Miter { limit } => {
+            let bisector = normal0 + normal1;
+            let bisector_length = (bisector.x * bisector.x + bisector.y * bisector.y).sqrt();
+            if bisector_length > 1e-4 {
+                let m = bisector * (1.0 / bisector_length);
+                let cos_half = normal0.x * m.x + normal0.y * m.y;
+                if 1.0 / cos_half.max(1e-6) <= limit {
+                    return vec![at, corner0, offset(m, half_width / cos_half), corner1];
+                }
+            }
+            vec![at, corner0, corner1]
+        }
+        LineJoin::Bevel => vec![at, corner0, corner1],
+    }
+}
+
+/// Outline of the cap at the end point `at` of a stroke with `half_width`, where `d` is
+/// the unit direction pointing away from the line. Empty for butt caps.
+pub(crate) fn cap_outline(
+    at: iced::Point,
+    d: iced::Vector,
+    half_width: f32,
+    cap: LineCap,
+) -> Vec<iced::Point> {
+    let normal = iced::Vector::new(-d.y, d.x);
+    let offset = |along: f32, across: f32| {
+        iced::Point::new(
+            at.x + d.x * along + normal.x * across,
+            at.y + d.y * along + normal.y * across,
+        )
+    };
+    match cap {
+        LineCap::Butt => Vec::new(),
+        LineCap::Square => vec![
+            offset(0.0, half_width),
+            offset(half_width, half_width),
+            offset(half_width, -half_width),
+            offset(0.0, -half_width),
+        ],
+        LineCap::Round => arc(
+            at,
+            half_width,
+            normal.y.atan2(normal.x),
+            -std::f32::consts::PI,
+        ),
+    }
+}
+
+/// Points along a circular arc, finely enough to look round at stroke widths.
+pub(crate) fn arc(center: iced::Point, radius: f32, start: f32, sweep: f32) -> Vec<iced::Point> {
+    let steps = (sweep.abs() / (std::f32::consts::PI / 16.0))
+        .ceil()
+        .max(1.0) as usize;
+    (0..=steps)
+        .map(|step| {
+            let angle = start + sweep * step as f32 / steps as f32;
+            iced::Point::new(
+                center.x + radius * angle.cos(),
+                center.y + radius * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iced::{Point, Vector};
+
+    #[test]
+    fn miter_joins_bevel_past_their_limit() {
+        let at = Point::ORIGIN;
+        let east = Vector::new(1.0, 0.0);
+        let north = Vector::new(0.0, 1.0);
+
+        // A right angle has a miter ratio of sqrt(2): the tip sits on the outer corner.
+        let miter = join_outline(at, east, north, 1.0, LineJoin::miter());
+        assert_eq!(miter.len(), 4);
+        assert!((miter[2].x - 1.0).abs() < 1e-5 && (miter[2].y + 1.0).abs() < 1e-5);
+        assert_eq!(
+            join_outline(at, east, north, 1.0, LineJoin::Miter { limit: 1.2 }),
+            join_outline(at, east, north, 1.0, LineJoin::Bevel)
+        );
+        assert!(join_outline(at, east, east, 1.0, LineJoin::Round).is_empty());
+
+        let cap = cap_outline(at, east, 1.0, LineCap::Round);
+        assert!(
+            cap.iter()
+                .all(|p| p.x >= -1e-5 && (p.x.hypot(p.y) - 1.0).abs() < 1e-5)
+        );
+        assert!(cap_outline(at, east, 1.0, LineCap::Butt).is_empty());
+    }
+
+    #[test]
+    fn world_dashes_follow_each_segment_direction() {
+        let pattern = crate::DashPattern::new([1.0]).with_units(crate::DashUnits::World);
+        let mut dashes = SegmentDashes::new(LineType::Pattern(pattern));
+
+        // One plot unit is 10 px across and 40 px up.
+        let (across, start) = dashes.segment([0.0, 0.0], [2.0, 0.0], 20.0);
+        let (up, next_start) = dashes.segment([2.0, 0.0], [2.0, 1.0], 40.0);
+        let lengths = |line_type| match line_type {
+            LineType::Pattern(pattern) => pattern.lengths().to_vec(),
+            _ => Vec::new(),
+        };
+        assert_eq!(lengths(across), [10.0, 10.0]);
+        assert_eq!(lengths(up), [40.0, 40.0]);
+        // Two units along, in pixels of the next segment's pattern.
+        assert_eq!((start, next_start), (0.0, 80.0));
+
+        let mut pixels = SegmentDashes::new(LineType::Pattern(crate::DashPattern::new([4.0])));
+        pixels.segment([0.0, 0.0], [2.0, 0.0], 20.0);
+        assert_eq!(pixels.segment([2.0, 0.0], [2.0, 1.0], 40.0).1, 20.0);
+    }
+}
@@ -1,16 +1,34 @@
 //! GPU renderer for PlotWidget.
 use super::{
-    CROSSHAIR_RGBA, SELECTION_FILL_RGBA, highlight_marker_plot_position,
-    highlight_mask_plot_position, highlight_mask_rgba,
+    CROSSHAIR_RGBA, LASSO_WIDTH_PX, SELECTION_FILL_RGBA, SELECTION_OUTLINE_RGBA,
+    highlight_marker_plot_position, highlight_mask_plot_position, highlight_mask_rgba,
+    ruler_segments,
 };
-use crate::LineStyle;
+use std::{borrow::Cow, sync::Arc};
+
 use crate::picking::PickingPass;
+use crate::plot_state::{RasterSpan, SelectionKind, SeriesSpan};
+use crate::raster::{RasterOrigin, RasterSampling};
 use crate::transform::data_value_to_plot_with_axis_range;
+use crate::{DashPattern, DashUnits, ErrorBarCap, LineCap, LineJoin, LineStyle};
 use crate::{LineType, Size, camera::CameraUniform, grid::Grid, plot_state::PlotState};
 use iced::widget::shader::Viewport;
 use iced::{Rectangle, wgpu::*};
 
 const MSAA_SAMPLE_COUNT: u32 = 4;
+const MARKER_INSTANCE_BYTES: usize = 36;
+const LINE_VERTEX_BYTES: usize = 88;
+/// Raster quad vertex: render position and texture coordinate.
+const RASTER_VERTEX_BYTES: usize = 16;
+/// Every line slot holds one segment quad (two triangles).
+const LINE_SLOT_BYTES: usize = 6 * LINE_VERTEX_BYTES;
+/// Width mode bit telling `line.wgsl` that a segment ends at a pixel offset from its start.
+const LINE_END_IN_PIXELS: u32 = 2;
+/// Picking id map entry for an unused marker slot.
+const EMPTY_PICK_ID: (u32, u32) = (u32::MAX, 0);
+/// Most custom dash patterns `line.wgsl` holds at once. Lines with further patterns are
+/// drawn solid.
+const MAX_DASH_PATTERNS: usize = 256;
 
 pub struct RenderParams<'a> {
     pub encoder: &'a mut CommandEncoder,
@@ -109,13 +127,228 @@ struct LineVertex<'a> {
     width_mode: u32,
     along: f32,
     side: f32,
+    prev_dir: [f32; 2],
+    next_dir: [f32; 2],
+    join_cap: u32,
+    miter_limit: f32,
 }
 
 #[derive(Clone, Copy)]
 struct LineRenderStyle {
     width: Size,
+    /// Line type, with a custom dash pattern's length count, units and index in
+    /// [`DashPatterns`] packed in (see `line.wgsl`).
     line_style: u32,
     style_param: f32,
+    /// Join in the low byte, cap in the next one (see `line.wgsl`).
+    join_cap: u32,
+    miter_limit: f32,
+}
+
+impl LineRenderStyle {
+    fn new(style: LineStyle, patterns: &DashPatterns) -> Self {
+        let (line_style, style_param) = line_style_params(style, patterns);
+        let (join_cap, miter_limit) = line_shape_params(style.join, style.cap);
+        Self {
+            width: style.width,
+            line_style,
+            style_param,
+            join_cap,
+            miter_limit,
+        }
+    }
+}
+
+/// Lengths of the custom dash patterns drawn, uploaded as a table `line.wgsl` looks them up
+/// in, so line vertices only carry an index. Patterns are kept once added, so vertices
+/// written earlier stay valid; when the table is full it starts over.
+#[derive(Default)]
+struct DashPatterns {
+    lengths: Vec<[f32; DashPattern::MAX_LENGTHS]>,
+    /// Whether the table changed since it was last uploaded.
+    changed: bool,
+}
+
+impl DashPatterns {
+    fn index(&self, lengths: &[f32; DashPattern::MAX_LENGTHS]) -> Option<u32> {
+        self.lengths
+            .iter()
+            .position(|known| known == lengths)
+            .map(|index| index as u32)
+    }
+
+    /// Add the patterns of the lines in `state`. Returns false if the table started over,
+    /// so indices written before no longer hold.
+    fn update(&mut self, state: &PlotState) -> bool {
+        let styles = (state.series.iter().filter_map(|s| s.line_style))
+            .chain(state.vlines.iter().map(|vline| vline.line_style))
+            .chain(state.hlines.iter().map(|hline| hline.line_style));
+        let mut used = Vec::new();
+        for lengths in styles.filter_map(dash_lengths) {
+            if !used.contains(&lengths) {
+                used.push(lengths);
+            }
+        }
+        let missing: Vec<_> = used
+            .iter()
+            .filter(|lengths| self.index(lengths).is_none())
+            .collect();
+        if missing.is_empty() {
+            return true;
+        }
+        self.changed = true;
+        if self.lengths.len() + missing.len() <= MAX_DASH_PATTERNS {
+            self.lengths.extend(missing);
+            return true;
+        }
+        // Start over with the patterns drawn now; any that still don't fit are drawn solid.
+        used.truncate(MAX_DASH_PATTERNS);
+        self.lengths = used;
+        false
+    }
+
+    /// The table as `line.wgsl` reads it: each pattern's lengths in two `vec4`s.
+    fn bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.lengths)
+    }
+}
+
+/// A line segment waiting to be written into its slot.
+#[derive(Clone, Copy)]
+struct SlotSegment {
+    start: [f32; 2],
+    end: [f32; 2],
+    /// Directions of the neighbouring segments, or zero at the ends of the line.
+    prev_dir: [f32; 2],
+    next_dir: [f32; 2],
+    start_color: iced::Color,
+    end_color: iced::Color,
+    distance_start: f32,
+    style: LineRenderStyle,
+}
+
+/// Slots reserved for one series span in the marker or line buffer.
+///
+/// Points are stored as a ring: the point at stream position `p` (see
+/// [`SeriesSpan::ring_head`]) lives in slot `first + (p % capacity) * pieces`. Appending
+/// points only writes their slots, and dropped points are cleared in place.
+#[derive(Clone, Copy)]
+struct SpanSlots {
+    /// Index of the span in [`PlotState::series`].
+    span: usize,
+    id: crate::ShapeId,
+    first: u32,
+    capacity: u32,
+    /// Slots per point: one for markers and straight lines, one per piece of a step line.
+    pieces: u32,
+    /// Stream position of the first stored point.
+    head: u64,
+    len: usize,
+    /// Cumulative line distance at the last stored point, used to continue dash patterns.
+    last_distance: f32,
+}
+
+/// A contiguous run of slots holding consecutive stream positions.
+struct SlotRun {
+    slot: usize,
+    position: u64,
+    len: usize,
+}
+
+impl SpanSlots {
+    /// Contiguous slot runs for the stream positions in `start..end`.
+    fn runs(&self, start: u64, end: u64) -> impl Iterator<Item = SlotRun> {
+        let capacity = self.capacity as u64;
+        let first = self.first as usize;
+        let pieces = self.pieces as usize;
+        let mut position = start;
+        std::iter::from_fn(move || {
+            if position >= end {
+                return None;
+            }
+            let offset = position % capacity;
+            let len = (capacity - offset).min(end - position);
+            let run = SlotRun {
+                slot: first + offset as usize * pieces,
+                position,
+                len: len as usize,
+            };
+            position += len;
+            Some(run)
+        })
+    }
+}
+
+fn slot_capacity(span: &SeriesSpan) -> usize {
+    span.max_len.unwrap_or(0).max(span.len)
+}
+
+/// Reserve `pieces(span)` slots per point for every span, skipping spans with none.
+/// Returns the slots and the total slot count.
+fn span_slots(state: &PlotState, pieces: impl Fn(&SeriesSpan) -> usize) -> (Vec<SpanSlots>, usize) {
+    let mut slots = Vec::new();
+    let mut total = 0;
+    for (index, span) in state.series.iter().enumerate() {
+        let capacity = slot_capacity(span);
+        let pieces = pieces(span);
+        if pieces == 0 || capacity == 0 {
+            continue;
+        }
+        slots.push(SpanSlots {
+            span: index,
+            id: span.id,
+            first: total as u32,
+            capacity: capacity as u32,
+            pieces: pieces as u32,
+            head: span.ring_head,
+            len: span.len,
+            last_distance: 0.0,
+        });
+        total += capacity * pieces;
+    }
+    (slots, total)
+}
+
+/// Whether the spans with slots only had points appended or dropped since `slots` were
+/// reserved, and still fit in them.
+fn slots_fit(
+    slots: &[SpanSlots],
+    state: &PlotState,
+    pieces: impl Fn(&SeriesSpan) -> usize,
+) -> bool {
+    let mut spans = state
+        .series
+        .iter()
+        .enumerate()
+        .filter(|(_, span)| pieces(span) > 0 && slot_capacity(span) > 0);
+    slots.iter().all(|slot| {
+        spans.next().is_some_and(|(index, span)| {
+            index == slot.span
+                && span.id == slot.id
+                && pieces(span) == slot.pieces as usize
+                && span.len <= slot.capacity as usize
+                && span.ring_head >= slot.head
+                && span.ring_head + span.len as u64 >= slot.head + slot.len as u64
+        })
+    }) && spans.next().is_none()
+}
+
+/// Most pieces a slotted line draws between two points: the three of a mid step.
+const MAX_SLOT_PIECES: usize = 3;
+
+/// Line slots per point of a span: one per piece of its straight or step line. Curves get
+/// none, since a natural spline moves everywhere when a point is added; they are drawn as
+/// plain polylines instead.
+fn line_slot_pieces(s: &SeriesSpan) -> usize {
+    match s.line_style {
+        Some(style) if !style.interpolation.is_curve() => style.interpolation.corner_count() + 1,
+        _ => 0,
+    }
+}
+
+/// Marker slots per point of a span: one if it draws markers.
+fn marker_slot_pieces(s: &SeriesSpan) -> usize {
+    usize::from(s.marker != u32::MAX)
 }
 
 struct PolylineRef<'a> {
@@ -129,6 +362,7 @@ struct PipelineCache {
     marker: Option<RenderPipeline>,
     line: Option<RenderPipeline>,
     fill: Option<RenderPipeline>,
+    raster: Option<RenderPipeline>,
     overlay: Option<RenderPipeline>,
     line_overlay: Option<RenderPipeline>,
     composite: Option<RenderPipeline>,
@@ -140,6 +374,7 @@ impl PipelineCache {
             marker: None,
             line: None,
             fill: None,
+            raster: None,
             overlay: None,
             line_overlay: None,
             composite: None,
@@ -157,29 +392,53 @@ struct MsaaTargets {
     composite_bind_group: BindGroup,
 }
 
+/// An uploaded raster image and the quad it is drawn on.
+struct RasterTexture {
+    /// The pixels the texture was uploaded from, to skip re-uploading unchanged rasters.
+    pixels: Arc<[u8]>,
+    sampling: RasterSampling,
+    _texture: Texture,
+    bind_group: BindGroup,
+    vertices: Buffer,
+}
+
 /// Cache for vertex buffers
 struct BufferCache {
     markers: Option<VertexBuffer>,
+    marker_slots: Vec<SpanSlots>,
     fills: Option<VertexBuffer>,
+    rasters: Vec<RasterTexture>,
     lines: Option<LineBuffer>,
+    line_slots: Vec<SpanSlots>,
+    /// Line distance at the start of the segment in each line slot, so segments whose
+    /// neighbours change can be rewritten without breaking the dash pattern.
+    line_slot_distances: Vec<f32>,
     reflines: Option<LineBuffer>,
+    error_bars: Option<LineBuffer>,
     selection: Option<VertexBuffer>,
     highlight: Option<VertexBuffer>,
     highlight_markers: Option<VertexBuffer>,
     crosshairs: Option<VertexBuffer>,
+    ruler: Option<VertexBuffer>,
 }
 
 impl BufferCache {
     fn new() -> Self {
         Self {
             markers: None,
+            marker_slots: Vec::new(),
             fills: None,
+            rasters: Vec::new(),
             lines: None,
+            line_slots: Vec::new(),
+            line_slot_distances: Vec::new(),
             reflines: None,
+            error_bars: None,
             selection: None,
             highlight: None,
             highlight_markers: None,
             crosshairs: None,
+            ruler: None,
         }
     }
 }
@@ -189,7 +448,11 @@ struct VersionTracker {
     markers: u64,
     fills: u64,
     lines: u64,
+    layout: u64,
     highlight: u64,
+    error_bars: u64,
+    /// Scale factor the pixel-sized error bar caps were written for.
+    scale_factor: f32,
     render_offset: glam::DVec2,
 }
 
@@ -199,7 +462,10 @@ impl VersionTracker {
             markers: 0,
             fills: 0,
             lines: 0,
+            layout: 0,
             highlight: 0,
+            error_bars: 0,
+            scale_factor: 0.0,
             render_offset: glam::DVec2::ZERO,
         }
     }
@@ -241,6 +507,10 @@ impl VertexWriter {
         self.write_f32(color.a);
     }
 
+    fn write_zeroed(&mut self, len: usize) {
+        self.data.resize(self.data.len() + len, 0);
+    }
+
     fn write_line_vertex(&mut self, vertex: LineVertex<'_>) {
         self.write_position(vertex.start);
         self.write_position(vertex.end);
@@ -253,6 +523,10 @@ impl VertexWriter {
         self.write_u32(vertex.width_mode);
         self.write_f32(vertex.along);
         self.write_f32(vertex.side);
+        self.write_position(vertex.prev_dir);
+        self.write_position(vertex.next_dir);
+        self.write_u32(vertex.join_cap);
+        self.write_f32(vertex.miter_limit);
     }
 
     fn byte_len(&self) -> usize {
@@ -271,14 +545,19 @@ impl VertexWriter {
 pub struct PlotRenderer {
     format: TextureFormat,
     camera_buffer: Buffer,
+    dash_pattern_buffer: Buffer,
     camera_bind_group: BindGroup,
     camera_bgl: BindGroupLayout,
     composite_bgl: BindGroupLayout,
     composite_sampler: Sampler,
+    raster_bgl: BindGroupLayout,
+    raster_nearest_sampler: Sampler,
+    raster_linear_sampler: Sampler,
     msaa_targets: Option<MsaaTargets>,
     // Caches
     pipelines: PipelineCache,
     buffers: BufferCache,
+    dash_patterns: DashPatterns,
     versions: VersionTracker,
     // Support objects
     grid: Grid,
@@ -293,16 +572,29 @@ impl PlotRenderer {
     pub fn new(device: &Device, _queue: &Queue, format: TextureFormat) -> Self {
         let camera_bgl = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
             label: Some("camera_bgl"),
-            entries: &[BindGroupLayoutEntry {
-                binding: 0,
-                visibility: ShaderStages::VERTEX_FRAGMENT,
-                ty: BindingType::Buffer {
-                    ty: BufferBindingType::Uniform,
-                    has_dynamic_offset: false,
-                    min_binding_size: None,
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX_FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                // Dash pattern table for `line.wgsl`.
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
                 },
-                count: None,
-            }],
+            ],
         });
         let camera_buffer = device.create_buffer(&BufferDescriptor {
             label: Some("camera_buffer"),
@@ -310,13 +602,25 @@ impl PlotRenderer {
             usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
+        let dash_pattern_buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("dash_pattern_buffer"),
+            size: (MAX_DASH_PATTERNS * DashPattern::MAX_LENGTHS * 4) as u64,
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
         let camera_bind_group = device.create_bind_group(&BindGroupDescriptor {
             label: Some("camera_bg"),
             layout: &camera_bgl,
-            entries: &[BindGroupEntry {
-                binding: 0,
-                resource: camera_buffer.as_entire_binding(),
-            }],
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: camera_buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: dash_pattern_buffer.as_entire_binding(),
+                },
+            ],
         });
         let composite_bgl = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
             label: Some("plot composite bgl"),
@@ -349,16 +653,56 @@ impl PlotRenderer {
             mipmap_filter: FilterMode::Nearest,
             ..SamplerDescriptor::default()
         });
+        let raster_bgl = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("raster bgl"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+        let raster_sampler = |label, filter| {
+            device.create_sampler(&SamplerDescriptor {
+                label: Some(label),
+                address_mode_u: AddressMode::ClampToEdge,
+                address_mode_v: AddressMode::ClampToEdge,
+                address_mode_w: AddressMode::ClampToEdge,
+                mag_filter: filter,
+                min_filter: filter,
+                mipmap_filter: FilterMode::Nearest,
+                ..SamplerDescriptor::default()
+            })
+        };
+        let raster_nearest_sampler = raster_sampler("raster nearest sampler", FilterMode::Nearest);
+        let raster_linear_sampler = raster_sampler("raster linear sampler", FilterMode::Linear);
         Self {
             format,
             camera_buffer,
+            dash_pattern_buffer,
             camera_bind_group,
             camera_bgl,
             composite_bgl,
             composite_sampler,
+            raster_bgl,
+            raster_nearest_sampler,
+            raster_linear_sampler,
             msaa_targets: None,
             pipelines: PipelineCache::new(),
             buffers: BufferCache::new(),
+            dash_patterns: DashPatterns::default(),
             versions: VersionTracker::new(),
             grid: Grid::default(),
             picking: PickingPass::default(),
@@ -419,12 +763,16 @@ impl PlotRenderer {
         self.grid
             .ensure_pipeline(device, self.format, &self.camera_bgl, MSAA_SAMPLE_COUNT);
         self.grid.update(device, state);
-        if !state.fills.is_empty() {
+        if !state.fills.is_empty() || !state.bars.is_empty() {
             self.ensure_fill_pipeline(device);
         }
+        if !state.rasters.is_empty() {
+            self.ensure_raster_pipeline(device);
+        }
         if state.series.iter().any(|s| s.line_style.is_some())
             || !state.vlines.is_empty()
             || !state.hlines.is_empty()
+            || !state.error_bars.is_empty()
         {
             self.ensure_line_pipeline(device);
         }
@@ -504,23 +852,47 @@ impl PlotRenderer {
         // Check if render offset changed - if so, we need to rebuild vertex buffers
         // since positions are stored relative to render_offset
         let offset_changed = self.versions.render_offset != state.camera.render_offset;
+        // Appends keep the span layout, so only the changed slots need to be written.
+        let appended = state.layout_version == self.versions.layout && !offset_changed;
+        // Line vertices refer to dash patterns by index, so lines are rebuilt if the table
+        // starts over.
+        let patterns_kept = self.dash_patterns.update(state);
+        if self.dash_patterns.changed {
+            queue.write_buffer(&self.dash_pattern_buffer, 0, self.dash_patterns.bytes());
+            self.dash_patterns.changed = false;
+        }
 
         if state.markers_version != self.versions.markers || offset_changed {
-            self.rebuild_markers(device, queue, state);
+            if !(appended && self.append_markers(queue, state)) {
+                self.rebuild_markers(device, queue, state);
+            }
             self.versions.markers = state.markers_version;
         }
         if state.fills_version != self.versions.fills || offset_changed {
             self.rebuild_fills(device, queue, state);
+            self.rebuild_rasters(device, queue, state);
             self.versions.fills = state.fills_version;
         }
-        if state.lines_version != self.versions.lines || offset_changed {
-            self.rebuild_lines(device, queue, state);
+        if state.lines_version != self.versions.lines || offset_changed || !patterns_kept {
+            if !(appended && patterns_kept && self.append_lines(queue, state)) {
+                self.rebuild_lines(device, queue, state);
+            }
             self.versions.lines = state.lines_version;
         }
+        self.versions.layout = state.layout_version;
 
         // Rebuild reference lines whenever camera changes
         self.rebuild_reflines(device, queue, state);
 
+        if state.error_bars_version != self.versions.error_bars
+            || offset_changed
+            || self.versions.scale_factor != self.scale_factor
+        {
+            self.rebuild_error_bars(device, queue, state);
+            self.versions.error_bars = state.error_bars_version;
+            self.versions.scale_factor = self.scale_factor;
+        }
+
         // Update cached render offset
         self.versions.render_offset = state.camera.render_offset;
 
@@ -537,6 +909,9 @@ impl PlotRenderer {
 
         // Crosshairs are rebuilt every frame when enabled.
         self.rebuild_crosshairs(device, queue, state);
+
+        // The ruler follows the camera.
+        self.rebuild_ruler(device, queue, state);
     }
 
     /// Prepare the renderer for a new frame given the viewport and current plot state.
@@ -702,8 +1077,9 @@ impl PlotRenderer {
                     // vec2<f32> segment_start (8) + vec2<f32> segment_end (8) + vec4<f32> color (16)
                     // + u32 line_style (4) + f32 distance_start (4) + f32 segment_length_world (4)
                     // + f32 style_param (4) + f32 width (4) + u32 width_mode (4)
-                    // + f32 along (4) + f32 side (4)
-                    array_stride: 64,
+                    // + f32 along (4) + f32 side (4) + vec2<f32> prev_dir (8)
+                    // + vec2<f32> next_dir (8) + u32 join_cap (4) + f32 miter_limit (4)
+                    array_stride: LINE_VERTEX_BYTES as u64,
                     step_mode: VertexStepMode::Vertex,
                     attributes: &[
                         VertexAttribute {
@@ -761,6 +1137,26 @@ impl PlotRenderer {
                             shader_location: 10,
                             format: VertexFormat::Float32, // side
                         },
+                        VertexAttribute {
+                            offset: 64,
+                            shader_location: 11,
+                            format: VertexFormat::Float32x2, // prev_dir
+                        },
+                        VertexAttribute {
+                            offset: 72,
+                            shader_location: 12,
+                            format: VertexFormat::Float32x2, // next_dir
+                        },
+                        VertexAttribute {
+                            offset: 80,
+                            shader_location: 13,
+                            format: VertexFormat::Uint32, // join_cap
+                        },
+                        VertexAttribute {
+                            offset: 84,
+                            shader_location: 14,
+                            format: VertexFormat::Float32, // miter_limit
+                        },
                     ],
                 }],
             },
@@ -852,6 +1248,67 @@ impl PlotRenderer {
         self.pipelines.fill = Some(pipeline);
     }
 
+    pub fn ensure_raster_pipeline(&mut self, device: &Device) {
+        if self.pipelines.raster.is_some() {
+            return;
+        }
+        let shader = device.create_shader_module(include_wgsl!("../shaders/raster.wgsl"));
+        let layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("raster layout"),
+            bind_group_layouts: &[&self.camera_bgl, &self.raster_bgl],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label: Some("raster pipeline"),
+            layout: Some(&layout),
+            vertex: VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                buffers: &[VertexBufferLayout {
+                    array_stride: RASTER_VERTEX_BYTES as u64,
+                    step_mode: VertexStepMode::Vertex,
+                    attributes: &[
+                        VertexAttribute {
+                            offset: 0,
+                            shader_location: 0,
+                            format: VertexFormat::Float32x2,
+                        },
+                        VertexAttribute {
+                            offset: 8,
+                            shader_location: 1,
+                            format: VertexFormat::Float32x2,
+                        },
+                    ],
+                }],
+            },
+            fragment: Some(FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: PipelineCompilationOptions::default(),
+                targets: &[Some(ColorTargetState {
+                    format: self.format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState {
+                topology: PrimitiveTopology::TriangleStrip,
+                strip_index_format: None,
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: msaa_state(),
+            multiview: None,
+            cache: None,
+        });
+        self.pipelines.raster = Some(pipeline);
+    }
+
     pub fn ensure_overlay_pipeline(&mut self, device: &Device) {
         if self.pipelines.overlay.is_some() {
             return;
@@ -1034,47 +1491,31 @@ impl PlotRenderer {
 
     fn rebuild_markers(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
         // Only include series that have markers (marker != u32::MAX)
-        let marker_series_count: usize = state
-            .series
-            .iter()
-            .filter(|s| s.marker != u32::MAX)
-            .map(|s| s.len)
-            .sum();
+        let (slots, instance_count) = span_slots(state, marker_slot_pieces);
+        self.buffers.marker_slots = slots;
 
-        if marker_series_count == 0 {
+        if instance_count == 0 {
             self.buffers.markers = None;
+            self.picking.set_id_map(Vec::new());
             return;
         }
 
-        let mut writer = VertexWriter::with_capacity(marker_series_count * 36);
-        let mut id_map: Vec<(u32, u32)> = Vec::with_capacity(marker_series_count);
-
-        // Iterate series so we can pick per-point color/marker for each point.
-        for (span_idx, s) in state.series.iter().enumerate() {
-            // Skip series without markers
-            if s.marker == u32::MAX {
-                continue;
-            }
-
-            // safety: ensure span indexes are valid with respect to points slice
-            let end = s.start + s.len;
-            if s.len == 0 || end > state.points.len() {
-                continue;
-            }
-
-            for (local_i, p) in state.points[s.start..end].iter().enumerate() {
-                // Subtract render_offset for high-precision rendering near zero
-                let render_pos = self.world_to_render_pos(p.position, &state.camera);
-                let color_idx = s.start + local_i;
-                let color = state.point_colors.get(color_idx).unwrap_or(&s.color);
-                writer.write_position(render_pos);
-                writer.write_color(color);
-                writer.write_u32(s.marker);
-                writer.write_f32(p.size);
-                writer.write_u32(crate::point::marker_flags(p.size_mode, s.pickable));
-
-                let original_idx = s.point_indices.get(local_i).copied().unwrap_or(local_i);
-                id_map.push((span_idx as u32, original_idx as u32));
+        let mut writer = VertexWriter::with_capacity(instance_count * MARKER_INSTANCE_BYTES);
+        let mut id_map: Vec<(u32, u32)> = Vec::with_capacity(instance_count);
+
+        // Each span is written as a ring of slots, so appends can overwrite single slots.
+        for slot in &self.buffers.marker_slots {
+            let s = &state.series[slot.span];
+            let capacity = slot.capacity as u64;
+            for k in 0..capacity {
+                let local_i = ((k + capacity - s.ring_head % capacity) % capacity) as usize;
+                if local_i < s.len {
+                    self.write_marker_instance(&mut writer, state, s, local_i);
+                    id_map.push((slot.span as u32, (s.ring_head + local_i as u64) as u32));
+                } else {
+                    writer.write_zeroed(MARKER_INSTANCE_BYTES);
+                    id_map.push(EMPTY_PICK_ID);
+                }
             }
         }
 
@@ -1094,10 +1535,10 @@ impl PlotRenderer {
                     usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
                     mapped_at_creation: false,
                 }),
-                vertex_count: marker_series_count as u32,
+                vertex_count: instance_count as u32,
             });
         } else if let Some(vb) = &mut self.buffers.markers {
-            vb.vertex_count = marker_series_count as u32;
+            vb.vertex_count = instance_count as u32;
         }
 
         if let Some(vb) = &self.buffers.markers {
@@ -1108,9 +1549,82 @@ impl PlotRenderer {
         self.picking.set_id_map(id_map);
     }
 
+    /// Write only the markers that changed since the last sync, after points were appended.
+    ///
+    /// Returns false if the existing slots can't hold the new points.
+    fn append_markers(&mut self, queue: &Queue, state: &PlotState) -> bool {
+        let Some(vb) = &self.buffers.markers else {
+            return false;
+        };
+        if !slots_fit(&self.buffers.marker_slots, state, marker_slot_pieces) {
+            return false;
+        }
+
+        for slot_index in 0..self.buffers.marker_slots.len() {
+            let slot = self.buffers.marker_slots[slot_index];
+            let s = &state.series[slot.span];
+            let old_end = slot.head + slot.len as u64;
+            let new_end = s.ring_head + s.len as u64;
+
+            for run in slot.runs(slot.head, s.ring_head.min(old_end)) {
+                let mut writer = VertexWriter::with_capacity(run.len * MARKER_INSTANCE_BYTES);
+                writer.write_zeroed(run.len * MARKER_INSTANCE_BYTES);
+                queue.write_buffer(
+                    &vb.buffer,
+                    (run.slot * MARKER_INSTANCE_BYTES) as u64,
+                    writer.as_slice(),
+                );
+                for offset in 0..run.len {
+                    self.picking.set_id(run.slot + offset, EMPTY_PICK_ID);
+                }
+            }
+            for run in slot.runs(old_end.max(s.ring_head), new_end) {
+                let mut writer = VertexWriter::with_capacity(run.len * MARKER_INSTANCE_BYTES);
+                for offset in 0..run.len {
+                    let position = run.position + offset as u64;
+                    let local_i = (position - s.ring_head) as usize;
+                    self.write_marker_instance(&mut writer, state, s, local_i);
+                    self.picking
+                        .set_id(run.slot + offset, (slot.span as u32, position as u32));
+                }
+                queue.write_buffer(
+                    &vb.buffer,
+                    (run.slot * MARKER_INSTANCE_BYTES) as u64,
+                    writer.as_slice(),
+                );
+            }
+
+            let slot = &mut self.buffers.marker_slots[slot_index];
+            slot.head = s.ring_head;
+            slot.len = s.len;
+        }
+        true
+    }
+
+    fn write_marker_instance(
+        &self,
+        writer: &mut VertexWriter,
+        state: &PlotState,
+        s: &SeriesSpan,
+        local_i: usize,
+    ) {
+        let p = &state.points[s.start + local_i];
+        // Subtract render_offset for high-precision rendering near zero
+        let render_pos = self.world_to_render_pos(p.position, &state.camera);
+        let color = state
+            .point_colors
+            .get(s.start + local_i)
+            .unwrap_or(&s.color);
+        writer.write_position(render_pos);
+        writer.write_color(color);
+        writer.write_u32(s.marker);
+        writer.write_f32(p.size);
+        writer.write_u32(crate::point::marker_flags(p.size_mode, s.pickable));
+    }
+
     fn rebuild_fills(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
         self.buffers.fills = None;
-        if state.fills.is_empty() {
+        if state.fills.is_empty() && state.bars.is_empty() {
             return;
         }
 
@@ -1122,6 +1636,16 @@ impl PlotRenderer {
                 writer.write_color(&fill.color);
             }
         }
+        // Bars share the fill pipeline: two triangles per rectangle.
+        for rect in state.bars.iter().flat_map(|span| span.rects.iter()) {
+            let [x0, y0] = rect.min;
+            let [x1, y1] = rect.max;
+            for world_pos in [[x0, y0], [x1, y0], [x0, y1], [x0, y1], [x1, y0], [x1, y1]] {
+                let render_pos = self.world_to_render_pos(world_pos, &state.camera);
+                writer.write_position(render_pos);
+                writer.write_color(&rect.color);
+            }
+        }
 
         if writer.is_empty() {
             return;
@@ -1143,92 +1667,169 @@ impl PlotRenderer {
         }
     }
 
+    fn rebuild_rasters(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
+        // Textures are reused while the raster's pixels are unchanged; only the quads move.
+        let mut previous = std::mem::take(&mut self.buffers.rasters);
+        for span in state.rasters.iter() {
+            let mut writer = VertexWriter::with_capacity(4 * RASTER_VERTEX_BYTES);
+            let [x0, y0] = span.grid.min;
+            let [x1, y1] = span.grid.max;
+            // Texture row 0 is the raster's row 0.
+            let (v0, v1) = match span.grid.origin {
+                RasterOrigin::Lower => (0.0, 1.0),
+                RasterOrigin::Upper => (1.0, 0.0),
+            };
+            for (world_pos, uv) in [
+                ([x0, y0], [0.0, v0]),
+                ([x1, y0], [1.0, v0]),
+                ([x0, y1], [0.0, v1]),
+                ([x1, y1], [1.0, v1]),
+            ] {
+                writer.write_position(self.world_to_render_pos(world_pos, &state.camera));
+                writer.write_position(uv);
+            }
+
+            let reused = previous.iter().position(|texture| {
+                Arc::ptr_eq(&texture.pixels, &span.pixels) && texture.sampling == span.sampling
+            });
+            let texture = match reused {
+                Some(index) => {
+                    let texture = previous.swap_remove(index);
+                    queue.write_buffer(&texture.vertices, 0, writer.as_slice());
+                    texture
+                }
+                None => self.upload_raster(device, queue, span, writer.as_slice()),
+            };
+            self.buffers.rasters.push(texture);
+        }
+    }
+
+    fn upload_raster(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        span: &RasterSpan,
+        vertices: &[u8],
+    ) -> RasterTexture {
+        let max_dimension = device.limits().max_texture_dimension_2d as usize;
+        let (pixels, width, height) = fit_raster_texture(
+            &span.pixels,
+            span.grid.columns,
+            span.grid.rows,
+            max_dimension,
+        );
+        let texture = create_color_texture(
+            device,
+            "raster texture",
+            width as u32,
+            height as u32,
+            1,
+            TextureFormat::Rgba8Unorm,
+            TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+        );
+        queue.write_texture(
+            TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            &pixels,
+            TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width as u32),
+                rows_per_image: Some(height as u32),
+            },
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+        );
+        let view = texture.create_view(&TextureViewDescriptor::default());
+        let sampler = match span.sampling {
+            RasterSampling::Nearest => &self.raster_nearest_sampler,
+            RasterSampling::Linear => &self.raster_linear_sampler,
+        };
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label: Some("raster bind group"),
+            layout: &self.raster_bgl,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::TextureView(&view),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: BindingResource::Sampler(sampler),
+                },
+            ],
+        });
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("raster vb"),
+            size: vertices.len() as u64,
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&buffer, 0, vertices);
+        RasterTexture {
+            pixels: span.pixels.clone(),
+            sampling: span.sampling,
+            _texture: texture,
+            bind_group,
+            vertices: buffer,
+        }
+    }
+
     fn rebuild_lines(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
         self.buffers.lines = None;
-        if state.series.iter().all(|s| s.line_style.is_none()) {
+        let (slots, segment_count) = span_slots(state, line_slot_pieces);
+        self.buffers.line_slots = slots;
+        let interpolated: Vec<&SeriesSpan> = state
+            .series
+            .iter()
+            .filter(|s| s.line_style.is_some() && line_slot_pieces(s) == 0)
+            .collect();
+        if segment_count == 0 && interpolated.is_empty() {
             return;
         }
 
-        let mut writer = VertexWriter::new();
-        let mut segs: Vec<LineSegment> = Vec::new();
-        for s in state.series.iter() {
-            let Some(line_style) = s.line_style else {
-                continue;
-            };
-            if s.len < 2 {
-                continue;
+        // Each point's slots hold the pieces of the line ending at that point, in the same
+        // ring order as the markers, so appends only write the pieces ending at new points.
+        let mut writer = VertexWriter::with_capacity(segment_count * LINE_SLOT_BYTES);
+        let mut segs: Vec<LineSegment> = Vec::with_capacity(self.buffers.line_slots.len());
+        let mut slots = std::mem::take(&mut self.buffers.line_slots);
+        let mut slot_distances = vec![0.0; segment_count];
+        for slot in &mut slots {
+            let s = &state.series[slot.span];
+            let capacity = slot.capacity as u64;
+            let pieces = slot.pieces as usize;
+            let mut segments = vec![[None; MAX_SLOT_PIECES]; slot.capacity as usize];
+            let mut distance = 0.0;
+            for local_i in 0..s.len {
+                let ring_slot = ((s.ring_head + local_i as u64) % capacity) as usize;
+                slot_distances[slot.first as usize + ring_slot * pieces] = distance;
+                let (segment, end_distance) = self.line_segment(state, s, local_i, distance);
+                distance = end_distance;
+                segments[ring_slot] = segment;
             }
-            let (line_style_u32, style_param) = line_style_params(line_style);
-            let render_style = LineRenderStyle {
-                width: line_style.width,
-                line_style: line_style_u32,
-                style_param,
-            };
-            let points_slice = &state.points[s.start..s.start + s.len];
-            let mut poly_positions: Vec<[f32; 2]> = Vec::new();
-            let mut poly_distances: Vec<f32> = Vec::new();
-            let mut poly_colors: Vec<iced::Color> = Vec::new();
-            let mut cumulative_distance = 0.0f32;
-
-            for (i, point) in points_slice.iter().enumerate() {
-                let break_segment = i > 0
-                    && s.point_indices
-                        .get(i)
-                        .zip(s.point_indices.get(i - 1))
-                        .is_some_and(|(curr, prev)| *curr != *prev + 1);
-
-                if break_segment {
-                    write_polyline_triangles(
-                        &mut writer,
-                        &mut segs,
-                        PolylineRef {
-                            positions: &poly_positions,
-                            distances: &poly_distances,
-                            colors: &poly_colors,
-                        },
-                        render_style,
-                    );
-                    poly_positions.clear();
-                    poly_distances.clear();
-                    poly_colors.clear();
-                    cumulative_distance = 0.0;
-                }
-
-                let render_pos = self.world_to_render_pos(point.position, &state.camera);
-                let color = *state.point_colors.get(s.start + i).unwrap_or(&s.color);
-
-                if let Some(last_pos) = poly_positions.last() {
-                    let dx = render_pos[0] - last_pos[0];
-                    let dy = render_pos[1] - last_pos[1];
-                    let segment_length = (dx * dx + dy * dy).sqrt();
-                    if segment_length <= f32::EPSILON {
-                        if let Some(last_color) = poly_colors.last_mut() {
-                            *last_color = color;
-                        }
-                        continue;
-                    }
-                    cumulative_distance += segment_length;
-                }
+            slot.last_distance = distance;
 
-                poly_positions.push(render_pos);
-                poly_distances.push(cumulative_distance);
-                poly_colors.push(color);
+            segs.push(LineSegment {
+                first_vertex: slot.first * 6,
+                vertex_count: slot.capacity * slot.pieces * 6,
+            });
+            for segment in &segments {
+                write_line_slot(&mut writer, &segment[..pieces]);
             }
-
-            write_polyline_triangles(
-                &mut writer,
-                &mut segs,
-                PolylineRef {
-                    positions: &poly_positions,
-                    distances: &poly_distances,
-                    colors: &poly_colors,
-                },
-                render_style,
-            );
         }
+        self.buffers.line_slots = slots;
+        self.buffers.line_slot_distances = slot_distances;
 
-        if writer.is_empty() {
-            return;
+        // Curves are written after the slots as plain polylines and rebuilt instead of
+        // appended to.
+        for s in interpolated {
+            self.write_line_paths(&mut writer, &mut segs, state, s);
         }
 
         let data = writer.as_slice();
@@ -1247,6 +1848,230 @@ impl PlotRenderer {
         }
     }
 
+    /// Write only the line segments that changed since the last sync, after points were
+    /// appended.
+    ///
+    /// Returns false if the existing slots can't hold the new points.
+    fn append_lines(&mut self, queue: &Queue, state: &PlotState) -> bool {
+        let Some(lb) = &self.buffers.lines else {
+            return false;
+        };
+        if !slots_fit(&self.buffers.line_slots, state, line_slot_pieces)
+            || state
+                .series
+                .iter()
+                .any(|s| s.line_style.is_some() && line_slot_pieces(s) == 0)
+        {
+            return false;
+        }
+
+        for slot_index in 0..self.buffers.line_slots.len() {
+            let slot = self.buffers.line_slots[slot_index];
+            let s = &state.series[slot.span];
+            let slot_bytes = slot.pieces as usize * LINE_SLOT_BYTES;
+            let old_end = slot.head + slot.len as u64;
+            let new_end = s.ring_head + s.len as u64;
+
+            // Clear dropped segments, and the one leading into the new first point.
+            let cleared_end = if s.ring_head > slot.head {
+                (s.ring_head + 1).min(old_end)
+            } else {
+                slot.head
+            };
+            for run in slot.runs(slot.head, cleared_end) {
+                let mut writer = VertexWriter::with_capacity(run.len * slot_bytes);
+                writer.write_zeroed(run.len * slot_bytes);
+                queue.write_buffer(
+                    &lb.buffer,
+                    (run.slot * LINE_SLOT_BYTES) as u64,
+                    writer.as_slice(),
+                );
+            }
+
+            // Kept segments whose neighbours changed: the one that now starts the line gets
+            // a cap, and the one that used to end it gets a join.
+            let first_kept = s.ring_head.max(cleared_end);
+            for position in [s.ring_head + 1, old_end.saturating_sub(1)] {
+                if position < first_kept || position >= old_end.min(new_end) {
+                    continue;
+                }
+                let local_i = (position - s.ring_head) as usize;
+                for run in slot.runs(position, position + 1) {
+                    let distance = self.buffers.line_slot_distances[run.slot];
+                    let (segment, _) = self.line_segment(state, s, local_i, distance);
+                    let mut writer = VertexWriter::with_capacity(slot_bytes);
+                    write_line_slot(&mut writer, &segment[..slot.pieces as usize]);
+                    queue.write_buffer(
+                        &lb.buffer,
+                        (run.slot * LINE_SLOT_BYTES) as u64,
+                        writer.as_slice(),
+                    );
+                }
+            }
+
+            let mut distance = slot.last_distance;
+            for run in slot.runs(old_end.max(s.ring_head), new_end) {
+                let mut writer = VertexWriter::with_capacity(run.len * slot_bytes);
+                for offset in 0..run.len {
+                    let local_i = (run.position + offset as u64 - s.ring_head) as usize;
+                    self.buffers.line_slot_distances[run.slot + offset * slot.pieces as usize] =
+                        distance;
+                    let (segment, end_distance) = self.line_segment(state, s, local_i, distance);
+                    distance = end_distance;
+                    write_line_slot(&mut writer, &segment[..slot.pieces as usize]);
+                }
+                queue.write_buffer(
+                    &lb.buffer,
+                    (run.slot * LINE_SLOT_BYTES) as u64,
+                    writer.as_slice(),
+                );
+            }
+
+            let slot = &mut self.buffers.line_slots[slot_index];
+            slot.head = s.ring_head;
+            slot.len = s.len;
+            slot.last_distance = distance;
+        }
+        true
+    }
+
+    /// Write the polylines of a span's interpolated line.
+    fn write_line_paths(
+        &self,
+        writer: &mut VertexWriter,
+        segs: &mut Vec<LineSegment>,
+        state: &PlotState,
+        s: &SeriesSpan,
+    ) {
+        let Some(line_style) = s.line_style else {
+            return;
+        };
+        let style = LineRenderStyle::new(line_style, &self.dash_patterns);
+        for path in s.line_paths(&state.points) {
+            let positions: Vec<[f32; 2]> = path
+                .iter()
+                .map(|&(position, _)| self.world_to_render_pos(position, &state.camera))
+                .collect();
+            let mut distance = 0.0;
+            let distances: Vec<f32> = positions
+                .iter()
+                .enumerate()
+                .map(|(i, p)| {
+                    if i > 0 {
+                        let q = positions[i - 1];
+                        distance += ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt();
+                    }
+                    distance
+                })
+                .collect();
+            let colors: Vec<iced::Color> = path
+                .iter()
+                .map(|&(_, index)| *state.point_colors.get(index).unwrap_or(&s.color))
+                .collect();
+            write_polyline_triangles(
+                writer,
+                segs,
+                PolylineRef {
+                    positions: &positions,
+                    distances: &distances,
+                    colors: &colors,
+                },
+                style,
+            );
+        }
+    }
+
+    /// The pieces of the line ending at local point `local_i`, given the cumulative line
+    /// distance at the previous point: one for a straight line, one per step for a step
+    /// line. Also returns the cumulative distance at `local_i`.
+    fn line_segment(
+        &self,
+        state: &PlotState,
+        s: &SeriesSpan,
+        local_i: usize,
+        distance: f32,
+    ) -> ([Option<SlotSegment>; MAX_SLOT_PIECES], f32) {
+        let mut pieces = [None; MAX_SLOT_PIECES];
+        let vertices = self.slot_vertices(state, s, local_i);
+        let Some(line_style) = s.line_style.filter(|_| !vertices.is_empty()) else {
+            return (pieces, 0.0);
+        };
+        let color = |index: usize| *state.point_colors.get(index).unwrap_or(&s.color);
+
+        let mut prev_dir = self.slot_segment_dir(state, s, local_i - 1, true);
+        let mut distance = distance;
+        for (k, pair) in vertices.windows(2).enumerate() {
+            let ((start, start_index), (end, end_index)) = (pair[0], pair[1]);
+            let dir = [end[0] - start[0], end[1] - start[1]];
+            let length = dir[0].hypot(dir[1]);
+            if length <= f32::EPSILON {
+                continue;
+            }
+            let next_dir = piece_dirs(&vertices[k + 1..])
+                .next()
+                .unwrap_or_else(|| self.slot_segment_dir(state, s, local_i + 1, false));
+            pieces[k] = Some(SlotSegment {
+                start,
+                end,
+                prev_dir,
+                next_dir,
+                start_color: color(start_index),
+                end_color: color(end_index),
+                distance_start: distance,
+                style: LineRenderStyle::new(line_style, &self.dash_patterns),
+            });
+            prev_dir = dir;
+            distance += length;
+        }
+        (pieces, distance)
+    }
+
+    /// Render-space vertices of the line from local point `local_i - 1` to `local_i`, each
+    /// paired with the index into the point buffer of the point whose color it carries.
+    /// Empty where there is no line.
+    fn slot_vertices(
+        &self,
+        state: &PlotState,
+        s: &SeriesSpan,
+        local_i: usize,
+    ) -> Vec<([f32; 2], usize)> {
+        let Some(line_style) = s.line_style else {
+            return Vec::new();
+        };
+        if local_i == 0 || local_i >= s.len || s.breaks_line_before(local_i) {
+            return Vec::new();
+        }
+        let (start_index, end_index) = (s.start + local_i - 1, s.start + local_i);
+        let (start, end) = (
+            state.points[start_index].position,
+            state.points[end_index].position,
+        );
+        let corners = line_style
+            .interpolation
+            .corners(start, end)
+            .map(|(corner, at_end)| (corner, if at_end { end_index } else { start_index }));
+        std::iter::once((start, start_index))
+            .chain(corners)
+            .chain(std::iter::once((end, end_index)))
+            .map(|(position, index)| (self.world_to_render_pos(position, &state.camera), index))
+            .collect()
+    }
+
+    /// Direction of the last (or first) piece of the line ending at local point `local_i`,
+    /// or zero if there is none.
+    fn slot_segment_dir(
+        &self,
+        state: &PlotState,
+        s: &SeriesSpan,
+        local_i: usize,
+        last: bool,
+    ) -> [f32; 2] {
+        let vertices = self.slot_vertices(state, s, local_i);
+        let mut dirs = piece_dirs(&vertices);
+        let dir = if last { dirs.next_back() } else { dirs.next() };
+        dir.unwrap_or([0.0; 2])
+    }
+
     fn rebuild_reflines(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
         self.buffers.reflines = None;
 
@@ -1281,12 +2106,7 @@ impl PlotRenderer {
                 continue;
             }
 
-            let (line_style_u32, style_param) = line_style_params(vline.line_style);
-            let render_style = LineRenderStyle {
-                width: vline.line_style.width,
-                line_style: line_style_u32,
-                style_param,
-            };
+            let render_style = LineRenderStyle::new(vline.line_style, &self.dash_patterns);
             // Create two endpoints spanning the visible vertical extent.
             let positions = [
                 self.world_to_render_pos([vx_plot, bottom], &state.camera),
@@ -1323,12 +2143,7 @@ impl PlotRenderer {
                 continue;
             }
 
-            let (line_style_u32, style_param) = line_style_params(hline.line_style);
-            let render_style = LineRenderStyle {
-                width: hline.line_style.width,
-                line_style: line_style_u32,
-                style_param,
-            };
+            let render_style = LineRenderStyle::new(hline.line_style, &self.dash_patterns);
             // Create two endpoints spanning the visible horizontal extent.
             let positions = [
                 self.world_to_render_pos([left, hy_plot], &state.camera),
@@ -1368,13 +2183,118 @@ impl PlotRenderer {
         }
     }
 
+    /// Caps are pixel offsets from the whisker ends, so the buffer only changes with the data.
+    fn rebuild_error_bars(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
+        self.buffers.error_bars = None;
+        if state.error_bars.is_empty() {
+            return;
+        }
+
+        let cam = &state.camera;
+        let mut writer = VertexWriter::new();
+        let mut segs: Vec<LineSegment> = Vec::new();
+        for span in state.error_bars.iter() {
+            let render_style = LineRenderStyle::new(
+                LineStyle::solid().with_pixel_width(span.style.line_width),
+                &self.dash_patterns,
+            );
+            let cap_half_px = match span.style.cap {
+                ErrorBarCap::Line { width } if width > 0.0 => 0.5 * width * self.scale_factor,
+                _ => 0.0,
+            };
+            for bar in span.bars.iter() {
+                let colors = [bar.color, bar.color];
+                let positions = [
+                    self.world_to_render_pos(bar.start, cam),
+                    self.world_to_render_pos(bar.end, cam),
+                ];
+                let dx = positions[1][0] - positions[0][0];
+                let dy = positions[1][1] - positions[0][1];
+                let distances = [0.0, (dx * dx + dy * dy).sqrt()];
+                write_polyline_triangles(
+                    &mut writer,
+                    &mut segs,
+                    PolylineRef {
+                        positions: &positions,
+                        distances: &distances,
+                        colors: &colors,
+                    },
+                    render_style,
+                );
+                if cap_half_px > 0.0 {
+                    let half = if bar.vertical {
+                        [cap_half_px, 0.0]
+                    } else {
+                        [0.0, cap_half_px]
+                    };
+                    let first_vertex = (writer.byte_len() / LINE_VERTEX_BYTES) as u32;
+                    for anchor in positions {
+                        write_error_bar_cap(&mut writer, anchor, half, bar.color, render_style);
+                    }
+                    segs.push(LineSegment {
+                        first_vertex,
+                        vertex_count: 4 * 6,
+                    });
+                }
+            }
+        }
+
+        if writer.is_empty() {
+            return;
+        }
+
+        let data = writer.as_slice();
+        self.buffers.error_bars = Some(LineBuffer {
+            buffer: device.create_buffer(&BufferDescriptor {
+                label: Some("error bar vb"),
+                size: data.len() as u64,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            segments: segs,
+        });
+
+        if let Some(lb) = &self.buffers.error_bars {
+            queue.write_buffer(&lb.buffer, 0, data);
+        }
+    }
+
     fn rebuild_selection(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
         let w = self.bounds_w.max(1) as f32;
         let h = self.bounds_h.max(1) as f32;
         if w <= 1.0 || h <= 1.0 {
             return;
         }
-        if state.selection.active || state.selection.moved {
+        let lasso = state.selection.kind == SelectionKind::Lasso;
+        if lasso && state.selection.path.len() >= 2 {
+            // A closed ribbon along the outline, as one triangle strip.
+            let path = &state.selection.path;
+            let half_width = LASSO_WIDTH_PX * 0.5 * self.scale_factor;
+            let mut data: Vec<f32> = Vec::with_capacity((path.len() + 1) * 12);
+            for i in 0..=path.len() {
+                let prev = path[(i + path.len() - 1) % path.len()];
+                let next = path[(i + 1) % path.len()];
+                let p = path[i % path.len()] * self.scale_factor;
+                let normal = (next - prev).normalize_or_zero().perp() * half_width;
+                for v in [p + normal, p - normal] {
+                    data.extend_from_slice(&self.screen_to_clip(v.x, v.y));
+                    data.extend_from_slice(&SELECTION_OUTLINE_RGBA);
+                }
+            }
+            let raw = bytemuck::cast_slice(&data);
+            self.buffers.selection = Some(VertexBuffer {
+                buffer: device.create_buffer(&BufferDescriptor {
+                    label: Some("lasso vb"),
+                    size: raw.len() as u64,
+                    usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }),
+                vertex_count: (data.len() / 6) as u32,
+            });
+            if let Some(vb) = &self.buffers.selection {
+                queue.write_buffer(&vb.buffer, 0, raw);
+            }
+        } else if !lasso && (state.selection.active || state.selection.moved) {
             let p0 = state.selection.start * self.scale_factor;
             let p1 = state.selection.end * self.scale_factor;
             let min_x = p0.x.min(p1.x);
@@ -1508,7 +2428,7 @@ impl PlotRenderer {
     fn rebuild_crosshairs(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
         self.buffers.crosshairs = None;
 
-        if !state.crosshairs_enabled {
+        if !state.crosshairs_enabled && !state.data_cursor_enabled {
             return;
         }
 
@@ -1537,11 +2457,13 @@ impl PlotRenderer {
         let top = [cursor_clip[0], 1.0];
         let bottom = [cursor_clip[0], -1.0];
 
-        // Add horizontal line vertices
-        data.extend_from_slice(&left);
-        data.extend_from_slice(&CROSSHAIR_RGBA);
-        data.extend_from_slice(&right);
-        data.extend_from_slice(&CROSSHAIR_RGBA);
+        // Add horizontal line vertices; the data cursor draws only the vertical line.
+        if state.crosshairs_enabled {
+            data.extend_from_slice(&left);
+            data.extend_from_slice(&CROSSHAIR_RGBA);
+            data.extend_from_slice(&right);
+            data.extend_from_slice(&CROSSHAIR_RGBA);
+        }
 
         // Add vertical line vertices
         data.extend_from_slice(&top);
@@ -1557,13 +2479,42 @@ impl PlotRenderer {
                 usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
                 mapped_at_creation: false,
             }),
-            vertex_count: 4,
+            vertex_count: (data.len() / 6) as u32,
         });
         if let Some(vb) = &self.buffers.crosshairs {
             queue.write_buffer(&vb.buffer, 0, raw);
         }
     }
 
+    fn rebuild_ruler(&mut self, device: &Device, queue: &Queue, state: &PlotState) {
+        self.buffers.ruler = None;
+        let Some(segments) = ruler_segments(state) else {
+            return;
+        };
+
+        let mut data: Vec<f32> = Vec::with_capacity(segments.len() * 12);
+        for p in segments.into_iter().flatten() {
+            data.extend_from_slice(
+                &self.screen_to_clip(p.x * self.scale_factor, p.y * self.scale_factor),
+            );
+            data.extend_from_slice(&SELECTION_OUTLINE_RGBA);
+        }
+
+        let raw = bytemuck::cast_slice(&data);
+        self.buffers.ruler = Some(VertexBuffer {
+            buffer: device.create_buffer(&BufferDescriptor {
+                label: Some("ruler vb"),
+                size: raw.len() as u64,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            vertex_count: (data.len() / 6) as u32,
+        });
+        if let Some(vb) = &self.buffers.ruler {
+            queue.write_buffer(&vb.buffer, 0, raw);
+        }
+    }
+
     pub fn encode(&self, params: RenderParams) {
         let Some(msaa_targets) = &self.msaa_targets else {
             return;
@@ -1599,6 +2550,18 @@ impl PlotRenderer {
 
             // grid
             self.grid.draw(&mut pass, &self.camera_bind_group);
+            // rasters
+            if let Some(pipeline) = self.pipelines.raster.as_ref()
+                && !self.buffers.rasters.is_empty()
+            {
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &self.camera_bind_group, &[]);
+                for raster in &self.buffers.rasters {
+                    pass.set_bind_group(1, &raster.bind_group, &[]);
+                    pass.set_vertex_buffer(0, raster.vertices.slice(..));
+                    pass.draw(0..4, 0..1);
+                }
+            }
             // fills
             if let (Some(pipeline), Some(vb)) = (self.pipelines.fill.as_ref(), &self.buffers.fills)
             {
@@ -1617,6 +2580,17 @@ impl PlotRenderer {
                     pass.draw(seg.first_vertex..seg.first_vertex + seg.vertex_count, 0..1);
                 }
             }
+            // error bars
+            if let (Some(pipeline), Some(lb)) =
+                (self.pipelines.line.as_ref(), &self.buffers.error_bars)
+            {
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &self.camera_bind_group, &[]);
+                pass.set_vertex_buffer(0, lb.buffer.slice(..));
+                for seg in &lb.segments {
+                    pass.draw(seg.first_vertex..seg.first_vertex + seg.vertex_count, 0..1);
+                }
+            }
             // reference lines (vlines and hlines)
             if let (Some(pipeline), Some(lb)) =
                 (self.pipelines.line.as_ref(), &self.buffers.reflines)
@@ -1686,11 +2660,11 @@ impl PlotRenderer {
             }
         }
 
-        // Crosshairs overlay (using line list topology)
-        if let (Some(pipeline), Some(vb)) = (
-            self.pipelines.line_overlay.as_ref(),
-            &self.buffers.crosshairs,
-        ) {
+        // Crosshairs and ruler overlay (using line list topology)
+        let line_overlays = [&self.buffers.crosshairs, &self.buffers.ruler];
+        if let Some(pipeline) = self.pipelines.line_overlay.as_ref()
+            && line_overlays.iter().any(|vb| vb.is_some())
+        {
             let mut pass = params.encoder.begin_render_pass(&RenderPassDescriptor {
                 label: Some("crosshairs overlay"),
                 color_attachments: &[Some(msaa_attachment(msaa_targets, LoadOp::Load))],
@@ -1709,8 +2683,10 @@ impl PlotRenderer {
             );
 
             pass.set_pipeline(pipeline);
-            pass.set_vertex_buffer(0, vb.buffer.slice(..));
-            pass.draw(0..vb.vertex_count, 0..1);
+            for vb in line_overlays.into_iter().flatten() {
+                pass.set_vertex_buffer(0, vb.buffer.slice(..));
+                pass.draw(0..vb.vertex_count, 0..1);
+            }
         }
 
         if let Some(pipeline) = self.pipelines.composite.as_ref() {
@@ -1743,15 +2719,80 @@ impl PlotRenderer {
     }
 }
 
+/// Downsample raster pixels (nearest) so neither side exceeds the GPU texture size limit.
+fn fit_raster_texture(
+    pixels: &[u8],
+    columns: usize,
+    rows: usize,
+    max_dimension: usize,
+) -> (Cow<'_, [u8]>, usize, usize) {
+    if columns <= max_dimension && rows <= max_dimension {
+        return (Cow::Borrowed(pixels), columns, rows);
+    }
+    let width = columns.min(max_dimension);
+    let height = rows.min(max_dimension);
+    let mut fitted = Vec::with_capacity(width * height * 4);
+    for y in 0..height {
+        let row = y * rows / height;
+        for x in 0..width {
+            let index = (row * columns + x * columns / width) * 4;
+            fitted.extend_from_slice(&pixels[index..index + 4]);
+        }
+    }
+    (Cow::Owned(fitted), width, height)
+}
+
 // Helper to extract line style parameters
-fn line_style_params(style: LineStyle) -> (u32, f32) {
+/// Line type and its parameter, as `line.wgsl` reads them. Patterns pack their length
+/// count, units and index in `patterns` into the line type, and are drawn solid if the
+/// table doesn't hold them.
+fn line_style_params(style: LineStyle, patterns: &DashPatterns) -> (u32, f32) {
     match style.line_type {
         LineType::Solid => (0u32, 0.0f32),
         LineType::Dotted { spacing } => (1u32, spacing),
         LineType::Dashed { length } => (2u32, length),
+        LineType::Pattern(pattern) => {
+            let Some(index) = dash_lengths(style).and_then(|lengths| patterns.index(&lengths))
+            else {
+                return (0u32, 0.0f32);
+            };
+            let units = match pattern.units {
+                DashUnits::Pixels => 0u32,
+                DashUnits::World => 1u32,
+            };
+            (
+                3u32 | (pattern.lengths().len() as u32) << 8 | units << 16 | index << 24,
+                pattern.offset,
+            )
+        }
     }
 }
 
+/// The lengths of a line's custom dash pattern, padded with zeros, if it has one to draw.
+fn dash_lengths(style: LineStyle) -> Option<[f32; DashPattern::MAX_LENGTHS]> {
+    let LineType::Pattern(pattern) = style.line_type else {
+        return None;
+    };
+    let mut lengths = [0.0; DashPattern::MAX_LENGTHS];
+    lengths[..pattern.lengths().len()].copy_from_slice(pattern.lengths());
+    (pattern.period() > 0.0).then_some(lengths)
+}
+
+/// Packed join and cap, and the miter limit, as `line.wgsl` reads them.
+fn line_shape_params(join: LineJoin, cap: LineCap) -> (u32, f32) {
+    let (join, miter_limit) = match join {
+        LineJoin::Miter { limit } => (0u32, limit),
+        LineJoin::Round => (1u32, 0.0f32),
+        LineJoin::Bevel => (2u32, 0.0f32),
+    };
+    let cap = match cap {
+        LineCap::Butt => 0u32,
+        LineCap::Round => 1u32,
+        LineCap::Square => 2u32,
+    };
+    (join | cap << 8, miter_limit)
+}
+
 fn write_polyline_triangles(
     writer: &mut VertexWriter,
     segs: &mut Vec<LineSegment>,
@@ -1765,45 +2806,40 @@ fn write_polyline_triangles(
         return;
     }
 
-    let (width, width_mode) = line_width_params(style.width);
-    let first_vertex = (writer.byte_len() / 64) as u32;
-    for index in 0..polyline.positions.len() - 1 {
-        let start = polyline.positions[index];
-        let end = polyline.positions[index + 1];
-        let segment_length_world = polyline.distances[index + 1] - polyline.distances[index];
-        if segment_length_world <= f32::EPSILON {
-            continue;
-        }
-
-        let start_color = &polyline.colors[index];
-        let end_color = &polyline.colors[index + 1];
-        let distance_start = polyline.distances[index];
-
-        for (color, along, side) in [
-            (start_color, 0.0, 1.0),
-            (start_color, 0.0, -1.0),
-            (end_color, 1.0, 1.0),
-            (start_color, 0.0, -1.0),
-            (end_color, 1.0, 1.0),
-            (end_color, 1.0, -1.0),
-        ] {
-            writer.write_line_vertex(LineVertex {
-                start,
-                end,
-                color,
-                style: style.line_style,
-                distance_start,
-                segment_length_world,
-                param: style.style_param,
-                width,
-                width_mode,
-                along,
-                side,
-            });
-        }
+    let first_vertex = (writer.byte_len() / LINE_VERTEX_BYTES) as u32;
+    // Zero-length segments are skipped, so joins connect the segments around them.
+    let drawn: Vec<usize> = (0..polyline.positions.len() - 1)
+        .filter(|&index| polyline.distances[index + 1] - polyline.distances[index] > f32::EPSILON)
+        .collect();
+    let direction = |index: usize| {
+        let [x0, y0] = polyline.positions[index];
+        let [x1, y1] = polyline.positions[index + 1];
+        [x1 - x0, y1 - y0]
+    };
+    for (order, &index) in drawn.iter().enumerate() {
+        let prev_dir = order
+            .checked_sub(1)
+            .map_or([0.0; 2], |prev| direction(drawn[prev]));
+        let next_dir = drawn
+            .get(order + 1)
+            .map_or([0.0; 2], |&next| direction(next));
+        write_segment_quad(
+            writer,
+            &SlotSegment {
+                start: polyline.positions[index],
+                end: polyline.positions[index + 1],
+                prev_dir,
+                next_dir,
+                start_color: polyline.colors[index],
+                end_color: polyline.colors[index + 1],
+                distance_start: polyline.distances[index],
+                style,
+            },
+            0,
+        );
     }
 
-    let vertex_count = (writer.byte_len() / 64) as u32 - first_vertex;
+    let vertex_count = (writer.byte_len() / LINE_VERTEX_BYTES) as u32 - first_vertex;
     if vertex_count > 0 {
         segs.push(LineSegment {
             first_vertex,
@@ -1812,6 +2848,90 @@ fn write_polyline_triangles(
     }
 }
 
+/// Write the six vertices of a segment quad. `end_mode` is or-ed into the width mode, e.g.
+/// [`LINE_END_IN_PIXELS`].
+fn write_segment_quad(writer: &mut VertexWriter, segment: &SlotSegment, end_mode: u32) {
+    let (width, width_mode) = line_width_params(segment.style.width);
+    let width_mode = width_mode | end_mode;
+    let dx = segment.end[0] - segment.start[0];
+    let dy = segment.end[1] - segment.start[1];
+    let segment_length_world = (dx * dx + dy * dy).sqrt();
+    let start_color = &segment.start_color;
+    let end_color = &segment.end_color;
+    for (color, along, side) in [
+        (start_color, 0.0, 1.0),
+        (start_color, 0.0, -1.0),
+        (end_color, 1.0, 1.0),
+        (start_color, 0.0, -1.0),
+        (end_color, 1.0, 1.0),
+        (end_color, 1.0, -1.0),
+    ] {
+        writer.write_line_vertex(LineVertex {
+            start: segment.start,
+            end: segment.end,
+            color,
+            style: segment.style.line_style,
+            distance_start: segment.distance_start,
+            segment_length_world,
+            param: segment.style.style_param,
+            width,
+            width_mode,
+            along,
+            side,
+            prev_dir: segment.prev_dir,
+            next_dir: segment.next_dir,
+            join_cap: segment.style.join_cap,
+            miter_limit: segment.style.miter_limit,
+        });
+    }
+}
+
+/// Write a cap centered on `anchor` as two halves reaching `half` pixels to either side.
+/// Each half starts with a hard edge at the anchor, so together they draw one bar.
+fn write_error_bar_cap(
+    writer: &mut VertexWriter,
+    anchor: [f32; 2],
+    half: [f32; 2],
+    color: iced::Color,
+    style: LineRenderStyle,
+) {
+    for direction in [half, [-half[0], -half[1]]] {
+        write_segment_quad(
+            writer,
+            &SlotSegment {
+                start: anchor,
+                end: direction,
+                prev_dir: [-direction[0], -direction[1]],
+                next_dir: [0.0; 2],
+                start_color: color,
+                end_color: color,
+                distance_start: 0.0,
+                style,
+            },
+            LINE_END_IN_PIXELS,
+        );
+    }
+}
+
+/// Write the line slots of one point: a segment quad per piece, or zeroed (transparent)
+/// vertices for unused pieces.
+fn write_line_slot(writer: &mut VertexWriter, pieces: &[Option<SlotSegment>]) {
+    for piece in pieces {
+        match piece {
+            Some(segment) => write_segment_quad(writer, segment, 0),
+            None => writer.write_zeroed(LINE_SLOT_BYTES),
+        }
+    }
+}
+
+/// Directions of the non-empty pieces between consecutive `vertices`.
+fn piece_dirs(vertices: &[([f32; 2], usize)]) -> impl DoubleEndedIterator<Item = [f32; 2]> + '_ {
+    vertices
+        .windows(2)
+        .map(|pair| [pair[1].0[0] - pair[0].0[0], pair[1].0[1] - pair[0].0[1]])
+        .filter(|dir| dir[0].hypot(dir[1]) > f32::EPSILON)
+}
+
 fn reference_line_half_extent(width: Size, vertical: bool, world_per_px: [f32; 2]) -> f32 {
     match width {
         Size::Pixels(size) => {
@@ -1,4 +1,8 @@
-use std::sync::Arc;
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    sync::{Arc, OnceLock},
+};
 
 use glam::{DVec2, Vec2};
 use iced::{
@@ -8,17 +12,35 @@ use iced::{
 };
 
 use crate::{
-    AxisLink, AxisScale, ClickAction, DragAction, DragEvent, HLine, HoverPickEvent, KeyAction,
-    LineStyle, PanDirection, PlotWidget, Point, ScrollAction, ShapeId, Size, VLine,
+    AxisLink, AxisScale, ClickAction, DragAction, DragEvent, Fill, HLine, HoverPickEvent,
+    KeyAction, LineStyle, Measurement, PanDirection, PlotWidget, Point, PointId, ScrollAction,
+    SelectionEvent, SelectionMode, ShapeId, Size, VLine,
     axis_scale::plot_point_to_data,
-    camera::Camera,
-    picking::PickingState,
-    plot_widget::{HighlightPoint, world_to_screen_position_x, world_to_screen_position_y},
+    bar::{BarOrientation, BarRect, clamp_log_bar_ends, layout_bars},
+    camera::{Camera, EPSILON_SMALL},
+    decimation::{Decimation, decimate},
+    error_bars::{ErrorBar, ErrorBarStyle},
+    interpolation::{CurveView, LineInterpolation},
+    picking::{PickTargets, PickingState, cpu_select, cpu_snap, marker_center_world},
+    plot_widget::{
+        HighlightPoint, UpdateEffects, world_to_screen_position_x, world_to_screen_position_y,
+    },
+    raster::{RasterGrid, RasterSampling},
+    secondary_axis::{SecondaryAxisMap, YAxis},
+    spatial_index::{PointIndex, SegmentIndex},
     style::GridStyle,
     ticks::{PositionedTick, TickFormatter, TickProducer},
-    transform::{data_point_to_plot_with_transform, data_value_to_plot_with_axis_range},
+    transform::{
+        PositionTransform, data_point_to_plot_with_transform, data_value_to_plot_with_axis_range,
+    },
 };
 
+/// Fraction of the data range added around it when autoscaling.
+const AUTOSCALE_PADDING: f64 = 0.05;
+
+/// Minimum distance, in screen pixels, between consecutive vertices of a lasso outline.
+const LASSO_SPACING_PX: f32 = 2.0;
+
 #[derive(Clone)]
 /// PlotState is a projection of the widget configuration, data, and interaction state.
 /// It holds the GPU-ready data needed for rendering the plot.
@@ -30,6 +52,9 @@ pub struct PlotState {
     pub(crate) point_colors: Arc<[Color]>, // per-point colors (matches points)
     pub(crate) series: Arc<[SeriesSpan]>,  // spans describing logical series
     pub(crate) fills: Arc<[FillSpan]>,     // triangulated fill spans
+    pub(crate) bars: Arc<[BarSpan]>,       // bar rectangles per bar series
+    pub(crate) rasters: Arc<[RasterSpan]>, // raster images, drawn below everything else
+    pub(crate) error_bars: Arc<[ErrorBarSpan]>, // error bar whiskers per series
     pub(crate) vlines: Arc<[VLine]>,       // vertical reference lines
     pub(crate) hlines: Arc<[HLine]>,       // horizontal reference lines
     pub(crate) data_min: Option<DVec2>,
@@ -37,18 +62,33 @@ pub struct PlotState {
     // Axis limits
     pub(crate) x_lim: Option<(f64, f64)>,
     pub(crate) y_lim: Option<(f64, f64)>,
+    pub(crate) y2_lim: Option<(f64, f64)>,
+    pub(crate) follow_latest: Option<f64>,
+    /// Number of categories, if the x-axis is categorical.
+    pub(crate) x_category_count: Option<usize>,
     pub(crate) x_axis_scale: AxisScale,
     pub(crate) y_axis_scale: AxisScale,
+    pub(crate) y2_axis_scale: AxisScale,
+    pub(crate) has_secondary_y_axis: bool,
+    /// Plot-space y range of the data on the secondary y-axis.
+    pub(crate) y2_data_range: Option<[f64; 2]>,
+    /// Map from primary to secondary y plot space for the current view.
+    pub(crate) y2_map: SecondaryAxisMap,
+    /// The map secondary-axis geometry was last built with.
+    pub(crate) built_y2_map: SecondaryAxisMap,
     // Axis links for synchronization
     pub(crate) x_axis_link: Option<AxisLink>,
     pub(crate) y_axis_link: Option<AxisLink>,
+    pub(crate) y2_axis_link: Option<AxisLink>,
     pub(crate) x_link_version: u64,
     pub(crate) y_link_version: u64,
+    pub(crate) y2_link_version: u64,
     // UI / camera
     pub(crate) camera: Camera,
     pub(crate) bounds: Rectangle,
     pub(crate) x_ticks: Vec<PositionedTick>,
     pub(crate) y_ticks: Vec<PositionedTick>,
+    pub(crate) y2_ticks: Vec<PositionedTick>,
     pub(crate) grid_style: GridStyle,
     // Interaction state
     pub(crate) cursor_position: Vec2,
@@ -60,6 +100,7 @@ pub struct PlotState {
     pub(crate) selection: SelectionState,
     pub(crate) pan: PanState,
     pub(crate) drag: DragState,
+    pub(crate) ruler: RulerState,
     /// Hover/select point rendering data (for incremental rendering)
     pub(crate) highlighted_points: Arc<[HighlightPoint]>,
     // Version counters
@@ -67,41 +108,65 @@ pub struct PlotState {
     pub(crate) lines_version: u64,
     pub(crate) fills_version: u64,
     pub(crate) highlight_version: u64,
+    pub(crate) error_bars_version: u64,
     pub(crate) data_src_version: u64, // version of source data last synced
+    pub(crate) append_src_version: u64, // version of appended data last synced
+    pub(crate) layout_version: u64,   // bumped when spans are rebuilt from scratch
     pub(crate) source_instance_id: Option<u64>,
+    /// The x-range decimated series were last reduced for, if any are drawn.
+    pub(crate) decimated_view: Option<DecimatedView>,
     // Hover/picking internals
     pub(crate) hover_enabled: bool,
     pub(crate) pick_enabled: bool,
     pub(crate) hover_radius_px: f32,
     pub(crate) picking: PickingState,
     pub(crate) crosshairs_enabled: bool,
+    /// The data cursor draws the vertical crosshair line.
+    pub(crate) data_cursor_enabled: bool,
     pub(crate) crosshairs_position: Vec2,
     pub(crate) x_axis_formatter: Option<TickFormatter>,
     pub(crate) y_axis_formatter: Option<TickFormatter>,
+    pub(crate) y2_axis_formatter: Option<TickFormatter>,
 }
 
 impl Default for PlotState {
     fn default() -> Self {
         Self {
             data_src_version: 0,
+            append_src_version: 0,
+            layout_version: 0,
             source_instance_id: None,
+            decimated_view: None,
             points: Arc::new([]),
             point_colors: Arc::new([]),
             highlighted_points: Arc::new([]),
             series: Arc::new([]),
             fills: Arc::new([]),
+            bars: Arc::new([]),
+            rasters: Arc::new([]),
+            error_bars: Arc::new([]),
             vlines: Arc::new([]),
             hlines: Arc::new([]),
             data_min: None,
             data_max: None,
             x_lim: None,
             y_lim: None,
+            y2_lim: None,
+            follow_latest: None,
+            x_category_count: None,
             x_axis_scale: AxisScale::Linear,
             y_axis_scale: AxisScale::Linear,
+            y2_axis_scale: AxisScale::Linear,
+            has_secondary_y_axis: false,
+            y2_data_range: None,
+            y2_map: SecondaryAxisMap::IDENTITY,
+            built_y2_map: SecondaryAxisMap::IDENTITY,
             x_axis_link: None,
             y_axis_link: None,
+            y2_axis_link: None,
             x_link_version: 0,
             y_link_version: 0,
+            y2_link_version: 0,
             camera: Camera::new(1000, 600),
             bounds: Rectangle::default(),
             grid_style: GridStyle::default(),
@@ -114,20 +179,25 @@ impl Default for PlotState {
             selection: SelectionState::default(),
             pan: PanState::default(),
             drag: DragState::default(),
+            ruler: RulerState::default(),
             markers_version: 1,
             lines_version: 1,
             fills_version: 1,
             highlight_version: 0,
+            error_bars_version: 1,
             hover_enabled: true,
             pick_enabled: true,
             hover_radius_px: 8.0,
             picking: PickingState::default(),
             crosshairs_enabled: false,
+            data_cursor_enabled: false,
             crosshairs_position: Vec2::ZERO,
             x_axis_formatter: None,
             y_axis_formatter: None,
+            y2_axis_formatter: None,
             x_ticks: Vec::new(),
             y_ticks: Vec::new(),
+            y2_ticks: Vec::new(),
         }
     }
 }
@@ -137,9 +207,16 @@ impl PlotState {
     ///
     /// Returns true if the overlay data changed.
     pub(crate) fn sync_highlighted_points_from_widget(&mut self, widget: &PlotWidget) -> bool {
+        let data_cursor_points = widget
+            .data_cursor_ui
+            .iter()
+            .flat_map(|readout| &readout.entries)
+            .map(|entry| &entry.point);
         let highlighted_points: Vec<_> = widget
             .visible_highlighted_points()
-            .map(|(highlight_point, _)| highlight_point.clone())
+            .map(|(highlight_point, _)| highlight_point)
+            .chain(data_cursor_points)
+            .cloned()
             .collect();
 
         if self.highlighted_points.as_ref() != highlighted_points.as_slice() {
@@ -151,16 +228,107 @@ impl PlotState {
         }
     }
 
+    /// Show the widget's measurement, unless a ruler is being dragged.
+    ///
+    /// Returns true if the ruler changed.
+    pub(crate) fn sync_ruler_from_widget(&mut self, widget: &PlotWidget) -> bool {
+        if self.ruler.active || self.ruler.measurement == widget.measurement {
+            return false;
+        }
+        self.ruler.measurement = widget.measurement.clone();
+        true
+    }
+
+    /// Scale of the given y-axis.
+    pub(crate) fn y_scale(&self, y_axis: YAxis) -> AxisScale {
+        match y_axis {
+            YAxis::Primary => self.y_axis_scale,
+            YAxis::Secondary => self.y2_axis_scale,
+        }
+    }
+
+    /// Map from primary y plot space into the plot space of the given y-axis.
+    pub(crate) fn y_map(&self, y_axis: YAxis) -> SecondaryAxisMap {
+        match y_axis {
+            YAxis::Primary => SecondaryAxisMap::IDENTITY,
+            YAxis::Secondary => self.y2_map,
+        }
+    }
+
+    /// The visible x and y ranges, with y in the plot space of the given y-axis.
+    pub(crate) fn axis_ranges(&self, y_axis: YAxis) -> ([f64; 2], [f64; 2]) {
+        let (x_range, y_range) = self.camera.axis_ranges();
+        (x_range, self.y_map(y_axis).range_to_secondary(y_range))
+    }
+
+    /// Convert a data point into the plot space of the y-axis it is bound to.
+    fn data_point_to_axis_plot(
+        &self,
+        point: [f64; 2],
+        y_axis: YAxis,
+        transform: &PositionTransform,
+    ) -> Option<[f64; 2]> {
+        data_point_to_plot_with_transform(
+            point,
+            self.x_axis_scale,
+            self.y_scale(y_axis),
+            transform,
+            Some(self.axis_ranges(y_axis)),
+        )
+    }
+
+    /// Convert a data point bound to `y_axis` into plot space.
+    pub(crate) fn data_point_to_plot(
+        &self,
+        point: [f64; 2],
+        y_axis: YAxis,
+        transform: &PositionTransform,
+    ) -> Option<[f64; 2]> {
+        let point = self.data_point_to_axis_plot(point, y_axis, transform)?;
+        Some(self.y_map(y_axis).point_to_primary(point))
+    }
+
+    /// Plot-space bounds of each contour's grid, which it covers even where no level line
+    /// or band is drawn.
+    fn contour_bounds(&self, widget: &PlotWidget) -> Vec<DataBounds> {
+        widget
+            .contours
+            .values()
+            .map(|contour| {
+                let mut bounds = DataBounds::default();
+                for corner in [contour.min, contour.max] {
+                    if let Some(p) =
+                        self.data_point_to_axis_plot(corner, YAxis::Primary, &Default::default())
+                    {
+                        bounds.include(p, true, true);
+                    }
+                }
+                bounds
+            })
+            .collect()
+    }
+
     /// Rebuild GPU data from widget configuration.
     pub(crate) fn rebuild_from_widget(&mut self, widget: &PlotWidget) {
+        // Positions are converted with the widget's scales below.
+        self.x_axis_scale = widget.x_axis_scale;
+        self.y_axis_scale = widget.y_axis_scale;
+        self.y2_axis_scale = widget.y2_axis_scale;
+        self.has_secondary_y_axis = widget.has_secondary_y_axis();
+
         let mut points = Vec::new();
         let mut point_colors = Vec::new();
         let mut series_spans = Vec::new();
-        let mut data_min_x: Option<f64> = None;
-        let mut data_max_x: Option<f64> = None;
-        let mut data_min_y: Option<f64> = None;
-        let mut data_max_y: Option<f64> = None;
+        let mut error_bar_spans = Vec::new();
         let axis_ranges = self.camera.axis_ranges();
+        let columns = self.bounds.width.max(1.0) as usize;
+        let decimated_view = DecimatedView::around(axis_ranges.0, columns);
+        let mut decimated_any = false;
+        let curve_view = CurveView::around(&self.camera, &self.bounds);
+        // Camera-only rebuilds (e.g. re-decimating after a zoom) don't change the data bounds.
+        let data_changed = self.data_src_version != widget.data_version
+            || self.append_src_version != widget.append_version
+            || self.source_instance_id != Some(widget.instance_id);
 
         // Process each series
         for (id, series) in &widget.series {
@@ -175,6 +343,9 @@ impl PlotState {
 
             let start = points.len();
             let mut point_indices = Vec::new();
+            let mut bounds = DataBounds::default();
+            // Bounds are tracked in the plot space of the series' own y-axis.
+            let y_map = self.y_map(series.y_axis);
             let x_uses_axes = series
                 .transform
                 .x
@@ -186,52 +357,87 @@ impl PlotState {
                 .as_ref()
                 .is_some_and(|transform| transform.uses_axes_coordinates());
 
+            // Only create points if we have markers OR lines (lines need points for geometry).
+            // Bar-only series contribute bounds through their bar rectangles below.
+            let draws_points = series.marker_style.is_some() || series.line_style.is_some();
+            let decimated = draws_points && series.decimation != Decimation::None;
+            let indices: Box<dyn Iterator<Item = usize>> = if decimated {
+                // Decimated series only draw a subset of their points, so gather their
+                // full-resolution bounds separately.
+                if data_changed {
+                    for &pos in &series.positions {
+                        if let Some(p) =
+                            self.data_point_to_axis_plot(pos, series.y_axis, &series.transform)
+                        {
+                            bounds.include(p, !x_uses_axes, !y_uses_axes);
+                        }
+                    }
+                } else if let Some(previous) = self.series.iter().find(|span| span.id == *id) {
+                    bounds = previous.bounds;
+                }
+                decimated_any = true;
+                let plot_x = |p: [f64; 2]| {
+                    data_value_to_plot_with_axis_range(
+                        p[0],
+                        widget.x_axis_scale,
+                        series.transform.x.as_ref(),
+                        Some(axis_ranges.0),
+                    )
+                };
+                let window =
+                    visible_index_window(&series.positions, decimated_view.x_range, plot_x);
+                Box::new(
+                    decimate(
+                        series.decimation,
+                        &series.positions,
+                        window,
+                        decimated_view.columns,
+                        |p| plot_x(p).map(|x| decimated_view.column_of(x)),
+                    )
+                    .into_iter(),
+                )
+            } else if draws_points {
+                Box::new(0..series.positions.len())
+            } else {
+                Box::new(std::iter::empty())
+            };
+
             // Add points and track bounds
-            for (pos_index, &pos) in series.positions.iter().enumerate() {
-                let Some(transformed) = data_point_to_plot_with_transform(
-                    pos,
-                    widget.x_axis_scale,
-                    widget.y_axis_scale,
+            let mut line_breaks = Vec::new();
+            let mut skipped = false;
+            for pos_index in indices {
+                let Some(transformed) = self.data_point_to_axis_plot(
+                    series.positions[pos_index],
+                    series.y_axis,
                     &series.transform,
-                    Some(axis_ranges),
                 ) else {
+                    skipped = true;
                     continue;
                 };
-
-                if !x_uses_axes {
-                    data_min_x =
-                        Some(data_min_x.map_or(transformed[0], |min| min.min(transformed[0])));
-                    data_max_x =
-                        Some(data_max_x.map_or(transformed[0], |max| max.max(transformed[0])));
-                }
-                if !y_uses_axes {
-                    data_min_y =
-                        Some(data_min_y.map_or(transformed[1], |min| min.min(transformed[1])));
-                    data_max_y =
-                        Some(data_max_y.map_or(transformed[1], |max| max.max(transformed[1])));
+                if std::mem::take(&mut skipped) && !point_indices.is_empty() {
+                    line_breaks.push(point_indices.len());
                 }
 
-                // Only create points if we have markers OR lines (lines need points for geometry)
-                if series.marker_style.is_some() || series.line_style.is_some() {
-                    let (size, size_mode) = series
-                        .marker_style
-                        .as_ref()
-                        .map(|ms| ms.size.to_raw())
-                        .unwrap_or((1.0, crate::point::MARKER_SIZE_PIXELS));
-                    let color = series
-                        .point_colors
-                        .as_ref()
-                        .and_then(|colors| colors.get(pos_index))
-                        .copied()
-                        .unwrap_or(series.color);
-                    points.push(Point {
-                        position: transformed,
-                        size,
-                        size_mode,
-                    });
-                    point_colors.push(color);
-                    point_indices.push(pos_index);
-                }
+                bounds.include(transformed, !x_uses_axes, !y_uses_axes);
+
+                let (size, size_mode) = series
+                    .marker_style
+                    .as_ref()
+                    .map(|ms| ms.size.to_raw())
+                    .unwrap_or((1.0, crate::point::MARKER_SIZE_PIXELS));
+                let color = series
+                    .point_colors
+                    .as_ref()
+                    .and_then(|colors| colors.get(pos_index))
+                    .copied()
+                    .unwrap_or(series.color);
+                points.push(Point {
+                    position: y_map.point_to_primary(transformed),
+                    size,
+                    size_mode,
+                });
+                point_colors.push(color);
+                point_indices.push(pos_index);
             }
 
             let (color, marker) = series
@@ -241,41 +447,196 @@ impl PlotState {
                 .map(|(marker,)| (series.color, marker))
                 .unwrap_or((series.color, u32::MAX));
 
+            let mut error_bars = build_error_bars(
+                series,
+                widget.x_axis_scale,
+                self.y_scale(series.y_axis),
+                self.axis_ranges(series.y_axis),
+            );
+            for bar in &mut error_bars {
+                bounds.include(bar.start, !x_uses_axes, !y_uses_axes);
+                bounds.include(bar.end, !x_uses_axes, !y_uses_axes);
+                bar.start = y_map.point_to_primary(bar.start);
+                bar.end = y_map.point_to_primary(bar.end);
+            }
+            if !error_bars.is_empty() {
+                error_bar_spans.push(ErrorBarSpan {
+                    style: series.error_bar_style,
+                    bars: error_bars.into(),
+                });
+            }
+
+            let padding =
+                world_marker_padding(series, widget.x_axis_scale, self.y_scale(series.y_axis));
+            bounds.pad_max(padding);
+            // Streams that drop points keep per-block bounds, so a drop rescans one block.
+            let mut block_bounds = BlockBounds::default();
+            if series.max_len.is_some() && supports_incremental_append(series) {
+                for point in &points[start..] {
+                    block_bounds.push(point_bounds(point, y_map, padding));
+                }
+            }
+
             series_spans.push(SeriesSpan {
                 id: *id,
                 start,
                 len: points.len() - start,
                 point_indices: point_indices.into(),
+                line_breaks: line_breaks.into(),
                 line_style: series.line_style,
                 color,
                 marker,
                 pickable: series.pickable,
+                y_axis: series.y_axis,
+                bounds,
+                max_len: series.max_len,
+                ring_head: 0,
+                block_bounds,
+                source_dropped: widget.dropped_points.get(id).copied().unwrap_or(0),
+                source_len: series.positions.len(),
+                index: Arc::default(),
+                curve_view,
+                paths: Arc::default(),
+                segments: Arc::default(),
             });
+        }
 
-            // If this series has a world-space marker, the data_max should be adjusted to account for the marker size.
-            if let Some(size) = series.marker_style.as_ref().and_then(|m| match m.size {
-                Size::World(size) => Some(size),
-                Size::Pixels(_) => None,
-            }) {
-                if !x_uses_axes
-                    && widget.x_axis_scale == AxisScale::Linear
-                    && let Some(max) = &mut data_max_x
-                {
-                    *max += size;
-                }
-                if !y_uses_axes
-                    && widget.y_axis_scale == AxisScale::Linear
-                    && let Some(max) = &mut data_max_y
-                {
-                    *max += size;
-                }
+        // Lay out bars across all visible series so grouped/stacked bars share categories.
+        let mut bars = Vec::new();
+        let visible_series = widget
+            .series
+            .iter()
+            .filter(|(id, _)| !widget.hidden_shapes.contains(id))
+            .map(|(_, series)| series);
+        let mut bar_layout = layout_bars(visible_series);
+        clamp_log_bar_ends(&mut bar_layout, |id| {
+            let series = widget.series.get(&id)?;
+            let (key, i, scale) = match series.bar_style?.orientation {
+                BarOrientation::Vertical => (Some(series.y_axis), 1, self.y_scale(series.y_axis)),
+                BarOrientation::Horizontal => (None, 0, self.x_axis_scale),
+            };
+            match scale {
+                AxisScale::Log { base } => Some((key, i, base)),
+                AxisScale::Linear | AxisScale::Time => None,
+            }
+        });
+        for (id, rects) in bar_layout {
+            let Some(series) = widget.series.get(&id) else {
+                continue;
+            };
+            let x_uses_axes = series
+                .transform
+                .x
+                .as_ref()
+                .is_some_and(|transform| transform.uses_axes_coordinates());
+            let y_uses_axes = series
+                .transform
+                .y
+                .as_ref()
+                .is_some_and(|transform| transform.uses_axes_coordinates());
+
+            let plot_rects: Vec<BarRect> = rects
+                .into_iter()
+                .filter_map(|rect| {
+                    let to_plot =
+                        |p| self.data_point_to_axis_plot(p, series.y_axis, &series.transform);
+                    let a = to_plot(rect.min)?;
+                    let b = to_plot(rect.max)?;
+                    Some(BarRect {
+                        min: [a[0].min(b[0]), a[1].min(b[1])],
+                        max: [a[0].max(b[0]), a[1].max(b[1])],
+                        ..rect
+                    })
+                })
+                .collect();
+
+            let mut bounds = DataBounds::default();
+            let y_map = self.y_map(series.y_axis);
+            let plot_rects: Vec<BarRect> = plot_rects
+                .into_iter()
+                .map(|rect| {
+                    bounds.include(rect.min, !x_uses_axes, !y_uses_axes);
+                    bounds.include(rect.max, !x_uses_axes, !y_uses_axes);
+                    BarRect {
+                        min: y_map.point_to_primary(rect.min),
+                        max: y_map.point_to_primary(rect.max),
+                        ..rect
+                    }
+                })
+                .collect();
+
+            if !plot_rects.is_empty() {
+                bars.push(BarSpan {
+                    id,
+                    pickable: series.pickable,
+                    y_axis: series.y_axis,
+                    rects: plot_rects.into(),
+                    bounds,
+                });
+            }
+        }
+
+        // Boxes are drawn (and violins picked) like bars; their lines are separate series.
+        for (id, plot) in &widget.distributions {
+            if widget.hidden_shapes.contains(id) {
+                continue;
+            }
+            let transform = Default::default();
+            let mut bounds = DataBounds::default();
+            let rects: Vec<BarRect> = plot
+                .rects
+                .iter()
+                .filter_map(|rect| {
+                    let to_plot = |p| self.data_point_to_axis_plot(p, YAxis::Primary, &transform);
+                    let a = to_plot(rect.min)?;
+                    let b = to_plot(rect.max)?;
+                    let min = [a[0].min(b[0]), a[1].min(b[1])];
+                    let max = [a[0].max(b[0]), a[1].max(b[1])];
+                    bounds.include(min, true, true);
+                    bounds.include(max, true, true);
+                    Some(BarRect { min, max, ..*rect })
+                })
+                .collect();
+            if !rects.is_empty() {
+                bars.push(BarSpan {
+                    id: *id,
+                    pickable: true,
+                    y_axis: YAxis::Primary,
+                    rects: rects.into(),
+                    bounds,
+                });
             }
         }
 
-        let data_min = (data_min_x.is_some() || data_min_y.is_some())
-            .then(|| DVec2::new(data_min_x.unwrap_or(-1.0), data_min_y.unwrap_or(-1.0)));
-        let data_max = (data_max_x.is_some() || data_max_y.is_some())
-            .then(|| DVec2::new(data_max_x.unwrap_or(1.0), data_max_y.unwrap_or(1.0)));
+        let rasters: Vec<_> = widget
+            .rasters
+            .iter()
+            .filter(|(id, _)| !widget.hidden_shapes.contains(id))
+            .filter_map(|(id, raster)| {
+                let grid = raster.plot_grid(widget.x_axis_scale, widget.y_axis_scale)?;
+                let mut bounds = DataBounds::default();
+                bounds.include(grid.min, true, true);
+                bounds.include(grid.max, true, true);
+                Some(RasterSpan {
+                    id: *id,
+                    pickable: raster.pickable,
+                    grid,
+                    sampling: raster.sampling,
+                    pixels: raster.pixels.clone(),
+                    bounds,
+                })
+            })
+            .collect();
+
+        let (data_min, data_max, y2_data_range) = if data_changed {
+            let contours = self.contour_bounds(widget);
+            let (bounds, y2_data_range) =
+                combined_bounds(&series_spans, &bars, &rasters, &contours);
+            let (data_min, data_max) = bounds.to_min_max();
+            (data_min, data_max, y2_data_range)
+        } else {
+            (self.data_min, self.data_max, self.y2_data_range)
+        };
 
         // Filter visible reference lines
         let vlines: Vec<_> = widget
@@ -292,46 +653,31 @@ impl PlotState {
             .map(|(_, h)| h.clone())
             .collect();
 
-        let x_domain = plot_x_domain(widget, data_min, data_max);
-        let y_domain = plot_y_domain(widget, data_min, data_max);
-
-        let fills: Vec<_> = widget
-            .fills
-            .iter()
-            .filter(|(fill_id, fill)| {
-                !widget.hidden_shapes.contains(fill_id)
-                    && !widget.hidden_shapes.contains(&fill.begin)
-                    && !widget.hidden_shapes.contains(&fill.end)
-            })
-            .filter_map(|(_, fill)| {
-                build_fill_span(
-                    widget,
-                    fill.begin,
-                    fill.end,
-                    fill.color,
-                    x_domain,
-                    y_domain,
-                    axis_ranges,
-                )
-                .filter(|span| !span.vertices.is_empty())
-            })
-            .collect();
+        let fills = build_fill_spans(self, widget, data_min, data_max);
 
         self.points = points.into();
         self.point_colors = point_colors.into();
         self.series = series_spans.into();
         self.fills = fills.into();
+        self.bars = bars.into();
+        self.rasters = rasters.into();
+        self.error_bars = error_bar_spans.into();
+        self.decimated_view = decimated_any.then_some(decimated_view);
         self.vlines = vlines.into();
         self.hlines = hlines.into();
         self.data_min = data_min;
         self.data_max = data_max;
+        self.y2_data_range = y2_data_range;
+        self.built_y2_map = self.y2_map;
         self.legend_collapsed = widget.legend_collapsed;
         self.x_lim = widget.x_lim;
         self.y_lim = widget.y_lim;
-        self.x_axis_scale = widget.x_axis_scale;
-        self.y_axis_scale = widget.y_axis_scale;
+        self.y2_lim = widget.y2_lim;
         self.x_axis_link = widget.x_axis_link.clone();
         self.y_axis_link = widget.y_axis_link.clone();
+        self.y2_axis_link = widget.y2_axis_link.clone();
+        self.follow_latest = widget.follow_latest;
+        self.x_category_count = widget.x_categories.as_ref().map(|labels| labels.len());
 
         // highlighted_points
         self.sync_highlighted_points_from_widget(widget);
@@ -339,15 +685,284 @@ impl PlotState {
         // Copy formatters
         self.x_axis_formatter = widget.x_axis_formatter.clone();
         self.y_axis_formatter = widget.y_axis_formatter.clone();
+        self.y2_axis_formatter = widget.y2_axis_formatter.clone();
 
         // Force GPU buffers to rebuild only when data actually changes
         // (not when only hover/pick changes - that's tracked by highlight_version)
         self.markers_version = self.markers_version.wrapping_add(1);
         self.lines_version = self.lines_version.wrapping_add(1);
         self.fills_version = self.fills_version.wrapping_add(1);
+        self.layout_version = self.layout_version.wrapping_add(1);
+        self.error_bars_version = self.error_bars_version.wrapping_add(1);
+    }
+
+    /// Whether the view left the range decimated series were reduced for, or was zoomed or
+    /// resized enough that their columns no longer match the pixels.
+    pub(crate) fn decimation_outdated(&self) -> bool {
+        let Some(view) = self.decimated_view else {
+            return false;
+        };
+        let [left, right] = self.camera.x_range();
+        let current = DecimatedView::around([left, right], self.bounds.width.max(1.0) as usize);
+        let zoom = current.column_width() / view.column_width();
+        left < view.x_range[0] || right > view.x_range[1] || !(0.8..=1.25).contains(&zoom)
+    }
+
+    /// Whether the view moved or zoomed away from the one curved lines were tessellated for,
+    /// so they need tessellating again to stay smooth.
+    pub(crate) fn curves_outdated(&self) -> bool {
+        if CurveView::around(&self.camera, &self.bounds).is_none() {
+            return false;
+        }
+        self.series.iter().any(|span| {
+            span.line_style
+                .is_some_and(|style| style.interpolation.is_curve())
+                && span
+                    .curve_view
+                    .is_none_or(|view| !view.covers(&self.camera, &self.bounds))
+        })
+    }
+
+    /// Sync points appended with [`PlotWidget::append_series_points`] without rebuilding
+    /// the other series.
+    ///
+    /// Returns false if the changes can't be applied incrementally (e.g. a decimated or
+    /// bar series was appended to); the caller should then fall back to
+    /// [`rebuild_from_widget`](Self::rebuild_from_widget).
+    pub(crate) fn append_from_widget(&mut self, widget: &PlotWidget) -> bool {
+        if self.y2_map != self.built_y2_map {
+            return false;
+        }
+        let mut spans = self.series.to_vec();
+        let mut updates = Vec::new();
+
+        for (id, series) in &widget.series {
+            if widget.hidden_shapes.contains(id) {
+                continue;
+            }
+            let dropped = widget.dropped_points.get(id).copied().unwrap_or(0);
+            let span_index = spans.iter().position(|span| span.id == *id);
+            let Some(span_index) = span_index else {
+                if series.positions.is_empty() {
+                    continue;
+                }
+                // The series was empty during the last rebuild and has no span yet.
+                return false;
+            };
+            let span = &spans[span_index];
+            if span.source_dropped == dropped && span.source_len == series.positions.len() {
+                continue;
+            }
+            if !supports_incremental_append(series) {
+                return false;
+            }
+
+            // Positions removed from the front, and the first position that is new.
+            let removed = (dropped - span.source_dropped) as usize;
+            let first_new = (span.source_dropped as usize + span.source_len)
+                .saturating_sub(dropped as usize)
+                .min(series.positions.len());
+
+            let removed_points = span.point_indices.partition_point(|&i| i < removed);
+            let mut point_indices: Vec<usize> = span.point_indices[removed_points..]
+                .iter()
+                .map(|&i| i - removed)
+                .collect();
+            let mut line_breaks: Vec<usize> = span
+                .line_breaks
+                .iter()
+                .filter(|&&b| b > removed_points)
+                .map(|&b| b - removed_points)
+                .collect();
+            let mut new_points =
+                self.points[span.start + removed_points..span.start + span.len].to_vec();
+            let mut new_colors =
+                self.point_colors[span.start + removed_points..span.start + span.len].to_vec();
+
+            let (size, size_mode) = series
+                .marker_style
+                .as_ref()
+                .map(|ms| ms.size.to_raw())
+                .unwrap_or((1.0, crate::point::MARKER_SIZE_PIXELS));
+            let mut skipped = point_indices
+                .last()
+                .is_some_and(|&last| last + 1 < first_new);
+            let kept = new_points.len();
+            let y_map = self.y_map(series.y_axis);
+            for pos_index in first_new..series.positions.len() {
+                let Some(transformed) = self.data_point_to_plot(
+                    series.positions[pos_index],
+                    series.y_axis,
+                    &series.transform,
+                ) else {
+                    skipped = true;
+                    continue;
+                };
+                if std::mem::take(&mut skipped) && !point_indices.is_empty() {
+                    line_breaks.push(point_indices.len());
+                }
+                new_points.push(Point {
+                    position: transformed,
+                    size,
+                    size_mode,
+                });
+                new_colors.push(
+                    series
+                        .point_colors
+                        .as_ref()
+                        .and_then(|colors| colors.get(pos_index))
+                        .copied()
+                        .unwrap_or(series.color),
+                );
+                point_indices.push(pos_index);
+            }
+
+            // Extend the bounds with the new points. Streams with a maximum length also
+            // keep per-block bounds, so points falling off the front only rescan the block
+            // they fell from.
+            let padding =
+                world_marker_padding(series, widget.x_axis_scale, self.y_scale(series.y_axis));
+            let mut bounds = span.bounds;
+            let mut block_bounds = span.block_bounds.clone();
+            block_bounds.drop_front(removed_points, |i| {
+                point_bounds(&new_points[i], y_map, padding)
+            });
+            for point in &new_points[kept..] {
+                let point_bounds = point_bounds(point, y_map, padding);
+                if span.max_len.is_some() {
+                    block_bounds.push(point_bounds);
+                }
+                bounds = bounds.union(point_bounds);
+            }
+            if removed_points > 0 {
+                bounds = block_bounds.total();
+            }
+
+            // The index over the span's markers stays valid for sorted appends.
+            let index = span.index.get().and_then(|index| {
+                index.extended(removed_points, new_points.len(), |i| {
+                    marker_center_world(&new_points[i]).to_array()
+                })
+            });
+
+            let span = &mut spans[span_index];
+            span.ring_head += removed_points as u64;
+            span.point_indices = point_indices.into();
+            span.line_breaks = line_breaks.into();
+            span.bounds = bounds;
+            span.block_bounds = block_bounds;
+            span.source_dropped = dropped;
+            span.source_len = series.positions.len();
+            span.index = Arc::new(index.map(OnceLock::from).unwrap_or_default());
+            span.paths = Arc::default();
+            span.segments = Arc::default();
+            updates.push((span_index, new_points, new_colors));
+        }
+
+        if updates.is_empty() {
+            return true;
+        }
+
+        let appended: Vec<ShapeId> = updates
+            .iter()
+            .map(|&(span_index, _, _)| spans[span_index].id)
+            .collect();
+
+        // Splice the updated spans back into the shared point buffers.
+        let mut points = Vec::with_capacity(self.points.len());
+        let mut point_colors = Vec::with_capacity(self.point_colors.len());
+        let mut updates = updates.into_iter().peekable();
+        for (span_index, span) in spans.iter_mut().enumerate() {
+            let start = points.len();
+            if let Some((_, new_points, new_colors)) =
+                updates.next_if(|(index, _, _)| *index == span_index)
+            {
+                points.extend(new_points);
+                point_colors.extend(new_colors);
+            } else {
+                points.extend_from_slice(&self.points[span.start..span.start + span.len]);
+                point_colors
+                    .extend_from_slice(&self.point_colors[span.start..span.start + span.len]);
+            }
+            span.start = start;
+            span.len = points.len() - start;
+        }
+
+        let contours = self.contour_bounds(widget);
+        let (bounds, y2_data_range) = combined_bounds(&spans, &self.bars, &self.rasters, &contours);
+        let (data_min, data_max) = bounds.to_min_max();
+        let domain_changed = (data_min, data_max) != (self.data_min, self.data_max);
+        self.points = points.into();
+        self.point_colors = point_colors.into();
+        self.series = spans.into();
+        self.data_min = data_min;
+        self.data_max = data_max;
+        self.y2_data_range = y2_data_range;
+        self.follow_latest = widget.follow_latest;
+
+        if let Some(fills) =
+            refresh_fill_spans(self, widget, &appended, domain_changed, data_min, data_max)
+        {
+            self.fills = fills.into();
+            self.fills_version = self.fills_version.wrapping_add(1);
+        }
+        self.markers_version = self.markers_version.wrapping_add(1);
+        self.lines_version = self.lines_version.wrapping_add(1);
+        true
     }
 
     pub(crate) fn autoscale(&mut self, update_axis_links: bool) {
+        let (min_v, max_v) = self.autoscale_bounds();
+        self.camera.set_bounds(min_v, max_v, AUTOSCALE_PADDING);
+        self.fit_secondary_axis();
+        if update_axis_links {
+            self.update_axis_links();
+        }
+    }
+
+    /// Autoscale after appending points. The render offset is kept so that geometry
+    /// already uploaded to the GPU stays valid, until the view drifts far enough from it
+    /// to cost precision.
+    pub(crate) fn autoscale_after_append(&mut self) {
+        const MAX_OFFSET_DRIFT: f64 = 64.0;
+        let (min_v, max_v) = self.autoscale_bounds();
+        self.camera
+            .set_bounds_preserve_offset(min_v, max_v, AUTOSCALE_PADDING);
+        self.fit_secondary_axis();
+        let drift = (self.camera.position - self.camera.render_offset).abs();
+        if drift.x > MAX_OFFSET_DRIFT * self.camera.half_extents.x
+            || drift.y > MAX_OFFSET_DRIFT * self.camera.half_extents.y
+        {
+            self.camera.render_offset = self.camera.position;
+        }
+        self.update_axis_links();
+    }
+
+    /// Fit the secondary y-axis to its limits, or else its data, over the primary y view.
+    ///
+    /// The map is left unchanged if the secondary axis has neither.
+    fn fit_secondary_axis(&mut self) {
+        let limits = self.y2_lim.and_then(|(min, max)| {
+            Some([
+                self.y2_axis_scale.data_to_plot(min)?,
+                self.y2_axis_scale.data_to_plot(max)?,
+            ])
+        });
+        let Some([min, max]) = limits.or(self.y2_data_range) else {
+            return;
+        };
+        // Pad like `Camera::set_bounds`, so the data sits where primary data would.
+        let half_extent = (max - min).max(EPSILON_SMALL) * (1.0 + AUTOSCALE_PADDING) / 2.0;
+        let center = (min + max) / 2.0;
+        if let Some(map) = SecondaryAxisMap::between(
+            self.camera.y_range(),
+            [center - half_extent, center + half_extent],
+        ) {
+            self.y2_map = map;
+        }
+    }
+
+    fn autoscale_bounds(&self) -> (DVec2, DVec2) {
         // Use user-specified limits if available, otherwise use data bounds
         let mut min_v = DVec2::new(-1.0, -1.0);
         let mut max_v = DVec2::new(1.0, 1.0);
@@ -357,6 +972,21 @@ impl PlotState {
             max_v = data_max;
         }
 
+        // Follow mode shows a fixed-width window ending at the newest x value.
+        if let (Some(window), Some(data_max)) = (self.follow_latest, self.data_max)
+            && let Some(latest) = self.x_axis_scale.plot_to_data(data_max.x)
+            && let Some(start) = self.x_axis_scale.data_to_plot(latest - window)
+        {
+            min_v.x = start;
+            max_v.x = data_max.x;
+        }
+
+        // A categorical axis shows all of its categories.
+        if let Some([x_min, x_max]) = self.category_range() {
+            min_v.x = x_min;
+            max_v.x = x_max;
+        }
+
         if let Some((y_min, y_max)) = self.y_lim
             && let (Some(y_min), Some(y_max)) = (
                 self.y_axis_scale.data_to_plot(y_min),
@@ -377,16 +1007,14 @@ impl PlotState {
             max_v.x = x_max;
         }
 
-        self.camera.set_bounds(min_v, max_v, 0.05);
-        if update_axis_links {
-            self.update_axis_links();
-        }
+        (min_v, max_v)
     }
 
     pub(crate) fn update_ticks(
         &mut self,
         x_tick_producer: Option<&TickProducer>,
         y_tick_producer: Option<&TickProducer>,
+        y2_tick_producer: Option<&TickProducer>,
     ) {
         // Calculate x-axis ticks
         let min_x_plot = self.camera.position.x - self.camera.half_extents.x;
@@ -447,6 +1075,40 @@ impl PlotState {
                 self.y_ticks.push(PositionedTick { screen_pos, tick });
             }
         }
+
+        // Secondary y-axis ticks are produced over the secondary view, then mapped back to
+        // primary plot space for positioning.
+        self.y2_ticks.clear();
+        if !self.has_secondary_y_axis {
+            return;
+        }
+        let [min_y2_plot, max_y2_plot] = self.y2_map.range_to_secondary([min_y_plot, max_y_plot]);
+        let min_y2 = self
+            .y2_axis_scale
+            .plot_to_data(min_y2_plot)
+            .unwrap_or(min_y2_plot);
+        let max_y2 = self
+            .y2_axis_scale
+            .plot_to_data(max_y2_plot)
+            .unwrap_or(max_y2_plot);
+
+        let y2_tick_values = match y2_tick_producer {
+            Some(producer) => producer(min_y2, max_y2),
+            None => Vec::new(),
+        };
+
+        for tick in y2_tick_values {
+            let Some(tick_plot) = self.y2_axis_scale.data_to_plot(tick.value) else {
+                continue;
+            };
+            if let Some(screen_pos) = world_to_screen_position_y(
+                self.y2_map.to_primary(tick_plot),
+                &self.camera,
+                &self.bounds,
+            ) {
+                self.y2_ticks.push(PositionedTick { screen_pos, tick });
+            }
+        }
     }
 
     pub(crate) fn point_inside(&self, x: f32, y: f32) -> bool {
@@ -482,22 +1144,24 @@ impl PlotState {
     }
 
     pub(crate) fn drag_in_progress(&self) -> bool {
-        self.pan.active || self.selection.active || self.drag.active
+        self.pan.active || self.selection.active || self.drag.active || self.ruler.active
     }
 
     fn drag_in_progress_for(&self, button: mouse::Button) -> bool {
         (self.pan.active && self.pan.button == Some(button))
             || (self.selection.active && self.selection.button == Some(button))
             || (self.drag.active && self.drag.button == Some(button))
+            || (self.ruler.active && self.ruler.button == Some(button))
     }
 
+    /// Handle a mouse event, recording the events to publish in `effects`. Returns whether
+    /// the plot needs a redraw.
     pub(crate) fn handle_mouse_event(
         &mut self,
         event: Event,
         cursor: mouse::Cursor,
         widget: &PlotWidget,
-        publish_hover_pick: &mut Option<HoverPickEvent>,
-        publish_drag_event: &mut Option<DragEvent>,
+        effects: &mut UpdateEffects,
     ) -> bool {
         // Only request redraws when something actually changes or when we need
         // to service a picking request for a new cursor position.
@@ -519,7 +1183,7 @@ impl PlotState {
 
                 self.cursor_position = position;
                 // Update crosshairs position when enabled
-                if widget.crosshairs_enabled {
+                if widget.crosshairs_enabled || widget.data_cursor.is_some() {
                     self.crosshairs_position = self.cursor_position;
                     needs_redraw = true;
                 }
@@ -528,6 +1192,13 @@ impl PlotState {
                 if self.selection.active {
                     self.selection.end = self.cursor_position;
                     self.selection.moved = true;
+                    if self.selection.kind == SelectionKind::Lasso
+                        && self.selection.path.last().is_none_or(|last| {
+                            last.distance(self.cursor_position) >= LASSO_SPACING_PX
+                        })
+                    {
+                        self.selection.path.push(self.cursor_position);
+                    }
                     needs_redraw = true;
                 }
 
@@ -545,6 +1216,7 @@ impl PlotState {
 
                     // Update camera position by applying the render space delta
                     self.camera.position = self.pan.start_camera_center - render_delta;
+                    self.clamp_to_categories();
                     self.update_axis_links();
                     needs_redraw = true;
                 }
@@ -553,7 +1225,19 @@ impl PlotState {
                     && let Some(button) = self.drag.button
                     && let Some(world) = self.cursor_world_data(viewport)
                 {
-                    *publish_drag_event = Some(DragEvent::Update { button, world });
+                    effects.drag_event = Some(DragEvent::Update { button, world });
+                }
+
+                if self.ruler.active {
+                    self.ruler.moved |= (self.cursor_position - self.press.start).length()
+                        > widget.controls.drag_delta_threshold();
+                    if self.ruler.moved
+                        && let Some(end) = self.ruler_end(widget, viewport)
+                    {
+                        self.ruler.measurement = self.ruler_measurement(end, false);
+                        effects.measurement.clone_from(&self.ruler.measurement);
+                        needs_redraw = true;
+                    }
                 }
 
                 // Hover picking (only when not panning or selecting)
@@ -573,6 +1257,7 @@ impl PlotState {
                     }
                 }
             }
+            #[allow(clippy::collapsible_match)]
             Event::CursorLeft => {
                 // Clear hover state on leave and request a redraw to clear hover halo
                 if self.picking.last_hover_cache.is_some() {
@@ -599,11 +1284,21 @@ impl PlotState {
 
                 if !double_click_pending {
                     match widget.controls.drag_action(button) {
-                        Some(DragAction::BoxZoom) => {
+                        Some(
+                            action @ (DragAction::BoxZoom
+                            | DragAction::BoxSelect
+                            | DragAction::LassoSelect),
+                        ) => {
                             self.selection.active = true;
                             self.selection.button = Some(button);
+                            self.selection.kind = match action {
+                                DragAction::BoxSelect => SelectionKind::Box,
+                                DragAction::LassoSelect => SelectionKind::Lasso,
+                                _ => SelectionKind::Zoom,
+                            };
                             self.selection.start = self.cursor_position;
                             self.selection.end = self.cursor_position;
+                            self.selection.path = vec![self.cursor_position];
                             self.selection.moved = false;
                             needs_redraw = true;
                         }
@@ -613,6 +1308,12 @@ impl PlotState {
                             self.pan.start_cursor = self.cursor_position.into();
                             self.pan.start_camera_center = self.camera.position;
                         }
+                        Some(DragAction::Measure) => {
+                            self.ruler.active = true;
+                            self.ruler.button = Some(button);
+                            self.ruler.moved = false;
+                            self.ruler.start = self.ruler_end(widget, viewport);
+                        }
                         _ => {}
                     }
 
@@ -620,7 +1321,7 @@ impl PlotState {
                         self.drag.active = true;
                         self.drag.button = Some(button);
                         if let Some(world) = self.cursor_world_data(viewport) {
-                            *publish_drag_event = Some(DragEvent::Start { button, world });
+                            effects.drag_event = Some(DragEvent::Start { button, world });
                         }
                     }
                 }
@@ -644,7 +1345,7 @@ impl PlotState {
                     false
                 };
 
-                let click_candidate = release_position_available
+                let mut click_candidate = release_position_available
                     && self.press.button == Some(button)
                     && (self.cursor_position - self.press.start).length()
                         <= widget.controls.drag_delta_threshold();
@@ -653,7 +1354,7 @@ impl PlotState {
                     && self.drag.button == Some(button)
                     && let Some(world) = self.cursor_world_data(viewport)
                 {
-                    *publish_drag_event = Some(DragEvent::End { button, world });
+                    effects.drag_event = Some(DragEvent::End { button, world });
                     self.drag.active = false;
                     self.drag.button = None;
                 }
@@ -665,12 +1366,37 @@ impl PlotState {
                     self.pan.active = false;
                     self.pan.button = None;
                 }
+                if self.ruler.active && self.ruler.button == Some(button) {
+                    // A click leaves the last measurement in place.
+                    if self.ruler.moved
+                        && let Some(end) = self.ruler_end(widget, viewport)
+                    {
+                        self.ruler.measurement = self.ruler_measurement(end, true);
+                        effects.measurement.clone_from(&self.ruler.measurement);
+                    }
+                    self.ruler.active = false;
+                    self.ruler.button = None;
+                    needs_redraw = true;
+                }
                 if self.selection.active && self.selection.button == Some(button) {
                     self.selection.end = self.cursor_position;
-                    let delta = self.selection.end - self.selection.start;
-                    let dragged = delta.length() > widget.controls.drag_delta_threshold();
-                    // Perform zoom if user actually dragged a region of non-trivial size
-                    if dragged {
+                    let start = self.selection.start;
+                    let threshold = widget.controls.drag_delta_threshold();
+                    // A lasso can end where it started, so any vertex far enough away counts.
+                    let dragged = if self.selection.kind == SelectionKind::Lasso {
+                        let dragged = self
+                            .selection
+                            .path
+                            .iter()
+                            .any(|p| (*p - start).length() > threshold);
+                        click_candidate &= !dragged;
+                        dragged
+                    } else {
+                        (self.selection.end - start).length() > threshold
+                    };
+                    if dragged && self.selection.kind != SelectionKind::Zoom {
+                        effects.selection = Some(self.selection_event());
+                    } else if dragged {
                         // Convert screen (pixels) to world coords using camera helper
                         let p1 = self.camera.screen_to_world(
                             DVec2::new(
@@ -691,16 +1417,20 @@ impl PlotState {
                             max_v,
                             widget.controls.selection_padding(),
                         );
+                        self.clamp_to_categories();
                         self.update_axis_links();
                     }
                     // Clear selection overlay after release
                     self.selection.active = false;
                     self.selection.button = None;
+                    self.selection.path.clear();
                     self.selection.moved = false;
                     needs_redraw = true;
                 }
 
-                if click_candidate && self.handle_mouse_click(button, widget, publish_hover_pick) {
+                if click_candidate
+                    && self.handle_mouse_click(button, widget, &mut effects.hover_pick)
+                {
                     needs_redraw = true;
                 }
 
@@ -735,6 +1465,7 @@ impl PlotState {
                             y as f64 * (self.camera.half_extents.y / (viewport.y / 2.0));
                         self.camera.position.x += world_pan_x;
                         self.camera.position.y += world_pan_y;
+                        self.clamp_to_categories();
                         self.update_axis_links();
                         needs_redraw = true;
                     }
@@ -787,6 +1518,31 @@ impl PlotState {
         }
     }
 
+    /// Plot-space x range spanned by the categories of a categorical x-axis, from half a
+    /// category before the first to half a category after the last. None when explicit
+    /// x limits frame the view instead.
+    fn category_range(&self) -> Option<[f64; 2]> {
+        if self.x_lim.is_some() {
+            return None;
+        }
+        let count = self.x_category_count.filter(|&count| count > 0)?;
+        Some([-0.5, count as f64 - 0.5])
+    }
+
+    /// Keep the view of a categorical x-axis between one category and all of them (as
+    /// framed by autoscale), and within the categories.
+    fn clamp_to_categories(&mut self) {
+        let Some([min, max]) = self.category_range() else {
+            return;
+        };
+        let max_half_extent = (max - min) * (1.0 + AUTOSCALE_PADDING) / 2.0;
+        let half_extent = self.camera.half_extents.x.clamp(0.5, max_half_extent);
+        let center = (min + max) / 2.0;
+        let slack = max_half_extent - half_extent;
+        self.camera.half_extents.x = half_extent;
+        self.camera.position.x = self.camera.position.x.clamp(center - slack, center + slack);
+    }
+
     fn update_axis_links(&mut self) {
         if let Some(ref link) = self.x_axis_link {
             link.set(self.camera.position.x, self.camera.half_extents.x);
@@ -796,6 +1552,11 @@ impl PlotState {
             link.set(self.camera.position.y, self.camera.half_extents.y);
             self.y_link_version = link.version();
         }
+        if let Some(ref link) = self.y2_axis_link {
+            let [min, max] = self.y2_map.range_to_secondary(self.camera.y_range());
+            link.set((min + max) / 2.0, (max - min) / 2.0);
+            self.y2_link_version = link.version();
+        }
     }
 
     fn is_double_click(&self, button: mouse::Button) -> bool {
@@ -861,17 +1622,68 @@ impl PlotState {
             return;
         }
 
-        let picked = if let Some(HoverPickEvent::Hover(point_id)) = *publish_hover_pick {
-            Some(point_id)
+        let picked = if let Some(HoverPickEvent::Hover(hit)) = *publish_hover_pick {
+            Some(hit)
         } else {
             widget.pick_hit(self)
         };
 
-        if let Some(point_id) = picked {
-            *publish_hover_pick = Some(HoverPickEvent::Pick(point_id));
+        if let Some(hit) = picked {
+            *publish_hover_pick = Some(HoverPickEvent::Pick(hit));
+        }
+    }
+
+    /// The points inside the finished selection rectangle or lasso, combined with the
+    /// current selection as the held modifiers ask.
+    fn selection_event(&mut self) -> SelectionEvent {
+        let (start, end) = (self.selection.start, self.selection.end);
+        let polygon = match self.selection.kind {
+            SelectionKind::Lasso => {
+                let mut path = std::mem::take(&mut self.selection.path);
+                path.push(end);
+                path
+            }
+            _ => vec![
+                start,
+                Vec2::new(end.x, start.y),
+                end,
+                Vec2::new(start.x, end.y),
+            ],
+        };
+        let mode = if self.modifiers.shift() {
+            SelectionMode::Add
+        } else if self.modifiers.control() {
+            SelectionMode::Subtract
+        } else {
+            SelectionMode::Replace
+        };
+        let (_, targets) = self.picking_and_targets();
+        SelectionEvent {
+            mode,
+            points: cpu_select(&targets, &polygon),
         }
     }
 
+    /// The picking state, alongside the shapes it hit-tests.
+    pub(crate) fn picking_and_targets(&mut self) -> (&mut PickingState, PickTargets<'_>) {
+        let targets = PickTargets {
+            points: &self.points,
+            series: &self.series,
+            bars: &self.bars,
+            fills: &self.fills,
+            rasters: &self.rasters,
+            vlines: &self.vlines,
+            hlines: &self.hlines,
+            camera: &self.camera,
+            bounds: &self.bounds,
+            x_axis_scale: self.x_axis_scale,
+            y_axis_scale: self.y_axis_scale,
+            y2_axis_scale: self.y2_axis_scale,
+            y2_map: self.y2_map,
+        };
+        (&mut self.picking, targets)
+    }
+
     fn zoom_at_cursor(&mut self, scroll_y: f32, viewport: DVec2) {
         let zoom_factor = if scroll_y > 0.0 { 0.95 } else { 1.05 };
 
@@ -888,6 +1700,7 @@ impl PlotState {
         );
 
         self.camera.position += cursor_render_before - cursor_render_after;
+        self.clamp_to_categories();
         self.update_axis_links();
     }
 
@@ -901,9 +1714,37 @@ impl PlotState {
         };
 
         self.camera.position += pan_delta;
+        self.clamp_to_categories();
         self.update_axis_links();
     }
 
+    /// The ruler end under the cursor, snapped to the nearest data point when the widget
+    /// snaps measurements.
+    fn ruler_end(&mut self, widget: &PlotWidget, viewport: DVec2) -> Option<RulerEnd> {
+        if widget.measure_snap {
+            let (x_scale, y_scale) = (self.x_axis_scale, self.y_axis_scale);
+            let (cursor, radius_px) = (self.cursor_position, self.hover_radius_px);
+            let (_, targets) = self.picking_and_targets();
+            if let Some((point, plot)) = cpu_snap(&targets, cursor, radius_px)
+                && let Some(data) = plot_point_to_data(plot, x_scale, y_scale)
+            {
+                return Some((data, Some(point)));
+            }
+        }
+        self.cursor_world_data(viewport).map(|data| (data, None))
+    }
+
+    fn ruler_measurement(&self, end: RulerEnd, finished: bool) -> Option<Measurement> {
+        let (start, start_point) = self.ruler.start?;
+        let (end, end_point) = end;
+        Some(Measurement {
+            start_point,
+            end_point,
+            finished,
+            ..Measurement::between(start, end, self.x_axis_scale, self.y_axis_scale)?
+        })
+    }
+
     fn cursor_world_data(&self, viewport: DVec2) -> Option<[f64; 2]> {
         let plot = self.camera.screen_to_world(
             DVec2::new(self.cursor_position.x as f64, self.cursor_position.y as f64),
@@ -915,9 +1756,17 @@ impl PlotState {
 
 #[derive(Debug, Clone)]
 pub(crate) struct FillSpan {
+    /// The fill reported by hover and pick. Contour bands, violin bodies and unpickable
+    /// fills have none.
+    pub(crate) fill_id: Option<ShapeId>,
+    /// The fill this span was built for. Contour bands and violin bodies have none.
+    pub(crate) source: Option<ShapeId>,
     pub(crate) color: Color,
     /// Triangle list vertices in plot/world coordinates.
     pub(crate) vertices: Arc<[[f64; 2]]>,
+    /// The y-axis hits report positions on: that of the first series the fill is bounded
+    /// by, if any.
+    pub(crate) y_axis: YAxis,
 }
 
 enum FillEndpoint<'a> {
@@ -977,30 +1826,274 @@ fn plot_y_domain(
     }
 }
 
-fn transformed_series_points(
-    series: &crate::Series,
-    x_axis_scale: AxisScale,
-    y_axis_scale: AxisScale,
-    axis_ranges: ([f64; 2], [f64; 2]),
-) -> Vec<[f64; 2]> {
-    series
-        .positions
+fn build_fill_spans(
+    state: &PlotState,
+    widget: &PlotWidget,
+    data_min: Option<DVec2>,
+    data_max: Option<DVec2>,
+) -> Vec<FillSpan> {
+    let x_domain = plot_x_domain(widget, data_min, data_max);
+    let y_domain = plot_y_domain(widget, data_min, data_max);
+    // Contour bands are drawn below the other fills.
+    let contour_bands = widget
+        .contours
+        .values()
+        .flat_map(|contour| &contour.bands)
+        .filter_map(|(color, vertices)| {
+            let vertices: Vec<[f64; 2]> = vertices
+                .chunks_exact(3)
+                .filter_map(|triangle| {
+                    triangle
+                        .iter()
+                        .map(|&p| state.data_point_to_plot(p, YAxis::Primary, &Default::default()))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .flatten()
+                .collect();
+            (!vertices.is_empty()).then(|| FillSpan {
+                fill_id: None,
+                source: None,
+                color: *color,
+                vertices: vertices.into(),
+                y_axis: YAxis::Primary,
+            })
+        });
+    let violin_bodies = widget
+        .distributions
         .iter()
-        .filter_map(|&p| {
-            data_point_to_plot_with_transform(
-                p,
-                x_axis_scale,
-                y_axis_scale,
-                &series.transform,
-                Some(axis_ranges),
-            )
-        })
-        .collect()
+        .filter(|(id, _)| !widget.hidden_shapes.contains(id))
+        .filter_map(|(_, plot)| {
+            let vertices: Vec<[f64; 2]> = plot
+                .bodies
+                .chunks_exact(3)
+                .filter_map(|triangle| {
+                    triangle
+                        .iter()
+                        .map(|&p| state.data_point_to_plot(p, YAxis::Primary, &Default::default()))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .flatten()
+                .collect();
+            let mut color = plot.color;
+            color.a *= plot.fill_opacity;
+            (!vertices.is_empty()).then(|| FillSpan {
+                fill_id: None,
+                source: None,
+                color,
+                vertices: vertices.into(),
+                y_axis: YAxis::Primary,
+            })
+        });
+    let fills = visible_fills(widget).filter_map(|(fill_id, fill)| {
+        build_user_fill_span(state, widget, *fill_id, fill, x_domain, y_domain)
+    });
+    contour_bands.chain(violin_bodies).chain(fills).collect()
 }
 
-/// Keep only strictly increasing-x points in their original order.
-///
-/// This avoids sorting and lets fill interpolation run in linear time.
+/// Rebuild the spans of the fills an append can change, and keep the others: fills bounded
+/// by an appended series, and fills between reference lines if the data domain changed.
+/// Returns `None` if no fill needs rebuilding.
+fn refresh_fill_spans(
+    state: &PlotState,
+    widget: &PlotWidget,
+    appended: &[ShapeId],
+    domain_changed: bool,
+    data_min: Option<DVec2>,
+    data_max: Option<DVec2>,
+) -> Option<Vec<FillSpan>> {
+    let affected = |fill: &Fill| {
+        let [begin, end] = [fill.begin, fill.end];
+        appended.contains(&begin)
+            || appended.contains(&end)
+            || (domain_changed
+                && !widget.series.contains_key(&begin)
+                && !widget.series.contains_key(&end))
+    };
+    if !visible_fills(widget).any(|(_, fill)| affected(fill)) {
+        return None;
+    }
+
+    let x_domain = plot_x_domain(widget, data_min, data_max);
+    let y_domain = plot_y_domain(widget, data_min, data_max);
+    let unchanged = state
+        .fills
+        .iter()
+        .filter(|span| span.source.is_none())
+        .cloned();
+    let fills = visible_fills(widget).filter_map(|(fill_id, fill)| {
+        if affected(fill) {
+            build_user_fill_span(state, widget, *fill_id, fill, x_domain, y_domain)
+        } else {
+            let previous = state
+                .fills
+                .iter()
+                .find(|span| span.source == Some(*fill_id));
+            previous.cloned()
+        }
+    });
+    Some(unchanged.chain(fills).collect())
+}
+
+/// The fills drawn: not hidden, and neither is either endpoint.
+fn visible_fills(widget: &PlotWidget) -> impl Iterator<Item = (&ShapeId, &Fill)> {
+    widget.fills.iter().filter(|(fill_id, fill)| {
+        !widget.hidden_shapes.contains(fill_id)
+            && !widget.hidden_shapes.contains(&fill.begin)
+            && !widget.hidden_shapes.contains(&fill.end)
+    })
+}
+
+fn build_user_fill_span(
+    state: &PlotState,
+    widget: &PlotWidget,
+    fill_id: ShapeId,
+    fill: &Fill,
+    x_domain: Option<(f64, f64)>,
+    y_domain: Option<(f64, f64)>,
+) -> Option<FillSpan> {
+    let mut span = build_fill_span(
+        state, widget, fill.begin, fill.end, fill.color, x_domain, y_domain,
+    )?;
+    span.fill_id = fill.pickable.then_some(fill_id);
+    span.source = Some(fill_id);
+    Some(span)
+}
+
+/// The plot-space outline of a series' line, following its interpolation.
+fn transformed_series_points(series: &crate::Series, state: &PlotState) -> Vec<[f64; 2]> {
+    let points: Vec<[f64; 2]> = series
+        .positions
+        .iter()
+        .filter_map(|&p| state.data_point_to_plot(p, series.y_axis, &series.transform))
+        .collect();
+    match series.line_style.map(|style| style.interpolation) {
+        Some(interpolation) if interpolation != LineInterpolation::Linear => interpolation
+            .path(
+                &points,
+                CurveView::around(&state.camera, &state.bounds).as_ref(),
+            )
+            .into_iter()
+            .map(|(p, _)| p)
+            .collect(),
+        _ => points,
+    }
+}
+
+/// Build error bar whiskers for a series in plot coordinates.
+///
+/// Whiskers whose ends can't be mapped by the axis scale (e.g. non-positive on log axes) are skipped.
+fn build_error_bars(
+    series: &crate::Series,
+    x_axis_scale: AxisScale,
+    y_axis_scale: AxisScale,
+    axis_ranges: ([f64; 2], [f64; 2]),
+) -> Vec<ErrorBar> {
+    if series.x_errors.is_none() && series.y_errors.is_none() {
+        return Vec::new();
+    }
+
+    let to_plot = |p| {
+        data_point_to_plot_with_transform(
+            p,
+            x_axis_scale,
+            y_axis_scale,
+            &series.transform,
+            Some(axis_ranges),
+        )
+    };
+    let mut out = Vec::new();
+    for (index, &[x, y]) in series.positions.iter().enumerate() {
+        let color = series.error_bar_style.color.unwrap_or_else(|| {
+            series
+                .point_colors
+                .as_ref()
+                .and_then(|colors| colors.get(index))
+                .copied()
+                .unwrap_or(series.color)
+        });
+        if let Some((lower, upper)) = series.x_errors.as_ref().and_then(|e| e.get(index))
+            && let Some(start) = to_plot([x - lower, y])
+            && let Some(end) = to_plot([x + upper, y])
+        {
+            out.push(ErrorBar {
+                start,
+                end,
+                vertical: false,
+                color,
+            });
+        }
+        if let Some((lower, upper)) = series.y_errors.as_ref().and_then(|e| e.get(index))
+            && let Some(start) = to_plot([x, y - lower])
+            && let Some(end) = to_plot([x, y + upper])
+        {
+            out.push(ErrorBar {
+                start,
+                end,
+                vertical: true,
+                color,
+            });
+        }
+    }
+    out
+}
+
+/// Index range of the positions inside the plot-space x-range, plus one neighbor on each
+/// side so lines continue to the plot edges. Assumes the finite x values are sorted in
+/// increasing order, as [`sorted_by_x`] checks.
+fn visible_index_window(
+    positions: &VecDeque<[f64; 2]>,
+    [left, right]: [f64; 2],
+    plot_x: impl Fn([f64; 2]) -> Option<f64>,
+) -> std::ops::Range<usize> {
+    let start = partition_sorted_x(positions, |p| plot_x(p).is_none_or(|x| x < left));
+    let end = partition_sorted_x(positions, |p| plot_x(p).is_none_or(|x| x <= right));
+    start.saturating_sub(1)..(end + 1).min(positions.len())
+}
+
+/// Like [`VecDeque::partition_point`], but gap points with a non-finite x take the order
+/// of the next point with a finite x, so they can't break the binary search.
+fn partition_sorted_x(positions: &VecDeque<[f64; 2]>, pred: impl Fn([f64; 2]) -> bool) -> usize {
+    let (mut low, mut high) = (0, positions.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match (mid..high).find(|&i| positions[i][0].is_finite()) {
+            Some(i) if pred(positions[i]) => low = i + 1,
+            _ => high = mid,
+        }
+    }
+    low
+}
+
+/// The plot-space x-range decimated series are reduced for: the visible range padded by its
+/// width on each side, so panning doesn't need to re-decimate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DecimatedView {
+    x_range: [f64; 2],
+    /// Number of pixel columns across `x_range`.
+    columns: usize,
+}
+
+impl DecimatedView {
+    fn around([left, right]: [f64; 2], visible_columns: usize) -> Self {
+        let width = right - left;
+        Self {
+            x_range: [left - width, right + width],
+            columns: 3 * visible_columns,
+        }
+    }
+
+    fn column_width(&self) -> f64 {
+        (self.x_range[1] - self.x_range[0]) / self.columns as f64
+    }
+
+    fn column_of(&self, x: f64) -> i64 {
+        ((x - self.x_range[0]) / self.column_width()).floor() as i64
+    }
+}
+
+/// Keep only strictly increasing-x points in their original order.
+///
+/// This avoids sorting and lets fill interpolation run in linear time.
 /// Out-of-order (or duplicate-x) points are skipped.
 fn monotonic_increasing_x(points: Vec<[f64; 2]>) -> Vec<[f64; 2]> {
     let mut out = Vec::with_capacity(points.len());
@@ -1182,33 +2275,31 @@ fn push_interpolated_series_fill_vertices(
 }
 
 fn build_fill_span(
+    state: &PlotState,
     widget: &PlotWidget,
     begin: ShapeId,
     end: ShapeId,
     color: Color,
     x_domain: Option<(f64, f64)>,
     y_domain: Option<(f64, f64)>,
-    axis_ranges: ([f64; 2], [f64; 2]),
 ) -> Option<FillSpan> {
+    let axis_ranges = state.camera.axis_ranges();
     let begin_endpoint = resolve_fill_endpoint(widget, begin)?;
     let end_endpoint = resolve_fill_endpoint(widget, end)?;
+    let y_axis = [&begin_endpoint, &end_endpoint]
+        .into_iter()
+        .find_map(|endpoint| match endpoint {
+            FillEndpoint::Series(series) => Some(series.y_axis),
+            FillEndpoint::HLine(_) | FillEndpoint::VLine(_) => None,
+        })
+        .unwrap_or_default();
 
     let mut vertices: Vec<[f64; 2]> = Vec::new();
 
     match (begin_endpoint, end_endpoint) {
         (FillEndpoint::Series(sa), FillEndpoint::Series(sb)) => {
-            let a_points = transformed_series_points(
-                sa,
-                widget.x_axis_scale,
-                widget.y_axis_scale,
-                axis_ranges,
-            );
-            let b_points = transformed_series_points(
-                sb,
-                widget.x_axis_scale,
-                widget.y_axis_scale,
-                axis_ranges,
-            );
+            let a_points = transformed_series_points(sa, state);
+            let b_points = transformed_series_points(sb, state);
 
             if !push_paired_series_fill_vertices(&mut vertices, &a_points, &b_points) {
                 push_interpolated_series_fill_vertices(&mut vertices, a_points, b_points)?;
@@ -1222,12 +2313,7 @@ fn build_fill_span(
                 hline.transform.as_ref(),
                 Some(axis_ranges.1),
             )?;
-            let points = transformed_series_points(
-                series,
-                widget.x_axis_scale,
-                widget.y_axis_scale,
-                axis_ranges,
-            );
+            let points = transformed_series_points(series, state);
             for segment in points.windows(2) {
                 let p0 = segment[0];
                 let p1 = segment[1];
@@ -1244,12 +2330,7 @@ fn build_fill_span(
                 vline.transform.as_ref(),
                 Some(axis_ranges.0),
             )?;
-            let points = transformed_series_points(
-                series,
-                widget.x_axis_scale,
-                widget.y_axis_scale,
-                axis_ranges,
-            );
+            let points = transformed_series_points(series, state);
             for segment in points.windows(2) {
                 let p0 = segment[0];
                 let p1 = segment[1];
@@ -1296,21 +2377,383 @@ fn build_fill_span(
     }
 
     (!vertices.is_empty()).then_some(FillSpan {
+        fill_id: None,
+        source: None,
         color,
         vertices: vertices.into(),
+        y_axis,
     })
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct BarSpan {
+    pub(crate) id: ShapeId,
+    pub(crate) pickable: bool,
+    pub(crate) y_axis: YAxis,
+    /// Bar rectangles in plot/world coordinates.
+    pub(crate) rects: Arc<[BarRect]>,
+    pub(crate) bounds: DataBounds,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct RasterSpan {
+    pub(crate) id: ShapeId,
+    pub(crate) pickable: bool,
+    /// Cell layout in plot/world coordinates.
+    pub(crate) grid: RasterGrid,
+    pub(crate) sampling: RasterSampling,
+    /// Straight RGBA8 pixels, row by row. Shared with the widget's raster, so renderers can
+    /// tell when it changed.
+    pub(crate) pixels: Arc<[u8]>,
+    pub(crate) bounds: DataBounds,
+}
+
+impl RasterSpan {
+    /// Index (`row * columns + column`) of the visible cell containing `point`, if any.
+    pub(crate) fn cell_index_at(&self, point: [f64; 2]) -> Option<usize> {
+        let (row, column) = self.grid.cell_at(point)?;
+        let index = row * self.grid.columns + column;
+        // Transparent cells (e.g. NaN values) can't be hovered or picked.
+        (self.pixels.get(index * 4 + 3) != Some(&0)).then_some(index)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ErrorBarSpan {
+    pub(crate) style: ErrorBarStyle,
+    /// Error bar whiskers in plot/world coordinates.
+    pub(crate) bars: Arc<[ErrorBar]>,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct SeriesSpan {
     pub(crate) id: ShapeId,
     pub(crate) start: usize,
     pub(crate) len: usize,
     pub(crate) point_indices: Arc<[usize]>,
+    /// Local point indices that start a new polyline, because points before them were skipped.
+    pub(crate) line_breaks: Arc<[usize]>,
     pub(crate) line_style: Option<LineStyle>,
     pub(crate) color: Color,
     pub(crate) marker: u32,
     pub(crate) pickable: bool,
+    pub(crate) y_axis: YAxis,
+    /// Data bounds contributed by this series, in the plot space of its y-axis.
+    pub(crate) bounds: DataBounds,
+    pub(crate) max_len: Option<usize>,
+    /// Points dropped from the front by appends since the spans were last rebuilt.
+    /// `ring_head + i` is the stream position of local point `i`.
+    pub(crate) ring_head: u64,
+    /// Bounds per block of points, kept for series with a maximum length.
+    pub(crate) block_bounds: BlockBounds,
+    /// Dropped-point count and length of the source series when this span was synced.
+    pub(crate) source_dropped: u64,
+    pub(crate) source_len: usize,
+    /// Spatial index over the marker centers, built on the first CPU pick.
+    pub(crate) index: Arc<OnceLock<PointIndex>>,
+    /// The view a curved line is tessellated for, if the plot had one.
+    pub(crate) curve_view: Option<CurveView>,
+    /// The polylines drawn for the line, built when first drawn or picked.
+    pub(crate) paths: Arc<OnceLock<Vec<LinePath>>>,
+    /// Spatial index over the pieces of `paths`, built on the first CPU pick that needs it.
+    pub(crate) segments: Arc<OnceLock<SegmentIndex<PieceOwners>>>,
+}
+
+/// A polyline drawn for a series' line. Each plot-space vertex is paired with the index
+/// into the point buffer of the point whose value and color it carries.
+pub(crate) type LinePath = Vec<([f64; 2], usize)>;
+
+/// Local indices of the points whose values the ends of a line piece carry, and of the
+/// point starting the data segment the piece lies on.
+pub(crate) type PieceOwners = (usize, usize, usize);
+
+impl SeriesSpan {
+    /// The spatial index over this span's marker centers in `points`, building it if needed.
+    pub(crate) fn point_index(&self, points: &[Point]) -> &PointIndex {
+        self.index.get_or_init(|| {
+            let points = &points[self.start..self.start + self.len];
+            PointIndex::new(points.len(), |i| marker_center_world(&points[i]).to_array())
+        })
+    }
+
+    /// Visit the local indices of this span's points in `points` whose marker centers lie
+    /// in the plot-space box from `min` to `max`.
+    pub(crate) fn for_each_point_within(
+        &self,
+        points: &[Point],
+        min: [f64; 2],
+        max: [f64; 2],
+        visit: impl FnMut(usize),
+    ) {
+        let span_points = &points[self.start..self.start + self.len];
+        self.point_index(points).for_each_within(
+            min,
+            max,
+            |i| marker_center_world(&span_points[i]).to_array(),
+            visit,
+        );
+    }
+
+    /// Whether the line is broken between local points `index - 1` and `index`.
+    pub(crate) fn breaks_line_before(&self, index: usize) -> bool {
+        self.line_breaks.binary_search(&index).is_ok()
+    }
+
+    /// The polylines drawn for this span's line in `points`, split at line breaks, with its
+    /// interpolation applied. Built once and kept until the span's points or curve view
+    /// change.
+    pub(crate) fn line_paths(&self, points: &[Point]) -> &[LinePath] {
+        self.paths.get_or_init(|| self.build_line_paths(points))
+    }
+
+    /// Spatial index over the pieces of [`Self::line_paths`], building it if needed.
+    pub(crate) fn segment_index(&self, points: &[Point]) -> &SegmentIndex<PieceOwners> {
+        self.segments.get_or_init(|| {
+            SegmentIndex::new(
+                self.line_paths(points).iter().flat_map(|path| {
+                    self.path_pieces(points, path, 0..path.len().saturating_sub(1))
+                }),
+            )
+        })
+    }
+
+    /// The pieces of `path` starting at the vertices in `range`, with their owners.
+    pub(crate) fn path_pieces<'a>(
+        &self,
+        points: &'a [Point],
+        path: &'a [([f64; 2], usize)],
+        range: Range<usize>,
+    ) -> impl Iterator<Item = ([[f64; 2]; 2], PieceOwners)> + 'a {
+        let start = self.start;
+        let on_point =
+            move |&(position, owner): &([f64; 2], usize)| points[owner].position == position;
+        // The first vertex of a path is always a data point, so a piece's segment is found
+        // by walking back to the last vertex that lies on one.
+        let mut segment = path[..=range.start.min(path.len() - 1)]
+            .iter()
+            .rev()
+            .find(|vertex| on_point(vertex))
+            .map_or(path[0].1, |vertex| vertex.1)
+            - start;
+        range.map(move |j| {
+            let ((from, from_owner), (to, to_owner)) = (path[j], path[j + 1]);
+            if on_point(&path[j]) {
+                segment = from_owner - start;
+            }
+            ([from, to], (from_owner - start, to_owner - start, segment))
+        })
+    }
+
+    fn build_line_paths(&self, points: &[Point]) -> Vec<LinePath> {
+        let Some(line_style) = self.line_style else {
+            return Vec::new();
+        };
+        let Some(points) = points.get(self.start..self.start + self.len) else {
+            return Vec::new();
+        };
+        let mut paths = Vec::new();
+        let mut run_start = 0;
+        for index in 1..=points.len() {
+            if index == points.len() || self.breaks_line_before(index) {
+                if index - run_start >= 2 {
+                    let positions: Vec<[f64; 2]> = points[run_start..index]
+                        .iter()
+                        .map(|point| point.position)
+                        .collect();
+                    let offset = self.start + run_start;
+                    let path = line_style
+                        .interpolation
+                        .path(&positions, self.curve_view.as_ref());
+                    paths.push(path.into_iter().map(|(p, i)| (p, offset + i)).collect());
+                }
+                run_start = index;
+            }
+        }
+        paths
+    }
+}
+
+/// Plot-space bounds of some data. Each axis is tracked separately, since shapes drawn in
+/// axes coordinates only contribute to the other axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct DataBounds {
+    x: Option<[f64; 2]>,
+    y: Option<[f64; 2]>,
+}
+
+impl DataBounds {
+    fn include(&mut self, [x, y]: [f64; 2], include_x: bool, include_y: bool) {
+        fn extend(range: &mut Option<[f64; 2]>, v: f64) {
+            *range = Some(range.map_or([v, v], |[min, max]| [min.min(v), max.max(v)]));
+        }
+        if include_x {
+            extend(&mut self.x, x);
+        }
+        if include_y {
+            extend(&mut self.y, y);
+        }
+    }
+
+    fn pad_max(&mut self, [x, y]: [f64; 2]) {
+        if let Some(range) = &mut self.x {
+            range[1] += x;
+        }
+        if let Some(range) = &mut self.y {
+            range[1] += y;
+        }
+    }
+
+    fn union(self, other: Self) -> Self {
+        fn merge(a: Option<[f64; 2]>, b: Option<[f64; 2]>) -> Option<[f64; 2]> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some([a[0].min(b[0]), a[1].max(b[1])]),
+                (a, b) => a.or(b),
+            }
+        }
+        Self {
+            x: merge(self.x, other.x),
+            y: merge(self.y, other.y),
+        }
+    }
+
+    /// Convert to `(data_min, data_max)`, defaulting a missing axis to `-1..1`.
+    fn to_min_max(self) -> (Option<DVec2>, Option<DVec2>) {
+        if self.x.is_none() && self.y.is_none() {
+            return (None, None);
+        }
+        let [min_x, max_x] = self.x.unwrap_or([-1.0, 1.0]);
+        let [min_y, max_y] = self.y.unwrap_or([-1.0, 1.0]);
+        (
+            Some(DVec2::new(min_x, min_y)),
+            Some(DVec2::new(max_x, max_y)),
+        )
+    }
+}
+
+/// Bounds of one series point, in the plot space of the series' y-axis.
+fn point_bounds(point: &Point, y_map: SecondaryAxisMap, padding: [f64; 2]) -> DataBounds {
+    let [x, y] = point.position;
+    let mut bounds = DataBounds::default();
+    bounds.include([x, y_map.to_secondary(y)], true, true);
+    bounds.pad_max(padding);
+    bounds
+}
+
+/// Data bounds of a span's points in blocks of [`BlockBounds::BLOCK_LEN`] points, counted
+/// from the start of the stream so blocks stay aligned while points are dropped.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BlockBounds {
+    /// Points dropped before the first block's first point.
+    head: u64,
+    len: usize,
+    blocks: VecDeque<DataBounds>,
+}
+
+impl BlockBounds {
+    const BLOCK_LEN: u64 = 1024;
+
+    /// Add the bounds of the next point.
+    fn push(&mut self, bounds: DataBounds) {
+        let position = self.head + self.len as u64;
+        match self.blocks.back_mut() {
+            Some(block) if !position.is_multiple_of(Self::BLOCK_LEN) => {
+                *block = block.union(bounds)
+            }
+            _ => self.blocks.push_back(bounds),
+        }
+        self.len += 1;
+    }
+
+    /// Drop the first `count` points. The rest of a partly dropped block is rescanned with
+    /// `point_bounds`, which takes the index of a remaining point.
+    fn drop_front(&mut self, count: usize, point_bounds: impl Fn(usize) -> DataBounds) {
+        let count = count.min(self.len);
+        if count == 0 {
+            return;
+        }
+        let head = self.head + count as u64;
+        let dropped_blocks = (head / Self::BLOCK_LEN - self.head / Self::BLOCK_LEN) as usize;
+        self.blocks.drain(..dropped_blocks.min(self.blocks.len()));
+        self.head = head;
+        self.len -= count;
+        let offset = head % Self::BLOCK_LEN;
+        if let Some(block) = self.blocks.front_mut()
+            && offset != 0
+        {
+            let rest = ((Self::BLOCK_LEN - offset) as usize).min(self.len);
+            *block = (0..rest)
+                .map(point_bounds)
+                .fold(DataBounds::default(), DataBounds::union);
+        }
+    }
+
+    fn total(&self) -> DataBounds {
+        self.blocks
+            .iter()
+            .fold(DataBounds::default(), |total, &block| total.union(block))
+    }
+}
+
+/// Combined bounds of all spans, and the y range of the spans on the secondary y-axis.
+///
+/// Secondary-axis spans only contribute their x range to the combined bounds. Rasters and
+/// contours are always on the primary y-axis.
+fn combined_bounds(
+    series: &[SeriesSpan],
+    bars: &[BarSpan],
+    rasters: &[RasterSpan],
+    contours: &[DataBounds],
+) -> (DataBounds, Option<[f64; 2]>) {
+    let spans = series
+        .iter()
+        .map(|span| (span.y_axis, span.bounds))
+        .chain(bars.iter().map(|span| (span.y_axis, span.bounds)))
+        .chain(rasters.iter().map(|span| (YAxis::Primary, span.bounds)))
+        .chain(contours.iter().map(|&bounds| (YAxis::Primary, bounds)));
+    let mut primary = DataBounds::default();
+    let mut secondary = DataBounds::default();
+    for (y_axis, bounds) in spans {
+        match y_axis {
+            YAxis::Primary => primary = primary.union(bounds),
+            YAxis::Secondary => {
+                primary = primary.union(DataBounds { y: None, ..bounds });
+                secondary = secondary.union(bounds);
+            }
+        }
+    }
+    (primary, secondary.y)
+}
+
+/// World-sized markers extend up and to the right of their position, so the data max
+/// is padded by the marker size on linear axes.
+fn world_marker_padding(
+    series: &crate::Series,
+    x_axis_scale: AxisScale,
+    y_axis_scale: AxisScale,
+) -> [f64; 2] {
+    let Some(Size::World(size)) = series.marker_style.as_ref().map(|m| m.size) else {
+        return [0.0, 0.0];
+    };
+    let pad = |scale| {
+        if scale == AxisScale::Linear {
+            size
+        } else {
+            0.0
+        }
+    };
+    [pad(x_axis_scale), pad(y_axis_scale)]
+}
+
+/// Whether appends to `series` can be synced without rebuilding every span.
+fn supports_incremental_append(series: &crate::Series) -> bool {
+    series.bar_style.is_none()
+        && series.x_errors.is_none()
+        && series.y_errors.is_none()
+        && series.decimation == Decimation::None
+        // Dropping points can change a fitted color range, which recolors every point.
+        && (series.color_values.is_none() || !series.color_scale.fits_values())
+        && !series.transform.uses_axes_coordinates()
+        && (series.marker_style.is_some() || series.line_style.is_some())
 }
 
 #[derive(Default, Debug, Clone)]
@@ -1324,11 +2767,26 @@ pub(crate) struct ButtonPressState {
 pub(crate) struct SelectionState {
     pub(crate) active: bool,
     pub(crate) button: Option<mouse::Button>,
+    pub(crate) kind: SelectionKind,
     pub(crate) start: Vec2,
     pub(crate) end: Vec2,
+    /// Vertices of the lasso outline drawn so far, in screen pixels.
+    pub(crate) path: Vec<Vec2>,
     pub(crate) moved: bool,
 }
 
+/// What a drag-drawn selection does on release.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SelectionKind {
+    /// Zoom to the rectangle.
+    #[default]
+    Zoom,
+    /// Select the points inside the rectangle.
+    Box,
+    /// Select the points inside the lasso outline.
+    Lasso,
+}
+
 #[derive(Default, Debug, Clone)]
 pub(crate) struct PanState {
     pub(crate) active: bool,
@@ -1343,13 +2801,26 @@ pub(crate) struct DragState {
     pub(crate) button: Option<mouse::Button>,
 }
 
+/// A ruler end in data coordinates, with the data point it snapped to.
+type RulerEnd = ([f64; 2], Option<PointId>);
+
+#[derive(Default, Debug, Clone)]
+pub(crate) struct RulerState {
+    pub(crate) active: bool,
+    pub(crate) button: Option<mouse::Button>,
+    pub(crate) start: Option<RulerEnd>,
+    pub(crate) moved: bool,
+    /// The ruler drawn: the one being dragged, or else the widget's measurement.
+    pub(crate) measurement: Option<Measurement>,
+}
+
 #[cfg(test)]
 mod tests {
     use glam::DVec2;
     use iced::Point;
 
     use super::*;
-    use crate::{PointId, Series};
+    use crate::{BarStyle, MarkerStyle, PickHit, PointId, Series, series::SeriesError};
 
     #[test]
     fn paired_series_fill_keeps_step_edges_with_duplicate_x() {
@@ -1386,6 +2857,29 @@ mod tests {
         assert!(vertices.contains(&[5.0, 1.0]));
     }
 
+    #[test]
+    fn fill_under_step_line_follows_the_steps() {
+        let mut widget = PlotWidget::new();
+        let series = Series::line_only(
+            vec![[0.0, 1.0], [1.0, 2.0], [2.0, 1.0]],
+            LineStyle::solid().with_interpolation(LineInterpolation::StepPost),
+        );
+        let baseline = HLine::new(0.0);
+        let fill = crate::Fill::new(series.id, baseline.id);
+        widget.add_series(series).unwrap();
+        widget.add_hline(baseline);
+        widget.add_fill(fill).unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+
+        // Two held values and two risers, one quad each.
+        let vertices = &state.fills[0].vertices;
+        assert_eq!(vertices.len(), 4 * 6);
+        assert!(vertices.contains(&[1.0, 1.0]));
+        assert!(vertices.contains(&[2.0, 2.0]));
+    }
+
     #[test]
     fn axes_transform_series_maps_to_camera_range_and_skips_autoscale_bounds() {
         let mut widget = PlotWidget::new();
@@ -1404,6 +2898,475 @@ mod tests {
         assert_eq!(state.data_max, None);
     }
 
+    #[test]
+    fn zooming_in_refines_curves() {
+        let mut widget = PlotWidget::new();
+        let positions = (0..50)
+            .map(|i| [i as f64, (i as f64 * 0.3).sin()])
+            .collect();
+        widget
+            .add_series(Series::line_only(
+                positions,
+                LineStyle::solid().with_interpolation(LineInterpolation::NaturalCubic),
+            ))
+            .unwrap();
+
+        let mut state = PlotState {
+            bounds: Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 200.0,
+                height: 100.0,
+            },
+            ..PlotState::default()
+        };
+        state.camera.position = DVec2::new(25.0, 0.0);
+        state.camera.half_extents = DVec2::new(25.0, 1.0);
+        state.rebuild_from_widget(&widget);
+        let vertices_between = |state: &PlotState, [left, right]: [f64; 2]| {
+            state.series[0].line_paths(&state.points)[0]
+                .iter()
+                .filter(|(p, _)| (left..=right).contains(&p[0]))
+                .count()
+        };
+        let coarse = vertices_between(&state, [5.0, 6.0]);
+        assert!(!state.curves_outdated());
+
+        // Small zooms and pans keep the tessellation.
+        state.camera.half_extents *= 0.9;
+        state.camera.position.x += 10.0;
+        assert!(!state.curves_outdated());
+
+        state.camera.position = DVec2::new(5.5, 0.99);
+        state.camera.half_extents = DVec2::new(0.5, 0.02);
+        assert!(state.curves_outdated());
+        state.rebuild_from_widget(&widget);
+        assert!(!state.curves_outdated());
+        let fine = vertices_between(&state, [5.0, 6.0]);
+        assert!(fine > 3 * coarse, "{coarse} -> {fine}");
+
+        // Segments far outside the view are drawn straight.
+        assert_eq!(vertices_between(&state, [20.0, 49.0]), 30);
+    }
+
+    #[test]
+    fn decimated_series_keeps_original_indices_and_full_bounds() {
+        let mut widget = PlotWidget::new();
+        let positions = (0..10_000).map(|i| [i as f64, (i % 7) as f64]).collect();
+        widget
+            .add_series(
+                Series::line_only(positions, LineStyle::solid())
+                    .with_decimation(Decimation::MinMax),
+            )
+            .unwrap();
+
+        let mut state = PlotState {
+            bounds: Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            ..PlotState::default()
+        };
+        state.camera.position = DVec2::new(500.0, 3.0);
+        state.camera.half_extents = DVec2::new(500.0, 3.0);
+        state.rebuild_from_widget(&widget);
+
+        // Decimated a visible width past each side, at the same pixel density.
+        let span = &state.series[0];
+        assert!(span.len <= 2 * 300 + 2);
+        assert!(span.point_indices.iter().all(|&i| i <= 2001));
+        assert_eq!(state.data_max, Some(DVec2::new(9999.0, 6.0)));
+
+        // Panning within the decimated range keeps the decimation.
+        state.camera.position.x += 400.0;
+        assert!(!state.decimation_outdated());
+        state.camera.position.x += 700.0;
+        assert!(state.decimation_outdated());
+        state.camera.position.x = 500.0;
+        state.camera.half_extents.x = 300.0;
+        assert!(state.decimation_outdated());
+    }
+
+    #[test]
+    fn decimated_series_keeps_points_after_gaps() {
+        let mut widget = PlotWidget::new();
+        let mut positions: Vec<_> = (0..=1000).map(|i| [i as f64, (i % 7) as f64]).collect();
+        positions[500] = [f64::NAN, f64::NAN];
+        positions[501][0] = f64::NAN;
+        widget
+            .add_series(
+                Series::line_only(positions, LineStyle::solid())
+                    .with_decimation(Decimation::MinMax),
+            )
+            .unwrap();
+
+        let mut state = PlotState {
+            bounds: Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            ..PlotState::default()
+        };
+        state.camera.position = DVec2::new(500.0, 3.0);
+        state.camera.half_extents = DVec2::new(500.0, 3.0);
+        state.rebuild_from_widget(&widget);
+
+        let indices = &state.series[0].point_indices;
+        assert_eq!(indices.first(), Some(&0));
+        assert_eq!(indices.last(), Some(&1000));
+
+        // The same with only the points past the gaps in view.
+        state.camera.position.x = 800.0;
+        state.camera.half_extents.x = 50.0;
+        state.rebuild_from_widget(&widget);
+        let indices = &state.series[0].point_indices;
+        assert_eq!(indices.first(), Some(&649));
+        assert_eq!(indices.last(), Some(&951));
+    }
+
+    #[test]
+    fn appended_points_match_full_rebuild() {
+        let mut widget = PlotWidget::new();
+        let streamed = Series::new(
+            vec![[0.0, 5.0], [1.0, f64::NAN], [2.0, 1.0]],
+            MarkerStyle::circle(4.0),
+            LineStyle::solid(),
+        )
+        .with_max_len(5);
+        let streamed_id = streamed.id;
+        widget.add_series(streamed).unwrap();
+        widget
+            .add_series(Series::circles(vec![[0.5, 2.0], [1.5, 3.0]], 4.0))
+            .unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        state.data_src_version = widget.data_version;
+        state.append_src_version = widget.append_version;
+
+        // Drops the first position, which holds the maximum y.
+        widget
+            .append_series_points(&streamed_id, [[3.0, f64::NAN], [4.0, 2.0], [5.0, 3.0]])
+            .unwrap();
+        assert!(state.append_from_widget(&widget));
+
+        let mut rebuilt = PlotState::default();
+        rebuilt.rebuild_from_widget(&widget);
+
+        let positions = |state: &PlotState| -> Vec<[f64; 2]> {
+            state.points.iter().map(|p| p.position).collect()
+        };
+        assert_eq!(positions(&state), positions(&rebuilt));
+        assert_eq!(state.series[0].ring_head, 1);
+        for (appended, rebuilt) in state.series.iter().zip(rebuilt.series.iter()) {
+            assert_eq!(appended.start, rebuilt.start);
+            assert_eq!(appended.point_indices, rebuilt.point_indices);
+            assert_eq!(appended.line_breaks, rebuilt.line_breaks);
+        }
+        assert_eq!(state.data_min, rebuilt.data_min);
+        assert_eq!(state.data_max, Some(DVec2::new(5.0, 3.0)));
+    }
+
+    #[test]
+    fn block_bounds_match_a_rescan_after_drops() {
+        let bounds_of = |i: u64| {
+            let mut bounds = DataBounds::default();
+            bounds.include([i as f64, ((i * 7919) % 1000) as f64], true, true);
+            bounds
+        };
+        let mut blocks = BlockBounds::default();
+        (0..3000).for_each(|i| blocks.push(bounds_of(i)));
+
+        for (dropped, count) in [(0, 10), (10, 1500), (1510, 514), (2024, 975)] {
+            blocks.drop_front(count, |i| bounds_of(dropped + count as u64 + i as u64));
+            let rescan = (dropped + count as u64..3000)
+                .map(bounds_of)
+                .fold(DataBounds::default(), DataBounds::union);
+            assert_eq!(blocks.total(), rescan);
+        }
+    }
+
+    #[test]
+    fn appends_only_rebuild_fills_they_change() {
+        let mut widget = PlotWidget::new();
+        let streamed = Series::line_only(vec![[0.0, 1.0], [1.0, 2.0]], LineStyle::solid());
+        let fixed = Series::line_only(vec![[0.0, 3.0], [1.0, 4.0]], LineStyle::solid());
+        let baseline = HLine::new(0.0);
+        let (streamed_id, fixed_id) = (streamed.id, fixed.id);
+        widget.add_series(streamed).unwrap();
+        widget.add_series(fixed).unwrap();
+        widget.add_hline(baseline.clone());
+        widget
+            .add_fill(crate::Fill::new(streamed_id, baseline.id))
+            .unwrap();
+        widget
+            .add_fill(crate::Fill::new(fixed_id, baseline.id))
+            .unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        state.data_src_version = widget.data_version;
+        let before = state.fills.clone();
+
+        widget
+            .append_series_points(&streamed_id, [[2.0, 1.0]])
+            .unwrap();
+        assert!(state.append_from_widget(&widget));
+
+        assert_eq!(state.fills[0].vertices.len(), 2 * 6);
+        assert!(Arc::ptr_eq(&state.fills[1].vertices, &before[1].vertices));
+    }
+
+    #[test]
+    fn appending_to_decimated_series_requires_rebuild() {
+        let mut widget = PlotWidget::new();
+        let series = Series::line_only(vec![[0.0, 0.0], [1.0, 1.0]], LineStyle::solid())
+            .with_decimation(Decimation::Lttb);
+        let id = series.id;
+        widget.add_series(series).unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        widget.append_series_points(&id, [[2.0, 0.0]]).unwrap();
+
+        assert!(!state.append_from_widget(&widget));
+        assert_eq!(
+            widget.append_series_points(&id, [[3.0, 0.0], [2.5, 0.0]]),
+            Err(SeriesError::UnsortedPositions)
+        );
+        assert_eq!(widget.series[&id].positions.len(), 3);
+    }
+
+    #[test]
+    fn follow_latest_shows_window_ending_at_newest_x() {
+        let mut widget = PlotWidget::new();
+        widget
+            .add_series(Series::circles(vec![[0.0, -1.0], [100.0, 1.0]], 4.0))
+            .unwrap();
+        widget.set_follow_latest(Some(10.0));
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        state.autoscale(false);
+
+        let [x_min, x_max] = state.camera.x_range();
+        assert!((x_min - 89.75).abs() < 1e-9);
+        assert!((x_max - 100.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn secondary_series_autoscales_independently() {
+        let mut widget = PlotWidget::new();
+        widget
+            .add_series(Series::circles(vec![[0.0, 0.0], [1.0, 1.0]], 4.0))
+            .unwrap();
+        widget
+            .add_series(
+                Series::circles(vec![[0.0, 1000.0], [2.0, 1100.0]], 4.0)
+                    .with_y_axis(YAxis::Secondary),
+            )
+            .unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        state.autoscale(false);
+
+        assert_eq!(state.data_max, Some(DVec2::new(2.0, 1.0)));
+        assert_eq!(state.y2_data_range, Some([1000.0, 1100.0]));
+        let [y_min, y_max] = state.camera.y_range();
+        assert_eq!(
+            state.y2_map.range_to_secondary([y_min, y_max]),
+            [997.5, 1102.5]
+        );
+
+        // Rebuilding with the fitted map places secondary points inside the view.
+        state.rebuild_from_widget(&widget);
+        let [_, y] = state.points[3].position;
+        assert!((y - y_max).abs() / (y_max - y_min) < 0.05);
+    }
+
+    #[test]
+    fn secondary_ticks_require_a_secondary_axis() {
+        let producer: TickProducer = Arc::new(crate::ticks::default_tick_producer);
+        let mut widget = PlotWidget::new();
+        widget
+            .add_series(Series::circles(vec![[0.0, 0.0], [1.0, 1.0]], 4.0))
+            .unwrap();
+
+        let mut state = PlotState {
+            bounds: Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            ..PlotState::default()
+        };
+        state.rebuild_from_widget(&widget);
+        state.autoscale(false);
+        state.update_ticks(Some(&producer), Some(&producer), Some(&producer));
+        assert!(state.y2_ticks.is_empty());
+
+        widget.set_y2_axis_label("right");
+        widget.set_y2_lim(100.0, 200.0);
+        state.rebuild_from_widget(&widget);
+        state.autoscale(false);
+        state.update_ticks(Some(&producer), Some(&producer), Some(&producer));
+        assert!(!state.y2_ticks.is_empty());
+        assert!(
+            state
+                .y2_ticks
+                .iter()
+                .all(|t| (100.0..=200.0).contains(&t.tick.value))
+        );
+    }
+
+    #[test]
+    fn color_values_refit_when_appends_drop_points() {
+        let mut widget = PlotWidget::new();
+        let series = Series::circles(vec![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]], 4.0)
+            .with_color_values(vec![0.0, 5.0, 10.0], crate::Colormap::Grays)
+            .with_max_len(3);
+        let id = series.id;
+        widget.add_series(series).unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        assert_eq!(state.point_colors[1], Color::from_rgb(0.5, 0.5, 0.5));
+
+        // Dropping the 0.0 value refits the range to [5, 10]; the new point has no value.
+        widget.append_series_points(&id, [[3.0, 0.0]]).unwrap();
+        assert!(!state.append_from_widget(&widget));
+        state.rebuild_from_widget(&widget);
+        let color = widget.series[&id].color;
+        assert_eq!(state.point_colors[..], [Color::BLACK, Color::WHITE, color]);
+    }
+
+    #[test]
+    fn error_bars_extend_autoscale_bounds() {
+        let mut widget = PlotWidget::new();
+        widget
+            .add_series(
+                Series::circles(vec![[0.0, 1.0], [2.0, 3.0]], 5.0)
+                    .with_x_errors(vec![0.5, 0.25])
+                    .with_y_errors((vec![0.5, 1.0], vec![2.0, 0.5])),
+            )
+            .unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+
+        assert_eq!(state.error_bars[0].bars.len(), 4);
+        assert_eq!(state.data_min, Some(DVec2::new(-0.5, 0.5)));
+        assert_eq!(state.data_max, Some(DVec2::new(2.25, 3.5)));
+    }
+
+    #[test]
+    fn setting_positions_resizes_error_values() {
+        let mut widget = PlotWidget::new();
+        let series = Series::circles(vec![[0.0, 1.0], [2.0, 3.0]], 5.0)
+            .with_x_errors(vec![0.5, 0.25])
+            .with_y_errors((vec![0.5, 1.0], vec![2.0, 0.5]));
+        let id = series.id;
+        widget.add_series(series).unwrap();
+
+        widget.set_series_positions(&id, &[[0.0, 1.0], [2.0, 3.0], [4.0, 5.0]]);
+        let series = &widget.series[&id];
+        let x_errors = series.x_errors.as_ref().unwrap();
+        let y_errors = series.y_errors.as_ref().unwrap();
+        assert!(x_errors.len_matches(3) && y_errors.len_matches(3));
+        assert_eq!(x_errors.get(1), Some((0.25, 0.25)));
+        assert_eq!(y_errors.get(2), Some((0.0, 0.0)));
+        assert_eq!(series.validate(), Ok(()));
+
+        widget.set_series_positions(&id, &[[0.0, 1.0]]);
+        let series = &widget.series[&id];
+        assert!(series.x_errors.as_ref().unwrap().len_matches(1));
+        assert!(series.y_errors.as_ref().unwrap().len_matches(1));
+        assert_eq!(series.validate(), Ok(()));
+    }
+
+    #[test]
+    fn contours_add_level_lines_and_bands_over_their_grid() {
+        let mut widget = PlotWidget::new();
+        let values = (0..9).map(|i| (i % 3) as f64).collect();
+        let contour = crate::Contour::new(3, 3, values)
+            .with_extent([1.0, 1.0], [3.0, 2.0])
+            .with_levels(vec![0.5, 1.5])
+            .with_filled(true);
+        let id = contour.id;
+        widget.add_contour(contour).unwrap();
+        let level_ids = widget.contour_level_ids(&id).unwrap().to_vec();
+        assert_eq!(level_ids.len(), 2);
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+
+        assert_eq!(state.series.len(), 2);
+        assert_eq!(state.fills.len(), 3);
+        assert_eq!(state.data_min, Some(DVec2::new(1.0, 1.0)));
+        assert_eq!(state.data_max, Some(DVec2::new(3.0, 2.0)));
+
+        widget
+            .update_contour(&id, |contour| contour.levels = vec![1.0].into())
+            .unwrap();
+        assert_eq!(widget.contour_level_ids(&id), Some(&level_ids[..1]));
+        assert_eq!(widget.series.len(), 1);
+        widget.remove_contour(&id).unwrap();
+        assert!(widget.series.is_empty());
+    }
+
+    #[test]
+    fn distribution_plots_add_boxes_and_resolve_outliers() {
+        let mut widget = PlotWidget::new();
+        let mut spread: Vec<f64> = (0..=10).map(f64::from).collect();
+        spread.push(40.0);
+        let plot = crate::DistributionPlot::boxes(vec![spread, vec![1.0, 2.0, 3.0]]);
+        let (id, outliers_id) = (plot.id, plot.outliers_id);
+        widget.add_distribution_plot(plot).unwrap();
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        assert_eq!(state.bars.len(), 1);
+        assert_eq!(state.bars[0].rects.len(), 2);
+        assert_eq!(state.data_max.map(|max| max.y), Some(40.0));
+
+        let outlier = widget
+            .distribution_stats(PointId {
+                series_id: outliers_id,
+                point_index: 0,
+            })
+            .unwrap();
+        assert_eq!((outlier.index, outlier.outlier), (0, Some(40.0)));
+        assert_eq!(outlier.upper_whisker, 10.0);
+        let second = widget
+            .distribution_stats(PointId {
+                series_id: id,
+                point_index: 1,
+            })
+            .unwrap();
+        assert_eq!((second.median, second.count), (2.0, 3));
+
+        widget.hidden_shapes.insert(id);
+        state.rebuild_from_widget(&widget);
+        assert!(state.bars.is_empty());
+
+        // Without outliers, the plot drops its outlier series.
+        widget
+            .set_distribution_samples(&id, vec![vec![1.0, 2.0, 3.0]])
+            .unwrap();
+        assert!(!widget.series.contains_key(&outliers_id));
+        widget.set_distribution_samples(&id, vec![]).unwrap();
+        assert!(widget.series.is_empty());
+
+        widget.remove_distribution_plot(&id).unwrap();
+        assert!(widget.series.is_empty());
+    }
+
     #[test]
     fn arrow_keys_use_configured_pan_fraction_when_enabled_and_hovered() {
         let mut widget = PlotWidget::new();
@@ -1440,6 +3403,52 @@ mod tests {
         assert_eq!(state.camera.position, DVec2::new(5.0, 0.0));
     }
 
+    #[test]
+    fn categorical_x_axis_places_named_points_and_clamps_the_view() {
+        let mut widget = PlotWidget::new();
+        widget.set_x_categories(["a", "b", "c", "d"]);
+        let series = Series::bars(vec![[0.0, 1.0], [0.0, 2.0]], BarStyle::new(0.8))
+            .with_x_categories(["c", "a"]);
+        let id = series.id;
+        widget.add_series(series).unwrap();
+        assert_eq!(widget.series[&id].positions, [[2.0, 1.0], [0.0, 2.0]]);
+        assert_eq!(
+            widget.add_series(Series::circles(vec![[0.0, 0.0]], 2.0).with_x_categories(["e"])),
+            Err(SeriesError::UnknownCategory("e".to_owned()))
+        );
+
+        // Reordering the categories moves the named points.
+        widget.set_x_categories(["c", "b", "a"]);
+        assert_eq!(widget.series[&id].positions, [[0.0, 1.0], [2.0, 2.0]]);
+
+        let mut state = PlotState::default();
+        state.rebuild_from_widget(&widget);
+        state.autoscale(false);
+        assert!((state.camera.half_extents.x - 1.5 * (1.0 + AUTOSCALE_PADDING)).abs() < 1e-12);
+
+        state.pan_by(PanDirection::Right, 1.0);
+        assert!((state.camera.position.x - 1.0).abs() < 1e-12);
+        state.camera.half_extents.x = 0.01;
+        state.pan_by(PanDirection::Left, 100.0);
+        assert_eq!(state.camera.half_extents.x, 0.5);
+        assert!(state.camera.position.x >= -0.5 && state.camera.position.x < 0.0);
+
+        // Explicit limits win over the category range.
+        widget.set_x_lim(-3.0, 7.0);
+        state.rebuild_from_widget(&widget);
+        state.autoscale(false);
+        assert_eq!(state.camera.position.x, 2.0);
+        assert!((state.camera.half_extents.x - 5.0 * (1.0 + AUTOSCALE_PADDING)).abs() < 1e-12);
+
+        // Named points can't be appended to, and keep their places once cleared.
+        assert_eq!(
+            widget.append_series_points(&id, [[1.0, 1.0]]),
+            Err(SeriesError::AppendToNamedCategories)
+        );
+        widget.clear_x_categories();
+        assert_eq!(widget.series[&id].positions, [[0.0, 1.0], [2.0, 2.0]]);
+    }
+
     #[test]
     fn arrow_keys_do_not_pan_when_disabled() {
         let mut widget = PlotWidget::new();
@@ -1485,14 +3494,12 @@ mod tests {
             ..PlotState::default()
         };
 
-        let mut hover_pick = None;
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
         state.handle_mouse_event(
             Event::ButtonPressed(mouse::Button::Middle),
             mouse::Cursor::Available(Point::new(50.0, 50.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert!(state.pan.active);
@@ -1502,8 +3509,7 @@ mod tests {
             Event::ButtonReleased(mouse::Button::Middle),
             mouse::Cursor::Available(Point::new(50.0, 50.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert!(!state.pan.active);
@@ -1524,14 +3530,12 @@ mod tests {
             ..PlotState::default()
         };
 
-        let mut hover_pick = None;
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
         state.handle_mouse_event(
             Event::ButtonPressed(mouse::Button::Left),
             mouse::Cursor::Levitating(Point::new(50.0, 350.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert!(!state.pan.active);
@@ -1553,14 +3557,12 @@ mod tests {
         state.camera.position = DVec2::ZERO;
         state.camera.half_extents = DVec2::new(10.0, 30.0);
 
-        let mut hover_pick = None;
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
         state.handle_mouse_event(
             Event::ButtonPressed(mouse::Button::Left),
             mouse::Cursor::Available(Point::new(50.0, 150.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         state.handle_mouse_event(
@@ -1569,8 +3571,7 @@ mod tests {
             },
             mouse::Cursor::Levitating(Point::new(50.0, 450.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert!(state.pan.active);
@@ -1594,16 +3595,14 @@ mod tests {
         state.camera.position = DVec2::ZERO;
         state.camera.half_extents = DVec2::new(10.0, 30.0);
 
-        let mut hover_pick = None;
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
         state.handle_mouse_event(
             Event::WheelScrolled {
                 delta: mouse::ScrollDelta::Lines { x: 0.0, y: 1.0 },
             },
             mouse::Cursor::Levitating(Point::new(50.0, 350.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert_eq!(state.camera.position, DVec2::ZERO);
@@ -1632,17 +3631,18 @@ mod tests {
             ..PlotState::default()
         };
 
-        let mut hover_pick = Some(HoverPickEvent::Hover(point_id));
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
+        effects.hover_pick = Some(HoverPickEvent::Hover(PickHit::Point(point_id)));
         state.handle_mouse_event(
             Event::ButtonReleased(mouse::Button::Left),
             mouse::Cursor::Levitating(Point::new(50.0, 350.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
-        assert!(matches!(hover_pick, Some(HoverPickEvent::Hover(id)) if id == point_id));
+        assert!(
+            matches!(effects.hover_pick, Some(HoverPickEvent::Hover(PickHit::Point(id))) if id == point_id)
+        );
         assert!(!state.press.active);
     }
 
@@ -1659,14 +3659,12 @@ mod tests {
             ..PlotState::default()
         };
 
-        let mut hover_pick = None;
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
         state.handle_mouse_event(
             Event::ButtonPressed(mouse::Button::Left),
             mouse::Cursor::Available(Point::new(50.0, 350.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert!(state.pan.active);
@@ -1747,18 +3745,16 @@ mod tests {
             ..PlotState::default()
         };
 
-        let mut hover_pick = None;
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
         state.handle_mouse_event(
             Event::ButtonPressed(mouse::Button::Middle),
             mouse::Cursor::Available(Point::new(50.0, 50.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert!(matches!(
-            drag_event,
+            effects.drag_event,
             Some(DragEvent::Start {
                 button: mouse::Button::Middle,
                 ..
@@ -1767,17 +3763,16 @@ mod tests {
         assert!(state.drag.active);
         assert_eq!(state.drag.button, Some(mouse::Button::Middle));
 
-        drag_event = None;
+        effects.drag_event = None;
         state.handle_mouse_event(
             Event::ButtonReleased(mouse::Button::Middle),
             mouse::Cursor::Available(Point::new(50.0, 50.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         assert!(matches!(
-            drag_event,
+            effects.drag_event,
             Some(DragEvent::End {
                 button: mouse::Button::Middle,
                 ..
@@ -1810,27 +3805,172 @@ mod tests {
             series_id: ShapeId::new(),
             point_index: 0,
         };
-        let mut hover_pick = Some(HoverPickEvent::Hover(point_id));
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
+        effects.hover_pick = Some(HoverPickEvent::Hover(PickHit::Point(point_id)));
 
         state.handle_mouse_event(
             Event::ButtonPressed(mouse::Button::Left),
             mouse::Cursor::Available(Point::new(50.0, 50.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
         );
 
         state.handle_mouse_event(
             Event::ButtonReleased(mouse::Button::Left),
             mouse::Cursor::Available(Point::new(50.0, 50.0)),
             &widget,
-            &mut hover_pick,
-            &mut drag_event,
+            &mut effects,
+        );
+
+        assert!(
+            matches!(effects.hover_pick, Some(HoverPickEvent::Pick(PickHit::Point(id))) if id == point_id)
+        );
+        assert!(!state.selection.active);
+    }
+
+    #[test]
+    fn lasso_select_publishes_the_points_inside_the_outline() {
+        let mut widget = PlotWidget::new();
+        widget
+            .controls
+            .set_drag_action(DragAction::LassoSelect, Some(mouse::Button::Left));
+        let series = Series::circles(vec![[-0.5, 0.5], [0.5, 0.5], [0.0, -0.5]], 4.0);
+        let series_id = series.id;
+        widget.add_series(series).unwrap();
+
+        let mut state = PlotState {
+            bounds: Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            ..PlotState::default()
+        };
+        state.rebuild_from_widget(&widget);
+        state.camera.position = DVec2::ZERO;
+        state.camera.half_extents = DVec2::ONE;
+        state.modifiers = keyboard::Modifiers::SHIFT;
+
+        let mut effects = UpdateEffects::default();
+        let mut send = |state: &mut PlotState, event, [x, y]: [f32; 2]| {
+            state.handle_mouse_event(
+                event,
+                mouse::Cursor::Available(Point::new(x, y)),
+                &widget,
+                &mut effects,
+            );
+        };
+        send(
+            &mut state,
+            Event::ButtonPressed(mouse::Button::Left),
+            [10.0, 10.0],
+        );
+        // Around the points at (25, 25) and (50, 75) px, but not the one at (75, 25) px.
+        for position in [[40.0, 10.0], [60.0, 90.0], [10.0, 90.0]] {
+            let event = Event::CursorMoved {
+                position: Point::new(position[0], position[1]),
+            };
+            send(&mut state, event, position);
+        }
+        assert_eq!(state.selection.path.len(), 4);
+        // Ending next to the start closes the outline instead of clicking.
+        send(
+            &mut state,
+            Event::ButtonReleased(mouse::Button::Left),
+            [12.0, 12.0],
         );
 
-        assert!(matches!(hover_pick, Some(HoverPickEvent::Pick(id)) if id == point_id));
+        let point = |point_index| PointId {
+            series_id,
+            point_index,
+        };
+        assert_eq!(
+            effects.selection,
+            Some(SelectionEvent {
+                mode: SelectionMode::Add,
+                points: vec![point(0), point(2)],
+            })
+        );
+        assert!(effects.hover_pick.is_none());
         assert!(!state.selection.active);
+        assert!(state.selection.path.is_empty());
+    }
+
+    #[test]
+    fn measure_drag_snaps_its_ends_to_data_points() {
+        let mut widget = PlotWidget::new();
+        widget
+            .controls
+            .set_drag_action(DragAction::Measure, Some(mouse::Button::Left));
+        widget.set_measure_snap(true);
+        let series = Series::line_only(
+            vec![[-0.5, 0.5], [0.5, 0.5], [0.0, -0.5]],
+            LineStyle::solid(),
+        );
+        let series_id = series.id;
+        widget.add_series(series).unwrap();
+        // Closer to the release below, but measured on another axis.
+        let secondary = Series::markers_only(vec![[0.05, -0.45]], MarkerStyle::circle(4.0))
+            .with_y_axis(YAxis::Secondary);
+        widget.add_series(secondary).unwrap();
+
+        let mut state = PlotState {
+            bounds: Rectangle {
+                x: 0.0,
+                y: 0.0,
+                width: 100.0,
+                height: 100.0,
+            },
+            ..PlotState::default()
+        };
+        state.rebuild_from_widget(&widget);
+        state.camera.position = DVec2::ZERO;
+        state.camera.half_extents = DVec2::ONE;
+
+        let send = |state: &mut PlotState, event, [x, y]: [f32; 2]| {
+            let mut effects = UpdateEffects::default();
+            state.handle_mouse_event(
+                event,
+                mouse::Cursor::Available(Point::new(x, y)),
+                &widget,
+                &mut effects,
+            );
+            effects.measurement
+        };
+        // Next to the points at (25, 25) px, then away from all of them.
+        send(
+            &mut state,
+            Event::ButtonPressed(mouse::Button::Left),
+            [27.0, 27.0],
+        );
+        let position = Point::new(62.0, 50.0);
+        let dragging = send(&mut state, Event::CursorMoved { position }, [62.0, 50.0]).unwrap();
+
+        let point = |point_index| {
+            Some(PointId {
+                series_id,
+                point_index,
+            })
+        };
+        assert_eq!(dragging.start, [-0.5, 0.5]);
+        assert_eq!((dragging.start_point, dragging.end_point), (point(0), None));
+        assert!(!dragging.finished);
+
+        // Released next to the point at (50, 75) px.
+        let measured = send(
+            &mut state,
+            Event::ButtonReleased(mouse::Button::Left),
+            [52.0, 73.0],
+        )
+        .unwrap();
+        assert_eq!(measured.end, [0.0, -0.5]);
+        assert_eq!(measured.end_point, point(2));
+        assert_eq!((measured.delta_x, measured.delta_y), (0.5, -1.0));
+        assert_eq!(measured.slope, Some(-2.0));
+        assert!(measured.finished);
+        assert!(!state.ruler.active);
+        assert_eq!(state.ruler.measurement, Some(measured));
     }
 
     #[test]
@@ -1855,22 +3995,19 @@ mod tests {
         };
         state.camera.position = DVec2::ZERO;
 
-        let mut hover_pick = None;
-        let mut drag_event = None;
+        let mut effects = UpdateEffects::default();
         for _ in 0..2 {
             state.handle_mouse_event(
                 Event::ButtonPressed(mouse::Button::Left),
                 mouse::Cursor::Available(Point::new(50.0, 50.0)),
                 &widget,
-                &mut hover_pick,
-                &mut drag_event,
+                &mut effects,
             );
             state.handle_mouse_event(
                 Event::ButtonReleased(mouse::Button::Left),
                 mouse::Cursor::Available(Point::new(50.0, 50.0)),
                 &widget,
-                &mut hover_pick,
-                &mut drag_event,
+                &mut effects,
             );
         }
 
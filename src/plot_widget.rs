@@ -2,7 +2,7 @@ use core::fmt;
 use std::{
     collections::{HashMap, HashSet},
     sync::{
-        Arc, RwLock,
+        Arc, Mutex, PoisonError, RwLock,
         atomic::{AtomicBool, AtomicU64, Ordering},
     },
 };
@@ -16,33 +16,52 @@ use iced::{
     padding::{self, Padding},
     wgpu::TextureFormat,
     widget::{
-        self, container,
+        self, container, row,
         shader::{self, Pipeline, Viewport},
         stack,
     },
 };
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 
 use crate::{
-    AxisScale, DragEvent, Fill, HLine, HoverPickEvent, KeyAction, MarkerStyle, PlotUiMessage,
-    PointId, Series, Size, TooltipContext, Transform, VLine, axes_labels,
+    AxisScale, Decimation, DragEvent, Fill, HLine, HoverPickEvent, KeyAction, MarkerStyle,
+    Measurement, PickHit, PlotUiMessage, PointId, SelectionMode, Series, Size, TooltipContext,
+    Transform, VLine, axes_labels,
     axis_link::AxisLink,
     axis_scale::plot_point_to_data,
     camera::Camera,
+    colorbar::{self, Colorbar},
+    contour::Contour,
     controls::PlotControls,
+    data_cursor::{self, DataCursorEntry, DataCursorMode, DataCursorReadout, SpanReading},
+    decimation::sorted_by_x,
     default_style,
-    legend::{self, LegendEntry},
-    message::{CursorPositionUiPayload, PlotRenderUpdate, TooltipUiPayload},
+    distribution::{DistributionPlot, DistributionStats},
+    histogram::{Histogram, HistogramBin},
+    legend::{self, LEGEND_SPACING, Legend, LegendEntry, LegendPosition},
+    message::{
+        CursorPositionUiPayload, DataBounds, PlotRenderUpdate, RenderUpdateDetails, SelectionEvent,
+        TooltipUiPayload,
+    },
     picking, plot_overlay,
     plot_renderer::{PlotRenderStrategy, PlotRenderer, RenderParams},
     plot_state::PlotState,
+    raster::{Raster, RasterCell, RasterData},
+    secondary_axis::{SecondaryAxisMap, YAxis},
     series::{SeriesError, ShapeId},
+    spatial_index::PointIndex,
     style::{PlotStyle, StyleFn},
     ticks::{self, PositionedTick, TickFormatter, TickProducer},
-    transform::{PositionTransform, data_point_to_plot_with_transform},
+    transform::{
+        PositionTransform, data_point_to_plot_with_transform, plot_value_to_data_with_transform,
+    },
 };
 
-const PLOT_CONTENT_PADDING: f32 = 2.0;
+pub(crate) const PLOT_CONTENT_PADDING: f32 = 2.0;
+pub(crate) const FRAME_PADDING: f32 = 3.0;
+/// Most selected points highlighted at once. Larger selections are still recorded, but
+/// only this many get a highlight marker.
+const MAX_SELECTION_HIGHLIGHTS: usize = 10_000;
 pub(crate) type CursorProvider = Arc<dyn Fn(f64, f64) -> String + Send + Sync>;
 
 /// Provider for highlighting a point.
@@ -53,6 +72,18 @@ pub(crate) type CursorProvider = Arc<dyn Fn(f64, f64) -> String + Send + Sync>;
 pub(crate) type HighlightPointProvider =
     Arc<dyn Fn(TooltipContext<'_>, &mut HighlightPoint) -> Option<String> + Send + Sync>;
 
+/// Spatial indices over series positions, keyed by series.
+type PointIndexCache = HashMap<ShapeId, CachedPointIndex>;
+
+/// A spatial index over a series' positions, with the state of the series it was built
+/// for. Appends show up as a change of the series' length or dropped points.
+pub(crate) struct CachedPointIndex {
+    data_version: u64,
+    dropped: u64,
+    len: usize,
+    index: Arc<PointIndex>,
+}
+
 /// A plot widget that renders data series with interactive features.
 pub struct PlotWidget {
     pub(crate) instance_id: u64,
@@ -61,37 +92,57 @@ pub struct PlotWidget {
     pub(crate) fills: IndexMap<ShapeId, Fill>,
     pub(crate) vlines: IndexMap<ShapeId, VLine>,
     pub(crate) hlines: IndexMap<ShapeId, HLine>,
+    pub(crate) rasters: IndexMap<ShapeId, Raster>,
+    pub(crate) contours: IndexMap<ShapeId, Contour>,
+    pub(crate) histograms: IndexMap<ShapeId, Histogram>,
+    pub(crate) distributions: IndexMap<ShapeId, DistributionPlot>,
     pub(crate) hidden_shapes: HashSet<ShapeId>,
     pub(crate) data_version: u64,
+    /// Bumped by appends, which are synced incrementally instead of through `data_version`.
+    pub(crate) append_version: u64,
+    /// Number of points dropped from the front of each series by appends.
+    pub(crate) dropped_points: HashMap<ShapeId, u64>,
     // Configuration
     pub(crate) autoscale_on_updates: bool,
+    pub(crate) follow_latest: Option<f64>,
     pub(crate) controls: PlotControls,
     pub(crate) highlight_on_hover: bool,
     pub(crate) show_controls_help: bool,
     pub(crate) legend_enabled: bool,
     pub(crate) legend_collapsed: bool,
+    pub(crate) legend: Legend,
+    pub(crate) colorbar: Option<Colorbar>,
     pub(crate) x_axis_label: String,
     pub(crate) y_axis_label: String,
+    pub(crate) y2_axis_label: String,
     pub(crate) x_lim: Option<(f64, f64)>,
     pub(crate) y_lim: Option<(f64, f64)>,
+    pub(crate) y2_lim: Option<(f64, f64)>,
     pub(crate) x_axis_scale: AxisScale,
+    pub(crate) x_categories: Option<Arc<[String]>>,
     pub(crate) y_axis_scale: AxisScale,
+    pub(crate) y2_axis_scale: AxisScale,
     pub(crate) x_axis_link: Option<AxisLink>,
     pub(crate) y_axis_link: Option<AxisLink>,
+    pub(crate) y2_axis_link: Option<AxisLink>,
     pub(crate) hover_radius_px: f32,
     pub(crate) pick_highlight_provider: Option<HighlightPointProvider>,
     pub(crate) hover_highlight_provider: Option<HighlightPointProvider>,
     pub(crate) cursor_overlay: bool,
     pub(crate) cursor_provider: Option<CursorProvider>,
     pub(crate) crosshairs_enabled: bool,
+    pub(crate) data_cursor: Option<DataCursorMode>,
+    pub(crate) measure_snap: bool,
     pub(crate) render_strategy: PlotRenderStrategy,
     pub(crate) controls_overlay_open: bool,
     #[cfg(feature = "canvas")]
     pub(crate) canvas_caches: crate::plot_renderer::canvas::CanvasCaches,
     pub(crate) x_axis_formatter: Option<TickFormatter>,
     pub(crate) y_axis_formatter: Option<TickFormatter>,
+    pub(crate) y2_axis_formatter: Option<TickFormatter>,
     pub(crate) x_tick_producer: Option<TickProducer>,
     pub(crate) y_tick_producer: Option<TickProducer>,
+    pub(crate) y2_tick_producer: Option<TickProducer>,
     pub(crate) tick_label_size: f32,
     pub(crate) axis_label_size: f32,
     pub(crate) data_aspect: Option<f64>,
@@ -102,12 +153,24 @@ pub struct PlotWidget {
     pub(crate) picked_points: IndexMap<PointId, (HighlightPoint, Option<TooltipUiPayload>)>,
     /// Map of hovered point id to highlight point data & tooltip text.
     pub(crate) hovered_points: IndexMap<PointId, (HighlightPoint, Option<TooltipUiPayload>)>,
+    /// Points selected by box or lasso selection, highlighted as picked points.
+    pub(crate) selected_points: IndexSet<PointId>,
     pub(crate) cursor_ui: Option<CursorPositionUiPayload>,
+    pub(crate) data_cursor_ui: Option<DataCursorReadout>,
+    /// The last ruler drawn with [`DragAction::Measure`](crate::DragAction::Measure).
+    pub(crate) measurement: Option<Measurement>,
     pub(crate) x_ticks: Vec<PositionedTick>,
     pub(crate) y_ticks: Vec<PositionedTick>,
+    pub(crate) y2_ticks: Vec<PositionedTick>,
     pub(crate) shape_overlays_enabled: AtomicBool,
     // Camera and bounds for coordinate conversion (updated when ticks are updated)
     pub(crate) camera_bounds: Option<(Camera, Rectangle)>,
+    /// Secondary y-axis map for coordinate conversion, published with `camera_bounds`.
+    pub(crate) y2_map: SecondaryAxisMap,
+    /// Extent of the data, published with `camera_bounds` to place the legend.
+    pub(crate) data_bounds: DataBounds,
+    /// Spatial indices for the nearest-point queries, keyed by series.
+    pub(crate) point_indices: Mutex<PointIndexCache>,
 }
 
 impl Default for PlotWidget {
@@ -125,36 +188,54 @@ impl PlotWidget {
             fills: IndexMap::new(),
             vlines: IndexMap::new(),
             hlines: IndexMap::new(),
+            rasters: IndexMap::new(),
+            contours: IndexMap::new(),
+            histograms: IndexMap::new(),
+            distributions: IndexMap::new(),
             hidden_shapes: HashSet::new(),
             data_version: 1,
+            append_version: 0,
+            dropped_points: HashMap::new(),
             autoscale_on_updates: false,
+            follow_latest: None,
             controls: PlotControls::default(),
             highlight_on_hover: true,
             show_controls_help: true,
             legend_enabled: true,
             legend_collapsed: false,
+            legend: Legend::new(),
+            colorbar: None,
             x_axis_label: String::new(),
             y_axis_label: String::new(),
+            y2_axis_label: String::new(),
             x_lim: None,
             y_lim: None,
+            y2_lim: None,
             x_axis_scale: AxisScale::Linear,
+            x_categories: None,
             y_axis_scale: AxisScale::Linear,
+            y2_axis_scale: AxisScale::Linear,
             x_axis_link: None,
             y_axis_link: None,
+            y2_axis_link: None,
             hover_radius_px: 8.0,
             pick_highlight_provider: None,
             hover_highlight_provider: None,
             cursor_overlay: true,
             cursor_provider: None,
             crosshairs_enabled: false,
+            data_cursor: None,
+            measure_snap: false,
             render_strategy: PlotRenderStrategy::default(),
             controls_overlay_open: false,
             #[cfg(feature = "canvas")]
             canvas_caches: crate::plot_renderer::canvas::CanvasCaches::default(),
             x_axis_formatter: Some(Arc::new(ticks::default_formatter)),
             y_axis_formatter: Some(Arc::new(ticks::default_formatter)),
+            y2_axis_formatter: Some(Arc::new(ticks::default_formatter)),
             x_tick_producer: Some(Arc::new(ticks::default_tick_producer)),
             y_tick_producer: Some(Arc::new(ticks::default_tick_producer)),
+            y2_tick_producer: Some(Arc::new(ticks::default_tick_producer)),
             tick_label_size: 10.0,
             axis_label_size: 16.0,
             data_aspect: None,
@@ -162,18 +243,35 @@ impl PlotWidget {
             resolved_style: RwLock::new(PlotStyle::default()),
             x_ticks: Vec::new(),
             y_ticks: Vec::new(),
+            y2_ticks: Vec::new(),
             picked_points: IndexMap::new(),
             hovered_points: IndexMap::new(),
+            selected_points: IndexSet::new(),
             cursor_ui: None,
+            data_cursor_ui: None,
+            measurement: None,
             shape_overlays_enabled: AtomicBool::new(false),
             camera_bounds: None,
+            y2_map: SecondaryAxisMap::IDENTITY,
+            data_bounds: None,
+            point_indices: Mutex::default(),
         }
     }
 
     /// Add a data series to the plot.
     /// If there exists a series with the same `item.id` ([ShapeId]), the old one will be replaced.
-    pub fn add_series(&mut self, item: Series) -> Result<(), SeriesError> {
+    pub fn add_series(&mut self, mut item: Series) -> Result<(), SeriesError> {
         item.validate()?;
+        if let Some(unknown) = item
+            .x_categories
+            .iter()
+            .flatten()
+            .find(|name| self.x_category_index(name).is_none())
+        {
+            return Err(SeriesError::UnknownCategory(unknown.clone()));
+        }
+        item.place_on_x_categories(self.x_categories.as_deref());
+        item.apply_color_values();
         self.series.insert(item.id, item);
         self.data_version += 1;
         Ok(())
@@ -199,8 +297,9 @@ impl PlotWidget {
 
     /// Remove a data series from the plot by its ID.
     pub fn remove_series(&mut self, id: &ShapeId) -> Result<(), SeriesError> {
-        if self.series.shift_remove(id).is_some() {
+        if self.forget_series(id).is_some() {
             self.hidden_shapes.remove(id);
+            self.histograms.shift_remove(id);
             self.data_version += 1;
             Ok(())
         } else {
@@ -208,6 +307,17 @@ impl PlotWidget {
         }
     }
 
+    /// Remove a series along with its dropped point count and spatial index.
+    fn forget_series(&mut self, id: &ShapeId) -> Option<Series> {
+        let series = self.series.shift_remove(id)?;
+        self.dropped_points.remove(id);
+        self.point_indices
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(id);
+        Some(series)
+    }
+
     /// Remove a fill from the plot by its ID.
     pub fn remove_fill(&mut self, id: &ShapeId) -> Result<(), SeriesError> {
         if self.fills.shift_remove(id).is_some() {
@@ -227,6 +337,8 @@ impl PlotWidget {
     ) -> Result<(), SeriesError> {
         if let Some(series) = self.series.get_mut(id) {
             f(series);
+            series.place_on_x_categories(self.x_categories.as_deref());
+            series.apply_color_values();
             self.data_version += 1;
             Ok(())
         } else {
@@ -234,6 +346,49 @@ impl PlotWidget {
         }
     }
 
+    /// Append points to the end of a series.
+    ///
+    /// Unlike [`update_series`](Self::update_series), this doesn't rebuild the other
+    /// series: only the new points are processed and uploaded to the GPU, and autoscale
+    /// bounds are updated incrementally. If the series has a [`Series::max_len`], the
+    /// oldest points are dropped once it is exceeded.
+    ///
+    /// Per-point colors are extended with the series color, color values with NaN and
+    /// per-point errors with zero. Points appended to a decimated series must continue its
+    /// increasing x, otherwise nothing is appended and [`SeriesError::UnsortedPositions`] is
+    /// returned. Series placed by [category names](Series::with_x_categories) can't be
+    /// appended to; use [`update_series`](Self::update_series) instead.
+    pub fn append_series_points(
+        &mut self,
+        id: &ShapeId,
+        points: impl IntoIterator<Item = [f64; 2]>,
+    ) -> Result<(), SeriesError> {
+        let Some(series) = self.series.get_mut(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        if series.x_categories.is_some() {
+            return Err(SeriesError::AppendToNamedCategories);
+        }
+        let points: Vec<[f64; 2]> = points.into_iter().collect();
+        if series.decimation != Decimation::None
+            && !sorted_by_x(
+                series
+                    .positions
+                    .iter()
+                    .rev()
+                    .find(|p| p[0].is_finite())
+                    .into_iter()
+                    .chain(&points),
+            )
+        {
+            return Err(SeriesError::UnsortedPositions);
+        }
+        let dropped = series.append(points);
+        *self.dropped_points.entry(*id).or_default() += dropped as u64;
+        self.append_version = self.append_version.wrapping_add(1);
+        Ok(())
+    }
+
     /// Add a vertical reference line to the plot.
     /// If there exists a line with the same `vline.id` ([ShapeId]), the old one will be replaced.
     pub fn add_vline(&mut self, vline: VLine) {
@@ -264,6 +419,290 @@ impl PlotWidget {
         self.data_version += 1;
     }
 
+    /// Add a raster image or heatmap to the plot. Rasters are drawn below all other shapes.
+    /// If there exists a raster with the same `raster.id` ([ShapeId]), the old one will be replaced.
+    pub fn add_raster(&mut self, mut raster: Raster) -> Result<(), SeriesError> {
+        raster.validate()?;
+        raster.resolve_pixels();
+        self.rasters.insert(raster.id, raster);
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Remove a raster from the plot by its ID.
+    pub fn remove_raster(&mut self, id: &ShapeId) -> Result<(), SeriesError> {
+        if self.rasters.shift_remove(id).is_some() {
+            self.hidden_shapes.remove(id);
+            self.data_version += 1;
+            Ok(())
+        } else {
+            Err(SeriesError::NotFound(*id))
+        }
+    }
+
+    /// Update a raster by its id, e.g. to replace its data with the next frame.
+    ///
+    /// The raster is left unchanged if the update makes it invalid.
+    pub fn update_raster<F: FnMut(&mut Raster)>(
+        &mut self,
+        id: &ShapeId,
+        mut f: F,
+    ) -> Result<(), SeriesError> {
+        let Some(raster) = self.rasters.get_mut(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        let mut updated = raster.clone();
+        f(&mut updated);
+        updated.validate()?;
+        updated.resolve_pixels();
+        *raster = updated;
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Replace the cell data of a raster, keeping its size, extent and color scale.
+    pub fn set_raster_data(&mut self, id: &ShapeId, data: RasterData) -> Result<(), SeriesError> {
+        let Some(raster) = self.rasters.get_mut(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        let previous = std::mem::replace(&mut raster.data, data);
+        if let Err(error) = raster.validate() {
+            raster.data = previous;
+            return Err(error);
+        }
+        raster.resolve_pixels();
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// The raster cell identified by a hovered or picked point, if it is one.
+    pub fn raster_cell(&self, point_id: PointId) -> Option<RasterCell> {
+        let raster = self.rasters.get(&point_id.series_id)?;
+        let (row, column) = raster.cell(point_id.point_index)?;
+        Some(RasterCell {
+            row,
+            column,
+            value: raster.value(row, column),
+        })
+    }
+
+    /// Add contour lines, and optionally filled bands, traced from a grid of values.
+    ///
+    /// Each level is drawn as a line series owned by the contour; see
+    /// [`contour_level_ids`](Self::contour_level_ids). If there exists a contour with the
+    /// same `contour.id` ([ShapeId]), the old one will be replaced.
+    pub fn add_contour(&mut self, mut contour: Contour) -> Result<(), SeriesError> {
+        contour.validate()?;
+        let levels = contour.resolve_levels();
+        let level_values: Vec<f64> = levels.iter().map(|(level, _)| *level).collect();
+        let geometry = contour.trace(&level_values);
+
+        // Keep the ids (and visibility) of existing levels, so updates don't reset the legend.
+        let previous_ids = self
+            .contours
+            .get(&contour.id)
+            .map(|previous| previous.level_ids.clone())
+            .unwrap_or_default();
+        contour.level_ids.clear();
+        if let Some(line_style) = contour.line_style {
+            for (i, ((_, text), (color, positions))) in
+                levels.iter().zip(geometry.lines).enumerate()
+            {
+                let mut series = Series::line_only(positions, line_style)
+                    .with_color(color)
+                    .with_pickable(false);
+                series.id = previous_ids.get(i).copied().unwrap_or(series.id);
+                if let Some(label) = &contour.label {
+                    series = series.with_label(format!("{label}: {text}"));
+                }
+                contour.level_ids.push(series.id);
+                self.series.insert(series.id, series);
+            }
+        }
+        for id in previous_ids.iter().skip(contour.level_ids.len()) {
+            self.forget_series(id);
+            self.hidden_shapes.remove(id);
+        }
+        contour.bands = geometry.bands;
+        self.contours.insert(contour.id, contour);
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Remove a contour and its level lines from the plot by its ID.
+    pub fn remove_contour(&mut self, id: &ShapeId) -> Result<(), SeriesError> {
+        let Some(contour) = self.contours.shift_remove(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        for level_id in &contour.level_ids {
+            self.forget_series(level_id);
+            self.hidden_shapes.remove(level_id);
+        }
+        self.hidden_shapes.remove(id);
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Update a contour by its id, e.g. to replace its values, and trace it again.
+    ///
+    /// The contour is left unchanged if the update makes it invalid.
+    pub fn update_contour<F: FnMut(&mut Contour)>(
+        &mut self,
+        id: &ShapeId,
+        mut f: F,
+    ) -> Result<(), SeriesError> {
+        let Some(contour) = self.contours.get(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        let mut updated = contour.clone();
+        f(&mut updated);
+        updated.id = *id;
+        self.add_contour(updated)
+    }
+
+    /// Ids of the line series drawn for each level of a contour, from the lowest level up.
+    pub fn contour_level_ids(&self, id: &ShapeId) -> Option<&[ShapeId]> {
+        self.contours
+            .get(id)
+            .map(|contour| contour.level_ids.as_slice())
+    }
+
+    /// Add a histogram to the plot, drawn as a series with the same id.
+    /// If there exists a histogram or series with the same `histogram.id` ([ShapeId]), the
+    /// old one will be replaced.
+    pub fn add_histogram(&mut self, mut histogram: Histogram) -> Result<(), SeriesError> {
+        histogram.validate()?;
+        histogram.rebin();
+        self.series.insert(histogram.id, histogram.series());
+        self.histograms.insert(histogram.id, histogram);
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Remove a histogram and its series from the plot by its ID.
+    pub fn remove_histogram(&mut self, id: &ShapeId) -> Result<(), SeriesError> {
+        if self.histograms.contains_key(id) {
+            self.remove_series(id)
+        } else {
+            Err(SeriesError::NotFound(*id))
+        }
+    }
+
+    /// Update a histogram by its id, e.g. to change its binning, and bin it again.
+    ///
+    /// The histogram is left unchanged if the update makes it invalid.
+    pub fn update_histogram<F: FnMut(&mut Histogram)>(
+        &mut self,
+        id: &ShapeId,
+        mut f: F,
+    ) -> Result<(), SeriesError> {
+        let Some(histogram) = self.histograms.get_mut(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        let mut updated = histogram.clone();
+        f(&mut updated);
+        updated.id = *id;
+        updated.validate()?;
+        updated.rebin();
+        self.series.insert(*id, updated.series());
+        *histogram = updated;
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Replace the samples of a histogram and bin them again.
+    pub fn set_histogram_samples(
+        &mut self,
+        id: &ShapeId,
+        samples: Vec<f64>,
+    ) -> Result<(), SeriesError> {
+        self.update_histogram(id, |histogram| histogram.samples = samples.clone())
+    }
+
+    /// The histogram bin identified by a hovered or picked point, if it is one.
+    pub fn histogram_bin(&self, point_id: PointId) -> Option<HistogramBin> {
+        self.histograms
+            .get(&point_id.series_id)?
+            .bin(point_id.point_index)
+    }
+
+    /// Add box plots or violins of groups of samples to the plot.
+    /// If there exists a plot with the same `plot.id` ([ShapeId]), the old one will be replaced.
+    pub fn add_distribution_plot(&mut self, mut plot: DistributionPlot) -> Result<(), SeriesError> {
+        plot.validate()?;
+        if let Some(previous) = self.distributions.get(&plot.id) {
+            plot.lines_id = previous.lines_id;
+            plot.outliers_id = previous.outliers_id;
+        }
+        let (lines, outliers) = plot.build();
+        let hidden = self.hidden_shapes.contains(&plot.id);
+        for series in [lines, outliers] {
+            // Plots without outliers (or without any samples) don't keep empty series.
+            if series.positions.is_empty() {
+                self.forget_series(&series.id);
+                self.hidden_shapes.remove(&series.id);
+                continue;
+            }
+            self.set_hidden(series.id, hidden);
+            self.series.insert(series.id, series);
+        }
+        self.distributions.insert(plot.id, plot);
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Remove box plots or violins from the plot by their ID.
+    pub fn remove_distribution_plot(&mut self, id: &ShapeId) -> Result<(), SeriesError> {
+        let Some(plot) = self.distributions.shift_remove(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        for series_id in [plot.lines_id, plot.outliers_id, *id] {
+            self.forget_series(&series_id);
+            self.hidden_shapes.remove(&series_id);
+        }
+        self.data_version += 1;
+        Ok(())
+    }
+
+    /// Update box plots or violins by their id, e.g. to change their style, and recompute
+    /// their statistics.
+    ///
+    /// The plot is left unchanged if the update makes it invalid.
+    pub fn update_distribution_plot<F: FnMut(&mut DistributionPlot)>(
+        &mut self,
+        id: &ShapeId,
+        mut f: F,
+    ) -> Result<(), SeriesError> {
+        let Some(plot) = self.distributions.get(id) else {
+            return Err(SeriesError::NotFound(*id));
+        };
+        let mut updated = plot.clone();
+        f(&mut updated);
+        updated.id = *id;
+        self.add_distribution_plot(updated)
+    }
+
+    /// Replace the samples of box plots or violins and recompute their statistics.
+    pub fn set_distribution_samples(
+        &mut self,
+        id: &ShapeId,
+        groups: Vec<Vec<f64>>,
+    ) -> Result<(), SeriesError> {
+        self.update_distribution_plot(id, |plot| plot.groups = groups.clone())
+    }
+
+    /// The statistics of the box, violin or outlier identified by a hovered or picked point,
+    /// if it is one.
+    pub fn distribution_stats(&self, point_id: PointId) -> Option<DistributionStats> {
+        if let Some(plot) = self.distributions.get(&point_id.series_id) {
+            return plot.group_stats(point_id.point_index);
+        }
+        self.distributions
+            .values()
+            .find(|plot| plot.outliers_id == point_id.series_id)?
+            .outlier_stats(point_id.point_index)
+    }
+
     /// Set the x-axis label.
     pub fn set_x_axis_label(&mut self, label: impl Into<String>) {
         self.x_axis_label = label.into();
@@ -274,6 +713,11 @@ impl PlotWidget {
         self.y_axis_label = label.into();
     }
 
+    /// Set the secondary y-axis label, shown on the right side of the plot.
+    pub fn set_y2_axis_label(&mut self, label: impl Into<String>) {
+        self.y2_axis_label = label.into();
+    }
+
     /// Set the x-axis limits (min, max) for the plot.
     ///
     /// If set, these will override autoscaling for the x-axis.
@@ -289,6 +733,61 @@ impl PlotWidget {
         self.data_version = self.data_version.wrapping_add(1);
     }
 
+    /// Make the x-axis categorical, with the given category labels in order.
+    ///
+    /// Category `i` is at x = `i`, so series can use category indices as x values, or
+    /// name their categories with [`Series::with_x_categories`]. The axis gets one tick per
+    /// category labelled with its name, and panning and zooming are kept within the
+    /// categories, unless [x-axis limits](Self::set_x_lim) are set, which take precedence.
+    /// Box plots and violins line up with categories by default.
+    ///
+    /// This sets a linear x-axis scale and replaces the x-axis tick producer and formatter.
+    /// Series placed by name are moved to their categories' new positions; names that are
+    /// no longer categories are placed at NaN, so they aren't drawn.
+    pub fn set_x_categories<S: Into<String>>(&mut self, labels: impl IntoIterator<Item = S>) {
+        let labels: Arc<[String]> = labels.into_iter().map(Into::into).collect();
+        let count = labels.len();
+        self.x_tick_producer = Some(Arc::new(move |min, max| {
+            ticks::category_tick_producer(count, min, max)
+        }));
+        let formatter_labels = labels.clone();
+        self.x_axis_formatter = Some(Arc::new(move |tick| {
+            ticks::category_formatter(&formatter_labels, tick)
+        }));
+        self.x_axis_scale = AxisScale::Linear;
+        self.x_categories = Some(labels);
+        for series in self.series.values_mut() {
+            series.place_on_x_categories(self.x_categories.as_deref());
+        }
+        self.data_version = self.data_version.wrapping_add(1);
+    }
+
+    /// Make the x-axis numeric again, restoring the default tick producer and formatter.
+    ///
+    /// Series placed by name keep the x values they were placed at, their categories'
+    /// indices (or NaN for names that weren't categories). Their original x values are not
+    /// restored; set new positions with [`update_series`](Self::update_series).
+    pub fn clear_x_categories(&mut self) {
+        if self.x_categories.take().is_some() {
+            self.x_tick_producer = Some(Arc::new(ticks::default_tick_producer));
+            self.x_axis_formatter = Some(Arc::new(ticks::default_formatter));
+            self.data_version = self.data_version.wrapping_add(1);
+        }
+    }
+
+    /// The category labels of the x-axis, if it is categorical.
+    pub fn x_categories(&self) -> Option<&[String]> {
+        self.x_categories.as_deref()
+    }
+
+    /// The index, and so the x value, of the x-axis category with the given label.
+    pub fn x_category_index(&self, label: &str) -> Option<usize> {
+        self.x_categories
+            .as_ref()?
+            .iter()
+            .position(|category| category == label)
+    }
+
     /// Set the y-axis limits (min, max) for the plot.
     ///
     /// If set, these will override autoscaling for the y-axis.
@@ -304,6 +803,21 @@ impl PlotWidget {
         self.data_version = self.data_version.wrapping_add(1);
     }
 
+    /// Set the secondary y-axis limits (min, max) for the plot.
+    ///
+    /// If set, these will override autoscaling for the secondary y-axis.
+    pub fn set_y2_lim(&mut self, min: f64, max: f64) {
+        self.y2_lim = Some((min, max));
+    }
+
+    /// Set the secondary y-axis scale mode.
+    ///
+    /// This does not modify tick producer/formatter settings.
+    pub fn set_y2_axis_scale(&mut self, scale: AxisScale) {
+        self.y2_axis_scale = scale;
+        self.data_version = self.data_version.wrapping_add(1);
+    }
+
     /// Link the x-axis to other plots. When the x-axis is panned or zoomed,
     /// all plots sharing this link will update synchronously.
     pub fn set_x_axis_link(&mut self, link: AxisLink) {
@@ -316,6 +830,12 @@ impl PlotWidget {
         self.y_axis_link = Some(link);
     }
 
+    /// Link the secondary y-axis to other plots. When the plot is panned or zoomed,
+    /// all plots sharing this link will update synchronously.
+    pub fn set_y2_axis_link(&mut self, link: AxisLink) {
+        self.y2_axis_link = Some(link);
+    }
+
     /// Update and return the current plot style for a given application theme.
     pub(crate) fn update_style(&self, theme: &Theme) -> PlotStyle {
         let style = (self.style)(theme);
@@ -326,23 +846,33 @@ impl PlotWidget {
         style
     }
 
+    /// Scale of the given y-axis and its map from primary plot space.
+    fn y_axis_frame(&self, y_axis: YAxis) -> (AxisScale, SecondaryAxisMap) {
+        match y_axis {
+            YAxis::Primary => (self.y_axis_scale, SecondaryAxisMap::IDENTITY),
+            YAxis::Secondary => (self.y2_axis_scale, self.y2_map),
+        }
+    }
+
     /// Convert world position to screen position using camera and bounds
     /// Similar to how PositionedTick calculates screen position
     fn world_to_screen_position(
         world: [f64; 2],
         camera_bounds: &(Camera, Rectangle),
         x_axis_scale: AxisScale,
-        y_axis_scale: AxisScale,
+        (y_axis_scale, y_map): (AxisScale, SecondaryAxisMap),
         transform: &PositionTransform,
     ) -> Option<[f32; 2]> {
         let (camera, bounds) = camera_bounds;
+        let (x_range, y_range) = camera.axis_ranges();
         let world = data_point_to_plot_with_transform(
             world,
             x_axis_scale,
             y_axis_scale,
             transform,
-            Some(camera.axis_ranges()),
+            Some((x_range, y_map.range_to_secondary(y_range))),
         )?;
+        let world = y_map.point_to_primary(world);
         if let (Some(screen_x), Some(screen_y)) = (
             world_to_screen_position_x(world[0], camera, bounds),
             world_to_screen_position_y(world[1], camera, bounds),
@@ -395,6 +925,8 @@ impl PlotWidget {
     /// Update tooltip positions for all hovered and picked points
     /// This should be called when the plot canvas position changes
     fn update_tooltip_positions(&mut self) {
+        let primary = self.y_axis_frame(YAxis::Primary);
+        let secondary = self.y_axis_frame(YAxis::Secondary);
         if let Some(camera_bounds) = &self.camera_bounds {
             for (highlight_point, tooltip) in self
                 .hovered_points
@@ -406,7 +938,10 @@ impl PlotWidget {
                         Self::tooltip_anchor_world(highlight_point),
                         camera_bounds,
                         self.x_axis_scale,
-                        self.y_axis_scale,
+                        match highlight_point.y_axis {
+                            YAxis::Primary => primary,
+                            YAxis::Secondary => secondary,
+                        },
                         &highlight_point.transform,
                     );
                 }
@@ -430,55 +965,76 @@ impl PlotWidget {
     /// Find the nearest point to a given position in the plot.
     pub fn nearest_point(&self, series_id: ShapeId, x: f64, y: f64) -> Option<PointId> {
         let series = self.series.get(&series_id)?;
-        let mut min_distance = f64::INFINITY;
-        let mut nearest_point = None;
-        for (i, position) in series.positions.iter().enumerate() {
-            let distance = (position[0] - x).powi(2) + (position[1] - y).powi(2);
-            if distance < min_distance {
-                min_distance = distance;
-                nearest_point = Some(PointId {
-                    series_id,
-                    point_index: i,
-                });
-            }
-        }
-        nearest_point
+        let position = |i: usize| series.positions[i];
+        self.series_point_index(series)
+            .nearest([x, y], [1.0, 1.0], f64::INFINITY, position)
+            .map(|(point_index, _)| PointId {
+                series_id,
+                point_index,
+            })
     }
 
     /// Find the nearest point to a given x-coordinate in the plot.
+    ///
+    /// Series with sorted x are binary searched.
     pub fn nearest_point_horizontal(&self, series_id: ShapeId, x: f64) -> Option<PointId> {
         let series = self.series.get(&series_id)?;
-        let mut min_distance = f64::INFINITY;
-        let mut nearest_point = None;
-        for (i, position) in series.positions.iter().enumerate() {
-            let distance = (position[0] - x).abs();
-            if distance < min_distance {
-                min_distance = distance;
-                nearest_point = Some(PointId {
-                    series_id,
-                    point_index: i,
-                });
-            }
-        }
-        nearest_point
+        let position = |i: usize| series.positions[i];
+        self.series_point_index(series)
+            .nearest_x(x, position)
+            .map(|point_index| PointId {
+                series_id,
+                point_index,
+            })
     }
 
     /// Find the nearest point to a given y-coordinate in the plot.
     pub fn nearest_point_vertical(&self, series_id: ShapeId, y: f64) -> Option<PointId> {
         let series = self.series.get(&series_id)?;
-        let mut min_distance = f64::INFINITY;
-        let mut nearest_point = None;
-        for (i, position) in series.positions.iter().enumerate() {
-            let distance = (position[1] - y).abs();
-            if distance < min_distance {
-                min_distance = distance;
-                nearest_point = Some(PointId {
-                    series_id,
-                    point_index: i,
-                });
+        let position = |i: usize| series.positions[i];
+        self.series_point_index(series)
+            .nearest_y(y, position)
+            .map(|point_index| PointId {
+                series_id,
+                point_index,
+            })
+    }
+
+    /// The spatial index over a series' positions, rebuilt after the data changes. Appends
+    /// to a series with sorted x extend its index instead.
+    fn series_point_index(&self, series: &Series) -> Arc<PointIndex> {
+        let dropped = self.dropped_points.get(&series.id).copied().unwrap_or(0);
+        let len = series.positions.len();
+        let position = |i: usize| series.positions[i];
+        // The cache holds nothing a rebuild can't restore, so a panic while it was locked
+        // doesn't leave it in a state worth refusing.
+        let mut indices = self
+            .point_indices
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let extended = match indices.get(&series.id) {
+            Some(cached) if cached.data_version == self.data_version => {
+                if (cached.dropped, cached.len) == (dropped, len) {
+                    return cached.index.clone();
+                }
+                // Appends keep a sorted index, only extending it.
+                dropped
+                    .checked_sub(cached.dropped)
+                    .and_then(|removed| cached.index.extended(removed as usize, len, position))
             }
-        }
-        nearest_point
+            _ => None,
+        };
+        let index = Arc::new(extended.unwrap_or_else(|| PointIndex::new(len, position)));
+        indices.insert(
+            series.id,
+            CachedPointIndex {
+                data_version: self.data_version,
+                dropped,
+                len,
+                index: index.clone(),
+            },
+        );
+        index
     }
 
     /// Add a hover point to the plot.
@@ -498,14 +1054,103 @@ impl PlotWidget {
         }
     }
 
-    /// Clear all pick points from the plot.
+    /// Clear all pick points from the plot, including the selection.
     pub fn clear_pick(&mut self) {
         if !self.picked_points.is_empty() {
             self.picked_points.clear();
         }
+        self.selected_points.clear();
+    }
+
+    /// Get the points selected by box or lasso selection, in the order they were selected.
+    pub fn selected_points(&self) -> Vec<PointId> {
+        self.selected_points.iter().copied().collect()
     }
 
-    fn cached_style(&self) -> PlotStyle {
+    /// Check whether a point is selected.
+    pub fn is_selected(&self, point_id: PointId) -> bool {
+        self.selected_points.contains(&point_id)
+    }
+
+    /// Select points as a box or lasso selection would, highlighting them as picked
+    /// points without tooltips. At most 10 000 points are highlighted; the rest are still
+    /// selected.
+    ///
+    /// [`SelectionMode::Replace`] also clears points picked by clicking.
+    pub fn select_points(
+        &mut self,
+        points: impl IntoIterator<Item = PointId>,
+        mode: SelectionMode,
+    ) {
+        match mode {
+            SelectionMode::Replace => {
+                self.clear_pick();
+                self.add_selected_points(points);
+            }
+            SelectionMode::Add => self.add_selected_points(points),
+            SelectionMode::Subtract => {
+                for point_id in points {
+                    if self.selected_points.shift_remove(&point_id) {
+                        self.picked_points.shift_remove(&point_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Clear the selection, leaving points picked by clicking.
+    pub fn clear_selection(&mut self) {
+        for point_id in std::mem::take(&mut self.selected_points) {
+            self.picked_points.shift_remove(&point_id);
+        }
+    }
+
+    /// Get the last measurement drawn with the ruler, until it is cleared.
+    pub fn measurement(&self) -> Option<&Measurement> {
+        self.measurement.as_ref()
+    }
+
+    /// Remove the ruler from the plot.
+    pub fn clear_measurement(&mut self) {
+        self.measurement = None;
+    }
+
+    fn add_selected_points(&mut self, points: impl IntoIterator<Item = PointId>) {
+        let points: Vec<PointId> = points
+            .into_iter()
+            .filter(|point_id| self.valid_point_id(point_id))
+            .collect();
+        let first = self.selected_points.len();
+        self.selected_points.extend(points);
+        let added = first..self.selected_points.len();
+        if added.is_empty() {
+            return;
+        }
+        let selected = &self.selected_points;
+        self.hovered_points.retain(|point_id, _| {
+            selected
+                .get_index_of(point_id)
+                .is_none_or(|index| index < first)
+        });
+
+        let Some(provider) = self.pick_highlight_provider.clone() else {
+            return;
+        };
+        let room = MAX_SELECTION_HIGHLIGHTS.saturating_sub(self.picked_points.len());
+        let highlights: Vec<_> = self.selected_points[added]
+            .iter()
+            .filter(|point_id| !self.picked_points.contains_key(*point_id))
+            .take(room)
+            // A tooltip per selected point would bury the plot.
+            .filter_map(|&point_id| {
+                let (highlight_point, _) = self.highlight_payload(&provider, point_id)?;
+                Some((point_id, (highlight_point, None)))
+            })
+            .collect();
+        self.picked_points.extend(highlights);
+    }
+
+    pub(crate) fn cached_style(&self) -> PlotStyle {
         *self
             .resolved_style
             .read()
@@ -514,70 +1159,163 @@ impl PlotWidget {
 
     fn handle_hover_pick<const PICK: bool>(&mut self, point_id: PointId) -> bool {
         let mut changed = false;
-        let (highlight_provider, points) = if PICK {
+        let highlight_provider = if PICK {
             // Clicking an already-picked point deselects it.
             if self.picked_points.shift_remove(&point_id).is_some() {
+                self.selected_points.shift_remove(&point_id);
                 return true;
             }
             changed |= self.hovered_points.shift_remove(&point_id).is_some();
-            (&self.pick_highlight_provider, &mut self.picked_points)
+            &self.pick_highlight_provider
         } else {
             if self.picked_points.contains_key(&point_id) {
                 return false;
             }
-            (&self.hover_highlight_provider, &mut self.hovered_points)
+            &self.hover_highlight_provider
         };
-        if let Some(highlight_provider) = highlight_provider
-            && let Some(series) = self.series.get(&point_id.series_id)
-            && let Some(position) = series.positions.get(point_id.point_index)
-            && let Some(camera_bounds) = &self.camera_bounds
-        {
-            let mut highlight_point = HighlightPoint {
-                x: position[0],
-                y: position[1],
-                transform: series.transform.clone(),
-                color: series
-                    .point_colors
-                    .as_ref()
-                    .map(|colors| colors[point_id.point_index])
-                    .unwrap_or(series.color),
-                marker_style: series.marker_style,
-                mask_padding: Some(3.0),
-            };
-            let tooltip_text = highlight_provider(
-                TooltipContext {
-                    series_id: series.id,
-                    series_label: series.label.as_deref().unwrap_or(""),
-                    point_index: point_id.point_index,
-                },
-                &mut highlight_point,
-            );
-            let tooltip = tooltip_text.map(|text| TooltipUiPayload {
-                screen_xy: Self::world_to_screen_position(
-                    Self::tooltip_anchor_world(&highlight_point),
-                    camera_bounds,
-                    self.x_axis_scale,
-                    self.y_axis_scale,
-                    &highlight_point.transform,
-                ),
-                text,
-            });
-            let new_payload = (highlight_point, tooltip);
-            match points.entry(point_id) {
-                indexmap::map::Entry::Occupied(mut occupied) => {
-                    if PartialEq::ne(occupied.get(), &new_payload) {
-                        occupied.insert(new_payload);
-                        changed = true;
-                    }
-                }
-                indexmap::map::Entry::Vacant(vacant) => {
-                    vacant.insert(new_payload);
+        let Some(new_payload) = highlight_provider
+            .as_ref()
+            .and_then(|provider| self.highlight_payload(provider, point_id))
+        else {
+            return changed;
+        };
+        let points = if PICK {
+            &mut self.picked_points
+        } else {
+            &mut self.hovered_points
+        };
+        match points.entry(point_id) {
+            indexmap::map::Entry::Occupied(mut occupied) => {
+                if PartialEq::ne(occupied.get(), &new_payload) {
+                    occupied.insert(new_payload);
                     changed = true;
                 }
             }
+            indexmap::map::Entry::Vacant(vacant) => {
+                vacant.insert(new_payload);
+                changed = true;
+            }
         }
         changed
     }
+
+    /// Run the highlight provider for a series point or raster cell.
+    fn highlight_payload(
+        &self,
+        highlight_provider: &HighlightPointProvider,
+        point_id: PointId,
+    ) -> Option<(HighlightPoint, Option<TooltipUiPayload>)> {
+        let camera_bounds = self.camera_bounds.as_ref()?;
+        let (highlight_point, tooltip_text) =
+            if let Some(raster) = self.rasters.get(&point_id.series_id) {
+                let (row, column) = raster.cell(point_id.point_index)?;
+                // Highlight the cell center; cells are spaced evenly in plot space.
+                let (min, max) = raster
+                    .plot_grid(self.x_axis_scale, self.y_axis_scale)?
+                    .cell_rect(row, column);
+                let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+                let [x, y] = plot_point_to_data(center, self.x_axis_scale, self.y_axis_scale)?;
+                let mut highlight_point = HighlightPoint {
+                    x,
+                    y,
+                    transform: PositionTransform::default(),
+                    y_axis: YAxis::Primary,
+                    color: raster
+                        .cell_color(point_id.point_index)
+                        .unwrap_or(Color::TRANSPARENT),
+                    marker_style: None,
+                    mask_padding: None,
+                };
+                let tooltip_text = highlight_provider(
+                    TooltipContext {
+                        series_id: raster.id,
+                        series_label: raster.label.as_deref().unwrap_or(""),
+                        point_index: point_id.point_index,
+                        x_error: None,
+                        y_error: None,
+                        cell: self.raster_cell(point_id),
+                        bin: None,
+                        distribution: None,
+                    },
+                    &mut highlight_point,
+                );
+                (highlight_point, tooltip_text)
+            } else if let Some(plot) = self.distributions.get(&point_id.series_id) {
+                let stats = plot.group_stats(point_id.point_index)?;
+                let mut highlight_point = HighlightPoint {
+                    x: plot.position(point_id.point_index),
+                    y: stats.median,
+                    transform: PositionTransform::default(),
+                    y_axis: YAxis::Primary,
+                    color: plot.color,
+                    marker_style: None,
+                    mask_padding: None,
+                };
+                let tooltip_text = highlight_provider(
+                    TooltipContext {
+                        series_id: plot.id,
+                        series_label: plot.label.as_deref().unwrap_or(""),
+                        point_index: point_id.point_index,
+                        x_error: None,
+                        y_error: None,
+                        cell: None,
+                        bin: None,
+                        distribution: Some(stats),
+                    },
+                    &mut highlight_point,
+                );
+                (highlight_point, tooltip_text)
+            } else {
+                let series = self.series.get(&point_id.series_id)?;
+                let position = series.positions.get(point_id.point_index)?;
+                let mut highlight_point = HighlightPoint {
+                    x: position[0],
+                    y: position[1],
+                    transform: series.transform.clone(),
+                    y_axis: series.y_axis,
+                    color: series
+                        .point_colors
+                        .as_ref()
+                        .map(|colors| colors[point_id.point_index])
+                        .unwrap_or(series.color),
+                    marker_style: series.marker_style,
+                    mask_padding: Some(3.0),
+                };
+                let tooltip_text = highlight_provider(
+                    TooltipContext {
+                        series_id: series.id,
+                        series_label: series.label.as_deref().unwrap_or(""),
+                        point_index: point_id.point_index,
+                        x_error: series
+                            .x_errors
+                            .as_ref()
+                            .and_then(|errors| errors.get(point_id.point_index)),
+                        y_error: series
+                            .y_errors
+                            .as_ref()
+                            .and_then(|errors| errors.get(point_id.point_index)),
+                        cell: None,
+                        bin: self.histogram_bin(point_id),
+                        distribution: self.distribution_stats(point_id),
+                    },
+                    &mut highlight_point,
+                );
+                (highlight_point, tooltip_text)
+            };
+        let y_frame = self.y_axis_frame(highlight_point.y_axis);
+        let tooltip = tooltip_text.map(|text| TooltipUiPayload {
+            screen_xy: Self::world_to_screen_position(
+                Self::tooltip_anchor_world(&highlight_point),
+                camera_bounds,
+                self.x_axis_scale,
+                y_frame,
+                &highlight_point.transform,
+            ),
+            text,
+        });
+        Some((highlight_point, tooltip))
+    }
+
     /// Handle a message sent to the plot widget.
     pub fn update(&mut self, msg: PlotUiMessage) {
         match msg {
@@ -591,43 +1329,69 @@ impl PlotWidget {
                 self.toggle_visibility(&id);
             }
             PlotUiMessage::RenderUpdate(payload) => {
+                let details = *payload.details;
                 // Update camera and bounds when ticks are updated (camera changed)
-                if let Some(camera_bounds) = payload.camera_bounds
-                    && self.camera_bounds != Some(*camera_bounds)
-                {
-                    self.camera_bounds = Some(*camera_bounds);
-                    // Update tooltip positions when camera/bounds change
-                    self.update_tooltip_positions();
+                if let Some(camera_bounds) = details.camera_bounds {
+                    let (camera, bounds, y2_map, data_bounds) = camera_bounds;
+                    self.data_bounds = data_bounds;
+                    if self.camera_bounds != Some((camera, bounds)) || self.y2_map != y2_map {
+                        self.camera_bounds = Some((camera, bounds));
+                        self.y2_map = y2_map;
+                        // Update tooltip positions when camera/bounds change
+                        self.update_tooltip_positions();
+                    }
                 }
 
                 match payload.hover_pick {
-                    Some(HoverPickEvent::Hover(point_id)) => {
+                    Some(HoverPickEvent::Hover(hit)) => {
                         self.hovered_points.clear();
-                        self.handle_hover_pick::<false>(point_id);
+                        if let Some(point_id) = hit.point_id() {
+                            self.handle_hover_pick::<false>(point_id);
+                        }
                     }
-                    Some(HoverPickEvent::Pick(point_id)) => {
-                        self.handle_hover_pick::<true>(point_id);
+                    Some(HoverPickEvent::Pick(hit)) => {
+                        if let Some(point_id) = hit.point_id() {
+                            self.handle_hover_pick::<true>(point_id);
+                        }
                     }
                     Some(HoverPickEvent::ClearHover) => {
                         self.hovered_points.clear();
                     }
                     Some(HoverPickEvent::ClearPick) => {
-                        self.picked_points.clear();
+                        self.clear_pick();
                     }
                     _ => {}
                 };
+                if let Some(selection) = details.selection {
+                    self.select_points(selection.points, selection.mode);
+                }
+                if details.clear_measurement {
+                    self.measurement = None;
+                }
+                if let Some(measurement) = details.measurement {
+                    self.measurement = Some(measurement);
+                }
                 if payload.clear_cursor_position {
                     self.cursor_ui = None;
                 }
                 if let Some(c) = payload.cursor_position_ui {
                     self.cursor_ui = Some(c);
                 }
+                if details.clear_data_cursor {
+                    self.data_cursor_ui = None;
+                }
+                if let Some(readout) = details.data_cursor {
+                    self.data_cursor_ui = Some(readout);
+                }
                 if let Some(ticks) = payload.x_ticks {
                     self.x_ticks = ticks;
                 }
                 if let Some(ticks) = payload.y_ticks {
                     self.y_ticks = ticks;
                 }
+                if let Some(ticks) = details.y2_ticks {
+                    self.y2_ticks = ticks;
+                }
             }
         }
     }
@@ -670,6 +1434,8 @@ impl PlotWidget {
         let tooltip_overlays = self
             .visible_highlighted_points()
             .filter_map(|(_, tooltip)| self.view_tooltip_overlay(tooltip, &self.camera_bounds))
+            .chain(self.view_data_cursor_overlay())
+            .chain(self.view_measurement_overlay())
             .map(|element| element.map(map_plot));
         let shapes_top = shapes_top.chain(tooltip_overlays);
 
@@ -681,6 +1447,11 @@ impl PlotWidget {
             None
         };
         let has_legend = legend.is_some();
+        let legend_position = self.legend_position();
+        let (inside_legend, outside_legend) = match legend_position {
+            LegendPosition::OutsideRight | LegendPosition::OutsideBottom => (None, legend),
+            _ => (legend, None),
+        };
 
         let mut layers = Vec::new();
         layers.push(inner_container);
@@ -690,25 +1461,54 @@ impl PlotWidget {
             layers.push(tick_labels.map(map_plot));
         }
 
-        if let Some(legend) = legend {
-            layers.push(legend.map(map_plot));
+        if let Some(legend) = inside_legend {
+            let (align_x, align_y) = match legend_position {
+                LegendPosition::TopRight => (Horizontal::Right, Vertical::Top),
+                LegendPosition::BottomLeft => (Horizontal::Left, Vertical::Bottom),
+                LegendPosition::BottomRight => (Horizontal::Right, Vertical::Bottom),
+                _ => (Horizontal::Left, Vertical::Top),
+            };
+            let legend = container(legend)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .align_x(align_x)
+                .align_y(align_y);
+            layers.push(Element::from(legend).map(map_plot));
         }
 
         let elements: Element<'a, Message> = stack(layers)
             .width(Length::Fill)
             .height(Length::Fill)
             .into();
+        let elements = match colorbar::colorbar(self) {
+            Some(colorbar) => row![elements, colorbar.map(map_plot)].into(),
+            None => elements,
+        };
 
-        container(axes_labels::stack_with_labels(
+        let labeled = axes_labels::stack_with_labels(
             elements,
             &self.x_axis_label,
             &self.y_axis_label,
+            &self.y2_axis_label,
             self.axis_label_size,
             style.axis_label_color,
-        ))
-        .padding(3.0)
-        .style(|theme: &Theme| self.update_style(theme).frame)
-        .into()
+        );
+        let labeled: Element<'a, Message> = match (outside_legend, legend_position) {
+            (Some(legend), LegendPosition::OutsideRight) => row![labeled, legend.map(map_plot)]
+                .spacing(LEGEND_SPACING)
+                .align_y(Vertical::Center)
+                .into(),
+            (Some(legend), _) => widget::column![labeled, legend.map(map_plot)]
+                .spacing(LEGEND_SPACING)
+                .align_x(Horizontal::Center)
+                .into(),
+            (None, _) => labeled,
+        };
+
+        container(labeled)
+            .padding(FRAME_PADDING)
+            .style(|theme: &Theme| self.update_style(theme).frame)
+            .into()
     }
 
     fn view_plot_area<'a, Message, MapPlot>(
@@ -774,11 +1574,26 @@ impl PlotWidget {
         self.autoscale_on_updates = enabled;
     }
 
+    /// Keep the newest `window` of x values in view as points are appended.
+    ///
+    /// The x-axis shows `[latest - window, latest]` and the y-axis fits the data. Panning
+    /// and zooming still work between updates. Pass `None` to stop following.
+    pub fn set_follow_latest(&mut self, window: Option<f64>) {
+        self.follow_latest = window.filter(|window| window.is_finite() && *window > 0.0);
+        self.data_version = self.data_version.wrapping_add(1);
+    }
+
     /// Set hover radius in logical pixels for picking markers (default: 8 px)
     pub fn hover_radius_px(&mut self, radius: f32) {
         self.hover_radius_px = radius.max(0.0);
     }
 
+    /// Snap the ends of the measurement ruler to the nearest data point within the hover
+    /// radius (default: false).
+    pub fn set_measure_snap(&mut self, enabled: bool) {
+        self.measure_snap = enabled;
+    }
+
     /// Set a custom highlighter for picked point.
     pub fn set_pick_highlight_provider(&mut self, provider: HighlightPointProvider) {
         self.pick_highlight_provider = Some(provider);
@@ -806,6 +1621,19 @@ impl PlotWidget {
         self.crosshairs_enabled = enabled;
     }
 
+    /// Enable a data cursor, or disable it with `None`. The data cursor draws a vertical
+    /// line at the cursor, marks every visible series where the line crosses it, and
+    /// lists their values in one tooltip.
+    ///
+    /// Series with a [`PositionTransform`] are read in their own data coordinates. Bars,
+    /// and series placed in axes coordinates, are not read.
+    pub fn set_data_cursor(&mut self, mode: Option<DataCursorMode>) {
+        self.data_cursor = mode;
+        if mode.is_none() {
+            self.data_cursor_ui = None;
+        }
+    }
+
     /// Set the rendering strategy used by the plot.
     pub fn set_render_strategy(&mut self, strategy: PlotRenderStrategy) {
         self.render_strategy = strategy;
@@ -875,15 +1703,40 @@ impl PlotWidget {
         self.y_tick_producer = Some(producer);
     }
 
+    /// Set a custom formatter for the secondary y-axis tick labels.
+    pub fn set_y2_axis_formatter(&mut self, formatter: TickFormatter) {
+        self.y2_axis_formatter = Some(formatter);
+    }
+
+    /// Set a custom tick producer for generating tick positions along the secondary y-axis.
+    pub fn set_y2_tick_producer(&mut self, producer: TickProducer) {
+        self.y2_tick_producer = Some(producer);
+    }
+
     /// Set the positions of an existing series.
+    ///
+    /// Per-point colors, color values and errors are kept for the points that remain.
+    /// New points get the series color and zero errors.
     pub fn set_series_positions(&mut self, id: &ShapeId, positions: &[[f64; 2]]) {
         if let Some(series) = self.series.get_mut(id) {
-            series.positions = positions.to_vec();
+            series.positions = positions.iter().copied().collect();
             if let Some(colors) = &mut series.point_colors
                 && colors.len() != series.positions.len()
             {
                 colors.resize(series.positions.len(), series.color);
             }
+            if let Some(values) = &mut series.color_values
+                && values.len() != series.positions.len()
+            {
+                values.resize(series.positions.len(), f64::NAN);
+            }
+            for errors in [&mut series.x_errors, &mut series.y_errors]
+                .into_iter()
+                .flatten()
+            {
+                errors.resize(series.positions.len());
+            }
+            series.apply_color_values();
             self.data_version += 1;
         }
     }
@@ -894,11 +1747,85 @@ impl PlotWidget {
             if colors.len() != series.positions.len() {
                 colors.resize(series.positions.len(), series.color);
             }
-            series.point_colors = Some(colors);
+            series.point_colors = Some(colors.into());
+            series.color_values = None;
+            self.data_version += 1;
+        }
+    }
+
+    /// Set per-point color values for an existing series, keeping its color scale.
+    pub fn set_series_color_values(&mut self, id: &ShapeId, mut values: Vec<f64>) {
+        if let Some(series) = self.series.get_mut(id) {
+            if values.len() != series.positions.len() {
+                values.resize(series.positions.len(), f64::NAN);
+            }
+            series.color_values = Some(values.into());
+            series.apply_color_values();
             self.data_version += 1;
         }
     }
 
+    /// Set the placement and layout of the legend.
+    pub fn set_legend(&mut self, legend: Legend) {
+        self.legend = legend;
+    }
+
+    /// The legend's position, with [`LegendPosition::Best`] resolved to a corner from an
+    /// estimate of the legend's size and the visible points each corner would cover.
+    /// Series with a position transform are not counted.
+    fn legend_position(&self) -> LegendPosition {
+        if self.legend.position != LegendPosition::Best {
+            return self.legend.position;
+        }
+        let Some((camera, bounds)) = self.camera_bounds else {
+            return LegendPosition::TopLeft;
+        };
+        let (_, size) = self.legend.layout(&self.legend_entries());
+        // The legend sits in the plot area, which the data is inset in.
+        let area = iced::Size::new(
+            bounds.width + 2.0 * PLOT_CONTENT_PADDING,
+            bounds.height + 2.0 * PLOT_CONTENT_PADDING,
+        );
+        self.legend.resolve_position(area, size, |rect| {
+            let (min, max) = legend::plot_box(&camera, &bounds, rect);
+            self.series
+                .iter()
+                .filter(|(id, series)| {
+                    !self.hidden_shapes.contains(*id)
+                        && series.transform == PositionTransform::default()
+                })
+                .map(|(_, series)| self.count_points_within(series, min, max))
+                .sum()
+        })
+    }
+
+    /// Number of `series`' points inside the plot-space box from `min` to `max`.
+    fn count_points_within(&self, series: &Series, min: [f64; 2], max: [f64; 2]) -> usize {
+        let (y_scale, y_map) = self.y_axis_frame(series.y_axis);
+        let to_data = |[x, y]: [f64; 2]| {
+            Some([
+                self.x_axis_scale.plot_to_data(x)?,
+                y_scale.plot_to_data(y_map.to_secondary(y))?,
+            ])
+        };
+        let (Some(a), Some(b)) = (to_data(min), to_data(max)) else {
+            return 0;
+        };
+        let mut count = 0;
+        self.series_point_index(series).for_each_within(
+            [a[0].min(b[0]), a[1].min(b[1])],
+            [a[0].max(b[0]), a[1].max(b[1])],
+            |i| series.positions[i],
+            |_| count += 1,
+        );
+        count
+    }
+
+    /// Show a colorbar beside the plot area, or hide it with `None`.
+    pub fn set_colorbar(&mut self, colorbar: Option<Colorbar>) {
+        self.colorbar = colorbar;
+    }
+
     pub(crate) fn legend_entries(&self) -> Vec<LegendEntry> {
         let mut out = Vec::new();
         for (id, s) in &self.series {
@@ -909,8 +1836,8 @@ impl PlotWidget {
                 if s.positions.is_empty() {
                     continue;
                 }
-                // Include series that have either markers or lines
-                if s.marker_style.is_some() || s.line_style.is_some() {
+                // Include series that have markers, lines, or bars
+                if s.marker_style.is_some() || s.line_style.is_some() || s.bar_style.is_some() {
                     let marker = if let Some(ref marker_style) = s.marker_style {
                         marker_style.marker_type as u32
                     } else {
@@ -920,13 +1847,33 @@ impl PlotWidget {
                         id: *id,
                         label: label.clone(),
                         color: s.color,
-                        _marker: marker,
-                        _line_style: s.line_style,
+                        marker,
+                        line_style: s.line_style,
+                        area: s.bar_style.is_some().then_some(s.color),
                         hidden: self.hidden_shapes.contains(id),
                     });
                 }
             }
         }
+        // Add box plots and violins to legend
+        for (id, plot) in &self.distributions {
+            if let Some(ref label) = plot.label
+                && !label.is_empty()
+            {
+                out.push(LegendEntry {
+                    id: *id,
+                    label: label.clone(),
+                    color: plot.color,
+                    marker: u32::MAX,
+                    line_style: Some(plot.line_style),
+                    area: Some(Color {
+                        a: plot.color.a * plot.fill_opacity,
+                        ..plot.color
+                    }),
+                    hidden: self.hidden_shapes.contains(id),
+                });
+            }
+        }
         // Add vertical reference lines to legend
         for (id, vline) in &self.vlines {
             if let Some(ref label) = vline.label
@@ -936,8 +1883,9 @@ impl PlotWidget {
                     id: *id,
                     label: label.clone(),
                     color: vline.color,
-                    _marker: u32::MAX,
-                    _line_style: Some(vline.line_style),
+                    marker: u32::MAX,
+                    line_style: Some(vline.line_style),
+                    area: None,
                     hidden: self.hidden_shapes.contains(id),
                 });
             }
@@ -951,8 +1899,9 @@ impl PlotWidget {
                     id: *id,
                     label: label.clone(),
                     color: hline.color,
-                    _marker: u32::MAX,
-                    _line_style: Some(hline.line_style),
+                    marker: u32::MAX,
+                    line_style: Some(hline.line_style),
+                    area: None,
                     hidden: self.hidden_shapes.contains(id),
                 });
             }
@@ -966,8 +1915,9 @@ impl PlotWidget {
                     id: *id,
                     label: label.clone(),
                     color: fill.color,
-                    _marker: u32::MAX,
-                    _line_style: None,
+                    marker: u32::MAX,
+                    line_style: None,
+                    area: Some(fill.color),
                     hidden: self.hidden_shapes.contains(id),
                 });
             }
@@ -980,11 +1930,34 @@ impl PlotWidget {
         payload: &'a Option<TooltipUiPayload>,
         camera_bounds: &Option<(Camera, Rectangle)>,
     ) -> Option<Element<'a, PlotUiMessage>> {
-        // Offset a bit from point position
-        const OFFSET: f32 = 8.0;
         let payload = payload.as_ref()?;
-        let [screen_x, screen_y] = payload.screen_xy?;
+        let screen_xy = payload.screen_xy?;
 
+        let tooltip_bubble = container(
+            widget::text(&payload.text)
+                .size(14.0)
+                .wrapping(widget::text::Wrapping::None),
+        )
+        .padding(6.0)
+        .style(|theme| self.update_style(theme).tooltip);
+
+        // Position tooltip at fixed location relative to point, not following cursor
+        Some(Self::position_tooltip(
+            tooltip_bubble.into(),
+            screen_xy,
+            camera_bounds,
+        ))
+    }
+
+    /// Place a tooltip next to a point in the plot area, flipping it towards the inside
+    /// of the plot when the point is near the right or bottom edge.
+    fn position_tooltip<'a>(
+        tooltip: Element<'a, PlotUiMessage>,
+        [screen_x, screen_y]: [f32; 2],
+        camera_bounds: &Option<(Camera, Rectangle)>,
+    ) -> Element<'a, PlotUiMessage> {
+        // Offset a bit from point position
+        const OFFSET: f32 = 8.0;
         let mut anchor = [screen_x + OFFSET, screen_y + OFFSET];
         let mut horizontal_position = Horizontal::Right;
         let mut vertical_position = Vertical::Bottom;
@@ -1004,20 +1977,86 @@ impl PlotWidget {
             }
         }
 
-        let tooltip_bubble = container(
-            widget::text(&payload.text)
+        plot_overlay::positioned_overlay(tooltip, anchor, horizontal_position, vertical_position)
+    }
+
+    fn view_measurement_overlay(&self) -> Option<Element<'_, PlotUiMessage>> {
+        let measurement = self.measurement.as_ref()?;
+        let camera_bounds = self.camera_bounds.as_ref()?;
+        let screen_xy = Self::world_to_screen_position(
+            measurement.end,
+            camera_bounds,
+            self.x_axis_scale,
+            (self.y_axis_scale, SecondaryAxisMap::IDENTITY),
+            &PositionTransform::default(),
+        )?;
+
+        let mut text = format!(
+            "Δx {}\nΔy {}\ndistance {:.4}",
+            format_measured_delta(measurement.delta_x, self.x_axis_scale),
+            format_measured_delta(measurement.delta_y, self.y_axis_scale),
+            measurement.distance,
+        );
+        if let Some(slope) = measurement.slope {
+            text.push_str(&format!("\nslope {slope:.4}"));
+        }
+        let bubble = container(
+            widget::text(text)
                 .size(14.0)
                 .wrapping(widget::text::Wrapping::None),
         )
         .padding(6.0)
         .style(|theme| self.update_style(theme).tooltip);
 
-        // Position tooltip at fixed location relative to point, not following cursor
-        Some(plot_overlay::positioned_overlay(
-            tooltip_bubble.into(),
-            anchor,
-            horizontal_position,
-            vertical_position,
+        Some(Self::position_tooltip(
+            bubble.into(),
+            screen_xy,
+            &self.camera_bounds,
+        ))
+    }
+
+    fn view_data_cursor_overlay(&self) -> Option<Element<'_, PlotUiMessage>> {
+        let readout = self.data_cursor_ui.as_ref()?;
+        if readout.entries.is_empty() {
+            return None;
+        }
+
+        let title = widget::text(&readout.title)
+            .size(14.0)
+            .wrapping(widget::text::Wrapping::None);
+        let entries = readout.entries.iter().map(|entry| {
+            let color = entry.color;
+            let swatch =
+                container(widget::Space::new().width(10.0).height(10.0)).style(move |_: &Theme| {
+                    container::Style {
+                        background: Some(color.into()),
+                        ..container::Style::default()
+                    }
+                });
+            let text = if entry.label.is_empty() {
+                entry.text.clone()
+            } else {
+                format!("{}: {}", entry.label, entry.text)
+            };
+            row![
+                swatch,
+                widget::text(text)
+                    .size(14.0)
+                    .wrapping(widget::text::Wrapping::None)
+            ]
+            .spacing(6.0)
+            .align_y(Vertical::Center)
+            .into()
+        });
+        let bubble =
+            container(widget::column(std::iter::once(title.into()).chain(entries)).spacing(2.0))
+                .padding(6.0)
+                .style(|theme| self.update_style(theme).tooltip);
+
+        Some(Self::position_tooltip(
+            bubble.into(),
+            readout.screen_xy,
+            &self.camera_bounds,
         ))
     }
 
@@ -1085,11 +2124,12 @@ impl PlotWidget {
     }
 
     fn view_tick_labels(&self) -> Option<Element<'_, PlotUiMessage>> {
-        if self.x_ticks.is_empty() && self.y_ticks.is_empty() {
+        if self.x_ticks.is_empty() && self.y_ticks.is_empty() && self.y2_ticks.is_empty() {
             return None;
         }
 
-        let mut tick_elements = Vec::with_capacity(self.x_ticks.len() + self.y_ticks.len());
+        let mut tick_elements =
+            Vec::with_capacity(self.x_ticks.len() + self.y_ticks.len() + self.y2_ticks.len());
         let tick_label_color = self.cached_style().tick_label_color;
         let tick_text = |text| {
             widget::text(text)
@@ -1128,6 +2168,21 @@ impl PlotWidget {
             }
         }
 
+        if let Some(formatter) = &self.y2_axis_formatter {
+            for tick in &self.y2_ticks {
+                let label_text = formatter(tick.tick);
+                let text_widget = tick_text(label_text);
+                let positioned_label = widget::container(text_widget)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .padding(padding::top(tick.screen_pos - 5.0))
+                    .align_x(alignment::Horizontal::Right)
+                    .align_y(Vertical::Top)
+                    .style(container::transparent);
+                tick_elements.push(positioned_label.into());
+            }
+        }
+
         if tick_elements.is_empty() {
             return None;
         }
@@ -1148,6 +2203,8 @@ impl PlotWidget {
 
     fn toggle_visibility(&mut self, id: &ShapeId) {
         let exists = self.series.contains_key(id)
+            || self.rasters.contains_key(id)
+            || self.distributions.contains_key(id)
             || self.fills.contains_key(id)
             || self.vlines.contains_key(id)
             || self.hlines.contains_key(id);
@@ -1157,12 +2214,25 @@ impl PlotWidget {
             return;
         }
         // toggle the visibility of the shape
-        if !self.hidden_shapes.remove(id) {
-            self.hidden_shapes.insert(*id);
+        let hidden = !self.hidden_shapes.contains(id);
+        self.set_hidden(*id, hidden);
+        // Box plots and violins are drawn by series they own, which follow their visibility.
+        if let Some(plot) = self.distributions.get(id) {
+            let (lines_id, outliers_id) = (plot.lines_id, plot.outliers_id);
+            self.set_hidden(lines_id, hidden);
+            self.set_hidden(outliers_id, hidden);
         }
         self.data_version += 1;
     }
 
+    fn set_hidden(&mut self, id: ShapeId, hidden: bool) {
+        if hidden {
+            self.hidden_shapes.insert(id);
+        } else {
+            self.hidden_shapes.remove(&id);
+        }
+    }
+
     fn is_fill_endpoint_available(&self, id: ShapeId) -> bool {
         self.series.contains_key(&id)
             || self.vlines.contains_key(&id)
@@ -1186,7 +2256,16 @@ impl PlotWidget {
                 .is_some_and(|transform| transform.uses_axes_coordinates())
     }
 
-    fn has_visible_dynamic_geometry_transforms(&self) -> bool {
+    /// Whether the secondary y-axis is shown: a series is bound to it or it has a label.
+    pub(crate) fn has_secondary_y_axis(&self) -> bool {
+        !self.y2_axis_label.is_empty()
+            || self
+                .series
+                .values()
+                .any(|series| series.y_axis == YAxis::Secondary)
+    }
+
+    pub(crate) fn has_visible_camera_dependent_geometry(&self) -> bool {
         self.series.iter().any(|(id, series)| {
             !self.hidden_shapes.contains(id) && series.transform.uses_axes_coordinates()
         }) || self.fills.iter().any(|(id, fill)| {
@@ -1213,13 +2292,19 @@ impl std::fmt::Debug for Primitive {
     }
 }
 
+/// What one update of the plot program publishes to the widget.
 #[derive(Default, Debug)]
-struct UpdateEffects {
+pub(crate) struct UpdateEffects {
     needs_redraw: bool,
-    hover_pick: Option<HoverPickEvent>,
-    drag_event: Option<DragEvent>,
+    pub(crate) hover_pick: Option<HoverPickEvent>,
+    pub(crate) drag_event: Option<DragEvent>,
+    pub(crate) selection: Option<SelectionEvent>,
+    pub(crate) measurement: Option<Measurement>,
+    clear_measurement: bool,
     cursor_ui: Option<CursorPositionUiPayload>,
     clear_cursor_position: bool,
+    data_cursor: Option<DataCursorReadout>,
+    clear_data_cursor: bool,
     /// Request publishing `camera_bounds` even when ticks didn't change.
     /// This keeps overlays in sync when tick producers are disabled.
     publish_camera_bounds: bool,
@@ -1294,31 +2379,20 @@ fn maybe_submit_hover_request(
         return;
     }
 
-    let PlotState {
-        picking: pick_state,
-        cursor_position,
-        hover_radius_px,
-        points,
-        series,
-        camera,
-        bounds,
-        ..
-    } = state;
+    let (cursor, hover_radius_px) = (state.cursor_position, state.hover_radius_px);
     let force_cpu_picking = widget.render_strategy.force_cpu_picking();
+    let (pick_state, targets) = state.picking_and_targets();
 
     match pick_state.request_hover(
         widget.instance_id,
-        *cursor_position,
-        *hover_radius_px,
+        &targets,
+        cursor,
+        hover_radius_px,
         force_cpu_picking,
-        points.as_ref(),
-        series.as_ref(),
-        camera,
-        bounds,
-        |pid| widget.valid_point_id(pid),
+        |hit| widget.valid_hit(hit),
     ) {
-        picking::HoverRequest::CpuHit(point) => {
-            effects.hover_pick = Some(HoverPickEvent::Hover(point));
+        picking::HoverRequest::CpuHit(hit) => {
+            effects.hover_pick = Some(HoverPickEvent::Hover(hit));
         }
         picking::HoverRequest::CpuMiss => {
             clear_hover_effect(widget, state, effects);
@@ -1330,6 +2404,22 @@ fn maybe_submit_hover_request(
     }
 }
 
+fn format_cursor_value(value: f64, scale: AxisScale) -> String {
+    match scale {
+        AxisScale::Time => ticks::format_timestamp(value),
+        AxisScale::Linear | AxisScale::Log { .. } => format!("{value:.4}"),
+    }
+}
+
+/// Format a measured delta: a difference, or a ratio on log axes.
+fn format_measured_delta(delta: f64, scale: AxisScale) -> String {
+    match scale {
+        AxisScale::Linear => format!("{delta:.4}"),
+        AxisScale::Log { .. } => format!("×{delta:.4}"),
+        AxisScale::Time => format!("{delta:.4} s"),
+    }
+}
+
 fn update_cursor_overlay_on_move(
     widget: &PlotWidget,
     state: &PlotState,
@@ -1353,15 +2443,35 @@ fn update_cursor_overlay_on_move(
             effects.clear_cursor_position = true;
             return;
         };
+        let y2 = state
+            .has_secondary_y_axis
+            .then(|| {
+                widget
+                    .y2_axis_scale
+                    .plot_to_data(state.y2_map.to_secondary(plot.y))
+            })
+            .flatten();
         let text = if let Some(p) = &widget.cursor_provider {
             (p)(world[0], world[1])
         } else {
-            format!("{:.4}, {:.4}", world[0], world[1])
+            let mut text = format!(
+                "{}, {}",
+                format_cursor_value(world[0], widget.x_axis_scale),
+                format_cursor_value(world[1], widget.y_axis_scale)
+            );
+            if let Some(y2) = y2 {
+                text.push_str(&format!(
+                    " | {}",
+                    format_cursor_value(y2, widget.y2_axis_scale)
+                ));
+            }
+            text
         };
 
         effects.cursor_ui = Some(CursorPositionUiPayload {
             x: world[0],
             y: world[1],
+            y2,
             text,
         });
     } else {
@@ -1369,6 +2479,88 @@ fn update_cursor_overlay_on_move(
     }
 }
 
+/// Read every series at the cursor x, or clear the readout once the cursor left the plot.
+fn update_data_cursor(widget: &PlotWidget, state: &PlotState, effects: &mut UpdateEffects) {
+    let Some(mode) = widget.data_cursor else {
+        return;
+    };
+    let plot_x = state.cursor_inside().then(|| {
+        let viewport = DVec2::new(state.bounds.width as f64, state.bounds.height as f64);
+        let cursor = DVec2::new(
+            state.cursor_position.x as f64,
+            state.cursor_position.y as f64,
+        );
+        state.camera.screen_to_world(cursor, viewport).x
+    });
+    let Some((plot_x, x)) =
+        plot_x.and_then(|plot_x| Some((plot_x, widget.x_axis_scale.plot_to_data(plot_x)?)))
+    else {
+        effects.clear_data_cursor = widget.data_cursor_ui.is_some();
+        return;
+    };
+
+    // Distributions draw their own series; their groups are read by hovering instead.
+    let owned_by_distribution = |id: ShapeId| {
+        widget
+            .distributions
+            .values()
+            .any(|plot| plot.lines_id == id || plot.outliers_id == id)
+    };
+    let entries = widget
+        .series
+        .values()
+        .filter(|series| {
+            series.bar_style.is_none()
+                && !series.transform.uses_axes_coordinates()
+                && !owned_by_distribution(series.id)
+        })
+        .filter_map(|series| {
+            // Hidden series have no span.
+            let span = state.series.iter().find(|span| span.id == series.id)?;
+            let y_scale = state.y_scale(series.y_axis);
+            let [x, y] = match data_cursor::read_span(span, &state.points, mode, plot_x)? {
+                SpanReading::Sample(local) => *series.positions.get(span.point_indices[local])?,
+                SpanReading::Line([x, y]) => [
+                    plot_value_to_data_with_transform(
+                        x,
+                        widget.x_axis_scale,
+                        series.transform.x.as_ref(),
+                    )?,
+                    plot_value_to_data_with_transform(
+                        state.y_map(series.y_axis).to_secondary(y),
+                        y_scale,
+                        series.transform.y.as_ref(),
+                    )?,
+                ],
+            };
+            let text = match &widget.cursor_provider {
+                Some(provider) => provider(x, y),
+                None => format_cursor_value(y, y_scale),
+            };
+            Some(DataCursorEntry {
+                label: series.label.clone().unwrap_or_default(),
+                color: series.color,
+                text,
+                point: HighlightPoint {
+                    x,
+                    y,
+                    transform: series.transform.clone(),
+                    y_axis: series.y_axis,
+                    color: series.color,
+                    marker_style: Some(MarkerStyle::circle(7.0)),
+                    mask_padding: None,
+                },
+            })
+        })
+        .collect();
+
+    effects.data_cursor = Some(DataCursorReadout {
+        screen_xy: [state.cursor_position.x, state.cursor_position.y],
+        title: format_cursor_value(x, widget.x_axis_scale),
+        entries,
+    });
+}
+
 fn invalidate_static_canvas(widget: &PlotWidget) {
     _ = widget;
     #[cfg(feature = "canvas")]
@@ -1400,15 +2592,22 @@ fn consume_gpu_pick_results(
         return;
     }
 
-    match state
-        .picking
-        .consume_gpu_result(widget.instance_id, |pid| widget.valid_point_id(pid))
-    {
-        Some(picking::GpuResultEvent::Pick(point)) => {
-            effects.hover_pick = Some(HoverPickEvent::Pick(point));
+    let (cursor, hover_radius_px) = (state.cursor_position, state.hover_radius_px);
+    let (pick_state, targets) = state.picking_and_targets();
+    match pick_state.consume_gpu_result(
+        widget.instance_id,
+        |hit| widget.valid_hit(hit),
+        || {
+            picking::cpu_pick_below_markers(&targets, cursor, hover_radius_px, |hit| {
+                widget.valid_hit(hit)
+            })
+        },
+    ) {
+        Some(picking::GpuResultEvent::Pick(hit)) => {
+            effects.hover_pick = Some(HoverPickEvent::Pick(hit));
         }
-        Some(picking::GpuResultEvent::Hover(point)) => {
-            effects.hover_pick = Some(HoverPickEvent::Hover(point));
+        Some(picking::GpuResultEvent::Hover(hit)) => {
+            effects.hover_pick = Some(HoverPickEvent::Hover(hit));
         }
         Some(picking::GpuResultEvent::HoverMiss) => {
             clear_hover_effect(widget, state, effects);
@@ -1417,39 +2616,59 @@ fn consume_gpu_pick_results(
     }
 }
 
+/// Ticks to publish for the x, y and secondary y axes.
+type PublishedTicks = (
+    Option<Vec<PositionedTick>>,
+    Option<Vec<PositionedTick>>,
+    Option<Vec<PositionedTick>>,
+);
+
 fn update_ticks_and_build_payload(
     widget: &PlotWidget,
     state: &mut PlotState,
     effects: &mut UpdateEffects,
     first_time_widget_view: bool,
-) -> (Option<Vec<PositionedTick>>, Option<Vec<PositionedTick>>) {
+) -> PublishedTicks {
     if !effects.needs_redraw {
-        return (None, None);
+        return (None, None, None);
     }
 
     let old_x = state.x_ticks.clone();
     let old_y = state.y_ticks.clone();
+    let old_y2 = state.y2_ticks.clone();
     state.update_ticks(
         widget.x_tick_producer.as_ref(),
         widget.y_tick_producer.as_ref(),
+        widget.y2_tick_producer.as_ref(),
     );
 
     let publish_x =
         (first_time_widget_view || (state.x_ticks != old_x)).then(|| state.x_ticks.clone());
     let publish_y =
         (first_time_widget_view || (state.y_ticks != old_y)).then(|| state.y_ticks.clone());
+    let publish_y2 =
+        (first_time_widget_view || (state.y2_ticks != old_y2)).then(|| state.y2_ticks.clone());
 
     // If tick producers are disabled, ticks might never change. Still publish camera/bounds
     // when overlays need them so screen-space positions stay in sync.
     if publish_x.is_none()
         && publish_y.is_none()
+        && publish_y2.is_none()
         && widget_needs_camera_bounds(widget)
-        && widget.camera_bounds != Some((state.camera, state.bounds))
+        && (widget.camera_bounds != Some((state.camera, state.bounds))
+            || widget.y2_map != state.y2_map)
+    {
+        effects.publish_camera_bounds = true;
+    }
+    // The best legend position depends on where the data is.
+    if widget.legend_enabled
+        && widget.legend.position == LegendPosition::Best
+        && widget.data_bounds != state.data_min.zip(state.data_max)
     {
         effects.publish_camera_bounds = true;
     }
 
-    (publish_x, publish_y)
+    (publish_x, publish_y, publish_y2)
 }
 
 fn update_plot_program<const IS_CANVAS: bool>(
@@ -1461,6 +2680,8 @@ fn update_plot_program<const IS_CANVAS: bool>(
 ) -> Option<shader::Action<PlotUiMessage>> {
     let mut effects = UpdateEffects::default();
     let mut invalidation = CanvasInvalidation::default();
+    // Set when the cursor moved or the data under it changed.
+    let mut refresh_data_cursor = false;
     let prev_camera = state.camera;
     let prev_bounds = state.bounds;
 
@@ -1471,6 +2692,8 @@ fn update_plot_program<const IS_CANVAS: bool>(
     state.pick_enabled = widget.controls.has_pick_action();
     state.hover_radius_px = widget.hover_radius_px;
     state.crosshairs_enabled = widget.crosshairs_enabled;
+    state.data_cursor_enabled = widget.data_cursor.is_some();
+    state.has_secondary_y_axis = widget.has_secondary_y_axis();
 
     if IS_CANVAS {
         let grid_style = widget.cached_style().grid;
@@ -1486,10 +2709,15 @@ fn update_plot_program<const IS_CANVAS: bool>(
         effects.needs_redraw = true;
         invalidation.overlay_layer();
     }
+    if state.sync_ruler_from_widget(widget) {
+        effects.needs_redraw = true;
+        invalidation.overlay_layer();
+    }
 
     // Check if limits have been manually set. This will always trigger an "autoscale"
     // to apply the new limits.
-    let limits_changed = widget.x_lim != state.x_lim || widget.y_lim != state.y_lim;
+    let limits_changed =
+        widget.x_lim != state.x_lim || widget.y_lim != state.y_lim || widget.y2_lim != state.y2_lim;
     let instance_switched = state.source_instance_id != Some(widget.instance_id);
     let first_time_widget_view = instance_switched && widget.camera_bounds.is_none();
 
@@ -1508,18 +2736,43 @@ fn update_plot_program<const IS_CANVAS: bool>(
         //
         // We do so on the first update, if autoscale_on_updates is enabled, or if
         // limits have been manually set.
-        if widget.autoscale_on_updates || limits_changed || first_time_widget_view {
+        if widget.autoscale_on_updates
+            || widget.follow_latest.is_some()
+            || limits_changed
+            || first_time_widget_view
+        {
             // Initial autoscale shouldn't update axis links.
             state.autoscale(!first_time_widget_view);
         }
 
         state.data_src_version = widget.data_version;
+        state.append_src_version = widget.append_version;
         state.source_instance_id = Some(widget.instance_id);
+        refresh_data_cursor = true;
+        effects.needs_redraw = true;
+        invalidation.all();
+    } else if widget.append_version != state.append_src_version {
+        // Points were appended: sync only the affected series when possible.
+        if state.append_from_widget(widget) {
+            if widget.autoscale_on_updates || widget.follow_latest.is_some() {
+                state.autoscale_after_append();
+            }
+        } else {
+            state.rebuild_from_widget(widget);
+            if widget.autoscale_on_updates || widget.follow_latest.is_some() {
+                state.autoscale(true);
+            }
+        }
+        maybe_submit_hover_request(widget, state, &mut effects);
+
+        state.append_src_version = widget.append_version;
+        refresh_data_cursor = true;
         effects.needs_redraw = true;
         invalidation.all();
     } else if limits_changed {
         state.x_lim = widget.x_lim;
         state.y_lim = widget.y_lim;
+        state.y2_lim = widget.y2_lim;
         state.autoscale(true);
         effects.needs_redraw = true;
         invalidation.all();
@@ -1548,16 +2801,25 @@ fn update_plot_program<const IS_CANVAS: bool>(
             invalidation.all();
         }
     }
+    if let Some(ref link) = state.y2_axis_link {
+        let link_version = link.version();
+        if link_version != state.y2_link_version {
+            let (position, half_extent, version) = link.get();
+            if let Some(map) = SecondaryAxisMap::between(
+                state.camera.y_range(),
+                [position - half_extent, position + half_extent],
+            ) {
+                state.y2_map = map;
+            }
+            state.y2_link_version = version;
+            effects.needs_redraw = true;
+            invalidation.all();
+        }
+    }
 
     match event {
         iced::Event::Mouse(mouse_event) => {
-            let mouse_redraw = state.handle_mouse_event(
-                *mouse_event,
-                cursor,
-                widget,
-                &mut effects.hover_pick,
-                &mut effects.drag_event,
-            );
+            let mouse_redraw = state.handle_mouse_event(*mouse_event, cursor, widget, &mut effects);
             effects.needs_redraw |= mouse_redraw;
             if mouse_redraw {
                 invalidation.overlay_layer();
@@ -1568,16 +2830,19 @@ fn update_plot_program<const IS_CANVAS: bool>(
                     if state.available_cursor_is_inside(cursor) {
                         maybe_submit_hover_request(widget, state, &mut effects);
                         update_cursor_overlay_on_move(widget, state, &mut effects);
+                        refresh_data_cursor = true;
                     } else {
                         clear_hover_effect(widget, state, &mut effects);
                         if widget.cursor_overlay {
                             effects.clear_cursor_position = true;
                         }
+                        effects.clear_data_cursor = widget.data_cursor_ui.is_some();
                     }
                     invalidation.overlay_layer();
                 }
                 iced::mouse::Event::CursorLeft => {
                     clear_hover_effect(widget, state, &mut effects);
+                    effects.clear_data_cursor = widget.data_cursor_ui.is_some();
                     invalidation.overlay_layer();
                 }
                 _ => {}
@@ -1586,10 +2851,18 @@ fn update_plot_program<const IS_CANVAS: bool>(
         iced::Event::Keyboard(keyboard_event) => {
             if let keyboard::Event::KeyPressed { key, .. } = keyboard_event
                 && state.available_cursor_is_inside(cursor)
-                && widget.controls.key_action(key) == Some(KeyAction::ClearPick)
             {
-                effects.hover_pick = Some(HoverPickEvent::ClearPick);
-                invalidation.overlay_layer();
+                match widget.controls.key_action(key) {
+                    Some(KeyAction::ClearPick) => {
+                        effects.hover_pick = Some(HoverPickEvent::ClearPick);
+                        invalidation.overlay_layer();
+                    }
+                    Some(KeyAction::ClearMeasurement) if widget.measurement.is_some() => {
+                        effects.clear_measurement = true;
+                        invalidation.overlay_layer();
+                    }
+                    _ => {}
+                }
             }
             effects.needs_redraw |= state.handle_keyboard_event(keyboard_event, widget, cursor);
         }
@@ -1603,14 +2876,24 @@ fn update_plot_program<const IS_CANVAS: bool>(
         invalidation.all();
     }
 
-    if (state.camera != prev_camera || state.bounds != prev_bounds)
-        && widget.has_visible_dynamic_geometry_transforms()
+    // Secondary-axis geometry is baked with the map it was built with. Autoscale and axis
+    // links re-fit the map, so rebuild when it changes.
+    if state.y2_map != state.built_y2_map
+        || ((state.camera != prev_camera || state.bounds != prev_bounds)
+            && (widget.has_visible_camera_dependent_geometry()
+                || state.decimation_outdated()
+                || state.curves_outdated()))
     {
         state.rebuild_from_widget(widget);
         effects.needs_redraw = true;
         invalidation.all();
     }
 
+    // Streaming data and moving views change what is under a still cursor too.
+    if refresh_data_cursor || state.camera != prev_camera || state.bounds != prev_bounds {
+        update_data_cursor(widget, state, &mut effects);
+    }
+
     // Hover/pick highlight mask boxes are baked in clip space. Rebuild them through the
     // existing highlight_version path whenever camera or viewport bounds change.
     if !state.highlighted_points.is_empty()
@@ -1639,29 +2922,37 @@ fn update_plot_program<const IS_CANVAS: bool>(
         effects.needs_redraw |= state.picking.has_outstanding_gpu_request();
     }
 
-    let (publish_x_ticks, publish_y_ticks) =
+    let (publish_x_ticks, publish_y_ticks, publish_y2_ticks) =
         update_ticks_and_build_payload(widget, state, &mut effects, first_time_widget_view);
-    if publish_x_ticks.is_some() || publish_y_ticks.is_some() {
+    let publish_ticks =
+        publish_x_ticks.is_some() || publish_y_ticks.is_some() || publish_y2_ticks.is_some();
+    if publish_ticks {
         invalidation.static_layer();
     }
 
     let needs_publish = effects.hover_pick.is_some()
         || effects.drag_event.is_some()
+        || effects.selection.is_some()
+        || effects.measurement.is_some()
+        || effects.clear_measurement
         || effects.cursor_ui.is_some()
-        || publish_x_ticks.is_some()
-        || publish_y_ticks.is_some()
+        || publish_ticks
         || effects.clear_cursor_position
+        || effects.data_cursor.is_some()
+        || effects.clear_data_cursor
         || effects.publish_camera_bounds;
 
-    let camera_bounds = if effects.hover_pick.is_some()
-        || publish_x_ticks.is_some()
-        || publish_y_ticks.is_some()
-        || effects.publish_camera_bounds
-    {
-        Some((state.camera, state.bounds))
-    } else {
-        None
-    };
+    let camera_bounds =
+        if effects.hover_pick.is_some() || publish_ticks || effects.publish_camera_bounds {
+            Some((
+                state.camera,
+                state.bounds,
+                state.y2_map,
+                state.data_min.zip(state.data_max),
+            ))
+        } else {
+            None
+        };
 
     if IS_CANVAS {
         invalidation.apply(widget);
@@ -1676,7 +2967,15 @@ fn update_plot_program<const IS_CANVAS: bool>(
                 cursor_position_ui: effects.cursor_ui,
                 x_ticks: publish_x_ticks,
                 y_ticks: publish_y_ticks,
-                camera_bounds: camera_bounds.map(Box::new),
+                details: Box::new(RenderUpdateDetails {
+                    selection: effects.selection,
+                    measurement: effects.measurement,
+                    clear_measurement: effects.clear_measurement,
+                    clear_data_cursor: effects.clear_data_cursor,
+                    data_cursor: effects.data_cursor,
+                    y2_ticks: publish_y2_ticks,
+                    camera_bounds,
+                }),
             },
         )))
     } else {
@@ -1815,14 +3114,31 @@ impl shader::Primitive for Primitive {
 impl PlotWidget {
     /// Check if the point index is valid for the series
     fn valid_point_id(&self, point_id: &PointId) -> bool {
+        if let Some(raster) = self.rasters.get(&point_id.series_id) {
+            return raster.pickable && raster.cell(point_id.point_index).is_some();
+        }
+        if let Some(plot) = self.distributions.get(&point_id.series_id) {
+            return plot.group_stats(point_id.point_index).is_some();
+        }
         self.series
             .get(&point_id.series_id)
             .map(|series| series.pickable && point_id.point_index < series.positions.len())
             .unwrap_or(false)
     }
+
+    fn valid_hit(&self, hit: &PickHit) -> bool {
+        match *hit {
+            PickHit::Point(point) | PickHit::Segment { point, .. } => self.valid_point_id(&point),
+            PickHit::Fill { fill_id, .. } => self.fills.get(&fill_id).is_some_and(|f| f.pickable),
+            PickHit::ReferenceLine { line_id, .. } => {
+                self.vlines.get(&line_id).is_some_and(|line| line.pickable)
+                    || self.hlines.get(&line_id).is_some_and(|line| line.pickable)
+            }
+        }
+    }
 }
 
-fn apply_data_aspect(camera: &mut Camera, bounds: &Rectangle, aspect: f64) -> bool {
+pub(crate) fn apply_data_aspect(camera: &mut Camera, bounds: &Rectangle, aspect: f64) -> bool {
     let width = bounds.width.max(1.0) as f64;
     let height = bounds.height.max(1.0) as f64;
     let target_half_y = aspect * camera.half_extents.x * (height / width);
@@ -1834,29 +3150,18 @@ fn apply_data_aspect(camera: &mut Camera, bounds: &Rectangle, aspect: f64) -> bo
 }
 
 impl PlotWidget {
-    pub(crate) fn pick_hit(&self, state: &mut PlotState) -> Option<PointId> {
-        let PlotState {
-            picking: pick_state,
-            cursor_position,
-            hover_radius_px,
-            points,
-            series,
-            camera,
-            bounds,
-            ..
-        } = state;
+    pub(crate) fn pick_hit(&self, state: &mut PlotState) -> Option<PickHit> {
+        let (cursor, hover_radius_px) = (state.cursor_position, state.hover_radius_px);
         let force_cpu_picking = self.render_strategy.force_cpu_picking();
+        let (pick_state, targets) = state.picking_and_targets();
 
         pick_state.request_pick_hit(
             self.instance_id,
-            *cursor_position,
-            *hover_radius_px,
+            &targets,
+            cursor,
+            hover_radius_px,
             force_cpu_picking,
-            points.as_ref(),
-            series.as_ref(),
-            camera,
-            bounds,
-            |pid| self.valid_point_id(pid),
+            |hit| self.valid_hit(hit),
         )
     }
 }
@@ -1897,6 +3202,8 @@ pub struct HighlightPoint {
     pub y: f64,
     /// How to interpret or convert the x/y values before drawing.
     pub transform: PositionTransform,
+    /// The y-axis the y value is on.
+    pub y_axis: YAxis,
     pub color: Color,
     /// Optional marker style for the series. If None, no markers are drawn.
     pub marker_style: Option<MarkerStyle>,
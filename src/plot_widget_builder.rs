@@ -4,15 +4,22 @@ use iced::Theme;
 
 use crate::axis_link::AxisLink;
 use crate::axis_scale::AxisScale;
+use crate::colorbar::Colorbar;
+use crate::contour::Contour;
 use crate::controls::PlotControls;
+use crate::data_cursor::DataCursorMode;
+use crate::distribution::DistributionPlot;
 use crate::fill::Fill;
+use crate::histogram::Histogram;
+use crate::legend::Legend;
 use crate::message::TooltipContext;
 use crate::plot_renderer::PlotRenderStrategy;
 use crate::plot_widget::{CursorProvider, HighlightPoint, HighlightPointProvider, PlotWidget};
+use crate::raster::Raster;
 use crate::reference_lines::{HLine, VLine};
 use crate::series::{Series, SeriesError};
 use crate::style::{PlotStyle, StyleFn};
-use crate::ticks::{Tick, TickFormatter, TickProducer};
+use crate::ticks::{self, Tick, TickFormatter, TickProducer};
 
 /// Builder for configuring and constructing a PlotWidget.
 ///
@@ -32,7 +39,9 @@ use crate::ticks::{Tick, TickFormatter, TickProducer};
 pub struct PlotWidgetBuilder {
     x_label: Option<String>,
     y_label: Option<String>,
+    y2_label: Option<String>,
     autoscale_on_updates: Option<bool>,
+    follow_latest: Option<f64>,
     hover_radius_px: Option<f32>,
     highlight_on_hover: Option<bool>,
     pick_highlight_provider: Option<HighlightPointProvider>,
@@ -40,22 +49,33 @@ pub struct PlotWidgetBuilder {
     cursor_overlay: Option<bool>,
     cursor_provider: Option<CursorProvider>,
     crosshairs: Option<bool>,
+    data_cursor: Option<DataCursorMode>,
+    measure_snap: Option<bool>,
     render_strategy: Option<PlotRenderStrategy>,
     controls: Option<PlotControls>,
     controls_help: Option<bool>,
     disable_legend: bool,
+    legend: Option<Legend>,
+    colorbar: Option<Colorbar>,
     x_lim: Option<(f64, f64)>,
     y_lim: Option<(f64, f64)>,
+    y2_lim: Option<(f64, f64)>,
     x_axis_scale: Option<AxisScale>,
     y_axis_scale: Option<AxisScale>,
+    y2_axis_scale: Option<AxisScale>,
+    x_categories: Option<Vec<String>>,
     x_axis_link: Option<AxisLink>,
     y_axis_link: Option<AxisLink>,
+    y2_axis_link: Option<AxisLink>,
     x_tick_formatter: Option<TickFormatter>,
     y_tick_formatter: Option<TickFormatter>,
+    y2_tick_formatter: Option<TickFormatter>,
     x_tick_producer: Option<TickProducer>,
     y_tick_producer: Option<TickProducer>,
+    y2_tick_producer: Option<TickProducer>,
     enable_x_tick_labels: Option<bool>,
     enable_y_tick_labels: Option<bool>,
+    enable_y2_tick_labels: Option<bool>,
     tick_label_size: Option<f32>,
     axis_label_size: Option<f32>,
     data_aspect: Option<f64>,
@@ -64,6 +84,10 @@ pub struct PlotWidgetBuilder {
     fills: Vec<Fill>,
     vlines: Vec<VLine>,
     hlines: Vec<HLine>,
+    rasters: Vec<Raster>,
+    contours: Vec<Contour>,
+    histograms: Vec<Histogram>,
+    distributions: Vec<DistributionPlot>,
 }
 
 impl PlotWidgetBuilder {
@@ -90,12 +114,29 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Set the secondary y-axis label, shown on the right side of the plot.
+    pub fn with_y2_label(mut self, label: impl Into<String>) -> Self {
+        let l = label.into();
+        if !l.is_empty() {
+            self.y2_label = Some(l);
+        }
+        self
+    }
+
     /// Enable or disable autoscaling of the plot when new data is added.
     pub fn with_autoscale_on_updates(mut self, enabled: bool) -> Self {
         self.autoscale_on_updates = Some(enabled);
         self
     }
 
+    /// Keep the newest `window` of x values in view as points are appended.
+    ///
+    /// See [`PlotWidget::set_follow_latest`].
+    pub fn with_follow_latest(mut self, window: f64) -> Self {
+        self.follow_latest = Some(window);
+        self
+    }
+
     /// Set the hover radius in pixels for detecting nearby points for highlighting.
     pub fn with_hover_radius_px(mut self, radius: f32) -> Self {
         self.hover_radius_px = Some(radius.max(0.0));
@@ -152,6 +193,19 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Enable a data cursor reading every visible series at the cursor x.
+    pub fn with_data_cursor(mut self, mode: DataCursorMode) -> Self {
+        self.data_cursor = Some(mode);
+        self
+    }
+
+    /// Snap the ends of the measurement ruler to the nearest data point within the hover
+    /// radius.
+    pub fn with_measure_snap(mut self, enabled: bool) -> Self {
+        self.measure_snap = Some(enabled);
+        self
+    }
+
     /// Select which rendering path the plot should use.
     pub fn with_render_strategy(mut self, strategy: PlotRenderStrategy) -> Self {
         self.render_strategy = Some(strategy);
@@ -176,6 +230,20 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Set the placement and layout of the legend: its position inside or outside the plot,
+    /// the direction its entries flow, its number of columns and its text size.
+    pub fn with_legend(mut self, legend: Legend) -> Self {
+        self.legend = Some(legend);
+        self
+    }
+
+    /// Show a colorbar beside the plot area for a series with
+    /// [color values](crate::Series::with_color_values).
+    pub fn with_colorbar(mut self, colorbar: Colorbar) -> Self {
+        self.colorbar = Some(colorbar);
+        self
+    }
+
     /// Set the full interaction controls behavior for the plot.
     pub fn with_controls(mut self, controls: PlotControls) -> Self {
         self.controls = Some(controls);
@@ -193,11 +261,26 @@ impl PlotWidgetBuilder {
     ///
     /// Hint: For log-style ticks, consider also setting the tick producer and formatter
     /// to use [`crate::log_tick_producer`] and [`crate::log_formatter`].
+    ///
+    /// [`AxisScale::Time`] defaults to [`crate::time_tick_producer`] and
+    /// [`crate::time_formatter`] unless a custom producer or formatter is set.
     pub fn with_x_scale(mut self, scale: AxisScale) -> Self {
         self.x_axis_scale = Some(scale);
         self
     }
 
+    /// Make the x-axis categorical, with the given category labels in order.
+    ///
+    /// See [`PlotWidget::set_x_categories`]. A custom x tick producer or formatter still
+    /// takes precedence.
+    pub fn with_x_categories<S: Into<String>>(
+        mut self,
+        labels: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.x_categories = Some(labels.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Set the y-axis limits (min, max) for the plot.
     /// If set, these will override autoscaling for the y-axis.
     pub fn with_y_lim(mut self, min: f64, max: f64) -> Self {
@@ -209,11 +292,31 @@ impl PlotWidgetBuilder {
     ///
     /// Hint: For log-style ticks, consider also setting the tick producer and formatter
     /// to use [`crate::log_tick_producer`] and [`crate::log_formatter`].
+    ///
+    /// [`AxisScale::Time`] defaults to [`crate::time_tick_producer`] and
+    /// [`crate::time_formatter`] unless a custom producer or formatter is set.
     pub fn with_y_scale(mut self, scale: AxisScale) -> Self {
         self.y_axis_scale = Some(scale);
         self
     }
 
+    /// Set the secondary y-axis limits (min, max) for the plot.
+    /// If set, these will override autoscaling for the secondary y-axis.
+    pub fn with_y2_lim(mut self, min: f64, max: f64) -> Self {
+        self.y2_lim = Some((min, max));
+        self
+    }
+
+    /// Set the secondary y-axis scale mode.
+    ///
+    /// Series are drawn against the secondary y-axis with
+    /// [`Series::with_y_axis`](crate::Series::with_y_axis). Tick producer and formatter
+    /// defaults follow [`with_y_scale`](Self::with_y_scale).
+    pub fn with_y2_scale(mut self, scale: AxisScale) -> Self {
+        self.y2_axis_scale = Some(scale);
+        self
+    }
+
     /// Link the x-axis to other plots. When the x-axis is panned or zoomed,
     /// all plots sharing this link will update synchronously.
     pub fn with_x_axis_link(mut self, link: AxisLink) -> Self {
@@ -228,6 +331,13 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Link the secondary y-axis to other plots. When the plot is panned or zoomed,
+    /// all plots sharing this link will update synchronously.
+    pub fn with_y2_axis_link(mut self, link: AxisLink) -> Self {
+        self.y2_axis_link = Some(link);
+        self
+    }
+
     /// Set a custom formatter for the x-axis tick labels.
     pub fn with_x_tick_formatter<F>(mut self, formatter: F) -> Self
     where
@@ -246,6 +356,15 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Set a custom formatter for the secondary y-axis tick labels.
+    pub fn with_y2_tick_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: Fn(Tick) -> String + Send + Sync + 'static,
+    {
+        self.y2_tick_formatter = Some(Arc::new(formatter));
+        self
+    }
+
     /// Set a custom tick producer for generating tick positions along the x-axis.
     pub fn with_x_tick_producer<F>(mut self, producer: F) -> Self
     where
@@ -264,6 +383,15 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Set a custom tick producer for generating tick positions along the secondary y-axis.
+    pub fn with_y2_tick_producer<F>(mut self, producer: F) -> Self
+    where
+        F: Fn(f64, f64) -> Vec<Tick> + Send + Sync + 'static,
+    {
+        self.y2_tick_producer = Some(Arc::new(producer));
+        self
+    }
+
     /// Set whether tick labels for the x axis will be shown.
     pub fn with_x_tick_labels(mut self, enabled: bool) -> Self {
         self.enable_x_tick_labels = Some(enabled);
@@ -276,6 +404,12 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Set whether tick labels for the secondary y axis will be shown.
+    pub fn with_y2_tick_labels(mut self, enabled: bool) -> Self {
+        self.enable_y2_tick_labels = Some(enabled);
+        self
+    }
+
     /// Set the font size for tick labels (the numbers on the axes).
     pub fn with_tick_label_size(mut self, size: f32) -> Self {
         self.tick_label_size = Some(size.max(1.0));
@@ -333,6 +467,30 @@ impl PlotWidgetBuilder {
         self
     }
 
+    /// Add a raster image or heatmap to the plot.
+    pub fn add_raster(mut self, raster: Raster) -> Self {
+        self.rasters.push(raster);
+        self
+    }
+
+    /// Add contour lines, and optionally filled bands, to the plot.
+    pub fn add_contour(mut self, contour: Contour) -> Self {
+        self.contours.push(contour);
+        self
+    }
+
+    /// Add a histogram to the plot.
+    pub fn add_histogram(mut self, histogram: Histogram) -> Self {
+        self.histograms.push(histogram);
+        self
+    }
+
+    /// Add box plots or violins of groups of samples.
+    pub fn add_distribution_plot(mut self, plot: DistributionPlot) -> Self {
+        self.distributions.push(plot);
+        self
+    }
+
     /// Disable background grid lines and ticks on both axes.
     pub fn without_grid(self) -> Self {
         self.with_x_tick_producer(|_, _| Vec::new())
@@ -340,33 +498,66 @@ impl PlotWidgetBuilder {
     }
 
     /// Default hover highlight provider that shows the tooltip text with
-    /// series label, x and y coordinates of the point.
+    /// series label, x and y coordinates of the point, and their errors if set.
+    /// Raster cells show their row, column and value, and histogram bins their range and
+    /// count.
     pub fn default_hover_highlight_provider(
         ctx: TooltipContext<'_>,
         point: &mut HighlightPoint,
     ) -> Option<String> {
-        if ctx.series_label.is_empty() {
-            Some(format!(
-                "x: {}, y: {}",
-                point.display_x(),
-                point.display_y()
-            ))
-        } else {
-            Some(format!(
-                "{}\nx: {}, y: {}",
-                ctx.series_label,
-                point.display_x(),
-                point.display_y()
-            ))
-        }
+        if let Some(bin) = ctx.bin {
+            let text = format!(
+                "bin: {:.2} to {:.2}\ncount: {}",
+                bin.start, bin.end, bin.count
+            );
+            return Some(labeled(ctx.series_label, text));
+        }
+        if let Some(stats) = ctx.distribution {
+            let text = match stats.outlier {
+                Some(value) => format!("outlier: {value:.2}"),
+                None => format!(
+                    "median: {:.2}\nq1 to q3: {:.2} to {:.2}\nwhiskers: {:.2} to {:.2}\nn: {}",
+                    stats.median,
+                    stats.q1,
+                    stats.q3,
+                    stats.lower_whisker,
+                    stats.upper_whisker,
+                    stats.count
+                ),
+            };
+            return Some(labeled(ctx.series_label, text));
+        }
+        if let Some(cell) = ctx.cell {
+            let value = cell
+                .value
+                .map(|value| format!(", value: {value:.4}"))
+                .unwrap_or_default();
+            let position = format!("row: {}, column: {}{value}", cell.row, cell.column);
+            return Some(labeled(ctx.series_label, position));
+        }
+        let x_error = ctx
+            .display_x_error()
+            .map(|e| format!(" {e}"))
+            .unwrap_or_default();
+        let y_error = ctx
+            .display_y_error()
+            .map(|e| format!(" {e}"))
+            .unwrap_or_default();
+        let position = format!(
+            "x: {}{x_error}, y: {}{y_error}",
+            point.display_x(),
+            point.display_y()
+        );
+        Some(labeled(ctx.series_label, position))
     }
 
     /// Build the PlotWidget; validates series and duplicate labels via PlotWidget::add_series.
     pub fn build(self) -> Result<PlotWidget, SeriesError> {
         let x_axis_scale = self.x_axis_scale.unwrap_or_default();
         let y_axis_scale = self.y_axis_scale.unwrap_or_default();
+        let y2_axis_scale = self.y2_axis_scale.unwrap_or_default();
 
-        for scale in [x_axis_scale, y_axis_scale] {
+        for scale in [x_axis_scale, y_axis_scale, y2_axis_scale] {
             if let AxisScale::Log { base } = scale
                 && !(base.is_finite() && base > 1.0)
             {
@@ -384,10 +575,19 @@ impl PlotWidgetBuilder {
         {
             return Err(SeriesError::InvalidAxisLimits);
         }
+        if let Some((y2_min, y2_max)) = self.y2_lim
+            && y2_min >= y2_max
+        {
+            return Err(SeriesError::InvalidAxisLimits);
+        }
 
         let mut w = PlotWidget::new();
         w.set_x_axis_scale(x_axis_scale);
         w.set_y_axis_scale(y_axis_scale);
+        w.set_y2_axis_scale(y2_axis_scale);
+        if let Some(labels) = self.x_categories {
+            w.set_x_categories(labels);
+        }
         if let Some(controls) = self.controls {
             w.set_controls(controls);
         }
@@ -397,10 +597,19 @@ impl PlotWidgetBuilder {
         if self.disable_legend {
             w.legend_enabled = false;
         }
+        if let Some(legend) = self.legend {
+            w.set_legend(legend);
+        }
+        if let Some(colorbar) = self.colorbar {
+            w.set_colorbar(Some(colorbar));
+        }
 
         if let Some(enabled) = self.autoscale_on_updates {
             w.autoscale_on_updates(enabled);
         }
+        if let Some(window) = self.follow_latest {
+            w.set_follow_latest(Some(window));
+        }
         if let Some(r) = self.hover_radius_px {
             w.hover_radius_px(r);
         }
@@ -413,12 +622,18 @@ impl PlotWidgetBuilder {
         if let Some(y) = self.y_label {
             w.set_y_axis_label(y);
         }
+        if let Some(y2) = self.y2_label {
+            w.set_y2_axis_label(y2);
+        }
         if let Some((min, max)) = self.x_lim {
             w.set_x_lim(min, max);
         }
         if let Some((min, max)) = self.y_lim {
             w.set_y_lim(min, max);
         }
+        if let Some((min, max)) = self.y2_lim {
+            w.set_y2_lim(min, max);
+        }
         if let Some(c) = self.cursor_overlay {
             w.set_cursor_overlay(c);
         }
@@ -436,6 +651,12 @@ impl PlotWidgetBuilder {
         if let Some(enabled) = self.crosshairs {
             w.set_crosshairs(enabled);
         }
+        if let Some(mode) = self.data_cursor {
+            w.set_data_cursor(Some(mode));
+        }
+        if let Some(enabled) = self.measure_snap {
+            w.set_measure_snap(enabled);
+        }
         if let Some(strategy) = self.render_strategy {
             w.set_render_strategy(strategy);
         }
@@ -445,11 +666,23 @@ impl PlotWidgetBuilder {
         if let Some(link) = self.y_axis_link {
             w.set_y_axis_link(link);
         }
+        if let Some(link) = self.y2_axis_link {
+            w.set_y2_axis_link(link);
+        }
         if let Some(formatter) = self.x_tick_formatter {
             w.set_x_axis_formatter(formatter);
+        } else if x_axis_scale == AxisScale::Time {
+            w.set_x_axis_formatter(Arc::new(ticks::time_formatter));
         }
         if let Some(formatter) = self.y_tick_formatter {
             w.set_y_axis_formatter(formatter);
+        } else if y_axis_scale == AxisScale::Time {
+            w.set_y_axis_formatter(Arc::new(ticks::time_formatter));
+        }
+        if let Some(formatter) = self.y2_tick_formatter {
+            w.set_y2_axis_formatter(formatter);
+        } else if y2_axis_scale == AxisScale::Time {
+            w.set_y2_axis_formatter(Arc::new(ticks::time_formatter));
         }
         if self.enable_x_tick_labels == Some(false) {
             w.x_axis_formatter = None;
@@ -457,11 +690,23 @@ impl PlotWidgetBuilder {
         if self.enable_y_tick_labels == Some(false) {
             w.y_axis_formatter = None;
         }
+        if self.enable_y2_tick_labels == Some(false) {
+            w.y2_axis_formatter = None;
+        }
         if let Some(producer) = self.x_tick_producer {
             w.set_x_tick_producer(producer);
+        } else if x_axis_scale == AxisScale::Time {
+            w.set_x_tick_producer(Arc::new(ticks::time_tick_producer));
         }
         if let Some(producer) = self.y_tick_producer {
             w.set_y_tick_producer(producer);
+        } else if y_axis_scale == AxisScale::Time {
+            w.set_y_tick_producer(Arc::new(ticks::time_tick_producer));
+        }
+        if let Some(producer) = self.y2_tick_producer {
+            w.set_y2_tick_producer(producer);
+        } else if y2_axis_scale == AxisScale::Time {
+            w.set_y2_tick_producer(Arc::new(ticks::time_tick_producer));
         }
         if let Some(size) = self.tick_label_size {
             w.tick_label_size = size;
@@ -475,9 +720,21 @@ impl PlotWidgetBuilder {
         if let Some(style) = self.style {
             w.style = style;
         }
+        for raster in self.rasters {
+            w.add_raster(raster)?;
+        }
+        for contour in self.contours {
+            w.add_contour(contour)?;
+        }
         for s in self.series {
             w.add_series(s)?;
         }
+        for histogram in self.histograms {
+            w.add_histogram(histogram)?;
+        }
+        for plot in self.distributions {
+            w.add_distribution_plot(plot)?;
+        }
         for vline in self.vlines {
             w.add_vline(vline);
         }
@@ -491,3 +748,12 @@ impl PlotWidgetBuilder {
         Ok(w)
     }
 }
+
+/// Put the series label, if any, on its own line above `text`.
+fn labeled(series_label: &str, text: String) -> String {
+    if series_label.is_empty() {
+        text
+    } else {
+        format!("{series_label}\n{text}")
+    }
+}
@@ -0,0 +1,327 @@
+use std::sync::Arc;
+
+use crate::{
+    AxisScale, Color, ColorScale,
+    series::{SeriesError, ShapeId},
+};
+
+/// The cell values of a [`Raster`], stored row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RasterData {
+    /// One scalar per cell, colored through the raster's [`ColorScale`]. Cells without a
+    /// color (e.g. NaN) are transparent.
+    Scalar(Vec<f64>),
+    /// Four bytes per cell: straight (non-premultiplied) RGBA.
+    Rgba(Vec<u8>),
+}
+
+/// How a [`Raster`] is sampled when a cell covers more or less than one pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RasterSampling {
+    /// Every cell is a flat block of color.
+    #[default]
+    Nearest,
+    /// Colors are interpolated between cell centers.
+    ///
+    /// Only the shader renderer interpolates; the canvas renderer and exports draw flat
+    /// cells.
+    Linear,
+}
+
+/// Where the first row of a [`Raster`] is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RasterOrigin {
+    /// Row 0 is at the bottom of the extent, so rows go up with y.
+    #[default]
+    Lower,
+    /// Row 0 is at the top of the extent, as in most image formats.
+    Upper,
+}
+
+/// A grid of cells drawn as an image over a rectangle in data coordinates, such as a
+/// heatmap or a picture.
+///
+/// Cells are spaced evenly between the corners of the extent (in plot space, on log axes).
+/// Hovering or picking a cell reports a [`PointId`](crate::PointId) whose `point_index`
+/// is `row * columns + column`; see [`PlotWidget::raster_cell`](crate::PlotWidget::raster_cell).
+#[derive(Debug, Clone)]
+pub struct Raster {
+    /// Unique identifier for the raster.
+    pub id: ShapeId,
+    /// Number of cells per row.
+    pub columns: usize,
+    /// Number of rows.
+    pub rows: usize,
+    /// Cell values, row by row.
+    pub data: RasterData,
+    /// Lower-left corner of the extent in data coordinates.
+    pub min: [f64; 2],
+    /// Upper-right corner of the extent in data coordinates.
+    pub max: [f64; 2],
+    /// Where row 0 is placed.
+    pub origin: RasterOrigin,
+    /// How cells are sampled.
+    pub sampling: RasterSampling,
+    /// Maps scalar values to colors. Unused for RGBA data.
+    pub color_scale: ColorScale,
+    /// Optional label, passed to hover/pick tooltips.
+    pub label: Option<String>,
+    /// Whether cells can be hovered and picked.
+    pub pickable: bool,
+    /// Resolved straight RGBA8 pixels, refreshed by the plot widget when the raster changes.
+    pub(crate) pixels: Arc<[u8]>,
+}
+
+impl Raster {
+    /// Create a raster from one scalar per cell, with an extent of one unit per cell.
+    pub fn scalar(columns: usize, rows: usize, values: Vec<f64>) -> Self {
+        Self::new(columns, rows, RasterData::Scalar(values))
+    }
+
+    /// Create a raster from RGBA bytes, with an extent of one unit per cell.
+    pub fn rgba(columns: usize, rows: usize, pixels: Vec<u8>) -> Self {
+        Self::new(columns, rows, RasterData::Rgba(pixels))
+    }
+
+    fn new(columns: usize, rows: usize, data: RasterData) -> Self {
+        Self {
+            id: ShapeId::new(),
+            columns,
+            rows,
+            data,
+            min: [0.0, 0.0],
+            max: [columns as f64, rows as f64],
+            origin: RasterOrigin::default(),
+            sampling: RasterSampling::default(),
+            color_scale: ColorScale::default(),
+            label: None,
+            pickable: true,
+            pixels: Arc::new([]),
+        }
+    }
+
+    /// Set the lower-left and upper-right corners of the raster in data coordinates.
+    pub fn with_extent(mut self, min: [f64; 2], max: [f64; 2]) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set where row 0 is placed.
+    pub fn with_origin(mut self, origin: RasterOrigin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Set how cells are sampled.
+    pub fn with_sampling(mut self, sampling: RasterSampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Set the color scale used for scalar values.
+    pub fn with_color_scale(mut self, scale: impl Into<ColorScale>) -> Self {
+        self.color_scale = scale.into();
+        self
+    }
+
+    /// Set a label, passed to hover/pick tooltips.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        let label = label.into();
+        if !label.is_empty() {
+            self.label = Some(label);
+        }
+        self
+    }
+
+    /// Set whether cells can be hovered and picked.
+    pub fn with_pickable(mut self, pickable: bool) -> Self {
+        self.pickable = pickable;
+        self
+    }
+
+    /// Row and column of the cell at `index` (`row * columns + column`).
+    pub fn cell(&self, index: usize) -> Option<(usize, usize)> {
+        (index < self.columns * self.rows).then(|| (index / self.columns, index % self.columns))
+    }
+
+    /// Scalar value of the cell at `row` and `column`. `None` for RGBA data.
+    pub fn value(&self, row: usize, column: usize) -> Option<f64> {
+        match &self.data {
+            RasterData::Scalar(values) if row < self.rows && column < self.columns => {
+                values.get(row * self.columns + column).copied()
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), SeriesError> {
+        if self.columns == 0 || self.rows == 0 {
+            return Err(SeriesError::Empty);
+        }
+        let expected = match &self.data {
+            RasterData::Scalar(values) => values.len(),
+            RasterData::Rgba(pixels) => pixels.len() / 4,
+        };
+        if expected != self.columns * self.rows
+            || matches!(&self.data, RasterData::Rgba(pixels) if pixels.len() % 4 != 0)
+        {
+            return Err(SeriesError::InvalidRasterDataLength);
+        }
+        let valid_extent = (0..2).all(|axis| {
+            self.min[axis].is_finite()
+                && self.max[axis].is_finite()
+                && self.min[axis] < self.max[axis]
+        });
+        if !valid_extent {
+            return Err(SeriesError::InvalidRasterExtent);
+        }
+        Ok(())
+    }
+
+    /// Refresh the resolved pixels from the cell data.
+    pub(crate) fn resolve_pixels(&mut self) {
+        self.pixels = match &self.data {
+            RasterData::Scalar(values) => self
+                .color_scale
+                .colors(values, Color::TRANSPARENT)
+                .into_iter()
+                .flat_map(|color| color.into_rgba8())
+                .collect(),
+            RasterData::Rgba(pixels) => pixels.as_slice().into(),
+        };
+    }
+
+    /// The straight RGBA color of the cell at `index`.
+    pub(crate) fn cell_color(&self, index: usize) -> Option<Color> {
+        let [r, g, b, a] = self.pixels.get(index * 4..index * 4 + 4)?.try_into().ok()?;
+        Some(Color::from_rgba8(r, g, b, a as f32 / 255.0))
+    }
+
+    /// The grid of cells in plot space for the given axis scales.
+    ///
+    /// Returns `None` if a corner can't be shown on the scales (e.g. non-positive on a
+    /// log axis).
+    pub(crate) fn plot_grid(&self, x_scale: AxisScale, y_scale: AxisScale) -> Option<RasterGrid> {
+        let min = [
+            x_scale.data_to_plot(self.min[0])?,
+            y_scale.data_to_plot(self.min[1])?,
+        ];
+        let max = [
+            x_scale.data_to_plot(self.max[0])?,
+            y_scale.data_to_plot(self.max[1])?,
+        ];
+        Some(RasterGrid {
+            columns: self.columns,
+            rows: self.rows,
+            min,
+            max,
+            origin: self.origin,
+        })
+    }
+}
+
+/// Identifies a hovered or picked [`Raster`] cell.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RasterCell {
+    /// Row of the cell.
+    pub row: usize,
+    /// Column of the cell.
+    pub column: usize,
+    /// Scalar value of the cell, if the raster holds scalars.
+    pub value: Option<f64>,
+}
+
+/// Cell layout of a raster between two corners in plot space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RasterGrid {
+    pub(crate) columns: usize,
+    pub(crate) rows: usize,
+    pub(crate) min: [f64; 2],
+    pub(crate) max: [f64; 2],
+    pub(crate) origin: RasterOrigin,
+}
+
+impl RasterGrid {
+    fn cell_size(&self) -> [f64; 2] {
+        [
+            (self.max[0] - self.min[0]) / self.columns as f64,
+            (self.max[1] - self.min[1]) / self.rows as f64,
+        ]
+    }
+
+    /// Row and column of the cell containing `point`, if any.
+    pub(crate) fn cell_at(&self, [x, y]: [f64; 2]) -> Option<(usize, usize)> {
+        if !(self.min[0]..self.max[0]).contains(&x) || !(self.min[1]..self.max[1]).contains(&y) {
+            return None;
+        }
+        let [width, height] = self.cell_size();
+        let column = (((x - self.min[0]) / width) as usize).min(self.columns - 1);
+        let from_bottom = (((y - self.min[1]) / height) as usize).min(self.rows - 1);
+        let row = match self.origin {
+            RasterOrigin::Lower => from_bottom,
+            RasterOrigin::Upper => self.rows - 1 - from_bottom,
+        };
+        Some((row, column))
+    }
+
+    /// Lower-left and upper-right corners of the cell at `row` and `column`.
+    pub(crate) fn cell_rect(&self, row: usize, column: usize) -> ([f64; 2], [f64; 2]) {
+        let [width, height] = self.cell_size();
+        let from_bottom = match self.origin {
+            RasterOrigin::Lower => row,
+            RasterOrigin::Upper => self.rows - 1 - row,
+        };
+        let min = [
+            self.min[0] + column as f64 * width,
+            self.min[1] + from_bottom as f64 * height,
+        ];
+        (min, [min[0] + width, min[1] + height])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Colormap;
+
+    #[test]
+    fn cells_follow_origin_and_extent() {
+        let raster = Raster::scalar(4, 2, vec![0.0; 8]).with_extent([-2.0, 0.0], [2.0, 1.0]);
+        let grid = raster
+            .plot_grid(AxisScale::Linear, AxisScale::Linear)
+            .unwrap();
+
+        assert_eq!(grid.cell_at([-1.5, 0.25]), Some((0, 0)));
+        assert_eq!(grid.cell_at([1.9, 0.75]), Some((1, 3)));
+        assert_eq!(grid.cell_at([2.0, 0.5]), None);
+        assert_eq!(grid.cell_rect(1, 2), ([0.0, 0.5], [1.0, 1.0]));
+
+        let upper = RasterGrid {
+            origin: RasterOrigin::Upper,
+            ..grid
+        };
+        assert_eq!(upper.cell_at([1.9, 0.75]), Some((0, 3)));
+        assert_eq!(upper.cell_rect(1, 2), ([0.0, 0.0], [1.0, 0.5]));
+        assert_eq!(raster.cell(6), Some((1, 2)));
+        assert_eq!(raster.cell(8), None);
+    }
+
+    #[test]
+    fn scalar_values_resolve_through_color_scale() {
+        let mut raster = Raster::scalar(3, 1, vec![0.0, f64::NAN, 2.0])
+            .with_color_scale(ColorScale::new(Colormap::Grays));
+        assert!(raster.validate().is_ok());
+        raster.resolve_pixels();
+
+        assert_eq!(
+            &raster.pixels[..],
+            &[0, 0, 0, 255, 0, 0, 0, 0, 255, 255, 255, 255]
+        );
+        assert_eq!(raster.value(0, 2), Some(2.0));
+        assert!(matches!(
+            Raster::rgba(2, 2, vec![0; 12]).validate(),
+            Err(SeriesError::InvalidRasterDataLength)
+        ));
+    }
+}
@@ -15,6 +15,8 @@ pub struct VLine {
     pub color: Color,
     /// Line styling options, including width and pattern (solid, dashed, dotted).
     pub line_style: LineStyle,
+    /// Whether the line can be hovered and picked.
+    pub pickable: bool,
 }
 
 impl VLine {
@@ -27,6 +29,7 @@ impl VLine {
             label: None,
             color: Color::from_rgb(0.5, 0.5, 0.5),
             line_style: LineStyle::default(),
+            pickable: true,
         }
     }
 
@@ -88,6 +91,12 @@ impl VLine {
         self.line_style.line_type = line_type;
         self
     }
+
+    /// Enable or disable hover/pick behavior for this line.
+    pub fn with_pickable(mut self, pickable: bool) -> Self {
+        self.pickable = pickable;
+        self
+    }
 }
 
 /// A horizontal line at a fixed y-coordinate.
@@ -105,6 +114,8 @@ pub struct HLine {
     pub color: Color,
     /// Line styling options, including width and pattern (solid, dashed, dotted).
     pub line_style: LineStyle,
+    /// Whether the line can be hovered and picked.
+    pub pickable: bool,
 }
 
 impl HLine {
@@ -117,6 +128,7 @@ impl HLine {
             label: None,
             color: Color::from_rgb(0.5, 0.5, 0.5),
             line_style: LineStyle::default(),
+            pickable: true,
         }
     }
 
@@ -178,4 +190,10 @@ impl HLine {
         self.line_style.line_type = line_type;
         self
     }
+
+    /// Enable or disable hover/pick behavior for this line.
+    pub fn with_pickable(mut self, pickable: bool) -> Self {
+        self.pickable = pickable;
+        self
+    }
 }
@@ -0,0 +1,76 @@
+/// The y-axis a series is drawn against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum YAxis {
+    /// The y-axis on the left side of the plot.
+    #[default]
+    Primary,
+    /// The y-axis on the right side of the plot, with its own scale, limits and ticks.
+    Secondary,
+}
+
+/// Affine map from primary y plot space to secondary y plot space: `y2 = y * scale + offset`.
+///
+/// The camera only tracks the primary axis. Secondary series are converted into primary plot
+/// space through this map, so panning and zooming move both axes together, while autoscale
+/// and axis links re-fit the map.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SecondaryAxisMap {
+    pub(crate) scale: f64,
+    pub(crate) offset: f64,
+}
+
+impl Default for SecondaryAxisMap {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl SecondaryAxisMap {
+    pub(crate) const IDENTITY: Self = Self {
+        scale: 1.0,
+        offset: 0.0,
+    };
+
+    /// The map taking the primary range `from` onto the secondary range `to`.
+    ///
+    /// Returns `None` if either range is empty, reversed or not finite.
+    pub(crate) fn between(from: [f64; 2], to: [f64; 2]) -> Option<Self> {
+        let scale = (to[1] - to[0]) / (from[1] - from[0]);
+        let offset = to[0] - from[0] * scale;
+        (scale.is_finite() && scale > 0.0 && offset.is_finite()).then_some(Self { scale, offset })
+    }
+
+    /// Convert a primary plot-space value to secondary plot space.
+    pub(crate) fn to_secondary(self, y: f64) -> f64 {
+        y.mul_add(self.scale, self.offset)
+    }
+
+    /// Convert a secondary plot-space value to primary plot space.
+    pub(crate) fn to_primary(self, y2: f64) -> f64 {
+        (y2 - self.offset) / self.scale
+    }
+
+    /// Convert a secondary plot-space point to primary plot space.
+    pub(crate) fn point_to_primary(self, [x, y2]: [f64; 2]) -> [f64; 2] {
+        [x, self.to_primary(y2)]
+    }
+
+    /// Convert a primary plot-space range to secondary plot space.
+    pub(crate) fn range_to_secondary(self, [min, max]: [f64; 2]) -> [f64; 2] {
+        [self.to_secondary(min), self.to_secondary(max)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_between_ranges_round_trips() {
+        let map = SecondaryAxisMap::between([0.0, 10.0], [1000.0, 1020.0]).unwrap();
+
+        assert_eq!(map.range_to_secondary([0.0, 10.0]), [1000.0, 1020.0]);
+        assert_eq!(map.to_primary(1010.0), 5.0);
+        assert!(SecondaryAxisMap::between([1.0, 1.0], [0.0, 1.0]).is_none());
+    }
+}
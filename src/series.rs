@@ -1,11 +1,18 @@
 use core::fmt;
+use std::collections::VecDeque;
 
 use iced::Rectangle;
 
 use crate::{
     Color,
+    bar::BarStyle,
     camera::Camera,
+    colormap::ColorScale,
+    decimation::{Decimation, sorted_by_x},
+    error_bars::{ErrorBarStyle, ErrorValues},
+    interpolation::LineInterpolation,
     point::MarkerType,
+    secondary_axis::YAxis,
     transform::{PositionTransform, Transform},
 };
 
@@ -21,6 +28,241 @@ pub enum LineType {
     Dotted { spacing: f32 },
     /// Dashed line with configurable dash length.
     Dashed { length: f32 },
+    /// Line following an arbitrary repeating dash pattern, such as dash-dot.
+    Pattern(DashPattern),
+}
+
+impl LineType {
+    /// The dash pattern of a dashed line, with lengths in pixels, as the CPU renderers
+    /// draw it. Patterns in world units are converted with `world_per_px`; solid and
+    /// dotted lines have none, and neither do patterns repeating within about a pixel.
+    ///
+    /// Dashes and gaps are at least a pixel long, like those of [`LineType::Dashed`].
+    pub(crate) fn dash_pattern_px(self, world_per_px: f64) -> Option<DashPattern> {
+        match self {
+            Self::Solid | Self::Dotted { .. } => None,
+            Self::Dashed { length } => {
+                let dash = length.max(1.0);
+                Some(DashPattern::new([dash, (dash * 0.5).max(1.0)]))
+            }
+            Self::Pattern(pattern) => {
+                let scale = match pattern.units {
+                    DashUnits::Pixels => 1.0,
+                    DashUnits::World => (1.0 / world_per_px.max(1e-12)) as f32,
+                };
+                let mut pixels = pattern;
+                pixels
+                    .lengths
+                    .iter_mut()
+                    .for_each(|length| *length *= scale);
+                pixels.offset *= scale;
+                pixels.units = DashUnits::Pixels;
+                let period = pixels.period();
+                if period < DashPattern::MIN_PERIOD_PX || !period.is_finite() {
+                    return None;
+                }
+                pixels
+                    .lengths
+                    .iter_mut()
+                    .filter(|length| **length > 0.0)
+                    .for_each(|length| *length = length.max(1.0));
+                Some(pixels)
+            }
+        }
+    }
+
+    /// Whether the line follows a dash pattern in plot units.
+    pub(crate) fn has_world_dashes(self) -> bool {
+        matches!(self, Self::Pattern(pattern) if pattern.units == DashUnits::World)
+    }
+
+    /// This line type with a dash pattern in plot units resolved to pixels at the current
+    /// zoom, as the CPU renderers draw it. Patterns with nothing to draw become solid.
+    pub(crate) fn in_pixels(self, world_per_px: f64) -> Self {
+        match self {
+            Self::Pattern(_) => self
+                .dash_pattern_px(world_per_px)
+                .map_or(Self::Solid, Self::Pattern),
+            other => other,
+        }
+    }
+}
+
+/// Units of the lengths in a [`DashPattern`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DashUnits {
+    /// Logical pixels, so the pattern looks the same at any zoom.
+    #[default]
+    Pixels,
+    /// Plot units along the line, so the pattern scales with the data.
+    World,
+}
+
+/// Repeating on/off lengths for [`LineType::Pattern`].
+///
+/// The line starts with a dash of the first length, followed by a gap of the second, and so
+/// on. As in SVG, an odd number of lengths is repeated to make it even, so `[4.0]` gives
+/// 4-pixel dashes with 4-pixel gaps.
+///
+/// A pattern that repeats within about a pixel, such as one in plot units seen zoomed far
+/// out, is drawn as a solid line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DashPattern {
+    lengths: [f32; DashPattern::MAX_LENGTHS],
+    count: usize,
+    /// Distance into the pattern at which the line starts.
+    pub offset: f32,
+    /// Units of the lengths and the offset.
+    pub units: DashUnits,
+}
+
+impl DashPattern {
+    /// Most lengths a pattern can hold; further lengths are ignored.
+    pub const MAX_LENGTHS: usize = 8;
+
+    /// Shortest period, in pixels, of a pattern the CPU renderers draw dashed rather than
+    /// solid.
+    const MIN_PERIOD_PX: f32 = 1.0;
+
+    /// Most dash and gap lengths walked along one stretch of line. The rest of a stretch
+    /// with more is drawn solid.
+    const MAX_STEPS_PER_STRETCH: usize = 16_384;
+
+    /// Create a pattern in pixels from alternating dash and gap lengths.
+    ///
+    /// Negative lengths are treated as zero. A pattern with no length at all draws a solid
+    /// line.
+    pub fn new(lengths: impl IntoIterator<Item = f32>) -> Self {
+        let mut pattern = Self {
+            lengths: [0.0; Self::MAX_LENGTHS],
+            count: 0,
+            offset: 0.0,
+            units: DashUnits::Pixels,
+        };
+        for length in lengths.into_iter().take(Self::MAX_LENGTHS) {
+            pattern.lengths[pattern.count] = if length.is_finite() {
+                length.max(0.0)
+            } else {
+                0.0
+            };
+            pattern.count += 1;
+        }
+        if pattern.count % 2 == 1 {
+            // Repeats that don't fit are cut off, still ending with a gap.
+            let count = pattern.count;
+            let repeat = count.min(Self::MAX_LENGTHS - count);
+            pattern.lengths.copy_within(0..repeat, count);
+            pattern.count += repeat;
+        }
+        pattern
+    }
+
+    /// A dash-dot pattern: a dash, a gap, a dot and a gap, in pixels.
+    pub fn dash_dot(dash: f32, gap: f32) -> Self {
+        Self::new([dash, gap, 1.0, gap])
+    }
+
+    /// Set the distance into the pattern at which the line starts.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the units of the lengths and the offset.
+    pub fn with_units(mut self, units: DashUnits) -> Self {
+        self.units = units;
+        self
+    }
+
+    /// The alternating dash and gap lengths.
+    pub fn lengths(&self) -> &[f32] {
+        &self.lengths[..self.count]
+    }
+
+    /// Length of one repetition of the pattern.
+    pub fn period(&self) -> f32 {
+        self.lengths().iter().sum()
+    }
+
+    /// Whether the pattern is in a dash (rather than a gap) at `distance` along the line.
+    pub(crate) fn is_on(&self, distance: f32) -> bool {
+        let period = self.period();
+        if period <= 0.0 {
+            return true;
+        }
+        let mut phase = (distance + self.offset).rem_euclid(period);
+        for (index, &length) in self.lengths().iter().enumerate() {
+            if phase < length {
+                return index % 2 == 0;
+            }
+            phase -= length;
+        }
+        true
+    }
+
+    /// The dashes of a stretch of line `length` long that starts `start` along the line,
+    /// as ranges measured from the start of the stretch.
+    pub(crate) fn dashes(&self, start: f32, length: f32) -> Vec<std::ops::Range<f32>> {
+        let period = self.period();
+        if period <= 0.0 {
+            return vec![0.0..length];
+        }
+        let mut dashes = Vec::new();
+        // Walk the pattern from the repetition containing the start of the stretch.
+        let mut position = -(start + self.offset).rem_euclid(period);
+        let steps = self.lengths().iter().enumerate().cycle();
+        for (index, &dash_length) in steps.take(Self::MAX_STEPS_PER_STRETCH) {
+            if position >= length {
+                return dashes;
+            }
+            let end = position + dash_length;
+            if index % 2 == 0 && end > 0.0 && dash_length > 0.0 {
+                dashes.push(position.max(0.0)..end.min(length));
+            }
+            position = end;
+        }
+        if position < length {
+            dashes.push(position.max(0.0)..length);
+        }
+        dashes
+    }
+}
+
+/// Shape drawn where two segments of a thick line meet.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineJoin {
+    /// Sharp corner, extending the outer edges until they meet. Corners sharper than
+    /// `limit` (the ratio of the miter length to the line width) are beveled instead.
+    Miter { limit: f32 },
+    /// Rounded corner.
+    #[default]
+    Round,
+    /// Corner cut off straight across.
+    Bevel,
+}
+
+impl LineJoin {
+    /// Miter limit used by [`LineJoin::miter`], matching SVG's default.
+    pub const DEFAULT_MITER_LIMIT: f32 = 4.0;
+
+    /// A miter join with the default limit.
+    pub fn miter() -> Self {
+        Self::Miter {
+            limit: Self::DEFAULT_MITER_LIMIT,
+        }
+    }
+}
+
+/// Shape drawn at the two ends of a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineCap {
+    /// The line stops exactly at its end points.
+    Butt,
+    /// The line ends in a half circle around each end point.
+    #[default]
+    Round,
+    /// The line extends half its width past each end point.
+    Square,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -32,6 +274,12 @@ pub struct LineStyle {
     pub width: Size,
     /// Shape of the line.
     pub line_type: LineType,
+    /// How the line connects consecutive points.
+    pub interpolation: LineInterpolation,
+    /// Shape of the corners between segments.
+    pub join: LineJoin,
+    /// Shape of the line ends.
+    pub cap: LineCap,
 }
 
 impl Default for LineStyle {
@@ -39,6 +287,9 @@ impl Default for LineStyle {
         Self {
             width: Size::Pixels(1.0),
             line_type: LineType::Solid,
+            interpolation: LineInterpolation::Linear,
+            join: LineJoin::Round,
+            cap: LineCap::Round,
         }
     }
 }
@@ -46,7 +297,11 @@ impl Default for LineStyle {
 impl LineStyle {
     /// Create a line style from an explicit width and line type.
     pub fn new(width: Size, line_type: LineType) -> Self {
-        Self { width, line_type }
+        Self {
+            width,
+            line_type,
+            ..Self::default()
+        }
     }
 
     /// Create a solid line style with the default width of 1 logical pixel.
@@ -70,6 +325,14 @@ impl LineStyle {
         }
     }
 
+    /// Create a line style following a custom dash pattern.
+    pub fn pattern(pattern: DashPattern) -> Self {
+        Self {
+            line_type: LineType::Pattern(pattern),
+            ..Self::default()
+        }
+    }
+
     /// Set the line width in either logical pixels or world units.
     pub fn with_width(mut self, width: impl Into<Size>) -> Self {
         self.width = width.into();
@@ -93,6 +356,24 @@ impl LineStyle {
         self.line_type = line_type;
         self
     }
+
+    /// Set how the line connects consecutive points.
+    pub fn with_interpolation(mut self, interpolation: LineInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    /// Set the shape of the corners between segments.
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    /// Set the shape of the line ends.
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -224,7 +505,7 @@ impl MarkerStyle {
 pub enum SeriesError {
     /// No points provided to the series.
     Empty,
-    /// Series has neither markers nor lines enabled.
+    /// Series has neither markers, lines, nor bars enabled.
     NoMarkersAndNoLines,
     /// A series with the given ID does not exist.
     NotFound(ShapeId),
@@ -234,10 +515,44 @@ pub enum SeriesError {
     InvalidAxisScale,
     /// Per-point colors length does not match positions length.
     InvalidPointColorsLength,
+    /// Per-point color values length does not match positions length.
+    InvalidColorValuesLength,
     /// Fill begin/end must reference different shapes.
     InvalidFillEndpoints,
     /// Fill endpoint references a shape that does not exist in the widget.
     FillEndpointNotFound(ShapeId),
+    /// Bar, box or violin width must be finite and positive.
+    InvalidBarWidth,
+    /// Per-point error values length does not match positions length.
+    InvalidErrorsLength,
+    /// Error values must be finite and non-negative.
+    InvalidErrorValues,
+    /// Decimated series must have positions sorted by increasing x.
+    UnsortedPositions,
+    /// Maximum series length must be greater than zero.
+    InvalidMaxLen,
+    /// Raster data length does not match its columns and rows.
+    InvalidRasterDataLength,
+    /// Raster extent must be finite with min < max on both axes.
+    InvalidRasterExtent,
+    /// Contour grid must be at least 2x2, with one value per grid point.
+    InvalidContourGrid,
+    /// Contour extent must be finite with min < max on both axes.
+    InvalidContourExtent,
+    /// Histogram bin count must be between 1 and 10 000, and bin width finite and positive.
+    InvalidBinning,
+    /// Histogram range must be finite with min <= max.
+    InvalidHistogramRange,
+    /// Distribution plot positions must be finite, one per group.
+    InvalidDistributionPositions,
+    /// Whisker range must be finite and non-negative.
+    InvalidWhiskerRange,
+    /// Per-point category names length does not match positions length.
+    InvalidCategoriesLength,
+    /// A category name is not one of the plot's x-axis categories.
+    UnknownCategory(String),
+    /// Points can't be appended to a series placed by x-axis category names.
+    AppendToNamedCategories,
 }
 
 /// Unique identifier for a shape in the plot.
@@ -290,14 +605,27 @@ pub struct Series {
     /// Unique identifier for the series.
     pub id: ShapeId,
 
-    /// Series point positions.
-    pub positions: Vec<[f64; 2]>,
+    /// Series point positions. Kept in a ring buffer, so appends past
+    /// [`max_len`](Self::max_len) drop the oldest points without moving the rest.
+    pub positions: VecDeque<[f64; 2]>,
 
     /// How this series interprets or converts point positions before drawing.
     pub transform: PositionTransform,
 
+    /// The y-axis this series is drawn against. Defaults to [`YAxis::Primary`].
+    pub y_axis: YAxis,
+
     /// Optional per-point colors. Must match the length of `positions` if set.
-    pub point_colors: Option<Vec<Color>>,
+    pub point_colors: Option<VecDeque<Color>>,
+
+    /// Optional per-point scalar values, colored through `color_scale`. Must match the
+    /// length of `positions` if set.
+    ///
+    /// Overrides `point_colors` when the series is added to a plot.
+    pub color_values: Option<VecDeque<f64>>,
+
+    /// Maps `color_values` to colors.
+    pub color_scale: ColorScale,
 
     /// Optional label for the entire series.
     pub label: Option<String>,
@@ -315,6 +643,31 @@ pub struct Series {
 
     /// Can be hovered or picked. Defaults to `true`.
     pub pickable: bool,
+
+    /// Bar style for the series. If set, each point is drawn as a bar.
+    pub bar_style: Option<BarStyle>,
+
+    /// Optional per-point x errors. Must match the length of `positions` if set.
+    pub x_errors: Option<ErrorValues>,
+
+    /// Optional per-point y errors. Must match the length of `positions` if set.
+    pub y_errors: Option<ErrorValues>,
+
+    /// Styling for error bars. Only used if `x_errors` or `y_errors` is set.
+    pub error_bar_style: ErrorBarStyle,
+
+    /// Level-of-detail reduction for markers and lines. Defaults to [`Decimation::None`].
+    pub decimation: Decimation,
+
+    /// Maximum number of points kept when appending with
+    /// [`PlotWidget::append_series_points`](crate::PlotWidget::append_series_points).
+    /// Once exceeded, the oldest points are dropped. If none, appended points are kept.
+    pub max_len: Option<usize>,
+
+    /// Optional per-point x-axis category names. If set, each point is placed at its
+    /// category of the plot's [categorical x-axis](crate::PlotWidget::set_x_categories)
+    /// instead of its x value. Must match the length of `positions` if set.
+    pub x_categories: Option<VecDeque<String>>,
 }
 
 impl Series {
@@ -322,14 +675,24 @@ impl Series {
     pub fn new(positions: Vec<[f64; 2]>, marker_style: MarkerStyle, line_style: LineStyle) -> Self {
         Self {
             id: ShapeId::new(),
-            positions,
+            positions: positions.into(),
             transform: PositionTransform::default(),
+            y_axis: YAxis::Primary,
             point_colors: None,
+            color_values: None,
+            color_scale: ColorScale::default(),
             label: None,
             color: Color::from_rgb(0.3, 0.3, 0.9),
             marker_style: Some(marker_style),
             line_style: Some(line_style),
             pickable: true,
+            bar_style: None,
+            x_errors: None,
+            y_errors: None,
+            error_bar_style: ErrorBarStyle::default(),
+            decimation: Decimation::None,
+            max_len: None,
+            x_categories: None,
         }
     }
 
@@ -337,14 +700,24 @@ impl Series {
     pub fn line_only(positions: Vec<[f64; 2]>, line_style: LineStyle) -> Self {
         Self {
             id: ShapeId::new(),
-            positions,
+            positions: positions.into(),
             transform: PositionTransform::default(),
+            y_axis: YAxis::Primary,
             point_colors: None,
+            color_values: None,
+            color_scale: ColorScale::default(),
             label: None,
             color: Color::from_rgb(0.3, 0.3, 0.9),
             marker_style: None,
             line_style: Some(line_style),
             pickable: true,
+            bar_style: None,
+            x_errors: None,
+            y_errors: None,
+            error_bar_style: ErrorBarStyle::default(),
+            decimation: Decimation::None,
+            max_len: None,
+            x_categories: None,
         }
     }
 
@@ -352,14 +725,24 @@ impl Series {
     pub fn markers_only(positions: Vec<[f64; 2]>, marker_style: MarkerStyle) -> Self {
         Self {
             id: ShapeId::new(),
-            positions,
+            positions: positions.into(),
             transform: PositionTransform::default(),
+            y_axis: YAxis::Primary,
             point_colors: None,
+            color_values: None,
+            color_scale: ColorScale::default(),
             label: None,
             color: Color::from_rgb(0.3, 0.3, 0.9),
             marker_style: Some(marker_style),
             line_style: None,
             pickable: true,
+            bar_style: None,
+            x_errors: None,
+            y_errors: None,
+            error_bar_style: ErrorBarStyle::default(),
+            decimation: Decimation::None,
+            max_len: None,
+            x_categories: None,
         }
     }
 
@@ -383,6 +766,31 @@ impl Series {
         Self::markers_only(positions, MarkerStyle::triangle(size))
     }
 
+    /// Create a new bar series. Each position becomes one bar.
+    pub fn bars(positions: Vec<[f64; 2]>, bar_style: BarStyle) -> Self {
+        Self {
+            id: ShapeId::new(),
+            positions: positions.into(),
+            transform: PositionTransform::default(),
+            y_axis: YAxis::Primary,
+            point_colors: None,
+            color_values: None,
+            color_scale: ColorScale::default(),
+            label: None,
+            color: Color::from_rgb(0.3, 0.3, 0.9),
+            marker_style: None,
+            line_style: None,
+            pickable: true,
+            bar_style: Some(bar_style),
+            x_errors: None,
+            y_errors: None,
+            error_bar_style: ErrorBarStyle::default(),
+            decimation: Decimation::None,
+            max_len: None,
+            x_categories: None,
+        }
+    }
+
     /// Set an label for the series.
     pub fn with_label(mut self, label: impl Into<String>) -> Self {
         let l = label.into();
@@ -398,6 +806,12 @@ impl Series {
         self
     }
 
+    /// Set or change the bar style for the series.
+    pub fn with_bar_style(mut self, style: BarStyle) -> Self {
+        self.bar_style = Some(style);
+        self
+    }
+
     /// Set the color of the entire series. Overridden by per-point colors if they are set.
     pub fn with_color(mut self, color: impl Into<Color>) -> Self {
         self.color = color.into();
@@ -406,7 +820,63 @@ impl Series {
 
     /// Set per-point colors for the series. Length must match the number of positions.
     pub fn with_point_colors(mut self, colors: Vec<Color>) -> Self {
-        self.point_colors = Some(colors);
+        self.point_colors = Some(colors.into());
+        self
+    }
+
+    /// Color each point by a scalar value through a color scale. Length must match the
+    /// number of positions.
+    ///
+    /// Points whose value has no color (such as NaN, or non-positive values on a log
+    /// scale) use the series color. Pass a [`Colormap`](crate::Colormap) to fit the
+    /// scale to the values.
+    pub fn with_color_values(
+        mut self,
+        values: Vec<f64>,
+        color_scale: impl Into<ColorScale>,
+    ) -> Self {
+        self.color_values = Some(values.into());
+        self.color_scale = color_scale.into();
+        self
+    }
+
+    /// Set per-point x errors. Pass a `Vec<f64>` for symmetric errors or a
+    /// `(lower, upper)` pair of vectors for asymmetric errors. Errors are distances from the
+    /// point, so they must be finite and non-negative.
+    pub fn with_x_errors(mut self, errors: impl Into<ErrorValues>) -> Self {
+        self.x_errors = Some(errors.into());
+        self
+    }
+
+    /// Set per-point y errors. Pass a `Vec<f64>` for symmetric errors or a
+    /// `(lower, upper)` pair of vectors for asymmetric errors. Errors are distances from the
+    /// point, so they must be finite and non-negative.
+    pub fn with_y_errors(mut self, errors: impl Into<ErrorValues>) -> Self {
+        self.y_errors = Some(errors.into());
+        self
+    }
+
+    /// Set the error bar style.
+    pub fn with_error_bar_style(mut self, style: ErrorBarStyle) -> Self {
+        self.error_bar_style = style;
+        self
+    }
+
+    /// Reduce the number of drawn points based on the visible x-range and plot width.
+    ///
+    /// Positions must be sorted by increasing x, otherwise adding the series fails with
+    /// [`SeriesError::UnsortedPositions`].
+    pub fn with_decimation(mut self, decimation: Decimation) -> Self {
+        self.decimation = decimation;
+        self
+    }
+
+    /// Keep at most `max_len` points when appending, dropping the oldest ones first.
+    ///
+    /// Series with a maximum length reserve that many points on the GPU, so appends only
+    /// upload the new points.
+    pub fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
         self
     }
 
@@ -446,6 +916,28 @@ impl Series {
         self
     }
 
+    /// Draw this series against the given y-axis.
+    ///
+    /// Series on [`YAxis::Secondary`] use the plot's secondary y-axis scale, limits and
+    /// ticks, shown on the right side of the plot.
+    pub fn with_y_axis(mut self, y_axis: YAxis) -> Self {
+        self.y_axis = y_axis;
+        self
+    }
+
+    /// Place each point at a named category of the plot's categorical x-axis, instead of
+    /// at its x value.
+    ///
+    /// Points can also use category indices directly as x values, since category `i` is
+    /// at x = `i`.
+    pub fn with_x_categories<S: Into<String>>(
+        mut self,
+        categories: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.x_categories = Some(categories.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Enable or disable interactive hover/pick behavior for this series.
     pub fn with_pickable(mut self, pickable: bool) -> Self {
         self.pickable = pickable;
@@ -481,6 +973,28 @@ impl Series {
         self
     }
 
+    /// Set or change how the line connects consecutive points.
+    pub fn line_interpolation(mut self, interpolation: LineInterpolation) -> Self {
+        self.line_style = Some(
+            self.line_style
+                .unwrap_or_default()
+                .with_interpolation(interpolation),
+        );
+        self
+    }
+
+    /// Set or change the shape of the line's corners.
+    pub fn line_join(mut self, join: LineJoin) -> Self {
+        self.line_style = Some(self.line_style.unwrap_or_default().with_join(join));
+        self
+    }
+
+    /// Set or change the shape of the line's ends.
+    pub fn line_cap(mut self, cap: LineCap) -> Self {
+        self.line_style = Some(self.line_style.unwrap_or_default().with_cap(cap));
+        self
+    }
+
     /// Set solid line style.
     pub fn line_solid(self) -> Self {
         self.line_type(LineType::Solid)
@@ -496,18 +1010,202 @@ impl Series {
         self.line_type(LineType::Dashed { length })
     }
 
+    /// Append points, padding per-point colors and errors, then drop the oldest points
+    /// beyond `max_len`. Returns the number of points dropped.
+    ///
+    /// Color values are padded with NaN, which keeps the series color. The other colors
+    /// are only recomputed when dropping points can change a fitted color range.
+    pub(crate) fn append(&mut self, points: impl IntoIterator<Item = [f64; 2]>) -> usize {
+        let old_len = self.positions.len();
+        self.positions.extend(points);
+        let added = self.positions.len() - old_len;
+        if let Some(colors) = &mut self.point_colors {
+            colors.resize(colors.len() + added, self.color);
+        }
+        if let Some(values) = &mut self.color_values {
+            values.resize(values.len() + added, f64::NAN);
+        }
+        for errors in [&mut self.x_errors, &mut self.y_errors]
+            .into_iter()
+            .flatten()
+        {
+            errors.extend_zero(added);
+        }
+
+        let dropped = self
+            .max_len
+            .map_or(0, |max_len| self.positions.len().saturating_sub(max_len));
+        if dropped > 0 {
+            self.positions.drain(..dropped);
+            if let Some(colors) = &mut self.point_colors {
+                colors.drain(..dropped);
+            }
+            if let Some(values) = &mut self.color_values {
+                values.drain(..dropped);
+            }
+            for errors in [&mut self.x_errors, &mut self.y_errors]
+                .into_iter()
+                .flatten()
+            {
+                errors.remove_front(dropped);
+            }
+            if self.color_scale.fits_values() {
+                self.apply_color_values();
+            }
+        }
+        dropped
+    }
+
+    /// Set the x value of each named point to the index of its category, or NaN if it
+    /// isn't one of `categories`.
+    pub(crate) fn place_on_x_categories(&mut self, categories: Option<&[String]>) {
+        let Some(names) = &self.x_categories else {
+            return;
+        };
+        for (position, name) in self.positions.iter_mut().zip(names) {
+            position[0] = categories
+                .and_then(|categories| categories.iter().position(|category| category == name))
+                .map_or(f64::NAN, |index| index as f64);
+        }
+    }
+
+    /// Recompute per-point colors from the color values, if set.
+    pub(crate) fn apply_color_values(&mut self) {
+        if let Some(values) = &self.color_values {
+            self.point_colors = Some(self.color_scale.colors(values, self.color).into());
+        }
+    }
+
     pub(super) fn validate(&self) -> Result<(), SeriesError> {
         if self.positions.is_empty() {
             return Err(SeriesError::Empty);
         }
-        if self.marker_style.is_none() && self.line_style.is_none() {
+        if self.marker_style.is_none() && self.line_style.is_none() && self.bar_style.is_none() {
             return Err(SeriesError::NoMarkersAndNoLines);
         }
+        if let Some(bar_style) = &self.bar_style
+            && !(bar_style.width.is_finite() && bar_style.width > 0.0)
+        {
+            return Err(SeriesError::InvalidBarWidth);
+        }
+        if let Some(values) = &self.color_values
+            && values.len() != self.positions.len()
+        {
+            return Err(SeriesError::InvalidColorValuesLength);
+        }
         if let Some(colors) = &self.point_colors
             && colors.len() != self.positions.len()
         {
             return Err(SeriesError::InvalidPointColorsLength);
         }
+        if [&self.x_errors, &self.y_errors]
+            .into_iter()
+            .flatten()
+            .any(|errors| !errors.len_matches(self.positions.len()))
+        {
+            return Err(SeriesError::InvalidErrorsLength);
+        }
+        if [&self.x_errors, &self.y_errors]
+            .into_iter()
+            .flatten()
+            .any(|errors| !errors.all_valid())
+        {
+            return Err(SeriesError::InvalidErrorValues);
+        }
+        if self.decimation != Decimation::None && !sorted_by_x(&self.positions) {
+            return Err(SeriesError::UnsortedPositions);
+        }
+        if self.max_len == Some(0) {
+            return Err(SeriesError::InvalidMaxLen);
+        }
+        if let Some(categories) = &self.x_categories
+            && categories.len() != self.positions.len()
+        {
+            return Err(SeriesError::InvalidCategoriesLength);
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_or_non_finite_errors_are_rejected() {
+        let series = || Series::circles(vec![[0.0, 1.0], [1.0, 2.0]], 4.0);
+
+        assert_eq!(
+            series().with_y_errors(vec![0.5, -0.5]).validate(),
+            Err(SeriesError::InvalidErrorValues)
+        );
+        assert_eq!(
+            series()
+                .with_x_errors((vec![0.1, 0.2], vec![f64::NAN, 0.2]))
+                .validate(),
+            Err(SeriesError::InvalidErrorValues)
+        );
+        assert_eq!(series().with_y_errors(vec![0.0, 0.5]).validate(), Ok(()));
+    }
+
+    #[test]
+    fn dash_patterns_repeat_odd_lengths_and_apply_their_offset() {
+        let pattern = DashPattern::new([4.0, 2.0, 1.0]);
+        assert_eq!(pattern.lengths(), &[4.0, 2.0, 1.0, 4.0, 2.0, 1.0]);
+        assert_eq!(pattern.period(), 14.0);
+
+        let pattern = DashPattern::new([4.0, 2.0]).with_offset(1.0);
+        assert!(pattern.is_on(0.0));
+        assert!(!pattern.is_on(3.5));
+        assert!(pattern.is_on(5.5));
+        assert!(pattern.is_on(-1.0));
+    }
+
+    #[test]
+    fn world_patterns_scale_with_zoom() {
+        let pattern = DashPattern::new([1.0, 0.5])
+            .with_offset(0.25)
+            .with_units(DashUnits::World);
+        let pixels = LineType::Pattern(pattern).dash_pattern_px(0.1).unwrap();
+        assert_eq!(pixels.lengths(), &[10.0, 5.0]);
+        assert_eq!(pixels.offset, 2.5);
+        assert_eq!(pixels.units, DashUnits::Pixels);
+
+        assert!(
+            LineType::Pattern(DashPattern::new([]))
+                .dash_pattern_px(1.0)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn tiny_dash_patterns_draw_solid() {
+        let tiny = DashPattern::new([1e-6, 1e-6]);
+        assert_eq!(LineType::Pattern(tiny).dash_pattern_px(1.0), None);
+        assert_eq!(LineType::Pattern(tiny).in_pixels(1.0), LineType::Solid);
+        // World patterns shrink below a pixel when zoomed out.
+        let world = LineType::Pattern(DashPattern::new([2.0, 1.0]).with_units(DashUnits::World));
+        assert!(world.dash_pattern_px(1.0).is_some());
+        assert_eq!(world.in_pixels(10.0), LineType::Solid);
+        // Dashes and gaps that are drawn last at least a pixel.
+        let short = LineType::Pattern(DashPattern::new([0.25, 1.0, 0.0, 1.0]));
+        assert_eq!(
+            short.dash_pattern_px(1.0).unwrap().lengths(),
+            [1.0, 1.0, 0.0, 1.0]
+        );
+
+        // Walking a tiny pattern directly stops and draws the rest solid.
+        let dashes = tiny.dashes(0.0, 1000.0);
+        assert!(dashes.len() <= DashPattern::MAX_STEPS_PER_STRETCH);
+        assert_eq!(dashes.last().map(|dash| dash.end), Some(1000.0));
+    }
+
+    #[test]
+    fn dashes_are_clipped_to_the_stretch() {
+        let pattern = DashPattern::new([4.0, 2.0]);
+        assert_eq!(
+            pattern.dashes(3.0, 10.0),
+            vec![0.0..1.0, 3.0..7.0, 9.0..10.0]
+        );
+    }
+}
@@ -5,19 +5,30 @@ struct CameraUniform {
 };
 @group(0) @binding(0)
 var<uniform> camera: CameraUniform;
+// Lengths of the custom dash patterns in use, two vec4s for each of `MAX_DASH_PATTERNS`.
+@group(0) @binding(1)
+var<uniform> dash_patterns: array<vec4<f32>, 512>;
 
 struct VsIn {
     @location(0) segment_start: vec2<f32>,
     @location(1) segment_end: vec2<f32>,
     @location(2) color: vec4<f32>,
-    @location(3) line_style: u32, // 0=solid, 1=dotted, 2=dashed
+    // 0=solid, 1=dotted, 2=dashed, 3=pattern | length count << 8 | world units << 16
+    // | pattern index << 24
+    @location(3) line_style: u32,
     @location(4) distance_start: f32, // cumulative distance at the segment start
     @location(5) segment_length_world: f32,
-    @location(6) style_param: f32, // spacing for dotted, length for dashed
+    @location(6) style_param: f32, // spacing for dotted, length for dashed, offset for patterns
     @location(7) width: f32,
+    // Bit 0: width in world units. Bit 1: segment_end is an offset in pixels from the start.
     @location(8) width_mode: u32,
     @location(9) along: f32, // 0=start edge, 1=end edge
     @location(10) side: f32, // -1 or +1
+    // Directions of the neighbouring segments; zero where the segment ends the line.
+    @location(11) prev_dir: vec2<f32>,
+    @location(12) next_dir: vec2<f32>,
+    @location(13) join_cap: u32, // join (0=miter, 1=round, 2=bevel) | cap << 8 (0=butt, 1=round, 2=square)
+    @location(14) miter_limit: f32,
 };
 
 struct VsOut {
@@ -31,15 +42,61 @@ struct VsOut {
     @location(6) local_y_px: f32,
     @location(7) half_width_px: f32,
     @location(8) segment_length_px: f32,
+    @interpolate(flat) @location(9) join_cap: u32,
+    @interpolate(flat) @location(10) miter_limit: f32,
+    @interpolate(flat) @location(11) has_prev: u32,
+    // Direction of the next segment in the (along, across) frame of this one; zero if none.
+    @interpolate(flat) @location(12) next_local: vec2<f32>,
 };
 
 const LINE_AA_RADIUS_PX: f32 = 1.0;
 const PIXEL_TO_WORLD_MIN: f32 = 1e-12;
+const JOIN_MITER: u32 = 0u;
+const JOIN_ROUND: u32 = 1u;
+const CAP_ROUND: u32 = 1u;
+const CAP_SQUARE: u32 = 2u;
 
 fn perp(v: vec2<f32>) -> vec2<f32> {
     return vec2<f32>(-v.y, v.x);
 }
 
+// Signed distance (negative inside) from `q`, measured from the end point with +x pointing
+// away from the line, to the cap.
+fn cap_distance(q: vec2<f32>, half_width: f32, cap: u32) -> f32 {
+    if cap == CAP_ROUND {
+        return length(q) - half_width;
+    }
+    if cap == CAP_SQUARE {
+        return max(q.x - half_width, abs(q.y) - half_width);
+    }
+    return max(q.x, abs(q.y) - half_width);
+}
+
+// Signed distance from `q`, relative to the joint, to the join filling the outer side of
+// the turn into the segment with direction `next`. The outline is the same whichever
+// segment draws it, so only the outer edges are antialiased.
+fn join_distance(q: vec2<f32>, next: vec2<f32>, half_width: f32, join: u32, limit: f32) -> f32 {
+    if join == JOIN_ROUND {
+        return length(q) - half_width;
+    }
+    // Outer normals of this segment and the next one.
+    let outer = select(1.0, -1.0, next.y > 0.0);
+    let normal0 = vec2<f32>(0.0, outer);
+    let normal1 = outer * perp(next);
+    let edges = max(dot(q, normal0), dot(q, normal1)) - half_width;
+    let bisector = normal0 + normal1;
+    if length(bisector) < 1e-4 {
+        // Turning back on itself: nothing sticks out past the end.
+        return q.x;
+    }
+    let m = normalize(bisector);
+    let cos_half = dot(normal0, m);
+    if join == JOIN_MITER && 1.0 / max(cos_half, 1e-6) <= limit {
+        return edges;
+    }
+    return max(edges, dot(q, m) - half_width * cos_half);
+}
+
 @vertex
 fn vs_main(in: VsIn) -> VsOut {
     var out: VsOut;
@@ -49,14 +106,18 @@ fn vs_main(in: VsIn) -> VsOut {
         max(camera.pixel_to_world.y, PIXEL_TO_WORLD_MIN),
     );
     let start_px = in.segment_start / pixel_to_world;
-    let end_px = in.segment_end / pixel_to_world;
+    let end_px = select(
+        in.segment_end / pixel_to_world,
+        start_px + in.segment_end,
+        (in.width_mode & 2u) != 0u,
+    );
     let delta_px = end_px - start_px;
     let segment_length_px = max(length(delta_px), 1e-6);
     let tangent_px = delta_px / segment_length_px;
     let normal_px = perp(tangent_px);
 
     var half_width_px: f32;
-    if in.width_mode == 0u {
+    if (in.width_mode & 1u) == 0u {
         half_width_px = max(in.width, 0.5) * 0.5;
     } else {
         let world_per_px_normal = max(
@@ -67,12 +128,26 @@ fn vs_main(in: VsIn) -> VsOut {
     }
     let outer_half_width_px = half_width_px + LINE_AA_RADIUS_PX;
 
+    let join = in.join_cap & 0xffu;
+    let next_px = in.next_dir / pixel_to_world;
+    let has_next = dot(next_px, next_px) > 0.0;
+    var next_local = vec2<f32>(0.0, 0.0);
+    if has_next {
+        let next_dir_px = normalize(next_px);
+        next_local = vec2<f32>(dot(next_dir_px, tangent_px), dot(next_dir_px, normal_px));
+    }
+    // Miter tips reach further than the half width, up to the miter limit.
+    var reach_px = outer_half_width_px;
+    if has_next && join == JOIN_MITER {
+        reach_px = half_width_px * max(in.miter_limit, 1.0) + LINE_AA_RADIUS_PX;
+    }
+
     let local_x_px = select(
         -outer_half_width_px,
-        segment_length_px + outer_half_width_px,
+        segment_length_px + reach_px,
         in.along > 0.5,
     );
-    let local_y_px = in.side * outer_half_width_px;
+    let local_y_px = in.side * reach_px;
 
     let offset_px = tangent_px * local_x_px + normal_px * local_y_px;
     let start_clip = camera.view_proj * vec4<f32>(in.segment_start, 0.0, 1.0);
@@ -95,15 +170,46 @@ fn vs_main(in: VsIn) -> VsOut {
     out.local_y_px = local_y_px;
     out.half_width_px = half_width_px;
     out.segment_length_px = segment_length_px;
+    out.join_cap = in.join_cap;
+    out.miter_limit = in.miter_limit;
+    out.has_prev = select(0u, 1u, dot(in.prev_dir, in.prev_dir) > 0.0);
+    out.next_local = next_local;
     return out;
 }
 
 @fragment
 fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     let clamped_x_px = clamp(in.local_x_px, 0.0, in.segment_length_px);
-    let cap_dx_px = in.local_x_px - clamped_x_px;
-    let distance_to_stroke_px = length(vec2<f32>(cap_dx_px, in.local_y_px));
-    let edge_alpha = clamp(in.half_width_px + 0.5 - distance_to_stroke_px, 0.0, 1.0);
+    let join = in.join_cap & 0xffu;
+    let cap = in.join_cap >> 8u;
+
+    // Each joint is drawn by the segment ending there: the next segment starts with a hard
+    // edge, and this one fills the outer side of the turn up to that edge.
+    var stroke_distance_px = abs(in.local_y_px) - in.half_width_px;
+    if in.local_x_px < 0.0 {
+        if in.has_prev != 0u {
+            discard;
+        }
+        let q = vec2<f32>(-in.local_x_px, in.local_y_px);
+        stroke_distance_px = cap_distance(q, in.half_width_px, cap);
+    } else if in.local_x_px > in.segment_length_px {
+        let q = vec2<f32>(in.local_x_px - in.segment_length_px, in.local_y_px);
+        if dot(in.next_local, in.next_local) > 0.0 {
+            if dot(q, in.next_local) > 0.0 {
+                discard;
+            }
+            stroke_distance_px = join_distance(
+                q,
+                in.next_local,
+                in.half_width_px,
+                join,
+                in.miter_limit,
+            );
+        } else {
+            stroke_distance_px = cap_distance(q, in.half_width_px, cap);
+        }
+    }
+    let edge_alpha = clamp(0.5 - stroke_distance_px, 0.0, 1.0);
 
     let pixel_to_world = max(camera.pixel_to_world.x, PIXEL_TO_WORLD_MIN);
     let distance_along_line = in.distance_start
@@ -111,7 +217,37 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
 
     var alpha = 1.0;
 
-    if in.line_style == 1u {
+    let line_type = in.line_style & 0xffu;
+    if line_type == 3u {
+        let count = (in.line_style >> 8u) & 0xffu;
+        let scale = select(pixel_to_world, 1.0, ((in.line_style >> 16u) & 1u) == 1u);
+        let pattern = in.line_style >> 24u;
+        let pattern0 = dash_patterns[pattern * 2u];
+        let pattern1 = dash_patterns[pattern * 2u + 1u];
+        var lengths = array<f32, 8>(
+            pattern0.x, pattern0.y, pattern0.z, pattern0.w,
+            pattern1.x, pattern1.y, pattern1.z, pattern1.w,
+        );
+        var period = 0.0;
+        for (var i = 0u; i < count; i++) {
+            period += lengths[i] * scale;
+        }
+        if period > 0.0 {
+            // Walk the pattern to the dash or gap containing this point.
+            let shifted = distance_along_line + in.style_param * scale;
+            var phase = shifted - floor(shifted / period) * period;
+            for (var i = 0u; i < count; i++) {
+                let length = lengths[i] * scale;
+                if phase < length {
+                    if i % 2u == 1u {
+                        alpha = 0.0;
+                    }
+                    break;
+                }
+                phase -= length;
+            }
+        }
+    } else if in.line_style == 1u {
         let spacing_world = in.style_param * pixel_to_world;
         let pattern_length = spacing_world * 2.0;
         let t = fract(distance_along_line / pattern_length);
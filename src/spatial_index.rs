@@ -0,0 +1,528 @@
+//! Spatial indices for nearest-point and nearby-segment queries over large series.
+use std::sync::OnceLock;
+
+/// Number of points below which a node is scanned instead of split.
+const LEAF_SIZE: usize = 16;
+
+/// A spatial index over the points of a series, for nearest-point queries that don't scan
+/// every point.
+///
+/// Series with sorted x answer x queries by binary search. Everything else goes through a
+/// k-d tree over the finite points, built on the first query that needs it. The tree is
+/// implicit: each node's points are contiguous in `tree`, with the median on the split axis
+/// in the middle and smaller values before it. Splits alternate between x and y, starting
+/// with x.
+///
+/// Positions are passed to each query rather than borrowed, and must not change between
+/// queries.
+#[derive(Debug, Default)]
+pub(crate) struct PointIndex {
+    len: usize,
+    /// Whether every point is finite and x never decreases along the series.
+    sorted_x: bool,
+    /// Finite positions paired with their point index, in tree order.
+    tree: OnceLock<Vec<([f64; 2], usize)>>,
+}
+
+impl PointIndex {
+    /// Create the index over `len` points.
+    pub(crate) fn new(len: usize, position: impl Fn(usize) -> [f64; 2]) -> Self {
+        let sorted_x = (0..len).all(|i| {
+            let [x, y] = position(i);
+            x.is_finite() && y.is_finite() && (i == 0 || position(i - 1)[0] <= x)
+        });
+        Self {
+            len,
+            sorted_x,
+            tree: OnceLock::new(),
+        }
+    }
+
+    /// The index after `removed` points were dropped from the front and more were appended
+    /// up to `len`, if it stays sorted by x. Other indices have to be built again.
+    pub(crate) fn extended(
+        &self,
+        removed: usize,
+        len: usize,
+        position: impl Fn(usize) -> [f64; 2],
+    ) -> Option<Self> {
+        let kept = self.len.checked_sub(removed)?;
+        if !self.sorted_x || kept > len {
+            return None;
+        }
+        let sorted_x = (kept..len).all(|i| {
+            let [x, y] = position(i);
+            x.is_finite() && y.is_finite() && (i == 0 || position(i - 1)[0] <= x)
+        });
+        sorted_x.then(|| Self {
+            len,
+            sorted_x,
+            tree: OnceLock::new(),
+        })
+    }
+
+    /// Whether every point is finite and x never decreases along the series.
+    pub(crate) fn is_sorted_x(&self) -> bool {
+        self.sorted_x
+    }
+
+    fn tree(&self, position: impl Fn(usize) -> [f64; 2]) -> &[([f64; 2], usize)] {
+        self.tree.get_or_init(|| {
+            let mut tree: Vec<_> = (0..self.len)
+                .map(|i| (position(i), i))
+                .filter(|([x, y], _)| x.is_finite() && y.is_finite())
+                .collect();
+            build(&mut tree, 0);
+            tree
+        })
+    }
+
+    /// Find the point nearest `target` within `max_distance_squared`, with the distance
+    /// along each axis multiplied by `scale`.
+    ///
+    /// A zero scale ignores that axis. Ties go to the lower index, as in a linear scan.
+    /// Returns the point index and its scaled squared distance.
+    pub(crate) fn nearest(
+        &self,
+        target: [f64; 2],
+        scale: [f64; 2],
+        max_distance_squared: f64,
+        position: impl Fn(usize) -> [f64; 2],
+    ) -> Option<(usize, f64)> {
+        let mut search = NearestSearch {
+            target,
+            scale,
+            bound: max_distance_squared,
+            best: None,
+        };
+        search.visit(self.tree(position), 0);
+        search.best
+    }
+
+    /// Find the point whose x is nearest `x`, ignoring y. Ties go to the lower index.
+    pub(crate) fn nearest_x(&self, x: f64, position: impl Fn(usize) -> [f64; 2]) -> Option<usize> {
+        if !self.sorted_x {
+            return self
+                .nearest([x, 0.0], [1.0, 0.0], f64::INFINITY, position)
+                .map(|(index, _)| index);
+        }
+
+        // The first point at or after `x`, and the first point of the run of equal x before it.
+        let after = partition_point(self.len, |i| position(i)[0] < x);
+        let before = after.checked_sub(1).map(|last| {
+            let last_x = position(last)[0];
+            partition_point(last, |i| position(i)[0] < last_x)
+        });
+        match (before, (after < self.len).then_some(after)) {
+            (Some(before), Some(after)) => {
+                if x - position(before)[0] <= position(after)[0] - x {
+                    Some(before)
+                } else {
+                    Some(after)
+                }
+            }
+            (before, after) => before.or(after),
+        }
+    }
+
+    /// Find the point whose y is nearest `y`, ignoring x. Ties go to the lower index.
+    pub(crate) fn nearest_y(&self, y: f64, position: impl Fn(usize) -> [f64; 2]) -> Option<usize> {
+        self.nearest([0.0, y], [0.0, 1.0], f64::INFINITY, position)
+            .map(|(index, _)| index)
+    }
+
+    /// Visit every point in the box from `min` to `max`, edges included. Series with sorted
+    /// x visit in order; others in no particular order.
+    pub(crate) fn for_each_within(
+        &self,
+        min: [f64; 2],
+        max: [f64; 2],
+        position: impl Fn(usize) -> [f64; 2],
+        mut visit: impl FnMut(usize),
+    ) {
+        let within = |p: [f64; 2]| (0..2).all(|axis| min[axis] <= p[axis] && p[axis] <= max[axis]);
+        if self.sorted_x {
+            let first = partition_point(self.len, |i| position(i)[0] < min[0]);
+            for i in first..self.len {
+                let p = position(i);
+                if p[0] > max[0] {
+                    break;
+                }
+                if within(p) {
+                    visit(i);
+                }
+            }
+            return;
+        }
+        visit_within(self.tree(position), 0, &within, min, max, &mut visit);
+    }
+}
+
+/// Visit the points of the k-d `tree` split on `axis` first that are `within` the box from
+/// `min` to `max`.
+fn visit_within(
+    tree: &[([f64; 2], usize)],
+    axis: usize,
+    within: &impl Fn([f64; 2]) -> bool,
+    min: [f64; 2],
+    max: [f64; 2],
+    visit: &mut impl FnMut(usize),
+) {
+    if tree.len() <= LEAF_SIZE {
+        for &(p, index) in tree {
+            if within(p) {
+                visit(index);
+            }
+        }
+        return;
+    }
+    let mid = tree.len() / 2;
+    let (split, index) = tree[mid];
+    if within(split) {
+        visit(index);
+    }
+    // Points equal to the split value may sit on either side.
+    if min[axis] <= split[axis] {
+        visit_within(&tree[..mid], 1 - axis, within, min, max, visit);
+    }
+    if split[axis] <= max[axis] {
+        visit_within(&tree[mid + 1..], 1 - axis, within, min, max, visit);
+    }
+}
+
+/// Arrange `tree` into an implicit k-d tree, splitting on `axis` first.
+fn build(tree: &mut [([f64; 2], usize)], axis: usize) {
+    if tree.len() <= LEAF_SIZE {
+        return;
+    }
+    let mid = tree.len() / 2;
+    tree.select_nth_unstable_by(mid, |(a, _), (b, _)| a[axis].total_cmp(&b[axis]));
+    let (before, after) = tree.split_at_mut(mid);
+    build(before, 1 - axis);
+    build(&mut after[1..], 1 - axis);
+}
+
+/// A bounding volume hierarchy over the pieces of a line, for finding the pieces near a
+/// point without scanning all of them.
+///
+/// Each node covers a contiguous run of `pieces`, split in half by piece center, alternating
+/// between x and y. Pieces with a non-finite end are left out.
+#[derive(Debug)]
+pub(crate) struct SegmentIndex<T> {
+    /// Piece ends paired with their payload, in tree order.
+    pieces: Vec<([[f64; 2]; 2], T)>,
+    /// Nodes in depth-first order, so a node's first child directly follows it.
+    nodes: Vec<SegmentNode>,
+}
+
+#[derive(Debug)]
+struct SegmentNode {
+    min: [f64; 2],
+    max: [f64; 2],
+    /// The node's pieces are `pieces[start..end]`.
+    start: usize,
+    end: usize,
+    /// Index of the second child, or zero for a leaf.
+    second: usize,
+}
+
+impl<T: Copy> SegmentIndex<T> {
+    /// Create the index over line pieces, each given by its two ends and a payload.
+    pub(crate) fn new(pieces: impl IntoIterator<Item = ([[f64; 2]; 2], T)>) -> Self {
+        let mut pieces: Vec<_> = pieces
+            .into_iter()
+            .filter(|(ends, _)| ends.iter().flatten().all(|v| v.is_finite()))
+            .collect();
+        let mut nodes = Vec::new();
+        build_segments(&mut pieces, 0, 0, &mut nodes);
+        Self { pieces, nodes }
+    }
+
+    /// Call `visit` with each piece whose bounding box comes within `reach` of `target` on
+    /// both axes, in no particular order.
+    pub(crate) fn for_each_near(
+        &self,
+        target: [f64; 2],
+        reach: [f64; 2],
+        mut visit: impl FnMut([[f64; 2]; 2], T),
+    ) {
+        let near = |min: [f64; 2], max: [f64; 2]| {
+            (0..2).all(|axis| {
+                min[axis] - reach[axis] <= target[axis] && target[axis] <= max[axis] + reach[axis]
+            })
+        };
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let Some(node) = self.nodes.get(index) else {
+                continue;
+            };
+            if !near(node.min, node.max) {
+                continue;
+            }
+            if node.second == 0 {
+                for &([a, b], payload) in &self.pieces[node.start..node.end] {
+                    if near(
+                        [a[0].min(b[0]), a[1].min(b[1])],
+                        [a[0].max(b[0]), a[1].max(b[1])],
+                    ) {
+                        visit([a, b], payload);
+                    }
+                }
+            } else {
+                stack.extend([index + 1, node.second]);
+            }
+        }
+    }
+}
+
+/// Append the node covering `pieces` (which start at `offset` in the index) and its
+/// children to `nodes`, splitting on `axis` first.
+fn build_segments<T>(
+    pieces: &mut [([[f64; 2]; 2], T)],
+    offset: usize,
+    axis: usize,
+    nodes: &mut Vec<SegmentNode>,
+) {
+    if pieces.is_empty() {
+        return;
+    }
+    let (min, max) = pieces.iter().flat_map(|(ends, _)| ends).fold(
+        ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]),
+        |(min, max), p| {
+            (
+                [min[0].min(p[0]), min[1].min(p[1])],
+                [max[0].max(p[0]), max[1].max(p[1])],
+            )
+        },
+    );
+    let index = nodes.len();
+    nodes.push(SegmentNode {
+        min,
+        max,
+        start: offset,
+        end: offset + pieces.len(),
+        second: 0,
+    });
+    if pieces.len() <= LEAF_SIZE {
+        return;
+    }
+    let mid = pieces.len() / 2;
+    let center = |([a, b], _): &([[f64; 2]; 2], T)| a[axis] + b[axis];
+    pieces.select_nth_unstable_by(mid, |p, q| center(p).total_cmp(&center(q)));
+    let (first, second) = pieces.split_at_mut(mid);
+    build_segments(first, offset, 1 - axis, nodes);
+    nodes[index].second = nodes.len();
+    build_segments(second, offset + mid, 1 - axis, nodes);
+}
+
+/// The number of leading indices in `0..len` for which `pred` holds, like
+/// [`slice::partition_point`].
+fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut lo, mut hi) = (0, len);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+struct NearestSearch {
+    target: [f64; 2],
+    scale: [f64; 2],
+    /// Squared distance a point must not exceed to be a candidate.
+    bound: f64,
+    best: Option<(usize, f64)>,
+}
+
+impl NearestSearch {
+    fn consider(&mut self, &([x, y], index): &([f64; 2], usize)) {
+        let dx = (x - self.target[0]) * self.scale[0];
+        let dy = (y - self.target[1]) * self.scale[1];
+        let d2 = dx * dx + dy * dy;
+        if d2 > self.bound {
+            return;
+        }
+        if self
+            .best
+            .is_some_and(|(best, best_d2)| d2 == best_d2 && best < index)
+        {
+            return;
+        }
+        self.best = Some((index, d2));
+        self.bound = d2;
+    }
+
+    fn visit(&mut self, tree: &[([f64; 2], usize)], axis: usize) {
+        if tree.len() <= LEAF_SIZE {
+            for entry in tree {
+                self.consider(entry);
+            }
+            return;
+        }
+        let mid = tree.len() / 2;
+        self.consider(&tree[mid]);
+
+        let offset = (self.target[axis] - tree[mid].0[axis]) * self.scale[axis];
+        let (near, far) = if offset < 0.0 {
+            (&tree[..mid], &tree[mid + 1..])
+        } else {
+            (&tree[mid + 1..], &tree[..mid])
+        };
+        self.visit(near, 1 - axis);
+        // Points equal to the split value may sit on either side, so ties are searched too.
+        if offset * offset <= self.bound {
+            self.visit(far, 1 - axis);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PointIndex, SegmentIndex};
+
+    /// Deterministic scattered points, with repeated coordinates and a non-finite point.
+    fn scattered(len: usize) -> Vec<[f64; 2]> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % 1000) as f64 / 10.0
+        };
+        let mut points: Vec<[f64; 2]> = (0..len).map(|_| [next(), next()]).collect();
+        points[len / 2] = [f64::NAN, 1.0];
+        points
+    }
+
+    fn brute_force(points: &[[f64; 2]], target: [f64; 2], scale: [f64; 2]) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (i, [x, y]) in points.iter().enumerate() {
+            let dx = (x - target[0]) * scale[0];
+            let dy = (y - target[1]) * scale[1];
+            let d2 = dx * dx + dy * dy;
+            if best.is_none_or(|(_, best_d2)| d2 < best_d2) {
+                best = Some((i, d2));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    #[test]
+    fn nearest_matches_a_linear_scan() {
+        let points = scattered(2000);
+        let index = PointIndex::new(points.len(), |i| points[i]);
+        for target in [
+            [0.0, 0.0],
+            [50.0, 50.0],
+            [33.3, 71.7],
+            [120.0, -5.0],
+            [10.0, 10.0],
+        ] {
+            for scale in [[1.0, 1.0], [4.0, 0.5]] {
+                let hit = index.nearest(target, scale, f64::INFINITY, |i| points[i]);
+                assert_eq!(hit.map(|(i, _)| i), brute_force(&points, target, scale));
+            }
+            assert_eq!(
+                index.nearest_x(target[0], |i| points[i]),
+                brute_force(&points, target, [1.0, 0.0])
+            );
+            assert_eq!(
+                index.nearest_y(target[1], |i| points[i]),
+                brute_force(&points, target, [0.0, 1.0])
+            );
+        }
+        assert_eq!(
+            index.nearest([0.0, 0.0], [1.0, 1.0], 0.0, |i| points[i]),
+            None
+        );
+    }
+
+    #[test]
+    fn box_queries_find_the_points_a_scan_finds() {
+        let scattered = scattered(2000);
+        let sorted: Vec<[f64; 2]> = (0..500)
+            .map(|i| [i as f64 * 0.2, (i % 13) as f64])
+            .collect();
+        for points in [scattered, sorted] {
+            let index = PointIndex::new(points.len(), |i| points[i]);
+            for (min, max) in [
+                ([10.0, 10.0], [30.0, 40.0]),
+                ([0.0, 0.0], [0.0, 100.0]),
+                ([-5.0, 3.0], [50.0, 3.0]),
+                ([60.0, 60.0], [50.0, 70.0]),
+            ] {
+                let mut found = Vec::new();
+                index.for_each_within(min, max, |i| points[i], |i| found.push(i));
+                found.sort_unstable();
+                let expected: Vec<usize> = (0..points.len())
+                    .filter(|&i| (0..2).all(|a| min[a] <= points[i][a] && points[i][a] <= max[a]))
+                    .collect();
+                assert_eq!(found, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn sorted_series_binary_search_x_and_prefer_the_first_of_equal_points() {
+        let points = [[0.0, 5.0], [1.0, 3.0], [1.0, 4.0], [3.0, 0.0], [4.0, 1.0]];
+        let index = PointIndex::new(points.len(), |i| points[i]);
+        assert!(index.sorted_x);
+        let nearest = |x| index.nearest_x(x, |i| points[i]);
+        assert_eq!(nearest(-10.0), Some(0));
+        assert_eq!(nearest(1.2), Some(1));
+        // Halfway between x = 1 and x = 3 goes to the earlier point.
+        assert_eq!(nearest(2.0), Some(1));
+        assert_eq!(nearest(2.1), Some(3));
+        assert_eq!(nearest(10.0), Some(4));
+        assert_eq!(
+            PointIndex::new(0, |_| [0.0; 2]).nearest_x(1.0, |_| [0.0; 2]),
+            None
+        );
+    }
+
+    #[test]
+    fn sorted_indices_extend_over_appends_that_stay_sorted() {
+        let points = [[0.0, 1.0], [1.0, 2.0], [2.0, 0.0]];
+        let index = PointIndex::new(points.len(), |i| points[i]);
+
+        // Two points dropped from the front, two appended.
+        let streamed = [[2.0, 0.0], [3.0, 1.0], [5.0, 4.0]];
+        let extended = index.extended(2, streamed.len(), |i| streamed[i]).unwrap();
+        assert!(extended.sorted_x);
+        assert_eq!(extended.nearest_x(4.9, |i| streamed[i]), Some(2));
+
+        // Going back in x, or past the old length, needs a new index.
+        let unsorted = [[0.0, 1.0], [1.0, 2.0], [2.0, 0.0], [1.5, 3.0]];
+        assert!(index.extended(0, unsorted.len(), |i| unsorted[i]).is_none());
+        assert!(index.extended(4, 1, |i| streamed[i]).is_none());
+    }
+
+    #[test]
+    fn segment_index_finds_the_pieces_a_scan_finds() {
+        let points = scattered(500);
+        let pieces: Vec<_> = points.windows(2).map(|pair| [pair[0], pair[1]]).collect();
+        let index = SegmentIndex::new(pieces.iter().copied().zip(0..));
+        for (target, reach) in [([50.0, 50.0], [1.0, 1.0]), ([10.0, 90.0], [0.5, 4.0])] {
+            let mut found = Vec::new();
+            index.for_each_near(target, reach, |_, i| found.push(i));
+            found.sort();
+            let expected: Vec<usize> = (0..pieces.len())
+                .filter(|&i| {
+                    let [a, b] = pieces[i];
+                    let finite = [a, b].iter().flatten().all(|v| v.is_finite());
+                    finite
+                        && (0..2).all(|axis| {
+                            a[axis].min(b[axis]) - reach[axis] <= target[axis]
+                                && target[axis] <= a[axis].max(b[axis]) + reach[axis]
+                        })
+                })
+                .collect();
+            assert!(!expected.is_empty());
+            assert_eq!(found, expected);
+        }
+    }
+}
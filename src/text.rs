@@ -0,0 +1,10 @@
+//! Text metrics for laying out labels without a text shaper, shared by the on-screen legend
+//! and the exporters.
+
+/// Relative line height iced uses for text by default.
+pub(crate) const LINE_HEIGHT: f32 = 1.3;
+
+/// Rough width of a single line of text.
+pub(crate) fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.6
+}
@@ -137,6 +137,34 @@ pub fn log_tick_producer(base: f64, min: f64, max: f64) -> Vec<Tick> {
     out
 }
 
+/// A tick producer for categorical axes with `count` categories.
+///
+/// Category `i` is at value `i`; one major tick is produced for every category in range.
+/// See [`crate::PlotWidget::set_x_categories`].
+pub fn category_tick_producer(count: usize, min: f64, max: f64) -> Vec<Tick> {
+    if !(min.is_finite() && max.is_finite()) || count == 0 {
+        return Vec::new();
+    }
+    let first = min.ceil().max(0.0);
+    let last = max.floor().min((count - 1) as f64);
+    if first > last {
+        return Vec::new();
+    }
+    (first as usize..=last as usize)
+        .map(|index| Tick::new(index as f64, 1.0, TickWeight::Major))
+        .collect()
+}
+
+/// A formatter for ticks produced by [`category_tick_producer`], showing the label of the
+/// category at the tick.
+pub fn category_formatter(labels: &[String], mark: Tick) -> String {
+    let index = mark.value.round();
+    if index < 0.0 || (mark.value - index).abs() > 1e-9 {
+        return String::new();
+    }
+    labels.get(index as usize).cloned().unwrap_or_default()
+}
+
 /// Calculate a "nice" step size for grid lines based on the desired number of divisions.
 /// Returns a value that is a multiple of 1, 2, 5, or 10 times a power of 10.
 pub fn nice_step(raw: f64) -> f64 {
@@ -153,3 +181,333 @@ pub fn nice_step(raw: f64) -> f64 {
     }
     base * 10.0
 }
+
+const SECONDS_PER_MINUTE: f64 = 60.0;
+const SECONDS_PER_HOUR: f64 = 3_600.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const SECONDS_PER_MONTH: f64 = 30.436_875 * SECONDS_PER_DAY;
+const SECONDS_PER_YEAR: f64 = 365.242_5 * SECONDS_PER_DAY;
+
+/// Fixed-length steps (in seconds) between one second and one week. Each sub-day step
+/// divides a day evenly, so those ticks always land on midnight; the multi-day steps are
+/// counted from the epoch (weeks from a Monday) instead.
+const TIME_STEPS: [f64; 20] = [
+    1.0,
+    2.0,
+    5.0,
+    10.0,
+    15.0,
+    30.0,
+    SECONDS_PER_MINUTE,
+    2.0 * SECONDS_PER_MINUTE,
+    5.0 * SECONDS_PER_MINUTE,
+    10.0 * SECONDS_PER_MINUTE,
+    15.0 * SECONDS_PER_MINUTE,
+    30.0 * SECONDS_PER_MINUTE,
+    SECONDS_PER_HOUR,
+    2.0 * SECONDS_PER_HOUR,
+    3.0 * SECONDS_PER_HOUR,
+    6.0 * SECONDS_PER_HOUR,
+    12.0 * SECONDS_PER_HOUR,
+    SECONDS_PER_DAY,
+    2.0 * SECONDS_PER_DAY,
+    7.0 * SECONDS_PER_DAY,
+];
+
+/// Month steps used once a week is too fine.
+const MONTH_STEPS: [i64; 4] = [1, 2, 3, 6];
+
+/// A tick producer for [`crate::AxisScale::Time`] axes.
+///
+/// Inputs are seconds since the Unix epoch (UTC). Ticks are aligned to calendar units:
+/// milliseconds, seconds, minutes, hours, days, weeks (starting on Monday), months and years.
+/// Ticks at the start of the next larger unit (whole seconds, midnight, the first of the
+/// month, January) are [`TickWeight::Major`]; the rest are [`TickWeight::Minor`].
+pub fn time_tick_producer(min: f64, max: f64) -> Vec<Tick> {
+    const TIME_TARGET_TICKS: f64 = 8.0;
+
+    let span = max - min;
+    if !span.is_finite() || span <= 0.0 {
+        return Vec::new();
+    }
+    let raw = span / TIME_TARGET_TICKS;
+
+    if raw < 1.0 {
+        let step = nice_step(raw);
+        return fixed_time_ticks(min, max, step, 0.0, |value| {
+            (value - value.round()).abs() < step * 1e-3
+        });
+    }
+
+    if let Some(&step) = TIME_STEPS.iter().find(|&&step| raw <= step) {
+        // 1970-01-01 was a Thursday; shift week ticks so they fall on Mondays.
+        let offset = if step > 2.0 * SECONDS_PER_DAY {
+            4.0 * SECONDS_PER_DAY
+        } else {
+            0.0
+        };
+        return fixed_time_ticks(min, max, step, offset, |value| {
+            if step < SECONDS_PER_DAY {
+                value.rem_euclid(SECONDS_PER_DAY) == 0.0
+            } else {
+                civil_from_timestamp(value).2 == 1
+            }
+        });
+    }
+
+    if let Some(&months) = MONTH_STEPS
+        .iter()
+        .find(|&&months| raw <= months as f64 * SECONDS_PER_MONTH)
+    {
+        return month_ticks(min, max, months);
+    }
+
+    let years = nice_step(raw / SECONDS_PER_YEAR).round().max(1.0) as i64;
+    year_ticks(min, max, years)
+}
+
+fn fixed_time_ticks(
+    min: f64,
+    max: f64,
+    step: f64,
+    offset: f64,
+    is_major: impl Fn(f64) -> bool,
+) -> Vec<Tick> {
+    let first = ((min - offset) / step).ceil() as i64;
+    let last = ((max - offset) / step).floor() as i64;
+    (first..=last)
+        .map(|idx| {
+            let value = idx as f64 * step + offset;
+            let weight = if is_major(value) {
+                TickWeight::Major
+            } else {
+                TickWeight::Minor
+            };
+            Tick::new(value, step, weight)
+        })
+        .collect()
+}
+
+fn month_ticks(min: f64, max: f64, months: i64) -> Vec<Tick> {
+    let (year, month, day) = civil_from_timestamp(min);
+    let mut index = year * 12 + (month as i64 - 1);
+    let on_boundary = day == 1 && min.rem_euclid(SECONDS_PER_DAY) == 0.0;
+    if !on_boundary {
+        index += 1;
+    }
+    index = ceil_to_multiple(index, months);
+
+    let step = months as f64 * SECONDS_PER_MONTH;
+    let mut ticks = Vec::new();
+    loop {
+        let value = timestamp_from_civil(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1);
+        if value > max {
+            break;
+        }
+        let weight = if index.rem_euclid(12) == 0 {
+            TickWeight::Major
+        } else {
+            TickWeight::Minor
+        };
+        ticks.push(Tick::new(value, step, weight));
+        index += months;
+    }
+    ticks
+}
+
+fn ceil_to_multiple(value: i64, multiple: i64) -> i64 {
+    let rem = value.rem_euclid(multiple);
+    if rem == 0 {
+        value
+    } else {
+        value + multiple - rem
+    }
+}
+
+fn year_ticks(min: f64, max: f64, years: i64) -> Vec<Tick> {
+    let (min_year, month, day) = civil_from_timestamp(min);
+    let on_boundary = month == 1 && day == 1 && min.rem_euclid(SECONDS_PER_DAY) == 0.0;
+    let first_year = if on_boundary { min_year } else { min_year + 1 };
+    let last_year = civil_from_timestamp(max).0;
+
+    let step = years as f64 * SECONDS_PER_YEAR;
+    (ceil_to_multiple(first_year, years)..=last_year)
+        .step_by(years as usize)
+        .map(|year| Tick::new(timestamp_from_civil(year, 1, 1), step, TickWeight::Major))
+        .collect()
+}
+
+/// A formatter for ticks produced by [`time_tick_producer`].
+///
+/// Major ticks carry the coarser context (the date, or the whole second below one-second
+/// steps) so the axis stays readable while zoomed in; minor ticks only show the part that
+/// changes at the tick's resolution. All values are UTC.
+pub fn time_formatter(mark: Tick) -> String {
+    const MONTH_NAMES: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    if !mark.value.is_finite() {
+        return String::new();
+    }
+    let (year, month, day) = civil_from_timestamp(mark.value);
+    let major = mark.line_type == TickWeight::Major;
+
+    if mark.step_size < 1.0 {
+        // Whole seconds anchor sub-second ticks.
+        let resolution = if major { 1.0 } else { mark.step_size };
+        format_time_of_day(mark.value, resolution)
+    } else if mark.step_size < SECONDS_PER_DAY {
+        if major {
+            format!("{year:04}-{month:02}-{day:02}")
+        } else {
+            format_time_of_day(mark.value, mark.step_size)
+        }
+    } else if mark.step_size < 28.0 * SECONDS_PER_DAY {
+        if major {
+            format!("{year:04}-{month:02}-{day:02}")
+        } else {
+            format!("{} {day}", MONTH_NAMES[month as usize - 1])
+        }
+    } else if mark.step_size < 360.0 * SECONDS_PER_DAY {
+        if major {
+            format!("{year:04}")
+        } else {
+            MONTH_NAMES[month as usize - 1].to_string()
+        }
+    } else {
+        format!("{year:04}")
+    }
+}
+
+/// Format a timestamp (seconds since the Unix epoch) as `YYYY-MM-DD HH:MM:SS.fff` in UTC.
+pub(crate) fn format_timestamp(seconds: f64) -> String {
+    if !seconds.is_finite() {
+        return String::new();
+    }
+    let (year, month, day) = civil_from_timestamp(seconds);
+    format!(
+        "{year:04}-{month:02}-{day:02} {}",
+        format_time_of_day(seconds, 0.001)
+    )
+}
+
+/// Format the time of day with enough fractional digits to resolve `resolution` seconds.
+fn format_time_of_day(seconds: f64, resolution: f64) -> String {
+    let decimals = if resolution >= 1.0 {
+        0
+    } else {
+        (-resolution.log10()).ceil().clamp(0.0, 9.0) as usize
+    };
+    let scale = 10f64.powi(decimals as i32);
+    let total = (seconds.rem_euclid(SECONDS_PER_DAY) * scale).round() as u64;
+    let whole = total / scale as u64;
+    let frac = total % scale as u64;
+    let (h, m, s) = ((whole / 3600) % 24, (whole / 60) % 60, whole % 60);
+
+    if decimals > 0 {
+        format!("{h:02}:{m:02}:{s:02}.{frac:0decimals$}")
+    } else if resolution < SECONDS_PER_MINUTE {
+        format!("{h:02}:{m:02}:{s:02}")
+    } else {
+        format!("{h:02}:{m:02}")
+    }
+}
+
+/// Convert a timestamp into a UTC `(year, month, day)` triple.
+fn civil_from_timestamp(seconds: f64) -> (i64, u32, u32) {
+    civil_from_days((seconds / SECONDS_PER_DAY).floor() as i64)
+}
+
+/// Midnight UTC on the given date, as seconds since the Unix epoch.
+fn timestamp_from_civil(year: i64, month: u32, day: u32) -> f64 {
+    days_from_civil(year, month, day) as f64 * SECONDS_PER_DAY
+}
+
+// Proleptic Gregorian calendar conversions, after Howard Hinnant's date algorithms
+// (http://howardhinnant.github.io/date_algorithms.html).
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let month = month as i64;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn category_ticks_label_each_visible_category() {
+        let labels: Vec<String> = ["a", "b", "c"].map(String::from).into();
+        let ticks = category_tick_producer(labels.len(), -0.6, 1.4);
+        let text: Vec<_> = ticks
+            .iter()
+            .map(|&tick| category_formatter(&labels, tick))
+            .collect();
+        assert_eq!(text, ["a", "b"]);
+        assert!(category_tick_producer(3, 2.5, 9.0).is_empty());
+        assert_eq!(
+            category_formatter(&labels, Tick::new(0.5, 1.0, TickWeight::Major)),
+            ""
+        );
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        for days in [-719_468, -1, 0, 59, 11_016, 19_782, 2_932_896] {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+    }
+
+    #[test]
+    fn hourly_ticks_mark_midnight_as_major() {
+        let midnight = timestamp_from_civil(2024, 3, 10);
+        let ticks = time_tick_producer(midnight - 6.0 * 3600.0, midnight + 6.0 * 3600.0);
+
+        assert!(ticks.iter().all(|t| t.step_size == SECONDS_PER_HOUR * 2.0));
+        let major: Vec<_> = ticks
+            .iter()
+            .filter(|t| t.line_type == TickWeight::Major)
+            .collect();
+        assert_eq!(major.len(), 1);
+        assert_eq!(major[0].value, midnight);
+        assert_eq!(time_formatter(*major[0]), "2024-03-10");
+        assert_eq!(time_formatter(ticks[0]), "18:00");
+    }
+
+    #[test]
+    fn month_ticks_start_on_first_of_month() {
+        let min = timestamp_from_civil(2023, 11, 15);
+        let max = timestamp_from_civil(2024, 4, 20);
+        let ticks = time_tick_producer(min, max);
+
+        let labels: Vec<_> = ticks.iter().map(|t| time_formatter(*t)).collect();
+        assert_eq!(labels, ["Dec", "2024", "Feb", "Mar", "Apr"]);
+    }
+
+    #[test]
+    fn format_timestamp_includes_milliseconds() {
+        let t = timestamp_from_civil(2000, 1, 1) + 3723.25;
+        assert_eq!(format_timestamp(t), "2000-01-01 01:02:03.250");
+    }
+}
@@ -110,7 +110,7 @@ pub struct Transform {
 impl From<AxisScale> for Transform {
     fn from(scale: AxisScale) -> Self {
         match scale {
-            AxisScale::Linear => Self::identity(),
+            AxisScale::Linear | AxisScale::Time => Self::identity(),
             AxisScale::Log { base } => Self::log(base),
         }
     }
@@ -311,6 +311,21 @@ pub(crate) fn plot_value_to_data(value: f64, axis_scale: AxisScale) -> Option<f6
         .transform_value(value)
 }
 
+/// Convert a plot-space value back to the data value that `transform` and the axis scale
+/// put there. Axes-coordinate transforms, and ones that can't be inverted, give `None`.
+pub(crate) fn plot_value_to_data_with_transform(
+    value: f64,
+    axis_scale: AxisScale,
+    transform: Option<&Transform>,
+) -> Option<f64> {
+    let value = plot_value_to_data(value, axis_scale)?;
+    match transform {
+        None => Some(value),
+        Some(transform) if transform.uses_axes_coordinates() => None,
+        Some(transform) => transform.inverted()?.transform_value(value),
+    }
+}
+
 pub(crate) fn data_point_to_plot_with_transform(
     point: [f64; 2],
     x_scale: AxisScale,
@@ -392,4 +407,18 @@ mod tests {
         );
         assert_eq!(value, Some(14.0));
     }
+
+    #[test]
+    fn plot_values_map_back_through_the_axis_scale_and_transform() {
+        let log = AxisScale::Log { base: 10.0 };
+        let transform = Transform::affine(2.0, 10.0);
+        let plot = data_value_to_plot(45.0, log, Some(&transform)).unwrap();
+
+        let data = plot_value_to_data_with_transform(plot, log, Some(&transform)).unwrap();
+        assert!((data - 45.0).abs() < 1e-12);
+        assert_eq!(
+            plot_value_to_data_with_transform(0.5, AxisScale::Linear, Some(&Transform::axes())),
+            None
+        );
+    }
 }